@@ -3,7 +3,9 @@
 #[cfg(not(target_arch = "wasm32"))]
 use rocket::serde::json::Json;
 #[cfg(not(target_arch = "wasm32"))]
-use rocket::{post, routes, State};
+use rocket::{get, post, routes, State};
+#[cfg(not(target_arch = "wasm32"))]
+use rocket_ws::{Channel, WebSocket};
 // #[cfg(not(target_arch = "wasm32"))]
 // use rocket::{Request, Response};
 #[cfg(not(target_arch = "wasm32"))]
@@ -15,8 +17,12 @@ use std::fs;
 #[cfg(not(target_arch = "wasm32"))]
 use std::sync::RwLock;
 #[cfg(not(target_arch = "wasm32"))]
+use tokio::sync::broadcast::{self, Sender};
+#[cfg(not(target_arch = "wasm32"))]
 use tss_wasm::common::{Entry, Index, Key, Params, PartySignup};
 #[cfg(not(target_arch = "wasm32"))]
+use tss_wasm::manager;
+#[cfg(not(target_arch = "wasm32"))]
 use uuid::Uuid;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -41,26 +47,45 @@ fn get(
 
 #[cfg(not(target_arch = "wasm32"))]
 #[post("/set", format = "json", data = "<request>")]
-fn set(db_mtx: &State<RwLock<HashMap<Key, String>>>, request: Json<Entry>) -> Json<Result<(), ()>> {
-    let entry: Entry = request.0;
-    let mut hm = db_mtx.write().unwrap();
-    hm.insert(entry.key.clone(), entry.value);
+fn set(
+    db_mtx: &State<RwLock<HashMap<Key, String>>>,
+    keys: &State<Sender<Key>>,
+    request: Json<Entry>,
+) -> Json<Result<(), ()>> {
+    manager::store(db_mtx, keys, request.0);
     Json(Ok(()))
 }
 
+/// A channel on which a party sets entries and subscribes to keys. The entries of the keys
+/// are pushed once they are stored, whether over a channel or `/set`.
 #[cfg(not(target_arch = "wasm32"))]
-#[post("/signupkeygen", format = "json")]
-fn signup_keygen(db_mtx: &State<RwLock<HashMap<Key, String>>>) -> Json<Result<PartySignup, ()>> {
+#[get("/ws")]
+fn ws<'r>(
+    ws: WebSocket,
+    db_mtx: &'r State<RwLock<HashMap<Key, String>>>,
+    keys: &'r State<Sender<Key>>,
+) -> Channel<'r> {
+    ws.channel(move |stream| Box::pin(manager::channel(stream, db_mtx, keys)))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_params() -> Params {
     let data = fs::read_to_string("params.json")
         .expect("Unable to read params, make sure config file is present in the same folder ");
-    let params: Params = serde_json::from_str(&data).unwrap();
-    let parties = params.parties.parse::<u16>().unwrap();
-
-    let key = "signup-keygen".to_string();
+    serde_json::from_str(&data).unwrap()
+}
 
+/// Hands out the next party number of the session under `key`, and starts a new session
+/// with a new uuid once `parties` have signed up.
+#[cfg(not(target_arch = "wasm32"))]
+fn signup(
+    db_mtx: &State<RwLock<HashMap<Key, String>>>,
+    key: &str,
+    parties: u16,
+) -> Json<Result<PartySignup, ()>> {
     let mut hm = db_mtx.write().unwrap();
     let party_signup = {
-        let value = hm.get(&key).unwrap();
+        let value = hm.get(key).unwrap();
         let client_signup: PartySignup = serde_json::from_str(value).unwrap();
         if client_signup.number < parties {
             PartySignup {
@@ -75,39 +100,60 @@ fn signup_keygen(db_mtx: &State<RwLock<HashMap<Key, String>>>) -> Json<Result<Pa
         }
     };
 
-    hm.insert(key, serde_json::to_string(&party_signup).unwrap());
+    hm.insert(
+        key.to_string(),
+        serde_json::to_string(&party_signup).unwrap(),
+    );
     Json(Ok(party_signup))
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+#[post("/signupkeygen", format = "json")]
+fn signup_keygen(db_mtx: &State<RwLock<HashMap<Key, String>>>) -> Json<Result<PartySignup, ()>> {
+    let parties = read_params().parties.parse::<u16>().unwrap();
+    signup(db_mtx, "signup-keygen", parties)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[post("/signuprefresh", format = "json")]
+fn signup_refresh(db_mtx: &State<RwLock<HashMap<Key, String>>>) -> Json<Result<PartySignup, ()>> {
+    let parties = read_params().parties.parse::<u16>().unwrap();
+    signup(db_mtx, "signup-refresh", parties)
+}
+
+/// All the parties of the key take part in a repair: the helpers and the party that lost
+/// its share, which signs up like the others.
+#[cfg(not(target_arch = "wasm32"))]
+#[post("/signuprepair", format = "json")]
+fn signup_repair(db_mtx: &State<RwLock<HashMap<Key, String>>>) -> Json<Result<PartySignup, ()>> {
+    let parties = read_params().parties.parse::<u16>().unwrap();
+    signup(db_mtx, "signup-repair", parties)
+}
+
+/// The number of participants of a resharing depends on how the old and new committees
+/// overlap, so the clients send it along.
+#[cfg(not(target_arch = "wasm32"))]
+#[post("/signupreshare", format = "json", data = "<request>")]
+fn signup_reshare(
+    db_mtx: &State<RwLock<HashMap<Key, String>>>,
+    request: Json<u16>,
+) -> Json<Result<PartySignup, ()>> {
+    signup(db_mtx, "signup-reshare", request.0)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[post("/signupsign", format = "json")]
 fn signup_sign(db_mtx: &State<RwLock<HashMap<Key, String>>>) -> Json<Result<PartySignup, ()>> {
-    //read parameters:
-    let data = fs::read_to_string("params.json")
-        .expect("Unable to read params, make sure config file is present in the same folder ");
-    let params: Params = serde_json::from_str(&data).unwrap();
-    let threshold = params.threshold.parse::<u16>().unwrap();
-    let key = "signup-sign".to_string();
-
-    let mut hm = db_mtx.write().unwrap();
-    let party_signup = {
-        let value = hm.get(&key).unwrap();
-        let client_signup: PartySignup = serde_json::from_str(value).unwrap();
-        if client_signup.number < threshold + 1 {
-            PartySignup {
-                number: client_signup.number + 1,
-                uuid: client_signup.uuid,
-            }
-        } else {
-            PartySignup {
-                number: 1,
-                uuid: Uuid::new_v4().to_string(),
-            }
-        }
-    };
+    let threshold = read_params().threshold.parse::<u16>().unwrap();
+    signup(db_mtx, "signup-sign", threshold + 1)
+}
 
-    hm.insert(key, serde_json::to_string(&party_signup).unwrap());
-    Json(Ok(party_signup))
+/// A two-party session always pairs a client with a cosigner, so it starts after two
+/// signups whatever params.json says.
+#[cfg(not(target_arch = "wasm32"))]
+#[post("/signuptwoparty", format = "json")]
+fn signup_two_party(db_mtx: &State<RwLock<HashMap<Key, String>>>) -> Json<Result<PartySignup, ()>> {
+    signup(db_mtx, "signup-two-party", 2)
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -115,6 +161,7 @@ fn signup_sign(db_mtx: &State<RwLock<HashMap<Key, String>>>) -> Json<Result<Part
 async fn main() {
     let db: HashMap<Key, String> = HashMap::new();
     let db_mtx = RwLock::new(db);
+    let (keys, _) = broadcast::channel::<Key>(1024);
 
     /////////////////////////////////////////////////////////////////
     //////////////////////////init signups://////////////////////////
@@ -122,9 +169,17 @@ async fn main() {
 
     let keygen_key = "signup-keygen".to_string();
     let sign_key = "signup-sign".to_string();
+    let refresh_key = "signup-refresh".to_string();
+    let reshare_key = "signup-reshare".to_string();
+    let repair_key = "signup-repair".to_string();
+    let two_party_key = "signup-two-party".to_string();
 
     let uuid_keygen = Uuid::new_v4().to_string();
     let uuid_sign = Uuid::new_v4().to_string();
+    let uuid_refresh = Uuid::new_v4().to_string();
+    let uuid_reshare = Uuid::new_v4().to_string();
+    let uuid_repair = Uuid::new_v4().to_string();
+    let uuid_two_party = Uuid::new_v4().to_string();
 
     let party1 = 0;
     let party_signup_keygen = PartySignup {
@@ -135,6 +190,22 @@ async fn main() {
         number: party1,
         uuid: uuid_sign,
     };
+    let party_signup_refresh = PartySignup {
+        number: party1,
+        uuid: uuid_refresh,
+    };
+    let party_signup_reshare = PartySignup {
+        number: party1,
+        uuid: uuid_reshare,
+    };
+    let party_signup_repair = PartySignup {
+        number: party1,
+        uuid: uuid_repair,
+    };
+    let party_signup_two_party = PartySignup {
+        number: party1,
+        uuid: uuid_two_party,
+    };
     {
         let mut hm = db_mtx.write().unwrap();
         hm.insert(
@@ -142,6 +213,22 @@ async fn main() {
             serde_json::to_string(&party_signup_keygen).unwrap(),
         );
         hm.insert(sign_key, serde_json::to_string(&party_signup_sign).unwrap());
+        hm.insert(
+            refresh_key,
+            serde_json::to_string(&party_signup_refresh).unwrap(),
+        );
+        hm.insert(
+            reshare_key,
+            serde_json::to_string(&party_signup_reshare).unwrap(),
+        );
+        hm.insert(
+            repair_key,
+            serde_json::to_string(&party_signup_repair).unwrap(),
+        );
+        hm.insert(
+            two_party_key,
+            serde_json::to_string(&party_signup_two_party).unwrap(),
+        );
     }
 
     let cors = CorsOptions::default()
@@ -156,9 +243,23 @@ async fn main() {
 
     /////////////////////////////////////////////////////////////////
     rocket::build()
-        .mount("/", routes![get, set, signup_keygen, signup_sign])
+        .mount(
+            "/",
+            routes![
+                get,
+                set,
+                ws,
+                signup_keygen,
+                signup_sign,
+                signup_refresh,
+                signup_reshare,
+                signup_repair,
+                signup_two_party
+            ],
+        )
         .attach(cors.to_cors().unwrap())
         .manage(db_mtx)
+        .manage(keys)
         .launch()
         .await
         .unwrap();
@@ -26,6 +26,26 @@ async function keygen(m, delay) {
   return keygen_json
 }
 
+async function refresh(m, key_store, delay) {
+  let context = await m.gg18_refresh_client_new_context(
+    addr,
+    t,
+    n,
+    key_store,
+    delay
+  )
+  console.log('refresh new context: ')
+  context = await m.gg18_refresh_client_round1(context, delay)
+  console.log('refresh round1:')
+  context = await m.gg18_refresh_client_round2(context, delay)
+  console.log('refresh round2: ')
+  context = await m.gg18_refresh_client_round3(context, delay)
+  console.log('refresh round3: ')
+  refresh_json = await m.gg18_refresh_client_round4(context, delay)
+  console.log('refresh json: ', refresh_json)
+  return refresh_json
+}
+
 async function sign(m, key_store, delay) {
   let context = await m.gg18_sign_client_new_context(
     addr,
@@ -58,6 +78,52 @@ async function sign(m, key_store, delay) {
   return sign_json
 }
 
+async function presign_sign(m, key_store, delay) {
+  let context = await m.gg18_presign_client_new_context(addr, t, n, key_store)
+  console.log('presign new context: ')
+  context = await m.gg18_presign_client_round0(context, delay)
+  context = await m.gg18_presign_client_round1(context, delay)
+  context = await m.gg18_presign_client_round2(context, delay)
+  context = await m.gg18_presign_client_round3(context, delay)
+  context = await m.gg18_presign_client_round4(context, delay)
+  context = await m.gg18_presign_client_round5(context, delay)
+  console.log('presign round5: ')
+  presignature_id = await m.gg18_presign_client_round6(context, delay)
+  console.log('presign round6: ')
+  // the message is only needed from here on
+  sign_json = await m.gg18_sign_client_online(
+    addr,
+    presignature_id,
+    digest.slice(2),
+    delay
+  )
+  console.log('online sign json: ', sign_json)
+  return sign_json
+}
+
+async function gg20_sign(m, key_store, delay) {
+  let context = await m.gg20_sign_client_new_context(addr, t, n, key_store)
+  console.log('gg20 sign new context: ')
+  context = await m.gg20_sign_client_round0(context, delay)
+  context = await m.gg20_sign_client_round1(context, delay)
+  context = await m.gg20_sign_client_round2(context, delay)
+  context = await m.gg20_sign_client_round3(context, delay)
+  console.log('gg20 sign round3: ')
+  context = await m.gg20_sign_client_round4(context, delay)
+  context = await m.gg20_sign_client_round5(context, delay)
+  console.log('gg20 sign round5: ')
+  presignature_id = await m.gg20_sign_client_round6(context, delay)
+  console.log('gg20 sign round6: ')
+  sign_json = await m.gg20_sign_client_online(
+    addr,
+    presignature_id,
+    digest.slice(2),
+    delay
+  )
+  console.log('gg20 sign json: ', sign_json)
+  return sign_json
+}
+
 async function main() {
   var results = await Promise.all(
     items.map(async (item) => {
@@ -67,6 +133,15 @@ async function main() {
     }),
   )
 
+  // rotate the shares and Paillier keys, keeping the address
+  results = await Promise.all(
+    results.map(async (item) => {
+      let delay = Math.max(Math.random() % 500, 100)
+      res = await refresh(gg18, item.res, delay)
+      return { idx: item.idx, res: res }
+    }),
+  )
+
   console.log('sign items: ', results)
   await Promise.all(
     results.map(async (item) => {
@@ -86,6 +161,26 @@ async function main() {
       }
     }),
   )
+
+  await Promise.all(
+    results.map(async (item) => {
+      if (item.idx < t + 1) {
+        let delay = Math.max(Math.random() % 500, 100)
+        res = JSON.parse(await presign_sign(gg18, item.res, delay))
+        console.log('Online sign result: ', res)
+      }
+    }),
+  )
+
+  await Promise.all(
+    results.map(async (item) => {
+      if (item.idx < t + 1) {
+        let delay = Math.max(Math.random() % 500, 100)
+        res = JSON.parse(await gg20_sign(gg18, item.res, delay))
+        console.log('GG20 sign result: ', res)
+      }
+    }),
+  )
 }
 
 main().then(() => {
@@ -1,64 +1,68 @@
 #![cfg(target_arch = "wasm32")]
 #![allow(non_snake_case)]
-use crate::common::{
-    aes_decrypt, aes_encrypt, broadcast, check_sig, poll_for_broadcasts, poll_for_p2p, postb,
-    public_key_address, sendp2p, PartySignup, AEAD, AES_KEY_BYTES_LEN,
+use crate::cggmp_2021::party_i::{
+    AuxInfo, AuxInfoBroadcastMessage, AuxInfoKeys, KeyShare, KeygenCommitMessage,
+    KeygenDecommitMessage, KeygenKeys, KeygenProofMessage, PresignKeys, PresignRound1Message,
+    PresignRound2Message,
 };
-use crate::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+use crate::client::{
+    derive_key, derive_key_store, exchange, gg18_keygen_ws, gg18_sign_ws, key_store_to_string,
+    new_client_with_headers, parse_key_store, parse_signup, run, signup_keygen, signup_sign,
+};
+use crate::common::{aes_decrypt, aes_encrypt, aes_key, check_sig, postb, PartySignup, AEAD};
+use crate::curv::elliptic::curves::ed25519::{Ed25519Point, Ed25519Scalar};
+use crate::curv::elliptic::curves::traits::{CurveScalar, ECPoint, ECScalar};
 use crate::curv::{
     arithmetic::num_bigint::BigInt,
     arithmetic::traits::Converter,
     cryptographic_primitives::{
-        proofs::sigma_correct_homomorphic_elgamal_enc::HomoELGamalProof,
         proofs::sigma_dlog::DLogProof, secret_sharing::feldman_vss::VerifiableSS,
     },
     elliptic::curves::secp256_k1::{Secp256k1Point as Point, Secp256k1Scalar as Scalar},
 };
-use crate::errors::Result;
-use crate::gg_2018::mta::*;
+use crate::errors::{Result, TssError};
+use crate::frost_2020::bip340::OutputKey;
+use crate::frost_2020::eddsa::{
+    KeyGenBroadcastMessage1 as EddsaKeyGenBroadcastMessage1,
+    KeyGenDecommitMessage1 as EddsaKeyGenDecommitMessage1, Keys as EddsaKeys,
+    SharedKeys as EddsaSharedKeys, SignBroadcastPhase1 as EddsaSignBroadcastPhase1,
+    SignBroadcastPhase2 as EddsaSignBroadcastPhase2, SignKeys as EddsaSignKeys,
+};
+use crate::frost_2020::party_i::{
+    SignBroadcastPhase1 as FrostSignBroadcastPhase1,
+    SignBroadcastPhase2 as FrostSignBroadcastPhase2, SignKeys as FrostSignKeys,
+};
+use crate::gg_2018::bip32::{self, ChainCode};
+use crate::gg_2018::dealer::{self, DealerBroadcastMessage};
 use crate::gg_2018::party_i::*;
+use crate::gg_2018::presign_store::{MemoryPresignatureStore, PresignatureStore};
+use crate::gg_2018::state_machine::{Complaints, Keygen, Msg, OfflineStage, Sign, StateMachine};
+use crate::gg_2020::party_i::{
+    SignBroadcastPhase3, SignBroadcastPhase5, SignBroadcastPhase6, SignPhase5Reveal,
+};
+use crate::lindell_2017::party_one::{
+    CommWitness as Lindell17CommWitness, CommitMsg as Lindell17CommitMsg,
+    KeyGenSecondMsg as Lindell17KeyGenSecondMsg,
+};
+use crate::lindell_2017::party_two::{
+    EcKeyPair as Lindell17EcKeyPair, KeyGenFirstMsg as Lindell17KeyGenFirstMsg,
+    KeyShare as Lindell17KeyShare, SignFirstMsg as Lindell17SignFirstMsg,
+};
 use crate::log;
+use crate::paillier::zkproofs::DLogStatement;
 use crate::paillier::EncryptionKey;
-use reqwest::header::{HeaderMap, HeaderValue};
+use crate::transport::{HttpTransport, Transport};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+/// A keygen session: the machine and the messages it still has to send.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GG18KeygenClientContext {
     addr: String,
-    params: Parameters,
-    party_num_int: u16,
     uuid: String,
-    bc1_vec: Option<Vec<KeyGenBroadcastMessage1>>,
-    decom_i: Option<KeyGenDecommitMessage1>,
-    party_keys: Option<Keys>,
-    y_sum: Option<crate::curv::elliptic::curves::secp256_k1::Secp256k1Point>,
-    vss_scheme: Option<VerifiableSS>,
-    secret_shares: Option<Vec<crate::curv::elliptic::curves::secp256_k1::Secp256k1Scalar>>,
-    enc_keys: Option<Vec<Vec<u8>>>,
-    party_shares: Option<Vec<Scalar>>,
-    point_vec: Option<Vec<Point>>,
-    dlog_proof: Option<DLogProof>,
-    shared_keys: Option<SharedKeys>,
-    vss_scheme_vec: Option<Vec<VerifiableSS>>,
-    public_key_address: Option<String>,
-}
-
-fn new_client_with_headers() -> Result<Client> {
-    let mut headers = HeaderMap::new();
-    headers.insert(
-        "Content-Type",
-        HeaderValue::from_static("Content-Type:application/json; charset=utf-8"),
-    );
-    headers.insert(
-        "Accept",
-        HeaderValue::from_static("application/json; charset=utf-8"),
-    );
-
-    Ok(reqwest::Client::builder()
-        .default_headers(headers)
-        .build()?)
+    keygen: Keygen,
+    outgoing: Vec<Msg>,
 }
 
 #[wasm_bindgen]
@@ -80,1059 +84,3388 @@ pub async fn gg18_keygen_client_new_context(
 
     Ok(serde_json::to_string(&GG18KeygenClientContext {
         addr,
-        params,
-        party_num_int,
         uuid,
-        bc1_vec: None,
-        decom_i: None,
-        party_keys: None,
-        y_sum: None,
-        vss_scheme: None,
-        secret_shares: None,
-        enc_keys: None,
-        party_shares: None,
-        point_vec: None,
-        dlog_proof: None,
-        shared_keys: None,
-        vss_scheme_vec: None,
-        public_key_address: None,
+        keygen: Keygen::new(params, party_num_int)?,
+        outgoing: Vec::new(),
     })?)
 }
 
+/// Exchanges the messages of `rounds` rounds of keygen.
+async fn gg18_keygen_client_exchange(
+    context: &mut GG18KeygenClientContext,
+    delay: u32,
+    rounds: usize,
+) -> Result<()> {
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    for _ in 0..rounds {
+        let outgoing = std::mem::take(&mut context.outgoing);
+        context.outgoing = exchange(&mut context.keygen, &transport, outgoing).await?;
+    }
+    Ok(())
+}
+
 #[wasm_bindgen]
 pub async fn gg18_keygen_client_round1(context: String, delay: u32) -> Result<String> {
     let mut context = serde_json::from_str::<GG18KeygenClientContext>(&context)?;
-    let client = reqwest::Client::new();
-    let party_keys = Keys::create(context.party_num_int as usize);
-    let (bc_i, decom_i) = party_keys.phase1_broadcast_phase3_proof_of_correct_key();
-
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round1",
-        serde_json::to_string(&bc_i)?,
-        context.uuid.clone(),
-    )
-    .await?;
-
-    let round1_ans_vec = poll_for_broadcasts(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        context.params.share_count as u16,
-        "round1",
-        context.uuid.clone(),
-        delay,
-    )
-    .await?;
-
-    let mut bc1_vec: Vec<_> = round1_ans_vec
-        .into_iter()
-        .map(|m| serde_json::from_str::<KeyGenBroadcastMessage1>(&m))
-        .collect::<std::result::Result<Vec<KeyGenBroadcastMessage1>, serde_json::Error>>()?;
-
-    bc1_vec.insert(context.party_num_int as usize - 1, bc_i);
-
-    context.bc1_vec = Some(bc1_vec);
-    context.party_keys = Some(party_keys);
-    context.decom_i = Some(decom_i);
-
+    context.outgoing = context.keygen.proceed()?;
+    gg18_keygen_client_exchange(&mut context, delay, 1).await?;
     Ok(serde_json::to_string(&context)?)
 }
 
 #[wasm_bindgen]
 pub async fn gg18_keygen_client_round2(context: String, delay: u32) -> Result<String> {
     let mut context = serde_json::from_str::<GG18KeygenClientContext>(&context)?;
-    let client = reqwest::Client::new();
-    // send ephemeral public keys and check commitments correctness
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round2",
-        serde_json::to_string(&context.decom_i.as_ref().unwrap())?,
-        context.uuid.clone(),
-    )
-    .await?;
-
-    let round2_ans_vec = poll_for_broadcasts(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        context.params.share_count as u16,
-        "round2",
-        context.uuid.clone(),
-        delay,
-    )
-    .await?;
-
-    let mut j = 0;
-    let mut point_vec: Vec<Point> = Vec::new();
-    let mut decom_vec: Vec<KeyGenDecommitMessage1> = Vec::new();
-    let mut enc_keys: Vec<Vec<u8>> = Vec::new();
-    for i in 1..=context.params.share_count as u16 {
-        if i == context.party_num_int {
-            point_vec.push(context.decom_i.as_ref().unwrap().y_i.clone());
-            decom_vec.push(context.decom_i.as_ref().unwrap().clone());
-        } else {
-            let decom_j: KeyGenDecommitMessage1 = serde_json::from_str(&round2_ans_vec[j])?;
-            point_vec.push(decom_j.y_i.clone());
-            decom_vec.push(decom_j.clone());
-            let key_bn: BigInt = (decom_j.y_i.clone()
-                * context.party_keys.as_ref().unwrap().u_i.clone())
-            .x_coor()
-            .unwrap();
-            let key_bytes = BigInt::to_vec(&key_bn);
-            let mut template: Vec<u8> = vec![0u8; AES_KEY_BYTES_LEN - key_bytes.len()];
-            template.extend_from_slice(&key_bytes[..]);
-            enc_keys.push(template);
-            j += 1;
-        }
-    }
-
-    let (head, tail) = point_vec.split_at(1);
-    let y_sum = tail.iter().fold(head[0].clone(), |acc, x| acc + x);
-
-    let (vss_scheme, secret_shares, _index) = context
-        .party_keys
-        .as_ref()
-        .unwrap()
-        .phase1_verify_com_phase3_verify_correct_key_phase2_distribute(
-            &context.params,
-            &decom_vec,
-            &(context.bc1_vec.as_ref().unwrap()),
-        )?;
-
-    context.y_sum = Some(y_sum.clone());
-    let pubkey_a = y_sum.get_element().serialize();
-    let pubkey = secp256k1::PublicKey::parse(&pubkey_a)?;
-    context.public_key_address = Some(hex::encode(public_key_address(&pubkey)));
-    context.vss_scheme = Some(vss_scheme);
-    context.secret_shares = Some(secret_shares);
-    context.enc_keys = Some(enc_keys);
-    context.point_vec = Some(point_vec);
-
+    gg18_keygen_client_exchange(&mut context, delay, 1).await?;
     Ok(serde_json::to_string(&context)?)
 }
 
 #[wasm_bindgen]
 pub async fn gg18_keygen_client_round3(context: String, delay: u32) -> Result<String> {
     let mut context = serde_json::from_str::<GG18KeygenClientContext>(&context)?;
-    let client = reqwest::Client::new();
-    let mut j = 0;
-    for (k, i) in (1..=context.params.share_count as u16).enumerate() {
-        if i != context.party_num_int {
-            // prepare encrypted ss for party i:
-            let key_i = &context.enc_keys.as_ref().unwrap()[j];
-            let plaintext =
-                BigInt::to_vec(&context.secret_shares.as_ref().unwrap()[k].to_big_int());
-            let aead_pack_i = aes_encrypt(key_i, &plaintext)?;
-            sendp2p(
-                &client,
-                &context.addr,
-                context.party_num_int,
-                i,
-                "round3",
-                serde_json::to_string(&aead_pack_i)?,
-                context.uuid.clone(),
-            )
-            .await?;
-            j += 1;
-        }
-    }
-
-    let round3_ans_vec = poll_for_p2p(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        context.params.share_count as u16,
-        delay,
-        "round3",
-        context.uuid.clone(),
-    )
-    .await?;
-
-    let mut j = 0;
-    let mut party_shares: Vec<Scalar> = Vec::new();
-    for i in 1..=context.params.share_count as u16 {
-        if i == context.party_num_int {
-            party_shares.push(context.secret_shares.as_ref().unwrap()[(i - 1) as usize].clone());
-        } else {
-            let aead_pack: AEAD = serde_json::from_str(&round3_ans_vec[j]).unwrap();
-            let key_i = &context.enc_keys.as_ref().unwrap()[j];
-            let out = aes_decrypt(key_i, aead_pack)?;
-            let out_bn = BigInt::from_bytes_be(&out[..]);
-            let out_fe = ECScalar::from(&out_bn);
-            party_shares.push(out_fe);
-
-            j += 1;
-        }
-    }
-
-    context.party_shares = Some(party_shares);
-
+    gg18_keygen_client_exchange(&mut context, delay, 1).await?;
     Ok(serde_json::to_string(&context)?)
 }
 
+/// Exchanges the VSS schemes, then settles the complaints about the shares.
 #[wasm_bindgen]
 pub async fn gg18_keygen_client_round4(context: String, delay: u32) -> Result<String> {
     let mut context = serde_json::from_str::<GG18KeygenClientContext>(&context)?;
-    let client = reqwest::Client::new();
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round4",
-        serde_json::to_string(&context.vss_scheme.as_ref().unwrap())?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round4_ans_vec = poll_for_broadcasts(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        context.params.share_count as u16,
-        "round4",
-        context.uuid.clone(),
-        delay,
-    )
-    .await?;
-
-    let mut j = 0;
-    let mut vss_scheme_vec: Vec<VerifiableSS> = Vec::new();
-    for i in 1..=context.params.share_count as u16 {
-        if i == context.party_num_int {
-            vss_scheme_vec.push(context.vss_scheme.as_ref().unwrap().clone());
-        } else {
-            let vss_scheme_j: VerifiableSS = serde_json::from_str(&round4_ans_vec[j]).unwrap();
-            vss_scheme_vec.push(vss_scheme_j);
-            j += 1;
-        }
-    }
-
-    let (shared_keys, dlog_proof) = context
-        .party_keys
-        .as_ref()
-        .unwrap()
-        .phase2_verify_vss_construct_keypair_phase3_pok_dlog(
-            &context.params,
-            &context.point_vec.as_ref().unwrap(),
-            &context.party_shares.as_ref().unwrap(),
-            &vss_scheme_vec,
-            &(context.party_num_int.clone() as usize), // FIXME
-        )?;
-
-    context.shared_keys = Some(shared_keys);
-    context.dlog_proof = Some(dlog_proof);
-    context.vss_scheme_vec = Some(vss_scheme_vec);
-
+    gg18_keygen_client_exchange(&mut context, delay, 3).await?;
     Ok(serde_json::to_string(&context)?)
 }
 
+/// Returns the key store.
 #[wasm_bindgen]
 pub async fn gg18_keygen_client_round5(context: String, delay: u32) -> Result<String> {
-    let context = serde_json::from_str::<GG18KeygenClientContext>(&context)?;
-    let client = reqwest::Client::new();
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round5",
-        serde_json::to_string(&context.dlog_proof.as_ref().unwrap())?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round5_ans_vec = poll_for_broadcasts(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        context.params.share_count as u16,
-        "round5",
-        context.uuid.clone(),
-        delay,
-    )
-    .await?;
+    let mut context = serde_json::from_str::<GG18KeygenClientContext>(&context)?;
+    gg18_keygen_client_exchange(&mut context, delay, 1).await?;
+    let key_store = context
+        .keygen
+        .output()
+        .ok_or_else(|| missing_message("round5"))?;
+    key_store_to_string(key_store)
+}
 
-    let mut j = 0;
-    let mut dlog_proof_vec: Vec<DLogProof> = Vec::new();
-    for i in 1..=context.params.share_count as u16 {
-        if i == context.party_num_int {
-            dlog_proof_vec.push(context.dlog_proof.as_ref().unwrap().clone());
-        } else {
-            let dlog_proof_j: DLogProof = serde_json::from_str(&round5_ans_vec[j])?;
-            dlog_proof_vec.push(dlog_proof_j);
-            j += 1;
-        }
+/// Runs all of keygen in one call, with the messages pushed over the `/ws` channel of the
+/// manager instead of polled for. Returns the key store, like `gg18_keygen_client_round5`.
+#[wasm_bindgen]
+pub async fn gg18_keygen_client_ws(addr: String, t: usize, n: usize) -> Result<String> {
+    key_store_to_string(&gg18_keygen_ws(&addr, t, n).await?)
+}
+
+/// The hex of the compressed public key at the non-hardened BIP-32 `path`, e.g. `m/0/1`,
+/// of a GG18 key store.
+#[wasm_bindgen]
+pub fn gg18_derive_public_key(key_store: String, path: String) -> Result<String> {
+    let (_, _, _, _, _, y_sum, _, chain_code) = parse_key_store(&key_store)?;
+    let (_, child, _) = derive_key(&y_sum, chain_code, &path)?;
+    Ok(hex::encode(child.to_bytes(true)))
+}
+
+/// Splits `secret`, the hex of an existing secp256k1 secret key, into the key stores of a
+/// (t, n) key, in the format of `gg18_keygen_client_round5`. Returns the n key stores, each
+/// to be handed to its party over a private channel, and the dealer message every party
+/// checks its key store against with `gg18_dealer_verify_key_store`.
+#[wasm_bindgen]
+pub fn gg18_dealer_import(secret: String, t: usize, n: usize) -> Result<String> {
+    let secret_bn = BigInt::from_bytes_be(&hex::decode(secret).map_err(|_| TssError::InvalidKey)?);
+    if secret_bn == BigInt::from(0u32) || secret_bn >= Scalar::q() {
+        return Err(TssError::InvalidKey);
     }
-    Keys::verify_dlog_proofs(
-        &context.params,
-        &dlog_proof_vec,
-        &context.point_vec.as_ref().unwrap(),
-    )?;
+    let secret: Scalar = ECScalar::from(&secret_bn);
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
 
-    //save key to file:
-    let paillier_key_vec = (0..context.params.share_count as u16)
-        .map(|i| context.bc1_vec.as_ref().unwrap()[i as usize].e.clone())
+    let (dealer_msg, shares) = dealer::deal(&params, &secret);
+    let paillier_key_vec = dealer_msg
+        .bc1_vec
+        .iter()
+        .map(|bc1| bc1.e.clone())
         .collect::<Vec<EncryptionKey>>();
+    let h1_h2_N_tilde_vec = dealer_msg
+        .bc1_vec
+        .iter()
+        .map(|bc1| bc1.dlog_statement.clone())
+        .collect::<Vec<DLogStatement>>();
+    let y_sum = dealer_msg.vss_scheme.commitments[0].clone();
+    let chain_code = bip32::chain_code(&dealer_msg.decom_vec);
+    let key_stores = shares
+        .into_iter()
+        .map(|(party_keys, x_i)| {
+            let party_num_int = party_keys.party_index as u16;
+            let shared_keys = SharedKeys {
+                y: y_sum.clone(),
+                x_i,
+            };
+            serde_json::to_string(&(
+                party_keys,
+                shared_keys,
+                party_num_int,
+                vec![dealer_msg.vss_scheme.clone()],
+                &paillier_key_vec,
+                &y_sum,
+                &h1_h2_N_tilde_vec,
+                Some(chain_code),
+            ))
+        })
+        .collect::<serde_json::Result<Vec<String>>>()?;
 
-    let keygen_json = serde_json::to_string(&(
-        context.party_keys.as_ref().unwrap(),
-        context.shared_keys.as_ref().unwrap(),
-        context.party_num_int,
-        context.vss_scheme_vec.as_ref().unwrap(),
-        paillier_key_vec,
-        context.y_sum.as_ref().unwrap(),
-    ))?;
-
-    Ok(keygen_json)
+    Ok(serde_json::to_string(&(key_stores, dealer_msg))?)
 }
 
-pub async fn signup_keygen(client: &Client, addr: &str) -> Result<PartySignup> {
-    let key = "signup-keygen".to_string();
-    let res_body = postb(client, addr, "signupkeygen", key).await?;
-    let u: std::result::Result<PartySignup, ()> = serde_json::from_str(&res_body)?;
-    Ok(u.unwrap())
-}
+/// Checks a key store of `gg18_dealer_import` against the dealer message: the keys and
+/// proofs of every party, and the share of the key store against the VSS commitments.
+/// Returns the hex of the compressed public key, to compare with the imported one.
+#[wasm_bindgen]
+pub fn gg18_dealer_verify_key_store(
+    t: usize,
+    n: usize,
+    key_store: String,
+    dealer_msg: String,
+) -> Result<String> {
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
+    let dealer_msg = serde_json::from_str::<DealerBroadcastMessage>(&dealer_msg)?;
+    let (
+        party_keys,
+        shared_keys,
+        party_num_int,
+        vss_scheme_vec,
+        paillier_key_vec,
+        y_sum,
+        h1_h2_N_tilde_vec,
+        chain_code,
+    ) = parse_key_store(&key_store)?;
+    let checked_keys =
+        dealer::verify_construct_keypair(&params, &dealer_msg, &party_keys, &shared_keys.x_i)?;
+
+    let bc1_vec = &dealer_msg.bc1_vec;
+    let matches_dealer_msg = usize::from(party_num_int) == party_keys.party_index
+        && shared_keys.y == checked_keys.y
+        && y_sum == checked_keys.y
+        && vss_scheme_vec == vec![dealer_msg.vss_scheme.clone()]
+        && paillier_key_vec.len() == n
+        && h1_h2_N_tilde_vec.len() == n
+        && (0..n).all(|i| {
+            paillier_key_vec[i] == bc1_vec[i].e
+                && h1_h2_N_tilde_vec[i].N == bc1_vec[i].dlog_statement.N
+                && h1_h2_N_tilde_vec[i].g == bc1_vec[i].dlog_statement.g
+                && h1_h2_N_tilde_vec[i].ni == bc1_vec[i].dlog_statement.ni
+        })
+        && chain_code == Some(bip32::chain_code(&dealer_msg.decom_vec));
+    if !matches_dealer_msg {
+        return Err(TssError::UnknownError {
+            msg: "key store does not match the dealer message".to_string(),
+            line: line!(),
+        });
+    }
 
-pub async fn signup_sign(client: &Client, addr: &str) -> Result<PartySignup> {
-    let key = "signup-sign".to_string();
-    let res_body = postb(client, addr, "signupsign", key).await?;
-    let u: std::result::Result<PartySignup, ()> = serde_json::from_str(&res_body)?;
-    Ok(u.unwrap())
+    Ok(hex::encode(y_sum.to_bytes(true)))
 }
 
-#[wasm_bindgen]
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct GG18SignClientContext {
+pub struct GG18RefreshClientContext {
     addr: String,
-    party_keys: Keys,
-    shared_keys: SharedKeys,
-    party_id: u16,
-    vss_scheme_vec: Vec<VerifiableSS>,
-    paillier_key_vector: Vec<EncryptionKey>,
-    y_sum: Point,
-    threshould: u16,
-    party_num_int: u16,
     uuid: String,
-    sign_keys: Option<SignKeys>,
-    com: Option<SignBroadcastPhase1>,
-    decommit: Option<SignDecommitPhase1>,
-    round1_ans_vec: Option<Vec<String>>,
-    signers_vec: Option<Vec<usize>>,
-    round2_ans_vec: Option<Vec<String>>,
-    xi_com_vec: Option<Vec<crate::curv::elliptic::curves::secp256_k1::Secp256k1Point>>,
-    beta_vec: Option<Vec<Scalar>>,
-    ni_vec: Option<Vec<Scalar>>,
-    bc1_vec: Option<Vec<SignBroadcastPhase1>>,
-    m_b_gamma_rec_vec: Option<Vec<MessageB>>,
-    delta_inv: Option<crate::curv::elliptic::curves::secp256_k1::Secp256k1Scalar>,
-    sigma: Option<crate::curv::elliptic::curves::secp256_k1::Secp256k1Scalar>,
-    message: Vec<u8>,
-    phase5_com: Option<Phase5Com1>,
-    phase_5a_decom: Option<Phase5ADecom1>,
-    helgamal_proof: Option<HomoELGamalProof>,
-    dlog_proof_rho: Option<DLogProof>,
-    commit5a_vec: Option<Vec<Phase5Com1>>,
-    local_sig: Option<LocalSignature>,
-    r: Option<crate::curv::elliptic::curves::secp256_k1::Secp256k1Point>,
-    phase5_com2: Option<Phase5Com2>,
-    phase_5d_decom2: Option<Phase5DDecom2>,
-    decommit5a_and_elgamal_and_dlog_vec: Option<Vec<(Phase5ADecom1, HomoELGamalProof, DLogProof)>>,
-    decommit5a_and_elgamal_and_dlog_vec_includes_i:
-        Option<Vec<(Phase5ADecom1, HomoELGamalProof, DLogProof)>>,
-    s_i: Option<crate::curv::elliptic::curves::secp256_k1::Secp256k1Scalar>,
-    commit5c_vec: Option<Vec<Phase5Com2>>,
+    refresh: Refresh,
+    outgoing: Vec<Msg>,
 }
 
+/// Starts a refresh of `key_store`, run by all n parties. Messages are sent under the
+/// party number of the key store; the signup only provides the session.
 #[wasm_bindgen]
-pub async fn gg18_sign_client_new_context(
+pub async fn gg18_refresh_client_new_context(
     addr: String,
     t: usize,
-    _n: usize,
+    n: usize,
     key_store: String,
-    message_str: String,
+    _delay: u32,
 ) -> Result<String> {
-    let message = match hex::decode(message_str.clone()) {
-        Ok(x) => x,
-        Err(_e) => message_str.as_bytes().to_vec(),
-    };
-    // let message = &message[..];
     let client = new_client_with_headers()?;
-
-    let (party_keys, shared_keys, party_id, vss_scheme_vec, paillier_key_vector, y_sum): (
-        Keys,
-        SharedKeys,
-        u16,
-        Vec<VerifiableSS>,
-        Vec<EncryptionKey>,
-        Point,
-    ) = serde_json::from_str(&key_store)?;
-
-    //signup:
-    let (party_num_int, uuid) = match signup_sign(&client, &addr).await? {
-        PartySignup { number, uuid } => (number, uuid),
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
     };
+    let key_store = parse_key_store(&key_store)?;
 
-    Ok(serde_json::to_string(&GG18SignClientContext {
+    let uuid = signup_refresh(&client, &addr).await?.uuid;
+
+    Ok(serde_json::to_string(&GG18RefreshClientContext {
         addr,
-        party_keys,
-        shared_keys,
-        party_id,
-        vss_scheme_vec,
-        paillier_key_vector,
-        y_sum,
-        threshould: t as u16,
-        party_num_int,
         uuid,
-        sign_keys: None,
-        com: None,
-        decommit: None,
-        round1_ans_vec: None,
-        signers_vec: None,
-        round2_ans_vec: None,
-        xi_com_vec: None,
-        beta_vec: None,
-        ni_vec: None,
-        bc1_vec: None,
-        m_b_gamma_rec_vec: None,
-        delta_inv: None,
-        message, // TODO: The message is plain now
-        sigma: None,
-        phase5_com: None,
-        phase_5a_decom: None,
-        helgamal_proof: None,
-        dlog_proof_rho: None,
-        commit5a_vec: None,
-        local_sig: None,
-        r: None,
-        phase5_com2: None,
-        phase_5d_decom2: None,
-        decommit5a_and_elgamal_and_dlog_vec: None,
-        decommit5a_and_elgamal_and_dlog_vec_includes_i: None,
-        s_i: None,
-        commit5c_vec: None,
+        refresh: Refresh::new(params, key_store)?,
+        outgoing: Vec::new(),
     })?)
 }
 
-#[wasm_bindgen]
-pub async fn gg18_sign_client_round0(context: String, delay: u32) -> Result<String> {
-    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+/// Exchanges the messages of `rounds` rounds of refresh.
+async fn gg18_refresh_client_exchange(
+    context: &mut GG18RefreshClientContext,
+    delay: u32,
+    rounds: usize,
+) -> Result<()> {
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    for _ in 0..rounds {
+        let outgoing = std::mem::take(&mut context.outgoing);
+        context.outgoing = exchange(&mut context.refresh, &transport, outgoing).await?;
+    }
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub async fn gg18_refresh_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18RefreshClientContext>(&context)?;
+    context.outgoing = context.refresh.proceed()?;
+    gg18_refresh_client_exchange(&mut context, delay, 1).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg18_refresh_client_round2(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18RefreshClientContext>(&context)?;
+    gg18_refresh_client_exchange(&mut context, delay, 1).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg18_refresh_client_round3(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18RefreshClientContext>(&context)?;
+    gg18_refresh_client_exchange(&mut context, delay, 1).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Exchanges the sharings of zero, settles the complaints about the shares and returns the
+/// refreshed key store, in the format of `gg18_keygen_client_round5`.
+#[wasm_bindgen]
+pub async fn gg18_refresh_client_round4(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18RefreshClientContext>(&context)?;
+    gg18_refresh_client_exchange(&mut context, delay, 3).await?;
+    let key_store = context
+        .refresh
+        .output()
+        .ok_or_else(|| missing_message("round4_reveals"))?;
+    key_store_to_string(key_store)
+}
+
+/// Who a participant of a resharing is: an old holder, identified by its party number in the
+/// old key store, a party of the new committee, or both.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct ReshareRole {
+    old_party_num: Option<u16>,
+    new_party_num: Option<u16>,
+    /// the BIP-32 chain code of the key store of an old holder
+    chain_code: Option<ChainCode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GG18ReshareClientContext {
+    addr: String,
+    params: Parameters,
+    new_params: Parameters,
+    parties: u16,
+    party_num_int: u16,
+    uuid: String,
+    role: ReshareRole,
+    party_keys: Option<Keys>,
+    shared_keys: Option<SharedKeys>,
+    vss_scheme_vec: Option<Vec<VerifiableSS>>,
+    s: Option<Vec<usize>>,
+    dealer_nums: Option<Vec<u16>>,
+    new_party_nums: Option<Vec<u16>>,
+    chain_code: Option<ChainCode>,
+    new_party_keys: Option<Keys>,
+    bc1_vec: Option<Vec<KeyGenBroadcastMessage1>>,
+    decom_i: Option<KeyGenDecommitMessage1>,
+    secret_shares: Option<Vec<Scalar>>,
+    reshare_msg_vec: Option<Vec<ReshareBroadcastMessage>>,
+    decom_vec: Option<Vec<KeyGenDecommitMessage1>>,
+}
+
+/// Starts a resharing of a (t, n) key to a (new_t, new_n) committee. Every participant signs
+/// up, `parties` of them in all: old holders pass their `key_store`, the others an empty
+/// string, and parties of the new committee pass their `new_party_num` in 1..=new_n, the
+/// others 0. The first t + 1 old holders, by party number, deal the new shares.
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen]
+pub async fn gg18_reshare_client_new_context(
+    addr: String,
+    t: usize,
+    n: usize,
+    new_t: usize,
+    new_n: usize,
+    parties: u16,
+    key_store: String,
+    new_party_num: u16,
+    _delay: u32,
+) -> Result<String> {
     let client = new_client_with_headers()?;
-    // round 0: collect signers IDs
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round0",
-        serde_json::to_string(&context.party_id)?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round0_ans_vec = poll_for_broadcasts(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        context.threshould + 1,
-        "round0",
-        context.uuid.clone(),
-        delay,
-    )
-    .await?;
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
+    let new_params = Parameters {
+        threshold: new_t,
+        share_count: new_n,
+    };
 
-    let mut j = 0;
-    let mut signers_vec: Vec<usize> = Vec::new();
-    for i in 1..=context.threshould + 1 {
-        if i == context.party_num_int {
-            signers_vec.push((context.party_id - 1).into());
+    let (party_keys, shared_keys, old_party_num, vss_scheme_vec, chain_code) =
+        if key_store.is_empty() {
+            (None, None, None, None, None)
         } else {
-            let signer_j: u16 = serde_json::from_str(&round0_ans_vec[j])?;
-            signers_vec.push((signer_j - 1).into());
-            j += 1;
-        }
+            let (party_keys, shared_keys, party_num_int, vss_scheme_vec, _, _, _, chain_code) =
+                parse_key_store(&key_store)?;
+            (
+                Some(party_keys),
+                Some(shared_keys),
+                Some(party_num_int),
+                Some(vss_scheme_vec),
+                chain_code,
+            )
+        };
+    let role = ReshareRole {
+        old_party_num,
+        new_party_num: Some(new_party_num).filter(|&j| j != 0),
+        chain_code,
+    };
+
+    let (party_num_int, uuid) = match signup_reshare(&client, &addr, parties).await? {
+        PartySignup { number, uuid } => (number, uuid),
+    };
+
+    Ok(serde_json::to_string(&GG18ReshareClientContext {
+        addr,
+        params,
+        new_params,
+        parties,
+        party_num_int,
+        uuid,
+        role,
+        party_keys,
+        shared_keys,
+        vss_scheme_vec,
+        s: None,
+        dealer_nums: None,
+        new_party_nums: None,
+        chain_code: None,
+        new_party_keys: None,
+        bc1_vec: None,
+        decom_i: None,
+        secret_shares: None,
+        reshare_msg_vec: None,
+        decom_vec: None,
+    })?)
+}
+
+/// Round 0: the participants exchange their roles and settle the dealers.
+#[wasm_bindgen]
+pub async fn gg18_reshare_client_round0(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18ReshareClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round0",
+            serde_json::to_string(&context.role)?,
+        )
+        .await?;
+    let round0_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.parties, "round0")
+        .await?;
+
+    let mut roles: Vec<ReshareRole> = Vec::new();
+    format_vec_from_reads(
+        &round0_ans_vec,
+        context.party_num_int as usize,
+        context.role.clone(),
+        &mut roles,
+    )?;
+
+    let mut dealers = roles
+        .iter()
+        .enumerate()
+        .filter_map(|(i, role)| role.old_party_num.map(|old| (old, i as u16 + 1)))
+        .collect::<Vec<(u16, u16)>>();
+    dealers.sort();
+    dealers.dedup_by_key(|(old, _)| *old);
+    dealers.truncate(context.params.threshold + 1);
+    if dealers.len() != context.params.threshold + 1 {
+        return Err(TssError::UnknownError {
+            msg: "not enough old holders to reshare".to_string(),
+            line: line!(),
+        });
+    }
+    let new_party_nums = (1..=context.new_params.share_count as u16)
+        .map(|j| {
+            roles
+                .iter()
+                .position(|role| role.new_party_num == Some(j))
+                .map(|i| i as u16 + 1)
+                .ok_or_else(|| TssError::UnknownError {
+                    msg: format!("new party {} missing", j),
+                    line: line!(),
+                })
+        })
+        .collect::<Result<Vec<u16>>>()?;
+
+    // the new parties take the chain code of the dealers
+    let chain_code = roles[usize::from(dealers[0].1 - 1)].chain_code;
+    if dealers
+        .iter()
+        .any(|(_, num)| roles[usize::from(num - 1)].chain_code != chain_code)
+    {
+        return Err(TssError::UnknownError {
+            msg: "dealers disagree on the chain code".to_string(),
+            line: line!(),
+        });
     }
 
-    let private =
-        PartyPrivate::set_private(context.party_keys.clone(), context.shared_keys.clone());
+    context.s = Some(dealers.iter().map(|(old, _)| *old as usize - 1).collect());
+    context.dealer_nums = Some(dealers.iter().map(|(_, num)| *num).collect());
+    context.new_party_nums = Some(new_party_nums);
+    context.chain_code = chain_code;
 
-    let sign_keys = SignKeys::create(
-        &private,
-        &context.vss_scheme_vec[usize::from(signers_vec[usize::from(context.party_num_int - 1)])],
-        signers_vec[usize::from(context.party_num_int - 1)].into(),
-        &signers_vec,
-    );
+    Ok(serde_json::to_string(&context)?)
+}
 
-    let xi_com_vec = Keys::get_commitments_to_xi(&context.vss_scheme_vec);
+/// Round 1: the new parties send their Paillier keys and h1, h2, N_tilde as in keygen, and
+/// the dealers their VSS commitments.
+#[wasm_bindgen]
+pub async fn gg18_reshare_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18ReshareClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    let s = context.s.clone().unwrap();
+    let dealer_nums = context.dealer_nums.clone().unwrap();
+    let new_party_nums = context.new_party_nums.clone().unwrap();
+
+    let new_party_keys = context.role.new_party_num.map(|j| Keys::create(j as usize));
+    let (bc_i, decom_i) = match &new_party_keys {
+        Some(keys) => {
+            let (bc_i, decom_i) = keys.phase1_broadcast_phase3_proof_of_correct_key();
+            (Some(bc_i), Some(decom_i))
+        }
+        None => (None, None),
+    };
+    let (msg_i, secret_shares) = match dealer_nums.iter().position(|&d| d == context.party_num_int)
+    {
+        Some(k) => {
+            let vss_scheme_vec = context.vss_scheme_vec.clone().unwrap();
+            let (vss_scheme, secret_shares) = Keys::reshare_distribute(
+                &context.new_params,
+                context.shared_keys.as_ref().unwrap(),
+                &vss_scheme_vec,
+                s[k],
+                &s,
+            );
+            let msg_i = ReshareBroadcastMessage {
+                y_i: context.party_keys.as_ref().unwrap().y_i.clone(),
+                old_vss_scheme_vec: vss_scheme_vec,
+                vss_scheme,
+            };
+            (Some(msg_i), Some(secret_shares))
+        }
+        None => (None, None),
+    };
 
-    context.sign_keys = Some(sign_keys);
-    context.signers_vec = Some(signers_vec);
-    context.xi_com_vec = Some(xi_com_vec);
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round1",
+            serde_json::to_string(&(&bc_i, &msg_i))?,
+        )
+        .await?;
+    let round1_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.parties, "round1")
+        .await?;
+
+    let mut round1_vec: Vec<(
+        Option<KeyGenBroadcastMessage1>,
+        Option<ReshareBroadcastMessage>,
+    )> = Vec::new();
+    format_vec_from_reads(
+        &round1_ans_vec,
+        context.party_num_int as usize,
+        (bc_i, msg_i),
+        &mut round1_vec,
+    )?;
+    let bc1_vec = new_party_nums
+        .iter()
+        .map(|&num| round1_vec[usize::from(num - 1)].0.clone())
+        .collect::<Option<Vec<KeyGenBroadcastMessage1>>>()
+        .ok_or_else(|| missing_message("round1"))?;
+    let reshare_msg_vec = dealer_nums
+        .iter()
+        .map(|&num| round1_vec[usize::from(num - 1)].1.clone())
+        .collect::<Option<Vec<ReshareBroadcastMessage>>>()
+        .ok_or_else(|| missing_message("round1"))?;
+
+    context.decom_i = match (&new_party_keys, decom_i) {
+        (Some(keys), Some(mut decom_i)) => {
+            decom_i.no_small_factor_proofs = keys.phase1_no_small_factor_proofs(&bc1_vec);
+            Some(decom_i)
+        }
+        _ => None,
+    };
+    context.new_party_keys = new_party_keys;
+    context.bc1_vec = Some(bc1_vec);
+    context.secret_shares = secret_shares;
+    context.reshare_msg_vec = Some(reshare_msg_vec);
 
     Ok(serde_json::to_string(&context)?)
 }
 
+/// Round 2: the new parties decommit and check each other's keys as in keygen.
 #[wasm_bindgen]
-pub async fn gg18_sign_client_round1(context: String, delay: u32) -> Result<String> {
-    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
-    let client = new_client_with_headers()?;
-    let (com, decommit) = context.sign_keys.as_ref().unwrap().phase1_broadcast();
-    let (m_a_k, _) = MessageA::a(
-        &context.sign_keys.as_ref().unwrap().k_i,
-        &context.party_keys.ek,
-        &[],
-    );
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round1",
-        serde_json::to_string(&(com.clone(), m_a_k))?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round1_ans_vec = poll_for_broadcasts(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        context.threshould + 1,
-        "round1",
-        context.uuid.clone(),
-        delay,
-    )
-    .await?;
+pub async fn gg18_reshare_client_round2(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18ReshareClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round2",
+            serde_json::to_string(&context.decom_i)?,
+        )
+        .await?;
+    let round2_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.parties, "round2")
+        .await?;
+
+    let mut round2_vec: Vec<Option<KeyGenDecommitMessage1>> = Vec::new();
+    format_vec_from_reads(
+        &round2_ans_vec,
+        context.party_num_int as usize,
+        context.decom_i.clone(),
+        &mut round2_vec,
+    )?;
+    let decom_vec = context
+        .new_party_nums
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|&num| round2_vec[usize::from(num - 1)].clone())
+        .collect::<Option<Vec<KeyGenDecommitMessage1>>>()
+        .ok_or_else(|| missing_message("round2"))?;
+
+    if let Some(keys) = &context.new_party_keys {
+        keys.phase1_verify_com_phase3_verify_correct_key(
+            &context.new_params,
+            &decom_vec,
+            context.bc1_vec.as_ref().unwrap(),
+        )
+        .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+    }
 
-    context.com = Some(com);
-    context.decommit = Some(decommit);
-    context.round1_ans_vec = Some(round1_ans_vec);
+    context.decom_vec = Some(decom_vec);
 
     Ok(serde_json::to_string(&context)?)
 }
 
+/// Round 3: every dealer sends its sub-shares to the new parties, which check them against
+/// the old VSS commitments. Returns the key store of a new party, in the format of
+/// `gg18_keygen_client_round5`, and an empty string to an old holder that leaves. Blames
+/// for the dealing carry old party numbers, blames for the keys new party numbers.
 #[wasm_bindgen]
-pub async fn gg18_sign_client_round2(context: String, delay: u32) -> Result<String> {
-    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+pub async fn gg18_reshare_client_round3(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<GG18ReshareClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    let s = context.s.as_ref().unwrap();
+    let dealer_nums = context.dealer_nums.as_ref().unwrap();
+    let new_party_nums = context.new_party_nums.as_ref().unwrap();
+    let decom_vec = context.decom_vec.as_ref().unwrap();
+
+    // everyone sends to everyone, so that the messages can be polled like in keygen
+    for i in (1..=context.parties).filter(|&i| i != context.party_num_int) {
+        let aead_pack_i = match (
+            &context.secret_shares,
+            new_party_nums.iter().position(|&num| num == i),
+        ) {
+            (Some(secret_shares), Some(j)) => {
+                let key_i = aes_key(&decom_vec[j].y_i, &context.party_keys.as_ref().unwrap().u_i);
+                let plaintext = BigInt::to_vec(&secret_shares[j].to_big_int());
+                Some(aes_encrypt(&key_i, &plaintext)?)
+            }
+            _ => None,
+        };
+        transport
+            .send_p2p(
+                context.party_num_int,
+                i,
+                "round3",
+                serde_json::to_string(&aead_pack_i)?,
+            )
+            .await?;
+    }
+    let round3_ans_vec = transport
+        .recv_p2p(context.party_num_int, context.parties, "round3")
+        .await?;
+
+    let new_party_keys = match &context.new_party_keys {
+        Some(keys) => keys,
+        None => return Ok(String::new()),
+    };
+    let new_party_num = context.role.new_party_num.unwrap() as usize;
+    let mut round3_vec: Vec<Option<AEAD>> = Vec::new();
+    format_vec_from_reads(
+        &round3_ans_vec,
+        context.party_num_int as usize,
+        None,
+        &mut round3_vec,
+    )?;
+
+    let reshare_msg_vec = context.reshare_msg_vec.as_ref().unwrap();
+    let party_shares = (0..s.len())
+        .map(|k| {
+            if dealer_nums[k] == context.party_num_int {
+                return context.secret_shares.as_ref().unwrap()[new_party_num - 1].clone();
+            }
+            let key_k = aes_key(&reshare_msg_vec[k].y_i, &new_party_keys.u_i);
+            // a share that does not decrypt fails the share check like any invalid share
+            match round3_vec[usize::from(dealer_nums[k] - 1)].clone() {
+                Some(aead_pack) => match aes_decrypt(&key_k, aead_pack) {
+                    Ok(out) => ECScalar::from(&BigInt::from_bytes_be(&out[..])),
+                    Err(_) => ECScalar::zero(),
+                },
+                None => ECScalar::zero(),
+            }
+        })
+        .collect::<Vec<Scalar>>();
+
+    // an old holder knows the old VSS commitments, a newcomer goes by the dealers' majority
+    let old_vss_scheme_vec =
+        Keys::reshare_old_vss_scheme_vec(reshare_msg_vec, context.vss_scheme_vec.as_deref())
+            .map_err(|e| e.map_blamed_parties(|k| s[k] + 1))?;
+    let vss_scheme_vec = reshare_msg_vec
+        .iter()
+        .map(|msg| msg.vss_scheme.clone())
+        .collect::<Vec<VerifiableSS>>();
+    let shared_keys = Keys::reshare_verify_construct_keypair(
+        &context.new_params,
+        &old_vss_scheme_vec,
+        s,
+        &party_shares,
+        &vss_scheme_vec,
+        new_party_num,
+    )
+    .map_err(|e| e.map_blamed_parties(|k| s[k] + 1))?;
+
+    let bc1_vec = context.bc1_vec.as_ref().unwrap();
+    let paillier_key_vec = bc1_vec
+        .iter()
+        .map(|bc1| bc1.e.clone())
+        .collect::<Vec<EncryptionKey>>();
+    let h1_h2_N_tilde_vec = bc1_vec
+        .iter()
+        .map(|bc1| bc1.dlog_statement.clone())
+        .collect::<Vec<DLogStatement>>();
+
+    let keygen_json = serde_json::to_string(&(
+        new_party_keys,
+        &shared_keys,
+        new_party_num as u16,
+        vss_scheme_vec,
+        paillier_key_vec,
+        &shared_keys.y,
+        h1_h2_N_tilde_vec,
+        context.chain_code,
+    ))?;
+
+    Ok(keygen_json)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GG18RepairClientContext {
+    addr: String,
+    params: Parameters,
+    party_num_int: u16,
+    lost_party_num: u16,
+    uuid: String,
+    party_keys: Keys,
+    shared_keys: Option<SharedKeys>,
+    vss_scheme_vec: Option<Vec<VerifiableSS>>,
+    bc1_vec: Option<Vec<KeyGenBroadcastMessage1>>,
+    decom_i: Option<KeyGenDecommitMessage1>,
+    enc_keys: Option<Vec<Vec<u8>>>,
+    repair_msg: Option<RepairBroadcastMessage>,
+    parts: Option<Vec<Scalar>>,
+    repair_msg_vec: Option<Vec<RepairBroadcastMessage>>,
+    sum: Option<Scalar>,
+    chain_code: Option<ChainCode>,
+}
+
+/// Starts the repair of the share of party `lost_party_num`, run by all n parties. The
+/// recovering party passes an empty `key_store` and gets a fresh Paillier key, which the
+/// other parties take into their key stores. The first t + 1 other parties help.
+#[wasm_bindgen]
+pub async fn gg18_repair_client_new_context(
+    addr: String,
+    t: usize,
+    n: usize,
+    key_store: String,
+    lost_party_num: u16,
+    _delay: u32,
+) -> Result<String> {
     let client = new_client_with_headers()?;
-    let mut j = 0;
-    let mut bc1_vec: Vec<SignBroadcastPhase1> = Vec::new();
-    let mut m_a_vec: Vec<MessageA> = Vec::new();
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
 
-    for i in 1..context.threshould + 2 {
-        if i == context.party_num_int {
-            bc1_vec.push(context.com.as_ref().unwrap().clone());
-        //   m_a_vec.push(m_a_k.clone());
+    let (party_keys, shared_keys, party_num_int, vss_scheme_vec, chain_code) =
+        if key_store.is_empty() {
+            let party_keys = Keys::create(lost_party_num as usize);
+            (party_keys, None, lost_party_num, None, None)
         } else {
-            //     if signers_vec.contains(&(i as usize)) {
-            let (bc1_j, m_a_party_j): (SignBroadcastPhase1, MessageA) =
-                serde_json::from_str(&context.round1_ans_vec.as_ref().unwrap()[j])?;
-            bc1_vec.push(bc1_j);
-            m_a_vec.push(m_a_party_j);
+            let (party_keys, shared_keys, party_num_int, vss_scheme_vec, _, _, _, chain_code) =
+                parse_key_store(&key_store)?;
+            (
+                party_keys,
+                Some(shared_keys),
+                party_num_int,
+                Some(vss_scheme_vec),
+                chain_code,
+            )
+        };
 
-            j += 1;
-            //       }
+    let uuid = signup_repair(&client, &addr).await?.uuid;
+
+    Ok(serde_json::to_string(&GG18RepairClientContext {
+        addr,
+        params,
+        party_num_int,
+        lost_party_num,
+        uuid,
+        party_keys,
+        shared_keys,
+        vss_scheme_vec,
+        bc1_vec: None,
+        decom_i: None,
+        enc_keys: None,
+        repair_msg: None,
+        parts: None,
+        repair_msg_vec: None,
+        sum: None,
+        chain_code,
+    })?)
+}
+
+/// Round 1: every party sends its Paillier key and h1, h2, N_tilde as in keygen, so that
+/// the recovering party learns them and the others learn its new ones.
+#[wasm_bindgen]
+pub async fn gg18_repair_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18RepairClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    let (bc_i, mut decom_i) = context
+        .party_keys
+        .phase1_broadcast_phase3_proof_of_correct_key();
+
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round1",
+            serde_json::to_string(&bc_i)?,
+        )
+        .await?;
+    let round1_ans_vec = transport
+        .recv_broadcasts(
+            context.party_num_int,
+            context.params.share_count as u16,
+            "round1",
+        )
+        .await?;
+
+    let mut bc1_vec = Vec::new();
+    format_vec_from_reads(
+        &round1_ans_vec,
+        context.party_num_int as usize,
+        bc_i,
+        &mut bc1_vec,
+    )?;
+    decom_i.no_small_factor_proofs = context.party_keys.phase1_no_small_factor_proofs(&bc1_vec);
+
+    context.bc1_vec = Some(bc1_vec);
+    context.decom_i = Some(decom_i);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Round 2: the parties decommit and check each other's keys, and the helpers split their
+/// terms of the Lagrange interpolation at the lost index.
+#[wasm_bindgen]
+pub async fn gg18_repair_client_round2(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18RepairClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round2",
+            serde_json::to_string(&context.decom_i.as_ref().unwrap())?,
+        )
+        .await?;
+    let round2_ans_vec = transport
+        .recv_broadcasts(
+            context.party_num_int,
+            context.params.share_count as u16,
+            "round2",
+        )
+        .await?;
+
+    let mut decom_vec = Vec::new();
+    format_vec_from_reads(
+        &round2_ans_vec,
+        context.party_num_int as usize,
+        context.decom_i.clone().unwrap(),
+        &mut decom_vec,
+    )?;
+    let enc_keys = decom_vec
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| i + 1 != context.party_num_int as usize)
+        .map(|(_, decom_j)| aes_key(&decom_j.y_i, &context.party_keys.u_i))
+        .collect::<Vec<Vec<u8>>>();
+
+    context
+        .party_keys
+        .phase1_verify_com_phase3_verify_correct_key(
+            &context.params,
+            &decom_vec,
+            context.bc1_vec.as_ref().unwrap(),
+        )
+        .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+
+    let s = repair_helpers(&context);
+    let lost_index = usize::from(context.lost_party_num - 1);
+    if let Some(shared_keys) = &context.shared_keys {
+        let index = usize::from(context.party_num_int - 1);
+        if s.contains(&index) {
+            let (repair_msg, parts) = Keys::repair_phase1_distribute(
+                shared_keys,
+                context.vss_scheme_vec.as_ref().unwrap(),
+                index,
+                &s,
+                lost_index,
+            );
+            context.repair_msg = Some(repair_msg);
+            context.parts = Some(parts);
         }
     }
-    assert_eq!(context.signers_vec.as_ref().unwrap().len(), bc1_vec.len());
+    context.enc_keys = Some(enc_keys);
 
-    //////////////////////////////////////////////////////////////////////////////
-    let mut m_b_gamma_send_vec: Vec<MessageB> = Vec::new();
-    let mut beta_vec: Vec<Scalar> = Vec::new();
-    let mut m_b_w_send_vec: Vec<MessageB> = Vec::new();
-    let mut ni_vec: Vec<Scalar> = Vec::new();
-    let mut j = 0;
-    for i in 1..context.threshould + 2 {
-        if i != context.party_num_int {
-            let (m_b_gamma, beta_gamma, _, _) = MessageB::b(
-                &context.sign_keys.as_ref().unwrap().gamma_i,
-                &context.paillier_key_vector
-                    [usize::from(context.signers_vec.as_ref().unwrap()[usize::from(i - 1)])],
-                m_a_vec[j].clone(),
-                &[],
-            )
-            .unwrap();
-            let (m_b_w, beta_wi, _, _) = MessageB::b(
-                &context.sign_keys.as_ref().unwrap().w_i,
-                &context.paillier_key_vector
-                    [usize::from(context.signers_vec.as_ref().unwrap()[usize::from(i - 1)])],
-                m_a_vec[j].clone(),
-                &[],
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Round 3: every helper commits to its parts and sends them to the other helpers, which
+/// check them and add them up.
+#[wasm_bindgen]
+pub async fn gg18_repair_client_round3(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18RepairClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    let s = repair_helpers(&context);
+    let share_count = context.params.share_count as u16;
+
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round3",
+            serde_json::to_string(&context.repair_msg)?,
+        )
+        .await?;
+    // everyone sends to everyone, so that the messages can be polled like in keygen
+    let enc_keys = context.enc_keys.as_ref().unwrap();
+    for (j, i) in (1..=share_count)
+        .filter(|&i| i != context.party_num_int)
+        .enumerate()
+    {
+        let aead_pack_i = match (
+            &context.parts,
+            s.iter().position(|&helper| helper + 1 == usize::from(i)),
+        ) {
+            (Some(parts), Some(k)) => {
+                let plaintext = BigInt::to_vec(&parts[k].to_big_int());
+                Some(aes_encrypt(&enc_keys[j], &plaintext)?)
+            }
+            _ => None,
+        };
+        transport
+            .send_p2p(
+                context.party_num_int,
+                i,
+                "round3",
+                serde_json::to_string(&aead_pack_i)?,
             )
-            .unwrap();
-            m_b_gamma_send_vec.push(m_b_gamma);
-            m_b_w_send_vec.push(m_b_w);
-            beta_vec.push(beta_gamma);
-            ni_vec.push(beta_wi);
-            j += 1;
-        }
+            .await?;
     }
+    let round3_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, share_count, "round3")
+        .await?;
+    let round3_p2p_ans_vec = transport
+        .recv_p2p(context.party_num_int, share_count, "round3")
+        .await?;
+
+    let mut repair_msg_vec: Vec<Option<RepairBroadcastMessage>> = Vec::new();
+    format_vec_from_reads(
+        &round3_ans_vec,
+        context.party_num_int as usize,
+        context.repair_msg.clone(),
+        &mut repair_msg_vec,
+    )?;
+    let repair_msg_vec = s
+        .iter()
+        .map(|&helper| repair_msg_vec[helper].clone())
+        .collect::<Option<Vec<RepairBroadcastMessage>>>()
+        .ok_or_else(|| missing_message("round3"))?;
+    context.repair_msg_vec = Some(repair_msg_vec.clone());
+
+    let position = match s
+        .iter()
+        .position(|&helper| helper + 1 == usize::from(context.party_num_int))
+    {
+        Some(position) => position,
+        None => return Ok(serde_json::to_string(&context)?),
+    };
+    let mut round3_p2p_vec: Vec<Option<AEAD>> = Vec::new();
+    format_vec_from_reads(
+        &round3_p2p_ans_vec,
+        context.party_num_int as usize,
+        None,
+        &mut round3_p2p_vec,
+    )?;
+    let parts = s
+        .iter()
+        .map(|&helper| {
+            if helper + 1 == usize::from(context.party_num_int) {
+                return context.parts.as_ref().unwrap()[position].clone();
+            }
+            let j = if helper + 1 < usize::from(context.party_num_int) {
+                helper
+            } else {
+                helper - 1
+            };
+            // a part that does not decrypt fails the commitment check like any invalid part
+            match round3_p2p_vec[helper].clone() {
+                Some(aead_pack) => match aes_decrypt(&enc_keys[j], aead_pack) {
+                    Ok(out) => ECScalar::from(&BigInt::from_bytes_be(&out[..])),
+                    Err(_) => ECScalar::zero(),
+                },
+                None => ECScalar::zero(),
+            }
+        })
+        .collect::<Vec<Scalar>>();
 
-    let mut j = 0;
-    for i in 1..context.threshould + 2 {
-        if i != context.party_num_int {
-            sendp2p(
-                &client,
-                &context.addr,
+    let sum = Keys::repair_phase2_sum_parts(
+        context.vss_scheme_vec.as_ref().unwrap(),
+        &s,
+        usize::from(context.lost_party_num - 1),
+        &parts,
+        &repair_msg_vec,
+        position,
+    )
+    .map_err(|e| e.map_blamed_parties(|k| s[k] + 1))?;
+
+    context.sum = Some(sum);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Round 4: the helpers send their sums to the recovering party, which checks its share
+/// against the VSS commitments. Returns the key store of every party, in the format of
+/// `gg18_keygen_client_round5`, with the new Paillier key of the recovering party.
+#[wasm_bindgen]
+pub async fn gg18_repair_client_round4(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<GG18RepairClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    let s = repair_helpers(&context);
+    let share_count = context.params.share_count as u16;
+    let enc_keys = context.enc_keys.as_ref().unwrap();
+
+    let bc1_vec = context.bc1_vec.as_ref().unwrap();
+    let paillier_key_vec = bc1_vec
+        .iter()
+        .map(|bc1| bc1.e.clone())
+        .collect::<Vec<EncryptionKey>>();
+    let h1_h2_N_tilde_vec = bc1_vec
+        .iter()
+        .map(|bc1| bc1.dlog_statement.clone())
+        .collect::<Vec<DLogStatement>>();
+
+    if context.party_num_int != context.lost_party_num {
+        let j = if context.lost_party_num < context.party_num_int {
+            context.lost_party_num - 1
+        } else {
+            context.lost_party_num - 2
+        };
+        let aead_pack = match &context.sum {
+            Some(sum) => {
+                let plaintext = BigInt::to_vec(&sum.to_big_int());
+                Some(aes_encrypt(&enc_keys[usize::from(j)], &plaintext)?)
+            }
+            None => None,
+        };
+        transport
+            .send_p2p(
                 context.party_num_int,
-                i,
-                "round2",
-                serde_json::to_string(&(m_b_gamma_send_vec[j].clone(), m_b_w_send_vec[j].clone()))?,
-                context.uuid.clone(),
+                context.lost_party_num,
+                "round4",
+                serde_json::to_string(&(&aead_pack, &context.chain_code))?,
             )
             .await?;
-            j += 1;
-        }
+
+        let shared_keys = context.shared_keys.as_ref().unwrap();
+        let keygen_json = serde_json::to_string(&(
+            &context.party_keys,
+            shared_keys,
+            context.party_num_int,
+            context.vss_scheme_vec.as_ref().unwrap(),
+            paillier_key_vec,
+            &shared_keys.y,
+            h1_h2_N_tilde_vec,
+            context.chain_code,
+        ))?;
+        return Ok(keygen_json);
     }
 
-    let round2_ans_vec = poll_for_p2p(
-        &client,
-        &context.addr,
+    let round4_ans_vec = transport
+        .recv_p2p(context.party_num_int, share_count, "round4")
+        .await?;
+    let mut round4_vec: Vec<(Option<AEAD>, Option<ChainCode>)> = Vec::new();
+    format_vec_from_reads(
+        &round4_ans_vec,
+        context.party_num_int as usize,
+        (None, None),
+        &mut round4_vec,
+    )?;
+
+    // the recovering party takes the chain code of the helpers
+    let chain_code = round4_vec[s[0]].1;
+    if s.iter().any(|&helper| round4_vec[helper].1 != chain_code) {
+        return Err(TssError::UnknownError {
+            msg: "helpers disagree on the chain code".to_string(),
+            line: line!(),
+        });
+    }
+
+    let sums = s
+        .iter()
+        .map(|&helper| {
+            let j = if helper + 1 < usize::from(context.party_num_int) {
+                helper
+            } else {
+                helper - 1
+            };
+            // a sum that does not decrypt fails the commitment check like any invalid sum
+            match round4_vec[helper].0.clone() {
+                Some(aead_pack) => match aes_decrypt(&enc_keys[j], aead_pack) {
+                    Ok(out) => ECScalar::from(&BigInt::from_bytes_be(&out[..])),
+                    Err(_) => ECScalar::zero(),
+                },
+                None => ECScalar::zero(),
+            }
+        })
+        .collect::<Vec<Scalar>>();
+
+    // the recovering party goes by the VSS commitments a majority of the helpers send
+    let repair_msg_vec = context.repair_msg_vec.as_ref().unwrap();
+    let sent_vec = repair_msg_vec
+        .iter()
+        .map(|msg| &msg.vss_scheme_vec[..])
+        .collect::<Vec<&[VerifiableSS<Scalar>]>>();
+    let vss_scheme_vec =
+        agree_vss_scheme_vec(&sent_vec, None).map_err(|e| e.map_blamed_parties(|k| s[k] + 1))?;
+    let shared_keys = Keys::repair_phase3_construct_keypair(
+        &vss_scheme_vec,
+        &s,
+        usize::from(context.lost_party_num - 1),
+        &sums,
+        repair_msg_vec,
+    )
+    .map_err(|e| e.map_blamed_parties(|k| s[k] + 1))?;
+
+    let keygen_json = serde_json::to_string(&(
+        &context.party_keys,
+        &shared_keys,
+        context.party_num_int,
+        vss_scheme_vec,
+        paillier_key_vec,
+        &shared_keys.y,
+        h1_h2_N_tilde_vec,
+        chain_code,
+    ))?;
+
+    Ok(keygen_json)
+}
+
+/// Positions of the helpers of a repair: the first t + 1 parties but the recovering one.
+fn repair_helpers(context: &GG18RepairClientContext) -> Vec<usize> {
+    (0..context.params.share_count)
+        .filter(|&i| i + 1 != usize::from(context.lost_party_num))
+        .take(context.params.threshold + 1)
+        .collect()
+}
+
+/// A participant left out the message its role requires.
+fn missing_message(round: &str) -> TssError {
+    TssError::UnknownError {
+        msg: format!("missing {} message", round),
+        line: line!(),
+    }
+}
+
+/// Sends share k to party k + 1 over its AES channel and returns the shares dealt to us,
+/// ours included.
+async fn exchange_shares<S>(
+    transport: &dyn Transport,
+    party_num_int: u16,
+    share_count: u16,
+    enc_keys: &[Vec<u8>],
+    secret_shares: &[S],
+) -> Result<Vec<S>>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    let mut j = 0;
+    for (k, i) in (1..=share_count).enumerate() {
+        if i != party_num_int {
+            // prepare encrypted ss for party i:
+            let key_i = &enc_keys[j];
+            let plaintext = BigInt::to_vec(&secret_shares[k].to_big_int());
+            let aead_pack_i = aes_encrypt(key_i, &plaintext)?;
+            transport
+                .send_p2p(
+                    party_num_int,
+                    i,
+                    "round3",
+                    serde_json::to_string(&aead_pack_i)?,
+                )
+                .await?;
+            j += 1;
+        }
+    }
+
+    let round3_ans_vec = transport
+        .recv_p2p(party_num_int, share_count, "round3")
+        .await?;
+
+    let mut j = 0;
+    let mut party_shares: Vec<S> = Vec::new();
+    for i in 1..=share_count {
+        if i == party_num_int {
+            party_shares.push(secret_shares[(i - 1) as usize].clone());
+        } else {
+            let aead_pack: AEAD = serde_json::from_str(&round3_ans_vec[j]).unwrap();
+            let key_i = &enc_keys[j];
+            // a share that does not decrypt is disputed in round 4 like any invalid share
+            let out_fe = match aes_decrypt(key_i, aead_pack) {
+                Ok(out) => ECScalar::from(&BigInt::from_bytes_be(&out[..])),
+                Err(_) => ECScalar::zero(),
+            };
+            party_shares.push(out_fe);
+
+            j += 1;
+        }
+    }
+    Ok(party_shares)
+}
+
+pub async fn signup_refresh(client: &Client, addr: &str) -> Result<PartySignup> {
+    let key = "signup-refresh".to_string();
+    let res_body = postb(client, addr, "signuprefresh", key).await?;
+    parse_signup(&res_body)
+}
+
+pub async fn signup_repair(client: &Client, addr: &str) -> Result<PartySignup> {
+    let key = "signup-repair".to_string();
+    let res_body = postb(client, addr, "signuprepair", key).await?;
+    parse_signup(&res_body)
+}
+
+/// The manager starts a new session after `parties` signups.
+pub async fn signup_reshare(client: &Client, addr: &str, parties: u16) -> Result<PartySignup> {
+    let res_body = postb(client, addr, "signupreshare", parties).await?;
+    parse_signup(&res_body)
+}
+
+/// The manager starts a new session after two signups, the client and the cosigner.
+pub async fn signup_two_party(client: &Client, addr: &str) -> Result<PartySignup> {
+    let key = "signup-two-party".to_string();
+    let res_body = postb(client, addr, "signuptwoparty", key).await?;
+    parse_signup(&res_body)
+}
+
+/// A signing session: the machine and the messages it still has to send.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GG18SignClientContext {
+    addr: String,
+    uuid: String,
+    sign: Sign,
+    outgoing: Vec<Msg>,
+}
+
+/// Starts a signing session. With a `derivation_path`, e.g. `m/0/1`, it signs for the
+/// child key of `gg18_derive_public_key` instead of the key of the key store.
+#[wasm_bindgen]
+pub async fn gg18_sign_client_new_context(
+    addr: String,
+    t: usize,
+    _n: usize,
+    key_store: String,
+    message_str: String,
+    derivation_path: Option<String>,
+) -> Result<String> {
+    let message = match hex::decode(message_str.clone()) {
+        Ok(x) => x,
+        Err(_e) => message_str.as_bytes().to_vec(),
+    };
+    let client = new_client_with_headers()?;
+
+    let key_store = parse_key_store(&key_store)?;
+    let key_store = match derivation_path {
+        Some(path) => derive_key_store(&key_store, &path)?,
+        None => key_store,
+    };
+
+    let (party_num_int, uuid) = match signup_sign(&client, &addr).await? {
+        PartySignup { number, uuid } => (number, uuid),
+    };
+
+    Ok(serde_json::to_string(&GG18SignClientContext {
+        addr,
+        uuid,
+        sign: Sign::new(key_store, t as u16, party_num_int, message)?,
+        outgoing: Vec::new(),
+    })?)
+}
+
+/// Exchanges the messages of one round of signing.
+async fn gg18_sign_client_exchange(context: &mut GG18SignClientContext, delay: u32) -> Result<()> {
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let outgoing = std::mem::take(&mut context.outgoing);
+    context.outgoing = exchange(&mut context.sign, &transport, outgoing).await?;
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub async fn gg18_sign_client_round0(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+    context.outgoing = context.sign.proceed()?;
+    gg18_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg18_sign_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+    gg18_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg18_sign_client_round2(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+    gg18_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg18_sign_client_round3(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+    gg18_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg18_sign_client_round4(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+    gg18_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg18_sign_client_round5(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+    gg18_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg18_sign_client_round6(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+    gg18_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg18_sign_client_round7(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+    gg18_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg18_sign_client_round8(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+    gg18_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Returns the signature as the JSON of `[r, s, recid]`, r and s in hex.
+#[wasm_bindgen]
+pub async fn gg18_sign_client_round9(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+    gg18_sign_client_exchange(&mut context, delay).await?;
+    let sig = context
+        .sign
+        .output()
+        .ok_or_else(|| missing_message("round9"))?;
+
+    let sign_json = serde_json::to_string(&vec![
+        //"r",
+        sig.r.to_big_int().to_hex(),
+        //"s",
+        sig.s.to_big_int().to_hex(),
+        //"v"
+        sig.recid.to_string(),
+    ])?;
+    crate::console_log!("sign_json: {:?}", sign_json);
+
+    Ok(sign_json)
+}
+
+/// Runs all of signing in one call, with the messages pushed over the `/ws` channel of the
+/// manager instead of polled for. Takes the arguments of `gg18_sign_client_new_context`, and
+/// returns the signature like `gg18_sign_client_round9`.
+#[wasm_bindgen]
+pub async fn gg18_sign_client_ws(
+    addr: String,
+    t: usize,
+    key_store: String,
+    message_str: String,
+    derivation_path: Option<String>,
+) -> Result<String> {
+    let message = match hex::decode(message_str.clone()) {
+        Ok(x) => x,
+        Err(_e) => message_str.as_bytes().to_vec(),
+    };
+    let key_store = parse_key_store(&key_store)?;
+    let sig = gg18_sign_ws(&addr, t, &key_store, &message, derivation_path.as_deref()).await?;
+    Ok(serde_json::to_string(&vec![
+        sig.r.to_big_int().to_hex(),
+        sig.s.to_big_int().to_hex(),
+        sig.recid.to_string(),
+    ])?)
+}
+
+/// A presignature with what the online round needs to find the other signers.
+#[derive(Debug, Serialize, Deserialize)]
+struct GG18Presignature {
+    uuid: String,
+    party_num_int: u16,
+    threshould: u16,
+    signers_vec: Vec<usize>,
+    presignature: Presignature,
+}
+
+/// Where `gg18_sign_client_online` finds a presignature: the public key and the
+/// presigning session.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GG18PresignatureId {
+    key_id: String,
+    presign_id: String,
+}
+
+lazy_static::lazy_static! {
+    // presignatures live as long as the module, and are gone after a reload
+    static ref PRESIGNATURE_STORE: MemoryPresignatureStore = MemoryPresignatureStore::new();
+}
+
+/// Starts a presigning session. It runs `gg18_presign_client_round0` to
+/// `gg18_presign_client_round6`.
+///
+/// The R_i = R^k_i and S_i = R^sigma_i that make a presignature must be proven, or the
+/// last signer to publish could pick them to add up whatever its shares: presigning runs
+/// the rounds of GG20 signing, whose phases 3, 5 and 6 prove them.
+#[wasm_bindgen]
+pub async fn gg18_presign_client_new_context(
+    addr: String,
+    t: usize,
+    n: usize,
+    key_store: String,
+) -> Result<String> {
+    gg20_sign_client_new_context(addr, t, n, key_store).await
+}
+
+#[wasm_bindgen]
+pub async fn gg18_presign_client_round0(context: String, delay: u32) -> Result<String> {
+    gg20_sign_client_round0(context, delay).await
+}
+
+#[wasm_bindgen]
+pub async fn gg18_presign_client_round1(context: String, delay: u32) -> Result<String> {
+    gg20_sign_client_round1(context, delay).await
+}
+
+#[wasm_bindgen]
+pub async fn gg18_presign_client_round2(context: String, delay: u32) -> Result<String> {
+    gg20_sign_client_round2(context, delay).await
+}
+
+#[wasm_bindgen]
+pub async fn gg18_presign_client_round3(context: String, delay: u32) -> Result<String> {
+    gg20_sign_client_round3(context, delay).await
+}
+
+#[wasm_bindgen]
+pub async fn gg18_presign_client_round4(context: String, delay: u32) -> Result<String> {
+    gg20_sign_client_round4(context, delay).await
+}
+
+#[wasm_bindgen]
+pub async fn gg18_presign_client_round5(context: String, delay: u32) -> Result<String> {
+    gg20_sign_client_round5(context, delay).await
+}
+
+/// Keeps the presignature in the module and returns its `GG18PresignatureId`.
+#[wasm_bindgen]
+pub async fn gg18_presign_client_round6(context: String, delay: u32) -> Result<String> {
+    gg20_sign_client_round6(context, delay).await
+}
+
+/// Keeps a presignature in the module store and returns its `GG18PresignatureId` as JSON.
+fn store_presignature(uuid: &str, sign: &Sign, presignature: Presignature) -> Result<String> {
+    store_session_presignature(
+        &sign.key_store().5,
+        GG18Presignature {
+            uuid: uuid.to_string(),
+            party_num_int: sign.party_num(),
+            threshould: sign.n() - 1,
+            signers_vec: sign.signers_vec().unwrap().clone(),
+            presignature,
+        },
+    )
+}
+
+/// As `store_presignature`, for the presignature of a session on the key `y_sum`.
+fn store_session_presignature(y_sum: &Point, presignature: GG18Presignature) -> Result<String> {
+    let presignature_id = GG18PresignatureId {
+        key_id: y_sum.bytes_compressed_to_big_int().to_hex(),
+        presign_id: presignature.uuid.clone(),
+    };
+    PRESIGNATURE_STORE.insert(
+        &presignature_id.key_id,
+        &presignature_id.presign_id,
+        serde_json::to_string(&presignature)?,
+    )?;
+
+    Ok(serde_json::to_string(&presignature_id)?)
+}
+
+/// Signs `message_str` with a presignature in a single round, run by the signers of the
+/// presigning session. The presignature is consumed before s_i is sent, so a second call
+/// with the same `presignature_id` fails. The output is that of `gg18_sign_client_round9`.
+#[wasm_bindgen]
+pub async fn gg18_sign_client_online(
+    addr: String,
+    presignature_id: String,
+    message_str: String,
+    delay: u32,
+) -> Result<String> {
+    let presignature_id = serde_json::from_str::<GG18PresignatureId>(&presignature_id)?;
+    let presignature = serde_json::from_str::<GG18Presignature>(
+        &PRESIGNATURE_STORE.consume(&presignature_id.key_id, &presignature_id.presign_id)?,
+    )?;
+    let message = match hex::decode(message_str.clone()) {
+        Ok(x) => x,
+        Err(_e) => message_str.as_bytes().to_vec(),
+    };
+    let transport =
+        HttpTransport::with_client(new_client_with_headers()?, &addr, &presignature.uuid, delay);
+
+    // we assume the message is already hashed (by the signer).
+    let message_bn = BigInt::from_bytes_be(&message[..]);
+    let local_sig = presignature.presignature.phase2_local_sig(&message_bn);
+    transport
+        .send_broadcast(
+            presignature.party_num_int,
+            "online",
+            serde_json::to_string(&local_sig.s_i)?,
+        )
+        .await?;
+    let online_ans_vec = transport
+        .recv_broadcasts(
+            presignature.party_num_int,
+            presignature.threshould + 1,
+            "online",
+        )
+        .await?;
+
+    let mut s_vec: Vec<Scalar> = Vec::new();
+    format_vec_from_reads(
+        &online_ans_vec,
+        presignature.party_num_int as usize,
+        local_sig.s_i.clone(),
+        &mut s_vec,
+    )?;
+    let sig = presignature
+        .presignature
+        .phase2_output_signature(&message_bn, &s_vec)
+        .map_err(|e| e.map_blamed_parties(|j| presignature.signers_vec[j] + 1))?;
+
+    let sign_json = serde_json::to_string(&vec![
+        //"r",
+        sig.r.to_big_int().to_hex(),
+        //"s",
+        sig.s.to_big_int().to_hex(),
+        //"v"
+        sig.recid.to_string(),
+    ])?;
+
+    check_sig(&sig.r, &sig.s, &message_bn, &local_sig.y)?;
+
+    Ok(sign_json)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GG20SignClientContext {
+    addr: String,
+    uuid: String,
+    sign: Sign,
+    outgoing: Vec<Msg>,
+    l_i: Option<Scalar>,
+    phase3_vec: Option<Vec<SignBroadcastPhase3>>,
+    delta_inv: Option<Scalar>,
+    /// the decommitted g^gamma_j of every signer, in signing order
+    g_gamma_vec: Option<Vec<Point>>,
+    r: Option<Point>,
+    phase5_vec: Option<Vec<SignBroadcastPhase5>>,
+}
+
+/// Starts a GG20 signing session on a GG18 key store. Rounds 0 to 6 make a presignature,
+/// which signs a message in the single round of `gg20_sign_client_online`.
+#[wasm_bindgen]
+pub async fn gg20_sign_client_new_context(
+    addr: String,
+    t: usize,
+    _n: usize,
+    key_store: String,
+) -> Result<String> {
+    let client = new_client_with_headers()?;
+    let key_store = parse_key_store(&key_store)?;
+    let (party_num_int, uuid) = match signup_sign(&client, &addr).await? {
+        PartySignup { number, uuid } => (number, uuid),
+    };
+
+    Ok(serde_json::to_string(&GG20SignClientContext {
+        addr,
+        uuid,
+        sign: Sign::offline(key_store, t as u16, party_num_int, OfflineStage::Mta)?,
+        outgoing: Vec::new(),
+        l_i: None,
+        phase3_vec: None,
+        delta_inv: None,
+        g_gamma_vec: None,
+        r: None,
+        phase5_vec: None,
+    })?)
+}
+
+/// Exchanges the messages of one of the rounds GG20 shares with GG18.
+async fn gg20_sign_client_exchange(context: &mut GG20SignClientContext, delay: u32) -> Result<()> {
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let outgoing = std::mem::take(&mut context.outgoing);
+    context.outgoing = exchange(&mut context.sign, &transport, outgoing).await?;
+    Ok(())
+}
+
+// rounds 0 to 2 of GG20, the choice of signers and the MtA, are those of GG18
+
+#[wasm_bindgen]
+pub async fn gg20_sign_client_round0(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG20SignClientContext>(&context)?;
+    context.outgoing = context.sign.proceed()?;
+    gg20_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg20_sign_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG20SignClientContext>(&context)?;
+    gg20_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg20_sign_client_round2(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG20SignClientContext>(&context)?;
+    gg20_sign_client_exchange(&mut context, delay).await?;
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg20_sign_client_round3(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG20SignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let sign = &context.sign;
+    let delta_i = sign.delta_i().ok_or_else(|| missing_message("round2"))?;
+    let (phase3_i, l_i) = SignBroadcastPhase3::create(delta_i, sign.sigma().unwrap());
+
+    transport
+        .send_broadcast(
+            sign.party_num(),
+            "round3",
+            serde_json::to_string(&phase3_i)?,
+        )
+        .await?;
+    let round3_ans_vec = transport
+        .recv_broadcasts(sign.party_num(), sign.n(), "round3")
+        .await?;
+    let mut phase3_vec: Vec<SignBroadcastPhase3> = Vec::new();
+    format_vec_from_reads(
+        &round3_ans_vec,
+        sign.party_num() as usize,
+        phase3_i,
+        &mut phase3_vec,
+    )?;
+    let delta_inv = SignBroadcastPhase3::verify_reconstruct_delta(&phase3_vec)
+        .map_err(|e| e.map_blamed_parties(|j| sign.signer_party_num(j + 1)))?;
+
+    context.delta_inv = Some(delta_inv);
+    context.l_i = Some(l_i);
+    context.phase3_vec = Some(phase3_vec);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Decommits to gamma_i and checks the decommitments of the other signers, as GG18 does.
+#[wasm_bindgen]
+pub async fn gg20_sign_client_round4(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG20SignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let sign = &context.sign;
+    let decommit = sign.decommit().unwrap();
+    transport
+        .send_broadcast(sign.party_num(), "round4", serde_json::to_string(decommit)?)
+        .await?;
+    let round4_ans_vec = transport
+        .recv_broadcasts(sign.party_num(), sign.n(), "round4")
+        .await?;
+
+    let decommit_vec = round4_ans_vec
+        .iter()
+        .map(|decommit_j| serde_json::from_str(decommit_j))
+        .collect::<serde_json::Result<Vec<SignDecommitPhase1>>>()?;
+    let mut g_gamma_vec = decommit_vec
+        .iter()
+        .map(|decommit_j| decommit_j.g_gamma_i.clone())
+        .collect::<Vec<Point>>();
+    g_gamma_vec.insert(
+        usize::from(sign.party_num() - 1),
+        decommit.g_gamma_i.clone(),
+    );
+    let R = sign.phase4_R(decommit_vec, context.delta_inv.as_ref().unwrap())?;
+
+    context.g_gamma_vec = Some(g_gamma_vec);
+    context.r = Some(R);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn gg20_sign_client_round5(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<GG20SignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let sign = &context.sign;
+    let (party_keys, _, _, _, paillier_key_vector, ..) = sign.key_store();
+    let R = context.r.as_ref().unwrap();
+    let (m_a_k, m_a_randomness) = sign.m_a().unwrap();
+    let phase5_i = SignBroadcastPhase5::create(
+        sign.sign_keys().unwrap(),
+        R,
+        &m_a_k.c,
+        m_a_randomness,
+        &party_keys.ek,
+        &sign.dlog_statements_except(sign.party_num()),
+    );
+    transport
+        .send_broadcast(
+            sign.party_num(),
+            "round5",
+            serde_json::to_string(&phase5_i)?,
+        )
+        .await?;
+    let round5_ans_vec = transport
+        .recv_broadcasts(sign.party_num(), sign.n(), "round5")
+        .await?;
+
+    let mut phase5_vec: Vec<SignBroadcastPhase5> = Vec::new();
+    format_vec_from_reads(
+        &round5_ans_vec,
+        sign.party_num() as usize,
+        phase5_i,
+        &mut phase5_vec,
+    )?;
+    let ek_vec = sign
+        .signers_vec()
+        .unwrap()
+        .iter()
+        .map(|&signer| paillier_key_vector[signer].clone())
+        .collect::<Vec<EncryptionKey>>();
+    SignBroadcastPhase5::verify(
+        &phase5_vec,
+        R,
+        sign.m_a_c_vec().unwrap(),
+        &ek_vec,
+        &party_keys.dlog_statement(),
+        usize::from(sign.party_num() - 1),
+    )
+    .map_err(|e| e.map_blamed_parties(|j| sign.signer_party_num(j + 1)))?;
+
+    if !SignBroadcastPhase5::sum_is_g(&phase5_vec) {
+        // a delta_j was wrong: reveal the MtA on gamma to find whose
+        let (m_b_gamma_vec, alpha_vec) = sign.m_b_gamma_rec().unwrap();
+        let reveal_i = SignPhase5Reveal::create(
+            &sign.sign_keys().unwrap().k_i,
+            m_a_randomness,
+            m_b_gamma_vec,
+            alpha_vec,
+        );
+        transport
+            .send_broadcast(
+                sign.party_num(),
+                "round5_reveal",
+                serde_json::to_string(&reveal_i)?,
+            )
+            .await?;
+        let reveal_ans_vec = transport
+            .recv_broadcasts(sign.party_num(), sign.n(), "round5_reveal")
+            .await?;
+        let mut reveal_vec: Vec<SignPhase5Reveal> = Vec::new();
+        format_vec_from_reads(
+            &reveal_ans_vec,
+            sign.party_num() as usize,
+            reveal_i,
+            &mut reveal_vec,
+        )?;
+        let delta_vec = context
+            .phase3_vec
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|phase3| phase3.delta_i.clone())
+            .collect::<Vec<Scalar>>();
+        let e = SignPhase5Reveal::identify(
+            &reveal_vec,
+            &delta_vec,
+            context.g_gamma_vec.as_ref().unwrap(),
+            sign.m_a_c_vec().unwrap(),
+            &ek_vec,
+        );
+        return Err(e.map_blamed_parties(|j| sign.signer_party_num(j + 1)));
+    }
+
+    context.phase5_vec = Some(phase5_vec);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Keeps the presignature in the module and returns its `GG18PresignatureId`.
+#[wasm_bindgen]
+pub async fn gg20_sign_client_round6(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<GG20SignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let sign = &context.sign;
+    let R = context.r.as_ref().unwrap();
+    let sigma = sign.sigma().unwrap();
+    let phase3_vec = context.phase3_vec.as_ref().unwrap();
+    let phase6_i = SignBroadcastPhase6::create(
+        R,
+        sigma,
+        context.l_i.as_ref().unwrap(),
+        phase3_vec[usize::from(sign.party_num() - 1)].T_i(),
+    );
+    transport
+        .send_broadcast(
+            sign.party_num(),
+            "round6",
+            serde_json::to_string(&phase6_i)?,
+        )
+        .await?;
+    let round6_ans_vec = transport
+        .recv_broadcasts(sign.party_num(), sign.n(), "round6")
+        .await?;
+
+    let mut phase6_vec: Vec<SignBroadcastPhase6> = Vec::new();
+    format_vec_from_reads(
+        &round6_ans_vec,
+        sign.party_num() as usize,
+        phase6_i,
+        &mut phase6_vec,
+    )?;
+    let presignature = SignBroadcastPhase6::verify_create_presignature(
+        &phase6_vec,
+        phase3_vec,
+        context.phase5_vec.as_ref().unwrap(),
+        R,
+        &sign.sign_keys().unwrap().k_i,
+        sigma,
+        &sign.key_store().5,
+    )
+    .map_err(|e| e.map_blamed_parties(|j| sign.signer_party_num(j + 1)))?;
+
+    store_presignature(&context.uuid, sign, presignature)
+}
+
+/// Signs `message_str` in a single round with a presignature of `gg20_sign_client_round6`.
+/// A wrong share s_j is blamed on its sender.
+#[wasm_bindgen]
+pub async fn gg20_sign_client_online(
+    addr: String,
+    presignature_id: String,
+    message_str: String,
+    delay: u32,
+) -> Result<String> {
+    gg18_sign_client_online(addr, presignature_id, message_str, delay).await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CGGMP21KeygenClientContext {
+    addr: String,
+    params: Parameters,
+    party_num_int: u16,
+    uuid: String,
+    keygen_keys: Option<KeygenKeys>,
+    com_vec: Option<Vec<KeygenCommitMessage>>,
+    decom_vec: Option<Vec<KeygenDecommitMessage>>,
+    enc_keys: Option<Vec<Vec<u8>>>,
+    party_shares: Option<Vec<Scalar>>,
+    key_share: Option<KeyShare>,
+    proof: Option<KeygenProofMessage>,
+}
+
+/// Starts a CGGMP21 keygen. Rounds 1 to 5 output a key share, which
+/// `cggmp21_aux_info_client_new_context` completes into a key store.
+#[wasm_bindgen]
+pub async fn cggmp21_keygen_client_new_context(
+    addr: String,
+    t: usize,
+    n: usize,
+    _delay: u32,
+) -> Result<String> {
+    let client = new_client_with_headers()?;
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
+
+    let (party_num_int, uuid) = match signup_keygen(&client, &addr).await? {
+        PartySignup { number, uuid } => (number, uuid),
+    };
+
+    Ok(serde_json::to_string(&CGGMP21KeygenClientContext {
+        addr,
+        params,
+        party_num_int,
+        uuid,
+        keygen_keys: None,
+        com_vec: None,
+        decom_vec: None,
+        enc_keys: None,
+        party_shares: None,
+        key_share: None,
+        proof: None,
+    })?)
+}
+
+#[wasm_bindgen]
+pub async fn cggmp21_keygen_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<CGGMP21KeygenClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let keygen_keys = KeygenKeys::create(&context.params, context.party_num_int as usize);
+    let com_i = keygen_keys.round1_commit();
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round1",
+            serde_json::to_string(&com_i)?,
+        )
+        .await?;
+    let round1_ans_vec = transport
+        .recv_broadcasts(
+            context.party_num_int,
+            context.params.share_count as u16,
+            "round1",
+        )
+        .await?;
+
+    let mut com_vec = Vec::new();
+    format_vec_from_reads(
+        &round1_ans_vec,
+        context.party_num_int as usize,
+        com_i,
+        &mut com_vec,
+    )?;
+
+    context.keygen_keys = Some(keygen_keys);
+    context.com_vec = Some(com_vec);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn cggmp21_keygen_client_round2(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<CGGMP21KeygenClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let keygen_keys = context.keygen_keys.as_ref().unwrap();
+    let decom_i = keygen_keys.round2_decommit();
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round2",
+            serde_json::to_string(&decom_i)?,
+        )
+        .await?;
+    let round2_ans_vec = transport
+        .recv_broadcasts(
+            context.party_num_int,
+            context.params.share_count as u16,
+            "round2",
+        )
+        .await?;
+
+    let mut decom_vec = Vec::new();
+    format_vec_from_reads(
+        &round2_ans_vec,
+        context.party_num_int as usize,
+        decom_i,
+        &mut decom_vec,
+    )?;
+    KeygenKeys::round2_verify_decommitments(
+        &context.params,
+        context.com_vec.as_ref().unwrap(),
+        &decom_vec,
+    )
+    .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+    let enc_keys = decom_vec
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| i + 1 != context.party_num_int as usize)
+        .map(|(_, decom_j)| aes_key(&decom_j.vss_scheme.commitments[0], keygen_keys.u_i()))
+        .collect::<Vec<Vec<u8>>>();
+
+    context.decom_vec = Some(decom_vec);
+    context.enc_keys = Some(enc_keys);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn cggmp21_keygen_client_round3(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<CGGMP21KeygenClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let party_shares = exchange_shares(
+        &transport,
+        context.party_num_int,
+        context.params.share_count as u16,
+        context.enc_keys.as_ref().unwrap(),
+        context.keygen_keys.as_ref().unwrap().secret_shares(),
+    )
+    .await?;
+
+    context.party_shares = Some(party_shares);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn cggmp21_keygen_client_round4(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<CGGMP21KeygenClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let keygen_keys = context.keygen_keys.as_ref().unwrap();
+    let decom_vec = context.decom_vec.as_ref().unwrap();
+    let vss_scheme_vec = decom_vec
+        .iter()
+        .map(|decom| decom.vss_scheme.clone())
+        .collect::<Vec<VerifiableSS>>();
+    let mut complaints = Complaints::new(
+        context.party_num_int,
+        context.party_shares.clone().unwrap(),
+        keygen_keys.secret_shares().to_vec(),
+        vss_scheme_vec,
+    )?;
+    let party_shares = run(&mut complaints, &transport).await?;
+
+    let (key_share, proof) = keygen_keys
+        .round4_create_key_share(decom_vec, &party_shares)
+        .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+
+    context.party_shares = Some(party_shares);
+    context.key_share = Some(key_share);
+    context.proof = Some(proof);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Returns the key share, which is not yet usable for presigning.
+#[wasm_bindgen]
+pub async fn cggmp21_keygen_client_round5(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<CGGMP21KeygenClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let proof_i = context.proof.clone().unwrap();
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round5",
+            serde_json::to_string(&proof_i)?,
+        )
+        .await?;
+    let round5_ans_vec = transport
+        .recv_broadcasts(
+            context.party_num_int,
+            context.params.share_count as u16,
+            "round5",
+        )
+        .await?;
+
+    let mut proof_vec = Vec::new();
+    format_vec_from_reads(
+        &round5_ans_vec,
+        context.party_num_int as usize,
+        proof_i,
+        &mut proof_vec,
+    )?;
+    let key_share = context.key_share.as_ref().unwrap();
+    key_share
+        .round5_verify_proofs(context.decom_vec.as_ref().unwrap(), &proof_vec)
+        .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+
+    Ok(serde_json::to_string(key_share)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CGGMP21AuxInfoClientContext {
+    addr: String,
+    n: u16,
+    party_num_int: u16,
+    uuid: String,
+    key_share: KeyShare,
+    aux_info_keys: Option<AuxInfoKeys>,
+    bc1_vec: Option<Vec<AuxInfoBroadcastMessage>>,
+}
+
+/// Starts the generation of Paillier keys and ring-Pedersen parameters for a key share of
+/// `cggmp21_keygen_client_round5`, run by all n parties. As for a refresh, messages are sent
+/// under the party number of the key share. Running it again replaces the auxiliary info.
+#[wasm_bindgen]
+pub async fn cggmp21_aux_info_client_new_context(
+    addr: String,
+    n: usize,
+    key_store: String,
+) -> Result<String> {
+    let client = new_client_with_headers()?;
+    let key_share: KeyShare = match serde_json::from_str::<(KeyShare, AuxInfo)>(&key_store) {
+        Ok((key_share, _)) => key_share,
+        Err(_) => serde_json::from_str(&key_store)?,
+    };
+
+    let uuid = signup_refresh(&client, &addr).await?.uuid;
+
+    Ok(serde_json::to_string(&CGGMP21AuxInfoClientContext {
+        addr,
+        n: n as u16,
+        party_num_int: key_share.party_index as u16,
+        uuid,
+        key_share,
+        aux_info_keys: None,
+        bc1_vec: None,
+    })?)
+}
+
+#[wasm_bindgen]
+pub async fn cggmp21_aux_info_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<CGGMP21AuxInfoClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let aux_info_keys = AuxInfoKeys::create(context.party_num_int as usize);
+    let bc1_i = aux_info_keys.round1_broadcast();
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round1",
+            serde_json::to_string(&bc1_i)?,
+        )
+        .await?;
+    let round1_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.n, "round1")
+        .await?;
+
+    let mut bc1_vec = Vec::new();
+    format_vec_from_reads(
+        &round1_ans_vec,
+        context.party_num_int as usize,
+        bc1_i,
+        &mut bc1_vec,
+    )?;
+
+    context.aux_info_keys = Some(aux_info_keys);
+    context.bc1_vec = Some(bc1_vec);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Returns the key store used for presigning: the key share with the auxiliary info.
+#[wasm_bindgen]
+pub async fn cggmp21_aux_info_client_round2(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<CGGMP21AuxInfoClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let aux_info_keys = context.aux_info_keys.as_ref().unwrap();
+    let bc1_vec = context.bc1_vec.as_ref().unwrap();
+    let proofs_i = aux_info_keys.round2_no_small_factor_proofs(bc1_vec);
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round2",
+            serde_json::to_string(&proofs_i)?,
+        )
+        .await?;
+    let round2_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.n, "round2")
+        .await?;
+
+    let mut proofs_vec = Vec::new();
+    format_vec_from_reads(
+        &round2_ans_vec,
+        context.party_num_int as usize,
+        proofs_i,
+        &mut proofs_vec,
+    )?;
+    let aux_info = aux_info_keys
+        .round2_verify_create_aux_info(bc1_vec, &proofs_vec)
+        .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+
+    Ok(serde_json::to_string(&(&context.key_share, aux_info))?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CGGMP21PresignClientContext {
+    addr: String,
+    key_share: KeyShare,
+    aux_info: AuxInfo,
+    threshould: u16,
+    party_num_int: u16,
+    uuid: String,
+    signers_vec: Option<Vec<usize>>,
+    presign_keys: Option<PresignKeys>,
+    round1_vec: Option<Vec<PresignRound1Message>>,
+    round2_vec: Option<Vec<PresignRound2Message>>,
+    beta_vec: Option<Vec<Scalar>>,
+    beta_hat_vec: Option<Vec<Scalar>>,
+    chi_i: Option<Scalar>,
+    r: Option<Point>,
+}
+
+/// Starts a CGGMP21 presigning session on a key store of `cggmp21_aux_info_client_round2`.
+/// Rounds 0 to 4 make a presignature, which signs a message in the single round of
+/// `cggmp21_sign_client_online`.
+#[wasm_bindgen]
+pub async fn cggmp21_presign_client_new_context(
+    addr: String,
+    t: usize,
+    _n: usize,
+    key_store: String,
+) -> Result<String> {
+    let client = new_client_with_headers()?;
+    let (key_share, aux_info): (KeyShare, AuxInfo) = serde_json::from_str(&key_store)?;
+
+    let (party_num_int, uuid) = match signup_sign(&client, &addr).await? {
+        PartySignup { number, uuid } => (number, uuid),
+    };
+
+    Ok(serde_json::to_string(&CGGMP21PresignClientContext {
+        addr,
+        key_share,
+        aux_info,
+        threshould: t as u16,
+        party_num_int,
+        uuid,
+        signers_vec: None,
+        presign_keys: None,
+        round1_vec: None,
+        round2_vec: None,
+        beta_vec: None,
+        beta_hat_vec: None,
+        chi_i: None,
+        r: None,
+    })?)
+}
+
+#[wasm_bindgen]
+pub async fn cggmp21_presign_client_round0(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<CGGMP21PresignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    // round 0: collect signers IDs
+    let party_index_i = context.key_share.party_index;
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round0",
+            serde_json::to_string(&party_index_i)?,
+        )
+        .await?;
+    let round0_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round0")
+        .await?;
+
+    let mut party_index_vec: Vec<usize> = Vec::new();
+    format_vec_from_reads(
+        &round0_ans_vec,
+        context.party_num_int as usize,
+        party_index_i,
+        &mut party_index_vec,
+    )?;
+    let signers_vec = party_index_vec
+        .iter()
+        .map(|party_index| party_index - 1)
+        .collect::<Vec<usize>>();
+    let presign_keys = PresignKeys::create(&context.key_share, &context.aux_info, &signers_vec);
+
+    context.signers_vec = Some(signers_vec);
+    context.presign_keys = Some(presign_keys);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn cggmp21_presign_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<CGGMP21PresignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let presign_keys = context.presign_keys.as_ref().unwrap();
+    let round1_i = presign_keys.round1_broadcast();
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round1",
+            serde_json::to_string(&round1_i)?,
+        )
+        .await?;
+    let round1_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round1")
+        .await?;
+
+    let mut round1_vec = Vec::new();
+    format_vec_from_reads(
+        &round1_ans_vec,
+        context.party_num_int as usize,
+        round1_i,
+        &mut round1_vec,
+    )?;
+    let signers_vec = context.signers_vec.as_ref().unwrap();
+    presign_keys
+        .round1_verify(&round1_vec)
+        .map_err(|e| e.map_blamed_parties(|j| signers_vec[j] + 1))?;
+
+    context.round1_vec = Some(round1_vec);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn cggmp21_presign_client_round2(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<CGGMP21PresignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let presign_keys = context.presign_keys.as_ref().unwrap();
+    let round1_vec = context.round1_vec.as_ref().unwrap();
+    let (round2_i, beta_vec, beta_hat_vec) = presign_keys.round2_broadcast(round1_vec);
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round2",
+            serde_json::to_string(&round2_i)?,
+        )
+        .await?;
+    let round2_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round2")
+        .await?;
+
+    let mut round2_vec = Vec::new();
+    format_vec_from_reads(
+        &round2_ans_vec,
+        context.party_num_int as usize,
+        round2_i,
+        &mut round2_vec,
+    )?;
+    let signers_vec = context.signers_vec.as_ref().unwrap();
+    presign_keys
+        .round2_verify(round1_vec, &round2_vec)
+        .map_err(|e| e.map_blamed_parties(|j| signers_vec[j] + 1))?;
+
+    context.round2_vec = Some(round2_vec);
+    context.beta_vec = Some(beta_vec);
+    context.beta_hat_vec = Some(beta_hat_vec);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn cggmp21_presign_client_round3(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<CGGMP21PresignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let presign_keys = context.presign_keys.as_ref().unwrap();
+    let round2_vec = context.round2_vec.as_ref().unwrap();
+    let (round3_i, chi_i) = presign_keys.round3_broadcast(
+        round2_vec,
+        context.beta_vec.as_ref().unwrap(),
+        context.beta_hat_vec.as_ref().unwrap(),
+    );
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round3",
+            serde_json::to_string(&round3_i)?,
+        )
+        .await?;
+    let round3_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round3")
+        .await?;
+
+    let mut round3_vec = Vec::new();
+    format_vec_from_reads(
+        &round3_ans_vec,
+        context.party_num_int as usize,
+        round3_i,
+        &mut round3_vec,
+    )?;
+    let signers_vec = context.signers_vec.as_ref().unwrap();
+    let R = presign_keys
+        .round3_verify_R(
+            context.round1_vec.as_ref().unwrap(),
+            round2_vec,
+            &round3_vec,
+        )
+        .map_err(|e| e.map_blamed_parties(|j| signers_vec[j] + 1))?;
+
+    context.chi_i = Some(chi_i);
+    context.r = Some(R);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Keeps the presignature in the module and returns its `GG18PresignatureId`.
+#[wasm_bindgen]
+pub async fn cggmp21_presign_client_round4(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<CGGMP21PresignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let presign_keys = context.presign_keys.as_ref().unwrap();
+    let R = context.r.as_ref().unwrap();
+    let chi_i = context.chi_i.as_ref().unwrap();
+    let presign_i = presign_keys.round4_broadcast(R, chi_i);
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round4",
+            serde_json::to_string(&presign_i)?,
+        )
+        .await?;
+    let round4_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round4")
+        .await?;
+
+    let mut presign_vec: Vec<PresignBroadcastMessage> = Vec::new();
+    format_vec_from_reads(
+        &round4_ans_vec,
+        context.party_num_int as usize,
+        presign_i,
+        &mut presign_vec,
+    )?;
+    let presignature = presign_keys.round4_create_presignature(R, chi_i, &presign_vec)?;
+
+    store_session_presignature(
+        &context.key_share.y,
+        GG18Presignature {
+            uuid: context.uuid.clone(),
+            party_num_int: context.party_num_int,
+            threshould: context.threshould,
+            signers_vec: context.signers_vec.clone().unwrap(),
+            presignature,
+        },
+    )
+}
+
+/// Signs `message_str` in a single round with a presignature of `cggmp21_presign_client_round4`.
+/// A wrong share s_j is blamed on its sender.
+#[wasm_bindgen]
+pub async fn cggmp21_sign_client_online(
+    addr: String,
+    presignature_id: String,
+    message_str: String,
+    delay: u32,
+) -> Result<String> {
+    gg18_sign_client_online(addr, presignature_id, message_str, delay).await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FrostSignClientContext {
+    addr: String,
+    shared_keys: SharedKeys,
+    party_id: u16,
+    vss_scheme_vec: Vec<VerifiableSS>,
+    output_key: OutputKey,
+    threshould: u16,
+    party_num_int: u16,
+    uuid: String,
+    message: Vec<u8>,
+    signers_vec: Option<Vec<usize>>,
+    sign_keys: Option<FrostSignKeys>,
+    bc1_vec: Option<Vec<FrostSignBroadcastPhase1>>,
+}
+
+/// The x-only key, in hex, that `frost_sign_client_round2` signs for with a GG18 key store.
+/// `merkle_root` is as for `frost_sign_client_new_context`.
+#[wasm_bindgen]
+pub fn frost_output_key(key_store: String, merkle_root: Option<String>) -> Result<String> {
+    let (_, _, _, _, _, y_sum, _, _) = parse_key_store(&key_store)?;
+    Ok(hex::encode(frost_key(&y_sum, merkle_root)?.x_only()))
+}
+
+fn frost_key(y_sum: &Point, merkle_root: Option<String>) -> Result<OutputKey> {
+    let merkle_root = match merkle_root {
+        Some(merkle_root) => Some(hex::decode(merkle_root).map_err(|_| TssError::ContextError)?),
+        None => None,
+    };
+    OutputKey::new(y_sum, merkle_root.as_deref())
+}
+
+/// Starts a FROST signing session on a GG18 key store, for a BIP-340 signature of
+/// `message_str`. Without `merkle_root` it signs for the x-only key of y_sum. With it, it
+/// signs for the Taproot output key of the internal key y_sum: an empty `merkle_root`
+/// for a key-path-only output, or the hex of the root of the script tree.
+#[wasm_bindgen]
+pub async fn frost_sign_client_new_context(
+    addr: String,
+    t: usize,
+    _n: usize,
+    key_store: String,
+    message_str: String,
+    merkle_root: Option<String>,
+) -> Result<String> {
+    let message = match hex::decode(message_str.clone()) {
+        Ok(x) => x,
+        Err(_e) => message_str.as_bytes().to_vec(),
+    };
+    let client = new_client_with_headers()?;
+
+    let (_, shared_keys, party_id, vss_scheme_vec, _, y_sum, _, _) = parse_key_store(&key_store)?;
+    let output_key = frost_key(&y_sum, merkle_root)?;
+
+    let (party_num_int, uuid) = match signup_sign(&client, &addr).await? {
+        PartySignup { number, uuid } => (number, uuid),
+    };
+
+    Ok(serde_json::to_string(&FrostSignClientContext {
+        addr,
+        shared_keys,
+        party_id,
+        vss_scheme_vec,
+        output_key,
+        threshould: t as u16,
+        party_num_int,
+        uuid,
+        message,
+        signers_vec: None,
+        sign_keys: None,
+        bc1_vec: None,
+    })?)
+}
+
+#[wasm_bindgen]
+pub async fn frost_sign_client_round0(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<FrostSignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    // round 0: collect signers IDs
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round0",
+            serde_json::to_string(&context.party_id)?,
+        )
+        .await?;
+    let round0_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round0")
+        .await?;
+
+    let mut party_id_vec: Vec<u16> = Vec::new();
+    format_vec_from_reads(
+        &round0_ans_vec,
+        context.party_num_int as usize,
+        context.party_id,
+        &mut party_id_vec,
+    )?;
+    let signers_vec = party_id_vec
+        .iter()
+        .map(|&party_id| usize::from(party_id - 1))
+        .collect::<Vec<usize>>();
+    let sign_keys = FrostSignKeys::create(
+        &context.shared_keys,
+        &context.vss_scheme_vec,
+        usize::from(context.party_id - 1),
+        &signers_vec,
+    );
+
+    context.signers_vec = Some(signers_vec);
+    context.sign_keys = Some(sign_keys);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn frost_sign_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<FrostSignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let bc1_i = context.sign_keys.as_ref().unwrap().phase1_broadcast();
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round1",
+            serde_json::to_string(&bc1_i)?,
+        )
+        .await?;
+    let round1_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round1")
+        .await?;
+
+    let mut bc1_vec = Vec::new();
+    format_vec_from_reads(
+        &round1_ans_vec,
+        context.party_num_int as usize,
+        bc1_i,
+        &mut bc1_vec,
+    )?;
+
+    context.bc1_vec = Some(bc1_vec);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Returns the hex of the 64-byte signature and of the x-only key it verifies against.
+#[wasm_bindgen]
+pub async fn frost_sign_client_round2(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<FrostSignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let sign_keys = context.sign_keys.as_ref().unwrap();
+    let bc1_vec = context.bc1_vec.as_ref().unwrap();
+    let bc2_i = sign_keys.phase2_sign(&context.message, &context.output_key, bc1_vec);
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round2",
+            serde_json::to_string(&bc2_i)?,
+        )
+        .await?;
+    let round2_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round2")
+        .await?;
+
+    let mut bc2_vec: Vec<FrostSignBroadcastPhase2> = Vec::new();
+    format_vec_from_reads(
+        &round2_ans_vec,
+        context.party_num_int as usize,
+        bc2_i,
+        &mut bc2_vec,
+    )?;
+    let signers_vec = context.signers_vec.as_ref().unwrap();
+    let sig = sign_keys
+        .phase2_verify_output_signature(&context.message, &context.output_key, bc1_vec, &bc2_vec)
+        .map_err(|e| e.map_blamed_parties(|j| signers_vec[j] + 1))?;
+
+    let sign_json = serde_json::to_string(&vec![
+        hex::encode(sig.to_bytes()),
+        hex::encode(context.output_key.x_only()),
+    ])?;
+
+    Ok(sign_json)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct EddsaKeygenClientContext {
+    addr: String,
+    params: Parameters,
+    party_num_int: u16,
+    uuid: String,
+    party_keys: Option<EddsaKeys>,
+    bc1_vec: Option<Vec<EddsaKeyGenBroadcastMessage1>>,
+    decom_i: Option<EddsaKeyGenDecommitMessage1>,
+    y_sum: Option<Ed25519Point>,
+    point_vec: Option<Vec<Ed25519Point>>,
+    enc_keys: Option<Vec<Vec<u8>>>,
+    vss_scheme: Option<VerifiableSS<Ed25519Scalar>>,
+    secret_shares: Option<Vec<Ed25519Scalar>>,
+    party_shares: Option<Vec<Ed25519Scalar>>,
+    shared_keys: Option<EddsaSharedKeys>,
+    dlog_proof: Option<DLogProof<Ed25519Scalar>>,
+    vss_scheme_vec: Option<Vec<VerifiableSS<Ed25519Scalar>>>,
+}
+
+/// Starts a keygen of an Ed25519 key for threshold EdDSA. The key store that round 5
+/// returns is used by `eddsa_sign_client_new_context`.
+#[wasm_bindgen]
+pub async fn eddsa_keygen_client_new_context(
+    addr: String,
+    t: usize,
+    n: usize,
+    _delay: u32,
+) -> Result<String> {
+    let client = new_client_with_headers()?;
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
+
+    let (party_num_int, uuid) = match signup_keygen(&client, &addr).await? {
+        PartySignup { number, uuid } => (number, uuid),
+    };
+
+    Ok(serde_json::to_string(&EddsaKeygenClientContext {
+        addr,
+        params,
+        party_num_int,
+        uuid,
+        party_keys: None,
+        bc1_vec: None,
+        decom_i: None,
+        y_sum: None,
+        point_vec: None,
+        enc_keys: None,
+        vss_scheme: None,
+        secret_shares: None,
+        party_shares: None,
+        shared_keys: None,
+        dlog_proof: None,
+        vss_scheme_vec: None,
+    })?)
+}
+
+#[wasm_bindgen]
+pub async fn eddsa_keygen_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<EddsaKeygenClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    let party_keys = EddsaKeys::create(context.party_num_int as usize);
+    let (bc_i, decom_i) = party_keys.phase1_broadcast();
+
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round1",
+            serde_json::to_string(&bc_i)?,
+        )
+        .await?;
+    let round1_ans_vec = transport
+        .recv_broadcasts(
+            context.party_num_int,
+            context.params.share_count as u16,
+            "round1",
+        )
+        .await?;
+
+    let mut bc1_vec = Vec::new();
+    format_vec_from_reads(
+        &round1_ans_vec,
+        context.party_num_int as usize,
+        bc_i,
+        &mut bc1_vec,
+    )?;
+
+    context.bc1_vec = Some(bc1_vec);
+    context.party_keys = Some(party_keys);
+    context.decom_i = Some(decom_i);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn eddsa_keygen_client_round2(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<EddsaKeygenClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    let decom_i = context.decom_i.clone().unwrap();
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round2",
+            serde_json::to_string(&decom_i)?,
+        )
+        .await?;
+    let round2_ans_vec = transport
+        .recv_broadcasts(
+            context.party_num_int,
+            context.params.share_count as u16,
+            "round2",
+        )
+        .await?;
+
+    let mut decom_vec: Vec<EddsaKeyGenDecommitMessage1> = Vec::new();
+    format_vec_from_reads(
+        &round2_ans_vec,
+        context.party_num_int as usize,
+        decom_i,
+        &mut decom_vec,
+    )?;
+    let party_keys = context.party_keys.as_ref().unwrap();
+    let enc_keys = decom_vec
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i + 1 != usize::from(context.party_num_int))
+        .map(|(_, decom_j)| aes_key(&decom_j.y_i, &party_keys.u_i))
+        .collect::<Vec<Vec<u8>>>();
+    let point_vec = decom_vec
+        .iter()
+        .map(|decom| decom.y_i.clone())
+        .collect::<Vec<Ed25519Point>>();
+    let y_sum = point_vec[1..]
+        .iter()
+        .fold(point_vec[0].clone(), |acc, y_i| acc + y_i);
+
+    let (vss_scheme, secret_shares, _index) = party_keys
+        .phase1_verify_com_phase2_distribute(
+            &context.params,
+            &decom_vec,
+            context.bc1_vec.as_ref().unwrap(),
+        )
+        .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+
+    context.y_sum = Some(y_sum);
+    context.point_vec = Some(point_vec);
+    context.enc_keys = Some(enc_keys);
+    context.vss_scheme = Some(vss_scheme);
+    context.secret_shares = Some(secret_shares);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+#[wasm_bindgen]
+pub async fn eddsa_keygen_client_round3(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<EddsaKeygenClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    let party_shares = exchange_shares(
+        &transport,
         context.party_num_int,
-        context.threshould + 1,
-        delay,
-        "round2",
-        context.uuid.clone(),
+        context.params.share_count as u16,
+        context.enc_keys.as_ref().unwrap(),
+        context.secret_shares.as_ref().unwrap(),
     )
     .await?;
 
-    context.round2_ans_vec = Some(round2_ans_vec);
-    context.beta_vec = Some(beta_vec);
-    context.ni_vec = Some(ni_vec);
-    context.bc1_vec = Some(bc1_vec);
+    context.party_shares = Some(party_shares);
 
     Ok(serde_json::to_string(&context)?)
 }
 
 #[wasm_bindgen]
-pub async fn gg18_sign_client_round3(context: String, delay: u32) -> Result<String> {
-    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
-    let client = new_client_with_headers()?;
-    let mut m_b_gamma_rec_vec: Vec<MessageB> = Vec::new();
-    let mut m_b_w_rec_vec: Vec<MessageB> = Vec::new();
-
-    for i in 0..context.threshould {
-        //  if signers_vec.contains(&(i as usize)) {
-        let (m_b_gamma_i, m_b_w_i): (MessageB, MessageB) =
-            serde_json::from_str(&context.round2_ans_vec.as_ref().unwrap()[i as usize])?;
-        m_b_gamma_rec_vec.push(m_b_gamma_i);
-        m_b_w_rec_vec.push(m_b_w_i);
-        //     }
-    }
-
-    let mut alpha_vec: Vec<Scalar> = Vec::new();
-    let mut miu_vec: Vec<Scalar> = Vec::new();
-
-    let mut j = 0;
-    for i in 1..context.threshould + 2 {
-        if i != context.party_num_int {
-            let m_b = m_b_gamma_rec_vec[j].clone();
-
-            let alpha_ij_gamma = m_b.verify_proofs_get_alpha(
-                &context.party_keys.dk,
-                &context.sign_keys.as_ref().unwrap().k_i,
-            )?;
-            let m_b = m_b_w_rec_vec[j].clone();
-            let alpha_ij_wi = m_b.verify_proofs_get_alpha(
-                &context.party_keys.dk,
-                &context.sign_keys.as_ref().unwrap().k_i,
-            )?;
-            alpha_vec.push(alpha_ij_gamma.0);
-            miu_vec.push(alpha_ij_wi.0);
-            let g_w_i = Keys::update_commitments_to_xi(
-                &context.xi_com_vec.as_ref().unwrap()
-                    [usize::from(context.signers_vec.as_ref().unwrap()[usize::from(i - 1)])],
-                &context.vss_scheme_vec
-                    [usize::from(context.signers_vec.as_ref().unwrap()[usize::from(i - 1)])],
-                context.signers_vec.as_ref().unwrap()[usize::from(i - 1)],
-                &context.signers_vec.as_ref().unwrap(),
-            );
-            assert_eq!(m_b.b_proof.pk, g_w_i);
-            j += 1;
-        }
-    }
-    //////////////////////////////////////////////////////////////////////////////
-    let delta_i = context
-        .sign_keys
-        .as_ref()
-        .unwrap()
-        .phase2_delta_i(&alpha_vec, &context.beta_vec.as_ref().unwrap());
-    let sigma = context
-        .sign_keys
-        .as_ref()
-        .unwrap()
-        .phase2_sigma_i(&miu_vec, &context.ni_vec.as_ref().unwrap());
-
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round3",
-        serde_json::to_string(&delta_i)?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round3_ans_vec = poll_for_broadcasts(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        context.threshould + 1,
-        "round3",
-        context.uuid.clone(),
-        delay,
-    )
-    .await?;
-    let mut delta_vec: Vec<Scalar> = Vec::new();
+pub async fn eddsa_keygen_client_round4(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<EddsaKeygenClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    let vss_scheme = context.vss_scheme.clone().unwrap();
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round4",
+            serde_json::to_string(&vss_scheme)?,
+        )
+        .await?;
+    let round4_ans_vec = transport
+        .recv_broadcasts(
+            context.party_num_int,
+            context.params.share_count as u16,
+            "round4",
+        )
+        .await?;
+
+    let mut vss_scheme_vec: Vec<VerifiableSS<Ed25519Scalar>> = Vec::new();
     format_vec_from_reads(
-        &round3_ans_vec,
+        &round4_ans_vec,
         context.party_num_int as usize,
-        delta_i,
-        &mut delta_vec,
+        vss_scheme,
+        &mut vss_scheme_vec,
     )?;
-    let delta_inv = SignKeys::phase3_reconstruct_delta(&delta_vec);
+    let (shared_keys, dlog_proof) = context
+        .party_keys
+        .as_ref()
+        .unwrap()
+        .phase2_verify_vss_construct_keypair_phase3_pok_dlog(
+            &context.params,
+            context.point_vec.as_ref().unwrap(),
+            context.party_shares.as_ref().unwrap(),
+            &vss_scheme_vec,
+            context.party_num_int as usize,
+        )
+        .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
 
-    context.m_b_gamma_rec_vec = Some(m_b_gamma_rec_vec);
-    context.delta_inv = Some(delta_inv);
-    context.sigma = Some(sigma);
+    context.shared_keys = Some(shared_keys);
+    context.dlog_proof = Some(dlog_proof);
+    context.vss_scheme_vec = Some(vss_scheme_vec);
 
     Ok(serde_json::to_string(&context)?)
 }
 
+/// Returns the key store: the keys of the party, its shared keys, its party number, the
+/// VSS schemes of all parties and the group key.
 #[wasm_bindgen]
-pub async fn gg18_sign_client_round4(context: String, delay: u32) -> Result<String> {
-    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
-    let client = new_client_with_headers()?;
-    // decommit to gamma_i
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round4",
-        serde_json::to_string(&context.decommit.as_ref().unwrap())?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round4_ans_vec = poll_for_broadcasts(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        context.threshould + 1,
-        "round4",
-        context.uuid.clone(),
-        delay,
-    )
-    .await?;
-
-    let mut decommit_vec: Vec<SignDecommitPhase1> = Vec::new();
+pub async fn eddsa_keygen_client_round5(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<EddsaKeygenClientContext>(&context)?;
+    let transport = HttpTransport::new(&context.addr, &context.uuid, delay);
+    let dlog_proof = context.dlog_proof.clone().unwrap();
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round5",
+            serde_json::to_string(&dlog_proof)?,
+        )
+        .await?;
+    let round5_ans_vec = transport
+        .recv_broadcasts(
+            context.party_num_int,
+            context.params.share_count as u16,
+            "round5",
+        )
+        .await?;
+
+    let mut dlog_proof_vec: Vec<DLogProof<Ed25519Scalar>> = Vec::new();
     format_vec_from_reads(
-        &round4_ans_vec,
+        &round5_ans_vec,
         context.party_num_int as usize,
-        context.decommit.clone().unwrap(),
-        &mut decommit_vec,
-    )?;
-
-    let decomm_i = decommit_vec.remove(usize::from(context.party_num_int - 1));
-    let _ = &context
-        .bc1_vec
-        .as_mut()
-        .unwrap()
-        .remove(usize::from(context.party_num_int - 1));
-    let b_proof_vec = (0..context.m_b_gamma_rec_vec.as_ref().unwrap().len())
-        .map(|i| &context.m_b_gamma_rec_vec.as_ref().unwrap()[i].b_proof)
-        .collect::<Vec<&DLogProof>>();
-
-    let R = SignKeys::phase4(
-        &context.delta_inv.as_ref().unwrap(),
-        &b_proof_vec,
-        decommit_vec,
-        &context.bc1_vec.as_ref().unwrap(),
+        dlog_proof,
+        &mut dlog_proof_vec,
     )?;
+    let vss_scheme_vec = context.vss_scheme_vec.as_ref().unwrap();
+    EddsaKeys::verify_dlog_proofs(&context.params, &dlog_proof_vec, vss_scheme_vec)
+        .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
 
-    // adding local g_gamma_i
-    let R = R + decomm_i.g_gamma_i * context.delta_inv.as_ref().unwrap();
+    let keygen_json = serde_json::to_string(&(
+        context.party_keys.as_ref().unwrap(),
+        context.shared_keys.as_ref().unwrap(),
+        context.party_num_int,
+        vss_scheme_vec,
+        context.y_sum.as_ref().unwrap(),
+    ))?;
 
-    // we assume the message is already hashed (by the signer).
-    let message = &context.message[..];
-    let message_bn = BigInt::from_bytes_be(message);
-    let local_sig = LocalSignature::phase5_local_sig(
-        &context.sign_keys.as_ref().unwrap().k_i,
-        &message_bn,
-        &R,
-        &context.sigma.as_ref().unwrap(),
-        &context.y_sum,
-    );
+    Ok(keygen_json)
+}
 
-    let (phase5_com, phase_5a_decom, helgamal_proof, dlog_proof_rho) =
-        local_sig.phase5a_broadcast_5b_zkproof();
+type EddsaKeyStore = (
+    EddsaKeys,
+    EddsaSharedKeys,
+    u16,
+    Vec<VerifiableSS<Ed25519Scalar>>,
+    Ed25519Point,
+);
 
-    context.phase5_com = Some(phase5_com);
-    context.phase_5a_decom = Some(phase_5a_decom);
-    context.helgamal_proof = Some(helgamal_proof);
-    context.dlog_proof_rho = Some(dlog_proof_rho);
-    context.local_sig = Some(local_sig);
-    context.r = Some(R);
+/// The hex of the 32-byte RFC 8032 public key of an EdDSA key store.
+#[wasm_bindgen]
+pub fn eddsa_public_key(key_store: String) -> Result<String> {
+    let (_, _, _, _, y_sum): EddsaKeyStore = serde_json::from_str(&key_store)?;
+    Ok(hex::encode(y_sum.to_bytes(true)))
+}
 
-    Ok(serde_json::to_string(&context)?)
+#[derive(Debug, Serialize, Deserialize)]
+struct EddsaSignClientContext {
+    addr: String,
+    shared_keys: EddsaSharedKeys,
+    party_id: u16,
+    vss_scheme_vec: Vec<VerifiableSS<Ed25519Scalar>>,
+    y_sum: Ed25519Point,
+    threshould: u16,
+    party_num_int: u16,
+    uuid: String,
+    message: Vec<u8>,
+    signers_vec: Option<Vec<usize>>,
+    sign_keys: Option<EddsaSignKeys>,
+    bc1_vec: Option<Vec<EddsaSignBroadcastPhase1>>,
 }
 
+/// Starts a threshold EdDSA signing session on an EdDSA key store, for an RFC 8032
+/// signature of `message_str`.
 #[wasm_bindgen]
-pub async fn gg18_sign_client_round5(context: String, delay: u32) -> Result<String> {
-    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+pub async fn eddsa_sign_client_new_context(
+    addr: String,
+    t: usize,
+    _n: usize,
+    key_store: String,
+    message_str: String,
+) -> Result<String> {
+    let message = match hex::decode(message_str.clone()) {
+        Ok(x) => x,
+        Err(_e) => message_str.as_bytes().to_vec(),
+    };
     let client = new_client_with_headers()?;
-    //phase (5A)  broadcast commit
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round5",
-        serde_json::to_string(&context.phase5_com.as_ref().unwrap())?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round5_ans_vec = poll_for_broadcasts(
-        &client,
+
+    let (_, shared_keys, party_id, vss_scheme_vec, y_sum): EddsaKeyStore =
+        serde_json::from_str(&key_store)?;
+
+    let (party_num_int, uuid) = match signup_sign(&client, &addr).await? {
+        PartySignup { number, uuid } => (number, uuid),
+    };
+
+    Ok(serde_json::to_string(&EddsaSignClientContext {
+        addr,
+        shared_keys,
+        party_id,
+        vss_scheme_vec,
+        y_sum,
+        threshould: t as u16,
+        party_num_int,
+        uuid,
+        message,
+        signers_vec: None,
+        sign_keys: None,
+        bc1_vec: None,
+    })?)
+}
+
+#[wasm_bindgen]
+pub async fn eddsa_sign_client_round0(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<EddsaSignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
         &context.addr,
-        context.party_num_int,
-        context.threshould + 1,
-        "round5",
-        context.uuid.clone(),
+        &context.uuid,
         delay,
-    )
-    .await?;
-
-    let mut commit5a_vec: Vec<Phase5Com1> = Vec::new();
+    );
+    // round 0: collect signers IDs
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round0",
+            serde_json::to_string(&context.party_id)?,
+        )
+        .await?;
+    let round0_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round0")
+        .await?;
+
+    let mut party_id_vec: Vec<u16> = Vec::new();
     format_vec_from_reads(
-        &round5_ans_vec,
+        &round0_ans_vec,
         context.party_num_int as usize,
-        context.phase5_com.clone().unwrap(),
-        &mut commit5a_vec,
+        context.party_id,
+        &mut party_id_vec,
     )?;
+    let signers_vec = party_id_vec
+        .iter()
+        .map(|&party_id| usize::from(party_id - 1))
+        .collect::<Vec<usize>>();
+    let sign_keys = EddsaSignKeys::create(
+        &context.shared_keys,
+        &context.vss_scheme_vec,
+        usize::from(context.party_id - 1),
+        &signers_vec,
+    );
 
-    context.commit5a_vec = Some(commit5a_vec);
+    context.signers_vec = Some(signers_vec);
+    context.sign_keys = Some(sign_keys);
 
     Ok(serde_json::to_string(&context)?)
 }
 
 #[wasm_bindgen]
-pub async fn gg18_sign_client_round6(context: String, delay: u32) -> Result<String> {
-    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
-    let client = new_client_with_headers()?;
-    //phase (5B)  broadcast decommit and (5B) ZK proof
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round6",
-        serde_json::to_string(&(
-            context.phase_5a_decom.clone().unwrap(),
-            context.helgamal_proof.clone().unwrap(),
-            context.dlog_proof_rho.clone().unwrap(),
-        ))?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round6_ans_vec = poll_for_broadcasts(
-        &client,
+pub async fn eddsa_sign_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<EddsaSignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
         &context.addr,
-        context.party_num_int,
-        context.threshould + 1,
-        "round6",
-        context.uuid.clone(),
+        &context.uuid,
         delay,
-    )
-    .await?;
-
-    let mut decommit5a_and_elgamal_and_dlog_vec: Vec<(Phase5ADecom1, HomoELGamalProof, DLogProof)> =
-        Vec::new();
+    );
+    let bc1_i = context.sign_keys.as_ref().unwrap().phase1_broadcast();
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round1",
+            serde_json::to_string(&bc1_i)?,
+        )
+        .await?;
+    let round1_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round1")
+        .await?;
+
+    let mut bc1_vec = Vec::new();
     format_vec_from_reads(
-        &round6_ans_vec,
+        &round1_ans_vec,
         context.party_num_int as usize,
-        (
-            context.phase_5a_decom.clone().unwrap(),
-            context.helgamal_proof.clone().unwrap(),
-            context.dlog_proof_rho.clone().unwrap(),
-        ),
-        &mut decommit5a_and_elgamal_and_dlog_vec,
-    )?;
-    let decommit5a_and_elgamal_and_dlog_vec_includes_i =
-        decommit5a_and_elgamal_and_dlog_vec.clone();
-    decommit5a_and_elgamal_and_dlog_vec.remove(usize::from(context.party_num_int - 1));
-    context
-        .commit5a_vec
-        .as_mut()
-        .unwrap()
-        .remove(usize::from(context.party_num_int - 1));
-    let phase_5a_decomm_vec = (0..context.threshould)
-        .map(|i| decommit5a_and_elgamal_and_dlog_vec[i as usize].0.clone())
-        .collect::<Vec<Phase5ADecom1>>();
-    let phase_5a_elgamal_vec = (0..context.threshould)
-        .map(|i| decommit5a_and_elgamal_and_dlog_vec[i as usize].1.clone())
-        .collect::<Vec<HomoELGamalProof>>();
-    let phase_5a_dlog_vec = (0..context.threshould)
-        .map(|i| decommit5a_and_elgamal_and_dlog_vec[i as usize].2.clone())
-        .collect::<Vec<DLogProof>>();
-    let (phase5_com2, phase_5d_decom2) = context.local_sig.clone().unwrap().phase5c(
-        &phase_5a_decomm_vec,
-        &context.commit5a_vec.as_ref().unwrap(),
-        &phase_5a_elgamal_vec,
-        &phase_5a_dlog_vec,
-        &context.phase_5a_decom.as_ref().unwrap().V_i,
-        &context.r.as_ref().unwrap(),
+        bc1_i,
+        &mut bc1_vec,
     )?;
 
-    context.phase5_com2 = Some(phase5_com2);
-    context.phase_5d_decom2 = Some(phase_5d_decom2);
-    context.decommit5a_and_elgamal_and_dlog_vec_includes_i =
-        Some(decommit5a_and_elgamal_and_dlog_vec_includes_i);
+    context.bc1_vec = Some(bc1_vec);
 
     Ok(serde_json::to_string(&context)?)
 }
 
+/// Returns the hex of the 64-byte signature and of the 32-byte public key it verifies
+/// against.
 #[wasm_bindgen]
-pub async fn gg18_sign_client_round7(context: String, delay: u32) -> Result<String> {
-    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
-    let client = new_client_with_headers()?;
-    //////////////////////////////////////////////////////////////////////////////
-    broadcast(
-        &client,
-        &context.addr,
-        context.party_num_int,
-        "round7",
-        serde_json::to_string(&context.phase5_com2.as_ref().unwrap())?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round7_ans_vec = poll_for_broadcasts(
-        &client,
+pub async fn eddsa_sign_client_round2(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<EddsaSignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
         &context.addr,
-        context.party_num_int,
-        context.threshould + 1,
-        "round7",
-        context.uuid.clone(),
+        &context.uuid,
         delay,
-    )
-    .await?;
-
-    let mut commit5c_vec: Vec<Phase5Com2> = Vec::new();
+    );
+    let sign_keys = context.sign_keys.as_ref().unwrap();
+    let bc1_vec = context.bc1_vec.as_ref().unwrap();
+    let bc2_i = sign_keys.phase2_sign(&context.message, &context.y_sum, bc1_vec);
+    transport
+        .send_broadcast(
+            context.party_num_int,
+            "round2",
+            serde_json::to_string(&bc2_i)?,
+        )
+        .await?;
+    let round2_ans_vec = transport
+        .recv_broadcasts(context.party_num_int, context.threshould + 1, "round2")
+        .await?;
+
+    let mut bc2_vec: Vec<EddsaSignBroadcastPhase2> = Vec::new();
     format_vec_from_reads(
-        &round7_ans_vec,
+        &round2_ans_vec,
         context.party_num_int as usize,
-        context.phase5_com2.clone().unwrap(),
-        &mut commit5c_vec,
+        bc2_i,
+        &mut bc2_vec,
     )?;
+    let signers_vec = context.signers_vec.as_ref().unwrap();
+    let sig = sign_keys
+        .phase2_verify_output_signature(&context.message, &context.y_sum, bc1_vec, &bc2_vec)
+        .map_err(|e| e.map_blamed_parties(|j| signers_vec[j] + 1))?;
 
-    context.commit5c_vec = Some(commit5c_vec);
+    let sign_json = serde_json::to_string(&vec![
+        hex::encode(sig.to_bytes()),
+        hex::encode(context.y_sum.to_bytes(true)),
+    ])?;
 
-    Ok(serde_json::to_string(&context)?)
+    Ok(sign_json)
 }
 
+// In a Lindell17 session the cosigner is always party one and the client party two,
+// whatever order they signed up in.
+const TWO_PARTY_COSIGNER: u16 = 1;
+const TWO_PARTY_CLIENT: u16 = 2;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Lindell17KeygenClientContext {
+    addr: String,
+    uuid: String,
+    first_message: Option<Lindell17KeyGenFirstMsg>,
+    ec_key_pair: Option<Lindell17EcKeyPair>,
+    party_one_first_message: Option<Lindell17CommitMsg>,
+}
+
+/// Starts a two-party keygen with a cosigner. The client is party two of Lindell17: it
+/// ends up with x2 and the encryption of the x1 of the cosigner.
 #[wasm_bindgen]
-pub async fn gg18_sign_client_round8(context: String, delay: u32) -> Result<String> {
-    let mut context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+pub async fn lindell17_keygen_client_new_context(addr: String, _delay: u32) -> Result<String> {
     let client = new_client_with_headers()?;
-    //phase (5B)  broadcast decommit and (5B) ZK proof
-    broadcast(
-        &client,
+    let uuid = signup_two_party(&client, &addr).await?.uuid;
+
+    Ok(serde_json::to_string(&Lindell17KeygenClientContext {
+        addr,
+        uuid,
+        first_message: None,
+        ec_key_pair: None,
+        party_one_first_message: None,
+    })?)
+}
+
+/// Sends Q2 once the cosigner has committed to Q1.
+#[wasm_bindgen]
+pub async fn lindell17_keygen_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<Lindell17KeygenClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
         &context.addr,
-        context.party_num_int,
-        "round8",
-        serde_json::to_string(&context.phase_5d_decom2.as_ref().unwrap())?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round8_ans_vec = poll_for_broadcasts(
-        &client,
+        &context.uuid,
+        delay,
+    );
+    let (first_message, ec_key_pair) = Lindell17KeyGenFirstMsg::create();
+
+    let round1_ans_vec = transport
+        .recv_broadcasts(TWO_PARTY_CLIENT, 2, "round1")
+        .await?;
+    let party_one_first_message: Lindell17CommitMsg = serde_json::from_str(&round1_ans_vec[0])?;
+    transport
+        .send_broadcast(
+            TWO_PARTY_CLIENT,
+            "round2",
+            serde_json::to_string(&first_message)?,
+        )
+        .await?;
+
+    context.first_message = Some(first_message);
+    context.ec_key_pair = Some(ec_key_pair);
+    context.party_one_first_message = Some(party_one_first_message);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Returns the key store of the client, for `lindell17_sign_client_new_context`.
+#[wasm_bindgen]
+pub async fn lindell17_keygen_client_round2(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<Lindell17KeygenClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
         &context.addr,
-        context.party_num_int,
-        context.threshould + 1,
-        "round8",
-        context.uuid.clone(),
+        &context.uuid,
         delay,
+    );
+    let round3_ans_vec = transport
+        .recv_broadcasts(TWO_PARTY_CLIENT, 2, "round3")
+        .await?;
+    let party_one_second_message: Lindell17KeyGenSecondMsg =
+        serde_json::from_str(&round3_ans_vec[0])?;
+
+    let key_share = Lindell17KeyShare::create(
+        context.ec_key_pair.as_ref().unwrap(),
+        context.first_message.as_ref().unwrap(),
+        context.party_one_first_message.as_ref().unwrap(),
+        &party_one_second_message,
     )
-    .await?;
-
-    let mut decommit5d_vec: Vec<Phase5DDecom2> = Vec::new();
-    format_vec_from_reads(
-        &round8_ans_vec,
-        context.party_num_int as usize,
-        context.phase_5d_decom2.clone().unwrap(),
-        &mut decommit5d_vec,
-    )?;
+    .map_err(|e| e.blame(TWO_PARTY_COSIGNER as usize))?;
 
-    let phase_5a_decomm_vec_includes_i = (0..=context.threshould)
-        .map(|i| {
-            context
-                .decommit5a_and_elgamal_and_dlog_vec_includes_i
-                .clone()
-                .unwrap()[i as usize]
-                .0
-                .clone()
-        })
-        .collect::<Vec<Phase5ADecom1>>();
-    let s_i = context.local_sig.clone().unwrap().phase5d(
-        &decommit5d_vec,
-        &context.commit5c_vec.as_ref().unwrap(),
-        &phase_5a_decomm_vec_includes_i,
-    )?;
+    Ok(serde_json::to_string(&key_share)?)
+}
 
-    context.s_i = Some(s_i);
+/// The hex of the compressed public key of a Lindell17 key store.
+#[wasm_bindgen]
+pub fn lindell17_public_key(key_store: String) -> Result<String> {
+    let key_share: Lindell17KeyShare = serde_json::from_str(&key_store)?;
+    Ok(hex::encode(key_share.Q.to_bytes(true)))
+}
 
-    Ok(serde_json::to_string(&context)?)
+#[derive(Debug, Serialize, Deserialize)]
+struct Lindell17SignClientContext {
+    addr: String,
+    uuid: String,
+    key_share: Lindell17KeyShare,
+    message: Vec<u8>,
+    eph_key_pair: Option<Lindell17EcKeyPair>,
+    party_one_first_message: Option<Lindell17CommitMsg>,
 }
 
+/// Starts a two-party signing session with the cosigner, on a Lindell17 key store.
 #[wasm_bindgen]
-pub async fn gg18_sign_client_round9(context: String, delay: u32) -> Result<String> {
-    let context = serde_json::from_str::<GG18SignClientContext>(&context)?;
+pub async fn lindell17_sign_client_new_context(
+    addr: String,
+    key_store: String,
+    message_str: String,
+) -> Result<String> {
+    let message = match hex::decode(message_str.clone()) {
+        Ok(x) => x,
+        Err(_e) => message_str.as_bytes().to_vec(),
+    };
     let client = new_client_with_headers()?;
-    //////////////////////////////////////////////////////////////////////////////
-    broadcast(
-        &client,
+    let key_share: Lindell17KeyShare = serde_json::from_str(&key_store)?;
+    let uuid = signup_two_party(&client, &addr).await?.uuid;
+
+    Ok(serde_json::to_string(&Lindell17SignClientContext {
+        addr,
+        uuid,
+        key_share,
+        message,
+        eph_key_pair: None,
+        party_one_first_message: None,
+    })?)
+}
+
+/// Sends R2 once the cosigner has committed to R1.
+#[wasm_bindgen]
+pub async fn lindell17_sign_client_round1(context: String, delay: u32) -> Result<String> {
+    let mut context = serde_json::from_str::<Lindell17SignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
         &context.addr,
-        context.party_num_int,
-        "round9",
-        serde_json::to_string(&context.s_i.as_ref().unwrap())?,
-        context.uuid.clone(),
-    )
-    .await?;
-    let round9_ans_vec = poll_for_broadcasts(
-        &client,
+        &context.uuid,
+        delay,
+    );
+    let (first_message, eph_key_pair) = Lindell17SignFirstMsg::create();
+
+    let round1_ans_vec = transport
+        .recv_broadcasts(TWO_PARTY_CLIENT, 2, "round1")
+        .await?;
+    let party_one_first_message: Lindell17CommitMsg = serde_json::from_str(&round1_ans_vec[0])?;
+    transport
+        .send_broadcast(
+            TWO_PARTY_CLIENT,
+            "round2",
+            serde_json::to_string(&first_message)?,
+        )
+        .await?;
+
+    context.eph_key_pair = Some(eph_key_pair);
+    context.party_one_first_message = Some(party_one_first_message);
+
+    Ok(serde_json::to_string(&context)?)
+}
+
+/// Checks the decommitment to R1 and sends the encrypted partial signature.
+#[wasm_bindgen]
+pub async fn lindell17_sign_client_round2(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<Lindell17SignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
         &context.addr,
-        context.party_num_int,
-        context.threshould + 1,
-        "round9",
-        context.uuid.clone(),
+        &context.uuid,
         delay,
-    )
-    .await?;
+    );
+    let round3_ans_vec = transport
+        .recv_broadcasts(TWO_PARTY_CLIENT, 2, "round3")
+        .await?;
+    let party_one_second_message: Lindell17CommWitness = serde_json::from_str(&round3_ans_vec[0])?;
+
+    let message_bn = BigInt::from_bytes_be(&context.message[..]);
+    let partial_sig = context
+        .key_share
+        .partial_sign(
+            context.eph_key_pair.as_ref().unwrap(),
+            context.party_one_first_message.as_ref().unwrap(),
+            &party_one_second_message,
+            &message_bn,
+        )
+        .map_err(|e| e.blame(TWO_PARTY_COSIGNER as usize))?;
+    transport
+        .send_broadcast(
+            TWO_PARTY_CLIENT,
+            "round4",
+            serde_json::to_string(&partial_sig)?,
+        )
+        .await?;
 
-    let mut s_i_vec: Vec<Scalar> = Vec::new();
-    format_vec_from_reads(
-        &round9_ans_vec,
-        context.party_num_int as usize,
-        context.s_i.unwrap(),
-        &mut s_i_vec,
-    )?;
+    Ok(serde_json::to_string(&context)?)
+}
 
-    s_i_vec.remove(usize::from(context.party_num_int - 1));
-    let sig = context
-        .local_sig
-        .clone()
-        .unwrap()
-        .output_signature(&s_i_vec)?;
+/// Waits for the signature the cosigner outputs and returns r, s and the recovery id,
+/// as `gg18_sign_client_round9`.
+#[wasm_bindgen]
+pub async fn lindell17_sign_client_round3(context: String, delay: u32) -> Result<String> {
+    let context = serde_json::from_str::<Lindell17SignClientContext>(&context)?;
+    let transport = HttpTransport::with_client(
+        new_client_with_headers()?,
+        &context.addr,
+        &context.uuid,
+        delay,
+    );
+    let round5_ans_vec = transport
+        .recv_broadcasts(TWO_PARTY_CLIENT, 2, "round5")
+        .await?;
+    let sig: Signature = serde_json::from_str(&round5_ans_vec[0])?;
+
+    let message_bn = BigInt::from_bytes_be(&context.message[..]);
+    check_sig(&sig.r, &sig.s, &message_bn, &context.key_share.Q)?;
 
     let sign_json = serde_json::to_string(&vec![
-        //"r",
         sig.r.to_big_int().to_hex(),
-        //"s",
         sig.s.to_big_int().to_hex(),
-        //"v"
         sig.recid.to_string(),
     ])?;
-    crate::console_log!("sign_json: {:?}", sign_json);
-
-    check_sig(
-        &sig.r,
-        &sig.s,
-        &context.local_sig.clone().unwrap().m,
-        &context.y_sum.clone(),
-    )?;
 
     Ok(sign_json)
 }
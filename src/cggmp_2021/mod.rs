@@ -0,0 +1,43 @@
+/*
+    CGGMP21 threshold ECDSA, https://eprint.iacr.org/2021/060.pdf
+
+    The proofs of this module are made non-interactive with Fiat-Shamir and, as the GG18
+    range proofs, sample their masks from nonnegative ranges instead of symmetric ones.
+    They are made against the ring-Pedersen parameters (N_hat, s, t) of the verifier,
+    which are the h1, h2, N_tilde of a `DLogStatement`.
+*/
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::paillier::zkproofs::DLogStatement;
+use crate::paillier::EncryptionKey;
+
+pub mod party_i;
+pub mod zk_aff_g;
+pub mod zk_enc;
+pub mod zk_log_star;
+
+// bit length of the secrets
+pub(crate) const L: usize = 256;
+// slackness of the range proofs
+pub(crate) const EPSILON: usize = 512;
+// bit length of the masks of the MtA
+pub(crate) const L_PRIME: usize = 1280;
+
+/// (1 + N)^m r^N mod N^2, for plaintexts that may exceed N.
+pub(crate) fn encrypt(ek: &EncryptionKey, m: &BigInt, r: &BigInt) -> BigInt {
+    BigInt::mod_mul(
+        &((m * &ek.n + 1u32) % &ek.nn),
+        &BigInt::mod_pow(r, &ek.n, &ek.nn),
+        &ek.nn,
+    )
+}
+
+/// s^a t^b mod N_hat, with the ring-Pedersen parameters of a verifier.
+pub(crate) fn commit(statement: &DLogStatement, a: &BigInt, b: &BigInt) -> BigInt {
+    BigInt::mod_mul(
+        &BigInt::mod_pow(&statement.g, a, &statement.N),
+        &BigInt::mod_pow(&statement.ni, b, &statement.N),
+        &statement.N,
+    )
+}
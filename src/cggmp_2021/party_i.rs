@@ -0,0 +1,823 @@
+#![allow(non_snake_case)]
+
+/*
+    CGGMP21 threshold ECDSA, https://eprint.iacr.org/2021/060.pdf
+
+    Keygen, Figure 5 with Feldman VSS for a threshold t:
+
+    - round 1: each party commits to its VSS commitments, to rid_i and to the first
+      message A_i of a Schnorr proof.
+    - round 2: the commitments are opened, then the shares are sent encrypted.
+    - round 4: the shares are checked and each party proves knowledge of x_i, with a
+      challenge bound to rid, the XOR of all rid_j.
+
+    Auxiliary info, Figure 6 without the refresh of the shares:
+
+    - round 1: each party publishes a Paillier key of safe primes and ring-Pedersen
+      parameters s, t on the same modulus, with Π-mod and Π-prm.
+    - round 2: each party proves to every other one that its modulus has no small factors.
+
+    Presigning, Figure 7:
+
+    - round 1: K_i = Enc(k_i) and G_i = Enc(gamma_i), with Π-enc for K_i.
+    - round 2: Gamma_i = g^gamma_i and the MtA of gamma_i and of w_i with every other
+      K_j, with Π-aff-g for both and Π-log* for Gamma_i.
+    - round 3: delta_i and Delta_i = Gamma^k_i, with Π-log*.
+    - round 4: R_i = R^k_i and S_i = R^chi_i, without the GG20 proofs of GG18 presigning,
+      so that the result is a GG18 `Presignature` and a wrong online share is blamed on
+      its sender.
+
+    When g^delta is not the sum of the Delta_j, CGGMP21 finds the culprit with further
+    proofs. These are not implemented: presigning then fails without blame.
+*/
+
+use crate::cggmp_2021::encrypt;
+use crate::cggmp_2021::zk_aff_g::{AffGProof, AffGStatement, AffGWitness};
+use crate::cggmp_2021::zk_enc::{EncProof, EncStatement};
+use crate::cggmp_2021::zk_log_star::{LogStarProof, LogStarStatement};
+use crate::cggmp_2021::L_PRIME;
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::cryptographic_primitives::commitments::hash_commitment::HashCommitment;
+use crate::curv::cryptographic_primitives::commitments::traits::Commitment;
+use crate::curv::cryptographic_primitives::hashing::ext::DigestExt;
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::BlameItem::{
+    self, DeltaLogStarProof, KeyGenDecommitment, RingPedersenProof, VssShare, XiDLogProof,
+};
+use crate::errors::TssError::{self, InvalidKey};
+use crate::errors::{Blame, Result};
+use crate::gg_2018::party_i::{
+    generate_h1_h2_from_safe_primes, verify_dlog_statement, Keys, Parameters,
+    PresignBroadcastMessage, Presignature,
+};
+use crate::gg_2018::range_proofs::SampleFromMultiplicativeGroup;
+use crate::paillier::zkproofs::{
+    CompositeDLogProof, DLogStatement, NoSmallFactorProof, PaillierBlumModulusProof,
+};
+use crate::paillier::{
+    Decrypt, DecryptionKey, EncryptionKey, KeyGeneration, Paillier, RawCiphertext,
+};
+use crate::sha2::Digest;
+
+use num_traits::{One, Zero};
+use sha2::Sha256;
+
+const SECURITY: usize = 256;
+
+/// What a party keeps during keygen.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeygenKeys {
+    pub party_index: usize,
+    u_i: FE,
+    tau_i: FE,
+    rid_i: BigInt,
+    blind_factor: BigInt,
+    pub vss_scheme: VerifiableSS,
+    secret_shares: Vec<FE>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeygenCommitMessage {
+    pub com: BigInt,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeygenDecommitMessage {
+    pub rid_i: BigInt,
+    pub vss_scheme: VerifiableSS,
+    /// first message of the Schnorr proof of round 4
+    pub A_i: GE,
+    pub blind_factor: BigInt,
+}
+
+/// Answer to the Schnorr challenge for the A_i of the decommitment, proving knowledge of x_i.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeygenProofMessage {
+    pub z: FE,
+}
+
+/// The output of keygen.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeyShare {
+    pub party_index: usize,
+    x_i: FE,
+    pub y: GE,
+    pub vss_scheme_vec: Vec<VerifiableSS>,
+    pub rid: BigInt,
+}
+
+/// What a party keeps during the generation of auxiliary info.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuxInfoKeys {
+    pub party_index: usize,
+    dk: DecryptionKey,
+    pub ek: EncryptionKey,
+    /// s, t and N_hat, on the modulus of `ek`
+    pub dlog_statement: DLogStatement,
+    lambda: BigInt,
+    lambda_inv: BigInt,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuxInfoBroadcastMessage {
+    pub ek: EncryptionKey,
+    pub dlog_statement: DLogStatement,
+    pub paillier_blum_modulus_proof: PaillierBlumModulusProof,
+    pub composite_dlog_proof_base_h1: CompositeDLogProof,
+    pub composite_dlog_proof_base_h2: CompositeDLogProof,
+}
+
+/// The output of the generation of auxiliary info.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuxInfo {
+    dk: DecryptionKey,
+    /// Paillier key of every party
+    pub ek_vec: Vec<EncryptionKey>,
+    /// s, t and N_hat of every party
+    pub dlog_statement_vec: Vec<DLogStatement>,
+}
+
+/// What a signer keeps during presigning. The vectors are in signing order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PresignKeys {
+    /// our position in the signing order
+    pub position: usize,
+    k_i: FE,
+    gamma_i: FE,
+    w_i: FE,
+    rho_i: BigInt,
+    nu_i: BigInt,
+    pub K_i: BigInt,
+    pub G_i: BigInt,
+    dk: DecryptionKey,
+    pub ek_vec: Vec<EncryptionKey>,
+    pub dlog_statement_vec: Vec<DLogStatement>,
+    pub g_w_vec: Vec<GE>,
+    pub y: GE,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PresignRound1Message {
+    pub K_i: BigInt,
+    pub G_i: BigInt,
+    /// one proof for every other signer, in signing order, against its s,t,N_hat
+    pub enc_proofs: Vec<EncProof>,
+}
+
+/// The MtA of a signer with the K_j of another signer j: D and F for gamma_i, D_hat and
+/// F_hat for w_i. D and D_hat are encrypted under the key of j, F and F_hat under ours.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PresignMtaMessage {
+    pub D: BigInt,
+    pub F: BigInt,
+    pub D_hat: BigInt,
+    pub F_hat: BigInt,
+    pub aff_g_proof: AffGProof,
+    pub aff_g_proof_hat: AffGProof,
+    /// proof that Gamma_i is made with the gamma_i of G_i
+    pub log_star_proof: LogStarProof,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PresignRound2Message {
+    pub Gamma_i: GE,
+    /// one MtA for every other signer, in signing order
+    pub mta: Vec<PresignMtaMessage>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PresignRound3Message {
+    pub delta_i: FE,
+    pub Delta_i: GE,
+    /// one proof for every other signer, in signing order, that Delta_i is made with the k_i
+    /// of K_i
+    pub log_star_proofs: Vec<LogStarProof>,
+}
+
+impl KeygenKeys {
+    pub fn create(params: &Parameters, index: usize) -> KeygenKeys {
+        let u_i: FE = ECScalar::new_random();
+        let (vss_scheme, secret_shares) =
+            VerifiableSS::share(params.threshold, params.share_count, &u_i);
+        KeygenKeys {
+            party_index: index,
+            u_i,
+            tau_i: ECScalar::new_random(),
+            rid_i: BigInt::sample(SECURITY),
+            blind_factor: BigInt::sample(SECURITY),
+            vss_scheme,
+            secret_shares,
+        }
+    }
+
+    pub fn u_i(&self) -> &FE {
+        &self.u_i
+    }
+
+    /// The share of every party, to be sent encrypted after round 2.
+    pub fn secret_shares(&self) -> &[FE] {
+        &self.secret_shares
+    }
+
+    pub fn round1_commit(&self) -> KeygenCommitMessage {
+        KeygenCommitMessage {
+            com: keygen_commitment(self.party_index, &self.round2_decommit()),
+        }
+    }
+
+    pub fn round2_decommit(&self) -> KeygenDecommitMessage {
+        let g: GE = ECPoint::generator();
+        KeygenDecommitMessage {
+            rid_i: self.rid_i.clone(),
+            vss_scheme: self.vss_scheme.clone(),
+            A_i: &g * &self.tau_i,
+            blind_factor: self.blind_factor.clone(),
+        }
+    }
+
+    /// Checks the decommitments of every party, and that each deals shares for `params`.
+    pub fn round2_verify_decommitments(
+        params: &Parameters,
+        com_vec: &[KeygenCommitMessage],
+        decom_vec: &[KeygenDecommitMessage],
+    ) -> Result<()> {
+        assert_eq!(com_vec.len(), params.share_count);
+        assert_eq!(decom_vec.len(), params.share_count);
+        let blames = (0..decom_vec.len())
+            .filter(|&i| {
+                let vss_parameters = &decom_vec[i].vss_scheme.parameters;
+                keygen_commitment(i + 1, &decom_vec[i]) != com_vec[i].com
+                    || vss_parameters.threshold != params.threshold
+                    || vss_parameters.share_count != params.share_count
+                    || decom_vec[i].vss_scheme.commitments.len() != params.threshold + 1
+            })
+            .map(|i| Blame::new(i, KeyGenDecommitment))
+            .collect::<Vec<Blame>>();
+
+        match blames.is_empty() {
+            true => Ok(()),
+            false => Err(TssError::Blame(blames)),
+        }
+    }
+
+    /// Checks the shares received from every party and adds them up to x_i. Returns the key
+    /// share with the proof of knowledge of x_i to broadcast.
+    pub fn round4_create_key_share(
+        &self,
+        decom_vec: &[KeygenDecommitMessage],
+        secret_shares_vec: &[FE],
+    ) -> Result<(KeyShare, KeygenProofMessage)> {
+        let blames = (0..decom_vec.len())
+            .filter(|&i| {
+                decom_vec[i]
+                    .vss_scheme
+                    .validate_share(&secret_shares_vec[i], self.party_index)
+                    .is_err()
+            })
+            .map(|i| Blame::new(i, VssShare))
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let head = decom_vec[0].vss_scheme.commitments[0].clone();
+        let y = decom_vec[1..]
+            .iter()
+            .fold(head, |acc, decom| acc + &decom.vss_scheme.commitments[0]);
+        let rid = decom_vec
+            .iter()
+            .fold(BigInt::zero(), |acc, decom| acc ^ &decom.rid_i);
+        let x_i = secret_shares_vec
+            .iter()
+            .fold(FE::zero(), |acc, share| acc + share);
+        let key_share = KeyShare {
+            party_index: self.party_index,
+            x_i,
+            y,
+            vss_scheme_vec: decom_vec
+                .iter()
+                .map(|decom| decom.vss_scheme.clone())
+                .collect(),
+            rid,
+        };
+
+        let g: GE = ECPoint::generator();
+        let A_i = &g * &self.tau_i;
+        let X_i = &g * &key_share.x_i;
+        let e = schnorr_challenge(&key_share.rid, self.party_index, &X_i, &A_i);
+        let z = self.tau_i.clone() + e * &key_share.x_i;
+        Ok((key_share, KeygenProofMessage { z }))
+    }
+}
+
+impl KeyShare {
+    /// g^x_j of every party.
+    pub fn public_shares(&self) -> Vec<GE> {
+        Keys::get_commitments_to_xi(&self.vss_scheme_vec)
+    }
+
+    /// Checks the proof of knowledge of x_j of every party.
+    pub fn round5_verify_proofs(
+        &self,
+        decom_vec: &[KeygenDecommitMessage],
+        proof_vec: &[KeygenProofMessage],
+    ) -> Result<()> {
+        let g: GE = ECPoint::generator();
+        let X_vec = self.public_shares();
+        let blames = (0..proof_vec.len())
+            .filter(|&j| {
+                let e = schnorr_challenge(&self.rid, j + 1, &X_vec[j], &decom_vec[j].A_i);
+                &g * &proof_vec[j].z != decom_vec[j].A_i.clone() + &X_vec[j] * &e
+            })
+            .map(|j| Blame::new(j, XiDLogProof))
+            .collect::<Vec<Blame>>();
+
+        match blames.is_empty() {
+            true => Ok(()),
+            false => Err(TssError::Blame(blames)),
+        }
+    }
+}
+
+impl AuxInfoKeys {
+    pub fn create(index: usize) -> AuxInfoKeys {
+        let (ek, dk) = Paillier::keypair_safe_primes().keys();
+        let (N, s, t, lambda, lambda_inv) = generate_h1_h2_from_safe_primes(&dk);
+        AuxInfoKeys {
+            party_index: index,
+            dk,
+            ek,
+            dlog_statement: DLogStatement { N, g: s, ni: t },
+            lambda,
+            lambda_inv,
+        }
+    }
+
+    pub fn round1_broadcast(&self) -> AuxInfoBroadcastMessage {
+        let dlog_statement_base_h2 = DLogStatement {
+            N: self.dlog_statement.N.clone(),
+            g: self.dlog_statement.ni.clone(),
+            ni: self.dlog_statement.g.clone(),
+        };
+        AuxInfoBroadcastMessage {
+            ek: self.ek.clone(),
+            dlog_statement: self.dlog_statement.clone(),
+            paillier_blum_modulus_proof: PaillierBlumModulusProof::prove(&self.dk),
+            composite_dlog_proof_base_h1: CompositeDLogProof::prove(
+                &self.dlog_statement,
+                &self.lambda,
+            ),
+            composite_dlog_proof_base_h2: CompositeDLogProof::prove(
+                &dlog_statement_base_h2,
+                &self.lambda_inv,
+            ),
+        }
+    }
+
+    /// One proof for every party, ourselves included, against its s,t,N_hat.
+    pub fn round2_no_small_factor_proofs(
+        &self,
+        bc1_vec: &[AuxInfoBroadcastMessage],
+    ) -> Vec<NoSmallFactorProof> {
+        bc1_vec
+            .iter()
+            .map(|bc1| NoSmallFactorProof::prove(&self.dk, &bc1.dlog_statement))
+            .collect()
+    }
+
+    /// Checks the messages of every party: Π-prm and Π-mod of round 1, and the Π-fac of
+    /// round 2 made for us.
+    pub fn round2_verify_create_aux_info(
+        &self,
+        bc1_vec: &[AuxInfoBroadcastMessage],
+        no_small_factor_proofs_vec: &[Vec<NoSmallFactorProof>],
+    ) -> Result<AuxInfo> {
+        let own_position = self.party_index - 1;
+        let blames = (0..bc1_vec.len())
+            .flat_map(|i| {
+                let no_small_factor = no_small_factor_proofs_vec[i]
+                    .get(own_position)
+                    .map_or(false, |proof| {
+                        proof.verify(&bc1_vec[i].ek.n, &self.dlog_statement).is_ok()
+                    });
+                let checks = [
+                    (
+                        bc1_vec[i].dlog_statement.N == bc1_vec[i].ek.n
+                            && verify_dlog_statement(
+                                &bc1_vec[i].dlog_statement,
+                                &bc1_vec[i].composite_dlog_proof_base_h1,
+                                &bc1_vec[i].composite_dlog_proof_base_h2,
+                            ),
+                        RingPedersenProof,
+                    ),
+                    (
+                        bc1_vec[i]
+                            .paillier_blum_modulus_proof
+                            .verify(&bc1_vec[i].ek)
+                            .is_ok(),
+                        BlameItem::PaillierBlumModulusProof,
+                    ),
+                    (no_small_factor, BlameItem::NoSmallFactorProof),
+                ];
+                checks
+                    .iter()
+                    .filter(|(passed, _)| !passed)
+                    .map(|&(_, item)| Blame::new(i, item))
+                    .collect::<Vec<Blame>>()
+            })
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        Ok(AuxInfo {
+            dk: self.dk.clone(),
+            ek_vec: bc1_vec.iter().map(|bc1| bc1.ek.clone()).collect(),
+            dlog_statement_vec: bc1_vec
+                .iter()
+                .map(|bc1| bc1.dlog_statement.clone())
+                .collect(),
+        })
+    }
+}
+
+impl PresignKeys {
+    /// `signers` holds the 0-based party indices of the signers, in signing order.
+    pub fn create(key_share: &KeyShare, aux_info: &AuxInfo, signers: &Vec<usize>) -> PresignKeys {
+        let index = key_share.party_index - 1;
+        let position = signers.iter().position(|&signer| signer == index).unwrap();
+        // only the parameters of the VSS scheme are used
+        let vss_scheme = &key_share.vss_scheme_vec[0];
+        let w_i = vss_scheme.map_share_to_new_params(index, signers) * &key_share.x_i;
+        let X_vec = key_share.public_shares();
+        let g_w_vec = signers
+            .iter()
+            .map(|&signer| {
+                Keys::update_commitments_to_xi(&X_vec[signer], vss_scheme, signer, signers)
+            })
+            .collect::<Vec<GE>>();
+
+        let ek = &aux_info.ek_vec[index];
+        let k_i: FE = ECScalar::new_random();
+        let gamma_i: FE = ECScalar::new_random();
+        let rho_i = BigInt::from_paillier_key(ek);
+        let nu_i = BigInt::from_paillier_key(ek);
+        PresignKeys {
+            position,
+            K_i: encrypt(ek, &k_i.to_big_int(), &rho_i),
+            G_i: encrypt(ek, &gamma_i.to_big_int(), &nu_i),
+            k_i,
+            gamma_i,
+            w_i,
+            rho_i,
+            nu_i,
+            dk: aux_info.dk.clone(),
+            ek_vec: signers
+                .iter()
+                .map(|&signer| aux_info.ek_vec[signer].clone())
+                .collect(),
+            dlog_statement_vec: signers
+                .iter()
+                .map(|&signer| aux_info.dlog_statement_vec[signer].clone())
+                .collect(),
+            g_w_vec,
+            y: key_share.y.clone(),
+        }
+    }
+
+    pub fn round1_broadcast(&self) -> PresignRound1Message {
+        let enc_proofs = self
+            .others()
+            .map(|j| {
+                let statement = EncStatement {
+                    ciphertext: &self.K_i,
+                    ek: self.ek(),
+                    dlog_statement: &self.dlog_statement_vec[j],
+                };
+                EncProof::prove(&self.k_i.to_big_int(), &self.rho_i, &statement)
+            })
+            .collect::<Vec<EncProof>>();
+        PresignRound1Message {
+            K_i: self.K_i.clone(),
+            G_i: self.G_i.clone(),
+            enc_proofs,
+        }
+    }
+
+    /// Checks the Π-enc made for us by every other signer. Blames refer to the positions in
+    /// the signing order.
+    pub fn round1_verify(&self, round1_vec: &[PresignRound1Message]) -> Result<()> {
+        let blames = self
+            .others()
+            .filter(|&j| {
+                let statement = EncStatement {
+                    ciphertext: &round1_vec[j].K_i,
+                    ek: &self.ek_vec[j],
+                    dlog_statement: self.dlog_statement(),
+                };
+                !round1_vec[j]
+                    .enc_proofs
+                    .get(self.index_in(j))
+                    .map_or(false, |proof| proof.verify(&statement))
+            })
+            .map(|j| Blame::new(j, BlameItem::EncProof))
+            .collect::<Vec<Blame>>();
+
+        match blames.is_empty() {
+            true => Ok(()),
+            false => Err(TssError::Blame(blames)),
+        }
+    }
+
+    /// Returns the message with our shares beta and beta_hat of every MtA, which are kept
+    /// for round 3.
+    pub fn round2_broadcast(
+        &self,
+        round1_vec: &[PresignRound1Message],
+    ) -> (PresignRound2Message, Vec<FE>, Vec<FE>) {
+        let g: GE = ECPoint::generator();
+        let Gamma_i = &g * &self.gamma_i;
+        let mut beta_vec: Vec<FE> = Vec::new();
+        let mut beta_hat_vec: Vec<FE> = Vec::new();
+        let mta = self
+            .others()
+            .map(|j| {
+                let K_j = &round1_vec[j].K_i;
+                let (D, F, aff_g_proof, beta) = self.mta(K_j, j, &self.gamma_i, &Gamma_i);
+                let (D_hat, F_hat, aff_g_proof_hat, beta_hat) =
+                    self.mta(K_j, j, &self.w_i, &self.g_w_vec[self.position]);
+                beta_vec.push(beta);
+                beta_hat_vec.push(beta_hat);
+                let statement = LogStarStatement {
+                    ciphertext: &self.G_i,
+                    ek: self.ek(),
+                    X: &Gamma_i,
+                    G: &g,
+                    dlog_statement: &self.dlog_statement_vec[j],
+                };
+                PresignMtaMessage {
+                    D,
+                    F,
+                    D_hat,
+                    F_hat,
+                    aff_g_proof,
+                    aff_g_proof_hat,
+                    log_star_proof: LogStarProof::prove(&self.gamma_i, &self.nu_i, &statement),
+                }
+            })
+            .collect::<Vec<PresignMtaMessage>>();
+        (
+            PresignRound2Message { Gamma_i, mta },
+            beta_vec,
+            beta_hat_vec,
+        )
+    }
+
+    /// Checks the MtA of every other signer with our K_i, and that its Gamma_j is made with
+    /// the gamma_j of G_j. Blames refer to the positions in the signing order.
+    pub fn round2_verify(
+        &self,
+        round1_vec: &[PresignRound1Message],
+        round2_vec: &[PresignRound2Message],
+    ) -> Result<()> {
+        let g: GE = ECPoint::generator();
+        let blames = self
+            .others()
+            .flat_map(|j| {
+                let Gamma_j = &round2_vec[j].Gamma_i;
+                let mta = match round2_vec[j].mta.get(self.index_in(j)) {
+                    Some(mta) => mta,
+                    None => return vec![Blame::new(j, BlameItem::AffGProof)],
+                };
+                let statement = AffGStatement {
+                    C: &self.K_i,
+                    D: &mta.D,
+                    Y: &mta.F,
+                    X: Gamma_j,
+                    ek0: self.ek(),
+                    ek1: &self.ek_vec[j],
+                    dlog_statement: self.dlog_statement(),
+                };
+                let statement_hat = AffGStatement {
+                    D: &mta.D_hat,
+                    Y: &mta.F_hat,
+                    X: &self.g_w_vec[j],
+                    ..statement
+                };
+                let aff_g = mta.aff_g_proof.verify(&statement)
+                    && mta.aff_g_proof_hat.verify(&statement_hat);
+                let log_star = mta.log_star_proof.verify(&LogStarStatement {
+                    ciphertext: &round1_vec[j].G_i,
+                    ek: &self.ek_vec[j],
+                    X: Gamma_j,
+                    G: &g,
+                    dlog_statement: self.dlog_statement(),
+                });
+                [
+                    (aff_g, BlameItem::AffGProof),
+                    (log_star, BlameItem::LogStarProof),
+                ]
+                .iter()
+                .filter(|(passed, _)| !passed)
+                .map(|&(_, item)| Blame::new(j, item))
+                .collect::<Vec<Blame>>()
+            })
+            .collect::<Vec<Blame>>();
+
+        match blames.is_empty() {
+            true => Ok(()),
+            false => Err(TssError::Blame(blames)),
+        }
+    }
+
+    /// Completes the MtA with the shares of round 2 and returns the message with chi_i,
+    /// which is kept for round 4.
+    pub fn round3_broadcast(
+        &self,
+        round2_vec: &[PresignRound2Message],
+        beta_vec: &[FE],
+        beta_hat_vec: &[FE],
+    ) -> (PresignRound3Message, FE) {
+        let Gamma = sum_Gamma(round2_vec);
+        let Delta_i = &Gamma * &self.k_i;
+        let mut delta_i = self.k_i.clone() * &self.gamma_i;
+        let mut chi_i = self.k_i.clone() * &self.w_i;
+        for (n, j) in self.others().enumerate() {
+            let mta = &round2_vec[j].mta[self.index_in(j)];
+            delta_i = delta_i + self.decrypt(&mta.D) + &beta_vec[n];
+            chi_i = chi_i + self.decrypt(&mta.D_hat) + &beta_hat_vec[n];
+        }
+
+        let log_star_proofs = self
+            .others()
+            .map(|j| {
+                let statement = LogStarStatement {
+                    ciphertext: &self.K_i,
+                    ek: self.ek(),
+                    X: &Delta_i,
+                    G: &Gamma,
+                    dlog_statement: &self.dlog_statement_vec[j],
+                };
+                LogStarProof::prove(&self.k_i, &self.rho_i, &statement)
+            })
+            .collect::<Vec<LogStarProof>>();
+        (
+            PresignRound3Message {
+                delta_i,
+                Delta_i,
+                log_star_proofs,
+            },
+            chi_i,
+        )
+    }
+
+    /// Checks that the Delta_j of every other signer is made with the k_j of K_j, and
+    /// returns R. Blames refer to the positions in the signing order.
+    pub fn round3_verify_R(
+        &self,
+        round1_vec: &[PresignRound1Message],
+        round2_vec: &[PresignRound2Message],
+        round3_vec: &[PresignRound3Message],
+    ) -> Result<GE> {
+        let Gamma = sum_Gamma(round2_vec);
+        let blames = self
+            .others()
+            .filter(|&j| {
+                let statement = LogStarStatement {
+                    ciphertext: &round1_vec[j].K_i,
+                    ek: &self.ek_vec[j],
+                    X: &round3_vec[j].Delta_i,
+                    G: &Gamma,
+                    dlog_statement: self.dlog_statement(),
+                };
+                !round3_vec[j]
+                    .log_star_proofs
+                    .get(self.index_in(j))
+                    .map_or(false, |proof| proof.verify(&statement))
+            })
+            .map(|j| Blame::new(j, DeltaLogStarProof))
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let g: GE = ECPoint::generator();
+        let delta = round3_vec
+            .iter()
+            .fold(FE::zero(), |acc, msg| acc + &msg.delta_i);
+        let head = round3_vec[0].Delta_i.clone();
+        let sum_Delta = round3_vec[1..]
+            .iter()
+            .fold(head, |acc, msg| acc + &msg.Delta_i);
+        if &g * &delta != sum_Delta {
+            return Err(InvalidKey);
+        }
+        Ok(Gamma * &delta.invert())
+    }
+
+    pub fn round4_broadcast(&self, R: &GE, chi_i: &FE) -> PresignBroadcastMessage {
+        Presignature::phase1_broadcast(&self.k_i, chi_i, R)
+    }
+
+    /// Checks the R_j and S_j of every signer, in signing order, against g and y.
+    pub fn round4_create_presignature(
+        &self,
+        R: &GE,
+        chi_i: &FE,
+        presign_vec: &[PresignBroadcastMessage],
+    ) -> Result<Presignature> {
+        Presignature::phase1_verify_create(&self.k_i, chi_i, R, &self.y, presign_vec)
+    }
+
+    /// D = K_j^x Enc_j(y) and F = Enc_i(y) for a random y of L_PRIME bits, with the proof
+    /// for signer j. Our share of x k_j is -y.
+    fn mta(&self, K_j: &BigInt, j: usize, x: &FE, X: &GE) -> (BigInt, BigInt, AffGProof, FE) {
+        let ek_j = &self.ek_vec[j];
+        let y = BigInt::sample_below(&(BigInt::one() << L_PRIME));
+        let rho = BigInt::from_paillier_key(ek_j);
+        let rho_y = BigInt::from_paillier_key(self.ek());
+        let D = BigInt::mod_mul(
+            &BigInt::mod_pow(K_j, &x.to_big_int(), &ek_j.nn),
+            &encrypt(ek_j, &y, &rho),
+            &ek_j.nn,
+        );
+        let F = encrypt(self.ek(), &y, &rho_y);
+        let proof = AffGProof::prove(
+            &AffGWitness {
+                x,
+                y: &y,
+                rho: &rho,
+                rho_y: &rho_y,
+            },
+            &AffGStatement {
+                C: K_j,
+                D: &D,
+                Y: &F,
+                X,
+                ek0: ek_j,
+                ek1: self.ek(),
+                dlog_statement: &self.dlog_statement_vec[j],
+            },
+        );
+        let beta = ECScalar::from(&BigInt::mod_sub(&BigInt::zero(), &y, &FE::q()));
+        (D, F, proof, beta)
+    }
+
+    fn decrypt(&self, ciphertext: &BigInt) -> FE {
+        let plaintext: BigInt =
+            Paillier::decrypt(&self.dk, &RawCiphertext::from(ciphertext)).into();
+        ECScalar::from(&plaintext)
+    }
+
+    fn ek(&self) -> &EncryptionKey {
+        &self.ek_vec[self.position]
+    }
+
+    fn dlog_statement(&self) -> &DLogStatement {
+        &self.dlog_statement_vec[self.position]
+    }
+
+    /// The positions of the other signers.
+    fn others(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.ek_vec.len()).filter(move |&j| j != self.position)
+    }
+
+    /// Where the message for us is in the list of signer j, which skips j itself.
+    fn index_in(&self, j: usize) -> usize {
+        if self.position < j {
+            self.position
+        } else {
+            self.position - 1
+        }
+    }
+}
+
+fn keygen_commitment(index: usize, decom: &KeygenDecommitMessage) -> BigInt {
+    let message = Sha256::new()
+        .chain_bigint(&BigInt::from(index as u64))
+        .chain_bigint(&decom.rid_i)
+        .chain_points(decom.vss_scheme.commitments.iter())
+        .chain_point(&decom.A_i)
+        .result_bigint();
+    HashCommitment::create_commitment_with_user_defined_randomness(&message, &decom.blind_factor)
+}
+
+fn schnorr_challenge(rid: &BigInt, index: usize, X: &GE, A: &GE) -> FE {
+    let e = Sha256::new()
+        .chain_bigint(rid)
+        .chain_bigint(&BigInt::from(index as u64))
+        .chain_point(X)
+        .chain_point(A)
+        .result_bigint();
+    ECScalar::from(&e)
+}
+
+fn sum_Gamma(round2_vec: &[PresignRound2Message]) -> GE {
+    let head = round2_vec[0].Gamma_i.clone();
+    round2_vec[1..]
+        .iter()
+        .fold(head, |acc, msg| acc + &msg.Gamma_i)
+}
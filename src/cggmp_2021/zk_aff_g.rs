@@ -0,0 +1,219 @@
+#![allow(non_snake_case)]
+
+//! Π-aff-g: proof that a Paillier ciphertext D = C^x (1 + N0)^y rho^N0 under the verifier's
+//! key N0 is made with the x of X = g^x and the y encrypted in Y under the prover's key N1,
+//! with x of at most L and y of at most L_PRIME bits, up to the slack of 2^EPSILON.
+//! Figure 15 of https://eprint.iacr.org/2021/060.pdf.
+
+use crate::cggmp_2021::{commit, encrypt, EPSILON, L, L_PRIME};
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::cryptographic_primitives::hashing::ext::DigestExt;
+use crate::curv::elliptic::curves::secp256_k1::Secp256k1Scalar as Scalar;
+use crate::curv::elliptic::curves::secp256_k1::GE;
+use crate::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+use crate::gg_2018::range_proofs::SampleFromMultiplicativeGroup;
+use crate::paillier::zkproofs::DLogStatement;
+use crate::paillier::EncryptionKey;
+use crate::sha2::Digest;
+
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// What the proof is about: `D = C^x Enc_0(y)`, `Y = Enc_1(y)` and `X = g^x`.
+pub struct AffGStatement<'a> {
+    /// ciphertext of the verifier, under `ek0`
+    pub C: &'a BigInt,
+    pub D: &'a BigInt,
+    /// ciphertext of the prover, under `ek1`
+    pub Y: &'a BigInt,
+    pub X: &'a GE,
+    pub ek0: &'a EncryptionKey,
+    pub ek1: &'a EncryptionKey,
+    /// s,t,N_hat of the verifier
+    pub dlog_statement: &'a DLogStatement,
+}
+
+/// The secrets of an `AffGStatement`: `rho` and `rho_y` are the Paillier randomness of D and Y.
+pub struct AffGWitness<'a> {
+    pub x: &'a Scalar,
+    pub y: &'a BigInt,
+    pub rho: &'a BigInt,
+    pub rho_y: &'a BigInt,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffGProof {
+    A: BigInt,
+    Bx: GE,
+    By: BigInt,
+    E: BigInt,
+    S: BigInt,
+    F: BigInt,
+    T: BigInt,
+    z1: BigInt,
+    z2: BigInt,
+    z3: BigInt,
+    z4: BigInt,
+    w: BigInt,
+    w_y: BigInt,
+}
+
+impl AffGProof {
+    pub fn prove(witness: &AffGWitness, statement: &AffGStatement) -> Self {
+        let N0 = &statement.ek0.n;
+        let NN0 = &statement.ek0.nn;
+        let N1 = &statement.ek1.n;
+        let N_hat = &statement.dlog_statement.N;
+        let x_bn = witness.x.to_big_int();
+
+        let alpha = BigInt::sample_below(&(BigInt::one() << (L + EPSILON)));
+        let beta = BigInt::sample_below(&(BigInt::one() << (L_PRIME + EPSILON)));
+        let r = BigInt::from_paillier_key(statement.ek0);
+        let r_y = BigInt::from_paillier_key(statement.ek1);
+        let gamma = BigInt::sample_below(&((BigInt::one() << (L + EPSILON)) * N_hat));
+        let m = BigInt::sample_below(&((BigInt::one() << L) * N_hat));
+        let delta = BigInt::sample_below(&((BigInt::one() << (L + EPSILON)) * N_hat));
+        let mu = BigInt::sample_below(&((BigInt::one() << L) * N_hat));
+
+        let A = BigInt::mod_mul(
+            &BigInt::mod_pow(statement.C, &alpha, NN0),
+            &encrypt(statement.ek0, &beta, &r),
+            NN0,
+        );
+        let alpha_fe: Scalar = ECScalar::from(&alpha);
+        let Bx = GE::generator() * alpha_fe;
+        let By = encrypt(statement.ek1, &beta, &r_y);
+        let E = commit(statement.dlog_statement, &alpha, &gamma);
+        let S = commit(statement.dlog_statement, &x_bn, &m);
+        let F = commit(statement.dlog_statement, &beta, &delta);
+        let T = commit(statement.dlog_statement, witness.y, &mu);
+
+        let e = challenge(statement, &[&A, &By, &E, &S, &F, &T], &Bx);
+
+        Self {
+            z1: &alpha + &e * &x_bn,
+            z2: &beta + &e * witness.y,
+            z3: &gamma + &e * &m,
+            z4: &delta + &e * &mu,
+            w: BigInt::mod_mul(&r, &BigInt::mod_pow(witness.rho, &e, N0), N0),
+            w_y: BigInt::mod_mul(&r_y, &BigInt::mod_pow(witness.rho_y, &e, N1), N1),
+            A,
+            Bx,
+            By,
+            E,
+            S,
+            F,
+            T,
+        }
+    }
+
+    pub fn verify(&self, statement: &AffGStatement) -> bool {
+        let NN0 = &statement.ek0.nn;
+        let NN1 = &statement.ek1.nn;
+        let N_hat = &statement.dlog_statement.N;
+
+        let e = challenge(
+            statement,
+            &[&self.A, &self.By, &self.E, &self.S, &self.F, &self.T],
+            &self.Bx,
+        );
+        let e_fe: Scalar = ECScalar::from(&e);
+        let z1_fe: Scalar = ECScalar::from(&self.z1);
+
+        // z1 < 2^(l + epsilon + 1), z2 < 2^(l' + epsilon + 1)
+        let in_range = self.z1 < BigInt::one() << (L + EPSILON + 1)
+            && self.z2 < BigInt::one() << (L_PRIME + EPSILON + 1);
+        // C^z1 (1 + N0)^z2 w^N0 = A D^e mod N0^2
+        let A_check = BigInt::mod_mul(
+            &BigInt::mod_pow(statement.C, &self.z1, NN0),
+            &encrypt(statement.ek0, &self.z2, &self.w),
+            NN0,
+        ) == BigInt::mod_mul(&self.A, &BigInt::mod_pow(statement.D, &e, NN0), NN0);
+        // g^z1 = Bx X^e
+        let Bx_check = GE::generator() * z1_fe == self.Bx.clone() + statement.X * &e_fe;
+        // (1 + N1)^z2 w_y^N1 = By Y^e mod N1^2
+        let By_check = encrypt(statement.ek1, &self.z2, &self.w_y)
+            == BigInt::mod_mul(&self.By, &BigInt::mod_pow(statement.Y, &e, NN1), NN1);
+        // s^z1 t^z3 = E S^e mod N_hat
+        let E_check = commit(statement.dlog_statement, &self.z1, &self.z3)
+            == BigInt::mod_mul(&self.E, &BigInt::mod_pow(&self.S, &e, N_hat), N_hat);
+        // s^z2 t^z4 = F T^e mod N_hat
+        let F_check = commit(statement.dlog_statement, &self.z2, &self.z4)
+            == BigInt::mod_mul(&self.F, &BigInt::mod_pow(&self.T, &e, N_hat), N_hat);
+
+        in_range && A_check && Bx_check && By_check && E_check && F_check
+    }
+}
+
+fn challenge(statement: &AffGStatement, values: &[&BigInt], Bx: &GE) -> BigInt {
+    [
+        &statement.ek0.n,
+        &statement.ek1.n,
+        statement.C,
+        statement.D,
+        statement.Y,
+        &statement.dlog_statement.N,
+    ]
+    .iter()
+    .chain(values.iter())
+    .fold(Sha256::new(), |acc, b| acc.chain_bigint(b))
+    .chain_point(statement.X)
+    .chain_point(Bx)
+    .result_bigint()
+        % Scalar::group_order()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gg_2018::range_proofs::tests::generate_init;
+    use crate::paillier::{KeyGeneration, Paillier};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_aff_g_proof() {
+        let (dlog_statement, ek0, _) = generate_init();
+        let (ek1, _) = Paillier::keypair().keys();
+        let k = Scalar::new_random().to_big_int();
+        let C = encrypt(&ek0, &k, &BigInt::from_paillier_key(&ek0));
+
+        let x = Scalar::new_random();
+        let y = BigInt::sample_below(&(BigInt::one() << L_PRIME));
+        let rho = BigInt::from_paillier_key(&ek0);
+        let rho_y = BigInt::from_paillier_key(&ek1);
+        let D = BigInt::mod_mul(
+            &BigInt::mod_pow(&C, &x.to_big_int(), &ek0.nn),
+            &encrypt(&ek0, &y, &rho),
+            &ek0.nn,
+        );
+        let Y = encrypt(&ek1, &y, &rho_y);
+        let X = GE::generator() * x.clone();
+        let statement = AffGStatement {
+            C: &C,
+            D: &D,
+            Y: &Y,
+            X: &X,
+            ek0: &ek0,
+            ek1: &ek1,
+            dlog_statement: &dlog_statement,
+        };
+        let witness = AffGWitness {
+            x: &x,
+            y: &y,
+            rho: &rho,
+            rho_y: &rho_y,
+        };
+        let proof = AffGProof::prove(&witness, &statement);
+        assert!(proof.verify(&statement));
+
+        // X made with another x than D
+        let X = GE::generator() * Scalar::new_random();
+        let statement = AffGStatement { X: &X, ..statement };
+        assert!(!proof.verify(&statement));
+    }
+}
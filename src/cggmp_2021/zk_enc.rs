@@ -0,0 +1,134 @@
+#![allow(non_snake_case)]
+
+//! Π-enc: proof that a Paillier ciphertext K encrypts a k of at most L bits, up to the
+//! slack of 2^EPSILON. Figure 14 of https://eprint.iacr.org/2021/060.pdf.
+
+use crate::cggmp_2021::{commit, encrypt, EPSILON, L};
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::cryptographic_primitives::hashing::ext::DigestExt;
+use crate::curv::elliptic::curves::secp256_k1::Secp256k1Scalar as Scalar;
+use crate::curv::elliptic::curves::traits::ECScalar;
+use crate::gg_2018::range_proofs::SampleFromMultiplicativeGroup;
+use crate::paillier::zkproofs::DLogStatement;
+use crate::paillier::EncryptionKey;
+use crate::sha2::Digest;
+
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// What the proof is about: `ciphertext` encrypts a small plaintext under `ek`.
+pub struct EncStatement<'a> {
+    pub ciphertext: &'a BigInt,
+    pub ek: &'a EncryptionKey,
+    /// s,t,N_hat of the verifier
+    pub dlog_statement: &'a DLogStatement,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncProof {
+    S: BigInt,
+    A: BigInt,
+    C: BigInt,
+    z1: BigInt,
+    z2: BigInt,
+    z3: BigInt,
+}
+
+impl EncProof {
+    /// `k` is the plaintext of the ciphertext and `rho` the Paillier randomness used to encrypt it.
+    pub fn prove(k: &BigInt, rho: &BigInt, statement: &EncStatement) -> Self {
+        let N0 = &statement.ek.n;
+        let N_hat = &statement.dlog_statement.N;
+
+        let alpha = BigInt::sample_below(&(BigInt::one() << (L + EPSILON)));
+        let mu = BigInt::sample_below(&((BigInt::one() << L) * N_hat));
+        let r = BigInt::from_paillier_key(statement.ek);
+        let gamma = BigInt::sample_below(&((BigInt::one() << (L + EPSILON)) * N_hat));
+
+        let S = commit(statement.dlog_statement, k, &mu);
+        let A = encrypt(statement.ek, &alpha, &r);
+        let C = commit(statement.dlog_statement, &alpha, &gamma);
+
+        let e = challenge(statement, &S, &A, &C);
+
+        Self {
+            z1: &alpha + &e * k,
+            z2: BigInt::mod_mul(&r, &BigInt::mod_pow(rho, &e, N0), N0),
+            z3: &gamma + &e * &mu,
+            S,
+            A,
+            C,
+        }
+    }
+
+    pub fn verify(&self, statement: &EncStatement) -> bool {
+        let NN0 = &statement.ek.nn;
+        let N_hat = &statement.dlog_statement.N;
+
+        let e = challenge(statement, &self.S, &self.A, &self.C);
+
+        // z1 < 2^(l + epsilon + 1)
+        let in_range = self.z1 < BigInt::one() << (L + EPSILON + 1);
+        // (1 + N0)^z1 z2^N0 = A K^e mod N0^2
+        let A_check = encrypt(statement.ek, &self.z1, &self.z2)
+            == BigInt::mod_mul(
+                &self.A,
+                &BigInt::mod_pow(statement.ciphertext, &e, NN0),
+                NN0,
+            );
+        // s^z1 t^z3 = C S^e mod N_hat
+        let C_check = commit(statement.dlog_statement, &self.z1, &self.z3)
+            == BigInt::mod_mul(&self.C, &BigInt::mod_pow(&self.S, &e, N_hat), N_hat);
+
+        in_range && A_check && C_check
+    }
+}
+
+fn challenge(statement: &EncStatement, S: &BigInt, A: &BigInt, C: &BigInt) -> BigInt {
+    Sha256::new()
+        .chain_bigint(&statement.ek.n)
+        .chain_bigint(statement.ciphertext)
+        .chain_bigint(&statement.dlog_statement.N)
+        .chain_bigint(S)
+        .chain_bigint(A)
+        .chain_bigint(C)
+        .result_bigint()
+        % Scalar::group_order()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gg_2018::range_proofs::tests::generate_init;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_enc_proof() {
+        let (dlog_statement, ek, _) = generate_init();
+        let k = Scalar::new_random().to_big_int();
+        let rho = BigInt::from_paillier_key(&ek);
+        let ciphertext = encrypt(&ek, &k, &rho);
+        let statement = EncStatement {
+            ciphertext: &ciphertext,
+            ek: &ek,
+            dlog_statement: &dlog_statement,
+        };
+        let proof = EncProof::prove(&k, &rho, &statement);
+        assert!(proof.verify(&statement));
+
+        // a plaintext far out of range
+        let k = BigInt::one() << (L + EPSILON + 256);
+        let ciphertext = encrypt(&ek, &k, &rho);
+        let statement = EncStatement {
+            ciphertext: &ciphertext,
+            ..statement
+        };
+        let proof = EncProof::prove(&k, &rho, &statement);
+        assert!(!proof.verify(&statement));
+    }
+}
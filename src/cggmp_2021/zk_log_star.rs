@@ -0,0 +1,149 @@
+#![allow(non_snake_case)]
+
+//! Π-log*: proof that a point X = G^x is made with the x of at most L bits encrypted in
+//! a Paillier ciphertext C, up to the slack of 2^EPSILON. Figure 25 of
+//! https://eprint.iacr.org/2021/060.pdf.
+
+use crate::cggmp_2021::{commit, encrypt, EPSILON, L};
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::cryptographic_primitives::hashing::ext::DigestExt;
+use crate::curv::elliptic::curves::secp256_k1::Secp256k1Scalar as Scalar;
+use crate::curv::elliptic::curves::secp256_k1::GE;
+use crate::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+use crate::gg_2018::range_proofs::SampleFromMultiplicativeGroup;
+use crate::paillier::zkproofs::DLogStatement;
+use crate::paillier::EncryptionKey;
+use crate::sha2::Digest;
+
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// What the proof is about: `X = G^x` for the x in `ciphertext`, encrypted under `ek`.
+pub struct LogStarStatement<'a> {
+    pub ciphertext: &'a BigInt,
+    pub ek: &'a EncryptionKey,
+    pub X: &'a GE,
+    pub G: &'a GE,
+    /// s,t,N_hat of the verifier
+    pub dlog_statement: &'a DLogStatement,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogStarProof {
+    S: BigInt,
+    A: BigInt,
+    Y: GE,
+    D: BigInt,
+    z1: BigInt,
+    z2: BigInt,
+    z3: BigInt,
+}
+
+impl LogStarProof {
+    /// `x` is the plaintext of the ciphertext and `rho` the Paillier randomness used to encrypt it.
+    pub fn prove(x: &Scalar, rho: &BigInt, statement: &LogStarStatement) -> Self {
+        let N0 = &statement.ek.n;
+        let N_hat = &statement.dlog_statement.N;
+        let x_bn = x.to_big_int();
+
+        let alpha = BigInt::sample_below(&(BigInt::one() << (L + EPSILON)));
+        let mu = BigInt::sample_below(&((BigInt::one() << L) * N_hat));
+        let r = BigInt::from_paillier_key(statement.ek);
+        let gamma = BigInt::sample_below(&((BigInt::one() << (L + EPSILON)) * N_hat));
+
+        let S = commit(statement.dlog_statement, &x_bn, &mu);
+        let A = encrypt(statement.ek, &alpha, &r);
+        let alpha_fe: Scalar = ECScalar::from(&alpha);
+        let Y = statement.G * &alpha_fe;
+        let D = commit(statement.dlog_statement, &alpha, &gamma);
+
+        let e = challenge(statement, &S, &A, &Y, &D);
+
+        Self {
+            z1: &alpha + &e * &x_bn,
+            z2: BigInt::mod_mul(&r, &BigInt::mod_pow(rho, &e, N0), N0),
+            z3: &gamma + &e * &mu,
+            S,
+            A,
+            Y,
+            D,
+        }
+    }
+
+    pub fn verify(&self, statement: &LogStarStatement) -> bool {
+        let NN0 = &statement.ek.nn;
+        let N_hat = &statement.dlog_statement.N;
+
+        let e = challenge(statement, &self.S, &self.A, &self.Y, &self.D);
+        let e_fe: Scalar = ECScalar::from(&e);
+        let z1_fe: Scalar = ECScalar::from(&self.z1);
+
+        // z1 < 2^(l + epsilon + 1)
+        let in_range = self.z1 < BigInt::one() << (L + EPSILON + 1);
+        // (1 + N0)^z1 z2^N0 = A C^e mod N0^2
+        let A_check = encrypt(statement.ek, &self.z1, &self.z2)
+            == BigInt::mod_mul(
+                &self.A,
+                &BigInt::mod_pow(statement.ciphertext, &e, NN0),
+                NN0,
+            );
+        // G^z1 = Y X^e
+        let Y_check = statement.G * &z1_fe == self.Y.clone() + statement.X * &e_fe;
+        // s^z1 t^z3 = D S^e mod N_hat
+        let D_check = commit(statement.dlog_statement, &self.z1, &self.z3)
+            == BigInt::mod_mul(&self.D, &BigInt::mod_pow(&self.S, &e, N_hat), N_hat);
+
+        in_range && A_check && Y_check && D_check
+    }
+}
+
+fn challenge(statement: &LogStarStatement, S: &BigInt, A: &BigInt, Y: &GE, D: &BigInt) -> BigInt {
+    Sha256::new()
+        .chain_bigint(&statement.ek.n)
+        .chain_bigint(statement.ciphertext)
+        .chain_point(statement.X)
+        .chain_point(statement.G)
+        .chain_bigint(&statement.dlog_statement.N)
+        .chain_bigint(S)
+        .chain_bigint(A)
+        .chain_point(Y)
+        .chain_bigint(D)
+        .result_bigint()
+        % Scalar::group_order()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gg_2018::range_proofs::tests::generate_init;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_log_star_proof() {
+        let (dlog_statement, ek, _) = generate_init();
+        let x = Scalar::new_random();
+        let rho = BigInt::from_paillier_key(&ek);
+        let ciphertext = encrypt(&ek, &x.to_big_int(), &rho);
+        let G = GE::random_point();
+        let X = &G * &x;
+        let statement = LogStarStatement {
+            ciphertext: &ciphertext,
+            ek: &ek,
+            X: &X,
+            G: &G,
+            dlog_statement: &dlog_statement,
+        };
+        let proof = LogStarProof::prove(&x, &rho, &statement);
+        assert!(proof.verify(&statement));
+
+        // a point made with another x
+        let X = &G * &Scalar::new_random();
+        let statement = LogStarStatement { X: &X, ..statement };
+        assert!(!proof.verify(&statement));
+    }
+}
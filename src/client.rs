@@ -0,0 +1,263 @@
+//! Async client of the SM manager for Rust services: GG18 keygen and signing, and key stores
+//! in and out.
+//!
+//! The protocols run as the state machines of `gg_2018::state_machine` over a `Transport`.
+//! `api` is the wasm layer over this module. Natively the futures run on tokio.
+
+use crate::common::{postb, PartySignup};
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::errors::{Result, TssError};
+use crate::gg_2018::bip32::{self, ChainCode};
+use crate::gg_2018::party_i::{Keys, Parameters, SharedKeys, Signature};
+use crate::gg_2018::state_machine::{other_party_num, KeyStore, Keygen, Msg, Sign, StateMachine};
+use crate::paillier::zkproofs::DLogStatement;
+use crate::paillier::EncryptionKey;
+use crate::transport::{HttpTransport, Transport, WsTransport};
+
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::Client;
+
+pub fn new_client_with_headers() -> Result<Client> {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "Content-Type",
+        HeaderValue::from_static("Content-Type:application/json; charset=utf-8"),
+    );
+    headers.insert(
+        "Accept",
+        HeaderValue::from_static("application/json; charset=utf-8"),
+    );
+
+    Ok(reqwest::Client::builder()
+        .default_headers(headers)
+        .build()?)
+}
+
+pub async fn signup_keygen(client: &Client, addr: &str) -> Result<PartySignup> {
+    let key = "signup-keygen".to_string();
+    let res_body = postb(client, addr, "signupkeygen", key).await?;
+    parse_signup(&res_body)
+}
+
+pub async fn signup_sign(client: &Client, addr: &str) -> Result<PartySignup> {
+    let key = "signup-sign".to_string();
+    let res_body = postb(client, addr, "signupsign", key).await?;
+    parse_signup(&res_body)
+}
+
+/// The `PartySignup` of a signup answer of the manager, which is `Err` when the manager
+/// turns the signup down.
+pub(crate) fn parse_signup(res_body: &str) -> Result<PartySignup> {
+    let u: std::result::Result<PartySignup, ()> = serde_json::from_str(res_body)?;
+    u.map_err(|()| TssError::UnknownError {
+        msg: "signup rejected by the manager".to_string(),
+        line: line!(),
+    })
+}
+
+/// Sends the messages `outgoing` of the current round of `machine`, and hands it the messages
+/// of the other parties for that round. Returns the messages of the next round.
+pub async fn exchange<M: StateMachine>(
+    machine: &mut M,
+    transport: &dyn Transport,
+    outgoing: Vec<Msg>,
+) -> Result<Vec<Msg>> {
+    let party_num = machine.party_num();
+    // all parties send the messages of a round alike
+    let mut p2p = false;
+    for msg in outgoing {
+        match msg.receiver {
+            Some(receiver) => {
+                p2p = true;
+                transport
+                    .send_p2p(party_num, receiver, &msg.round, msg.body)
+                    .await?
+            }
+            None => {
+                transport
+                    .send_broadcast(party_num, &msg.round, msg.body)
+                    .await?
+            }
+        }
+    }
+
+    let round = match machine.current_round() {
+        Some(round) => round,
+        None => return Ok(Vec::new()),
+    };
+    let bodies = match p2p {
+        true => transport.recv_p2p(party_num, machine.n(), round).await?,
+        false => {
+            transport
+                .recv_broadcasts(party_num, machine.n(), round)
+                .await?
+        }
+    };
+    let mut outgoing = Vec::new();
+    for (j, body) in bodies.into_iter().enumerate() {
+        outgoing.extend(machine.handle_incoming(Msg {
+            sender: other_party_num(party_num, j),
+            receiver: p2p.then(|| party_num),
+            round: round.to_string(),
+            body,
+        })?);
+    }
+    Ok(outgoing)
+}
+
+/// Runs `machine` over `transport` to the end.
+pub async fn run<M>(machine: &mut M, transport: &dyn Transport) -> Result<M::Output>
+where
+    M: StateMachine,
+    M::Output: Clone,
+{
+    let mut outgoing = machine.proceed()?;
+    while !machine.is_finished() {
+        outgoing = exchange(machine, transport, outgoing).await?;
+    }
+    Ok(machine.output().unwrap().clone())
+}
+
+/// Signs up for keygen with the manager at `addr` and runs it among `n` parties, polling
+/// every `delay` ms. Any t+1 of the key stores can sign.
+pub async fn gg18_keygen(addr: &str, t: usize, n: usize, delay: u32) -> Result<KeyStore> {
+    let client = new_client_with_headers()?;
+    let PartySignup { number, uuid } = signup_keygen(&client, addr).await?;
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
+    let transport = HttpTransport::with_client(client, addr, &uuid, delay);
+    run(&mut Keygen::new(params, number)?, &transport).await
+}
+
+/// Signs up for signing with the manager at `addr` and signs `message`, already hashed, with
+/// t other parties. With a `derivation_path`, e.g. `m/0/1`, it signs for that child key.
+pub async fn gg18_sign(
+    addr: &str,
+    t: usize,
+    key_store: &KeyStore,
+    message: &[u8],
+    derivation_path: Option<&str>,
+    delay: u32,
+) -> Result<Signature> {
+    let key_store = match derivation_path {
+        Some(path) => derive_key_store(key_store, path)?,
+        None => key_store.clone(),
+    };
+    let client = new_client_with_headers()?;
+    let PartySignup { number, uuid } = signup_sign(&client, addr).await?;
+    let transport = HttpTransport::with_client(client, addr, &uuid, delay);
+    let mut sign = Sign::new(key_store, t as u16, number, message.to_vec())?;
+    run(&mut sign, &transport).await
+}
+
+/// Like `gg18_keygen`, but the manager pushes the messages over its `/ws` channel.
+pub async fn gg18_keygen_ws(addr: &str, t: usize, n: usize) -> Result<KeyStore> {
+    let client = new_client_with_headers()?;
+    let PartySignup { number, uuid } = signup_keygen(&client, addr).await?;
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
+    let transport = WsTransport::connect(addr, &uuid).await?;
+    run(&mut Keygen::new(params, number)?, &transport).await
+}
+
+/// Like `gg18_sign`, but the manager pushes the messages over its `/ws` channel.
+pub async fn gg18_sign_ws(
+    addr: &str,
+    t: usize,
+    key_store: &KeyStore,
+    message: &[u8],
+    derivation_path: Option<&str>,
+) -> Result<Signature> {
+    let key_store = match derivation_path {
+        Some(path) => derive_key_store(key_store, path)?,
+        None => key_store.clone(),
+    };
+    let client = new_client_with_headers()?;
+    let PartySignup { number, uuid } = signup_sign(&client, addr).await?;
+    let transport = WsTransport::connect(addr, &uuid).await?;
+    let mut sign = Sign::new(key_store, t as u16, number, message.to_vec())?;
+    run(&mut sign, &transport).await
+}
+
+/// Parses a key store of `gg18_keygen_client_round5`. Key stores made before BIP-32 support
+/// have no chain code, and no child keys. Those made before the MtA range proofs, a tuple of
+/// six, lack the h1, h2, N_tilde of every party and cannot be used to sign any more.
+pub fn parse_key_store(key_store: &str) -> Result<KeyStore> {
+    let fields = serde_json::from_str::<serde_json::Value>(key_store)?;
+    match fields.as_array().map(Vec::len) {
+        Some(6) => Err(TssError::OutdatedKeyStore(
+            "without the h1, h2, N_tilde of the parties: run keygen again".to_string(),
+        )),
+        Some(7) => {
+            let (party_keys, shared_keys, party_id, vss_scheme_vec, ek_vec, y_sum, dlog_statements): (
+                Keys,
+                SharedKeys,
+                u16,
+                Vec<VerifiableSS>,
+                Vec<EncryptionKey>,
+                GE,
+                Vec<DLogStatement>,
+            ) = serde_json::from_value(fields)?;
+            Ok((
+                party_keys,
+                shared_keys,
+                party_id,
+                vss_scheme_vec,
+                ek_vec,
+                y_sum,
+                dlog_statements,
+                None,
+            ))
+        }
+        _ => Ok(serde_json::from_value(fields)?),
+    }
+}
+
+pub fn key_store_to_string(key_store: &KeyStore) -> Result<String> {
+    Ok(serde_json::to_string(key_store)?)
+}
+
+/// The tweak, the public key and the chain code at `path` of y_sum.
+pub fn derive_key(
+    y_sum: &GE,
+    chain_code: Option<ChainCode>,
+    path: &str,
+) -> Result<(FE, GE, ChainCode)> {
+    let chain_code = chain_code.ok_or_else(|| TssError::UnknownError {
+        msg: "key store without chain code".to_string(),
+        line: line!(),
+    })?;
+    bip32::derive(y_sum, &chain_code, &bip32::parse_path(path)?)
+}
+
+/// The key store of the child key at `path`, which signs like the key store of keygen. It
+/// holds the chain code of the child, so that paths from it continue the derivation.
+pub fn derive_key_store(key_store: &KeyStore, path: &str) -> Result<KeyStore> {
+    let (
+        party_keys,
+        shared_keys,
+        party_id,
+        vss_scheme_vec,
+        ek_vec,
+        y_sum,
+        dlog_statements,
+        chain_code,
+    ) = key_store.clone();
+    let (tweak, child, child_chain_code) = derive_key(&y_sum, chain_code, path)?;
+    let (shared_keys, vss_scheme_vec) = bip32::tweak_key(&shared_keys, &vss_scheme_vec, &tweak);
+    Ok((
+        party_keys,
+        shared_keys,
+        party_id,
+        vss_scheme_vec,
+        ek_vec,
+        child,
+        dlog_statements,
+        Some(child_chain_code),
+    ))
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use crate::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+use crate::curv::elliptic::curves::traits::{CurveScalar, ECPoint, ECScalar};
 use crate::errors::TssError;
 #[cfg(target_arch = "wasm32")]
 use crate::log;
@@ -49,6 +49,17 @@ pub struct Entry {
     pub value: String,
 }
 
+/// A frame of the `/ws` channel of the SM manager, as JSON text.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum WsFrame {
+    /// From a party: stores the entry like `/set` does
+    Set(Entry),
+    /// From a party: asks for the entries of the keys, each pushed once it is stored
+    Subscribe(Vec<Key>),
+    /// From the manager: an entry the party subscribed to
+    Push(Entry),
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Params {
     pub parties: String,
@@ -92,6 +103,18 @@ pub fn aes_decrypt(key: &[u8], aead_pack: AEAD) -> Result<Vec<u8>> {
     out
 }
 
+/// AES key of the channel with the party whose y_j is given.
+pub fn aes_key<S>(y_j: &S::Point, u_i: &S) -> Vec<u8>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    let key_bn: BigInt = (y_j.clone() * u_i.clone()).x_coor().unwrap();
+    let key_bytes = BigInt::to_vec(&key_bn);
+    let mut template: Vec<u8> = vec![0u8; AES_KEY_BYTES_LEN - key_bytes.len()];
+    template.extend_from_slice(&key_bytes[..]);
+    template
+}
+
 #[cfg(target_arch = "wasm32")]
 pub async fn sleep(ms: u32) {
     let promise = js_sys::Promise::new(&mut |resolve, _| {
@@ -105,7 +128,7 @@ pub async fn sleep(ms: u32) {
 
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn sleep(ms: u32) {
-    std::thread::sleep(core::time::Duration::from_millis(ms as u64));
+    tokio::time::sleep(core::time::Duration::from_millis(ms as u64)).await;
 }
 
 pub async fn postb<T>(client: &Client, addr: &str, path: &str, body: T) -> Result<String>
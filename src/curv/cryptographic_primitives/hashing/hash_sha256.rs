@@ -7,8 +7,7 @@
 
 use super::traits::Hash;
 use crate::curv::arithmetic::num_bigint::{from, BigInt};
-use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
-use crate::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+use crate::curv::elliptic::curves::traits::{CurveScalar, ECPoint, ECScalar};
 use cryptoxide::digest::Digest;
 use cryptoxide::sha2::Sha256;
 
@@ -28,7 +27,10 @@ impl Hash for HSha256 {
         from(result.as_ref())
     }
 
-    fn create_hash_from_ge(ge_vec: &[&GE]) -> FE {
+    fn create_hash_from_ge<S>(ge_vec: &[&S::Point]) -> S
+    where
+        S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+    {
         let mut hasher = Sha256::new();
 
         for value in ge_vec {
@@ -47,7 +49,7 @@ mod tests {
     use super::HSha256;
     use super::Hash;
     use crate::curv::arithmetic::num_bigint::BigInt;
-    use crate::curv::elliptic::curves::secp256_k1::GE;
+    use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
     use crate::curv::elliptic::curves::traits::ECPoint;
     use crate::curv::elliptic::curves::traits::ECScalar;
     use num_traits::{One, Zero};
@@ -69,11 +71,11 @@ mod tests {
     #[test]
     fn create_hash_from_ge_test() {
         let point = GE::base_point2();
-        let result1 = HSha256::create_hash_from_ge(&vec![&point, &GE::generator()]);
+        let result1: FE = HSha256::create_hash_from_ge(&vec![&point, &GE::generator()]);
         assert!(result1.to_big_int().to_str_radix(2).len() > 240);
-        let result2 = HSha256::create_hash_from_ge(&vec![&GE::generator(), &point]);
+        let result2: FE = HSha256::create_hash_from_ge(&vec![&GE::generator(), &point]);
         assert_ne!(result1, result2);
-        let result3 = HSha256::create_hash_from_ge(&vec![&GE::generator(), &point]);
+        let result3: FE = HSha256::create_hash_from_ge(&vec![&GE::generator(), &point]);
         assert_eq!(result2, result3);
     }
 }
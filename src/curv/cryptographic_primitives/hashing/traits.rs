@@ -6,11 +6,14 @@
 */
 
 use crate::curv::arithmetic::num_bigint::BigInt;
-use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::traits::{CurveScalar, ECScalar};
 
 pub trait Hash {
     fn create_hash(big_ints: &[&BigInt]) -> BigInt;
-    fn create_hash_from_ge(ge_vec: &[&GE]) -> FE;
+    /// The hash of points of the curve of `S`, as a scalar of that curve.
+    fn create_hash_from_ge<S>(ge_vec: &[&S::Point]) -> S
+    where
+        S: CurveScalar + ECScalar<<S as CurveScalar>::SK>;
 }
 
 pub trait KeyedHash {
@@ -10,6 +10,7 @@ use std::fmt;
 
 pub mod sigma_correct_homomorphic_elgamal_enc;
 pub mod sigma_dlog;
+pub mod sigma_valid_pedersen;
 
 pub const PROOF_ERROR_DESCRIPTION: &str = "Error while verifying";
 
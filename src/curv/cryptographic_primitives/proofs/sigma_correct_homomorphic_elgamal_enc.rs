@@ -9,7 +9,7 @@
 use super::ProofError;
 use crate::curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::curv::cryptographic_primitives::hashing::traits::Hash;
-use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::secp256_k1::FE;
 use crate::curv::elliptic::curves::traits::*;
 use zeroize::Zeroize;
 
@@ -19,42 +19,49 @@ use zeroize::Zeroize;
 /// Specifically, the witness is ω = (x, r), the statement is δ = (G, H, Y, D, E).
 /// The relation R outputs 1 if D = xH+rY , E = rG (for the case of G=H this is ElGamal)
 ///
+/// Like `DLogProof`, the proof is generic over the scalar S of a curve.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct HomoELGamalProof {
-    pub T: GE,
-    pub A3: GE,
-    pub z1: FE,
-    pub z2: FE,
+pub struct HomoELGamalProof<S: CurveScalar = FE> {
+    pub T: S::Point,
+    pub A3: S::Point,
+    pub z1: S,
+    pub z2: S,
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct HomoElGamalWitness {
-    pub r: FE,
-    pub x: FE,
+pub struct HomoElGamalWitness<S: CurveScalar = FE> {
+    pub r: S,
+    pub x: S,
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct HomoElGamalStatement {
-    pub G: GE,
-    pub H: GE,
-    pub Y: GE,
-    pub D: GE,
-    pub E: GE,
+pub struct HomoElGamalStatement<S: CurveScalar = FE> {
+    pub G: S::Point,
+    pub H: S::Point,
+    pub Y: S::Point,
+    pub D: S::Point,
+    pub E: S::Point,
 }
 
-impl HomoELGamalProof {
-    pub fn prove(w: &HomoElGamalWitness, delta: &HomoElGamalStatement) -> HomoELGamalProof {
-        let mut s1: FE = ECScalar::new_random();
-        let mut s2: FE = ECScalar::new_random();
+impl<S> HomoELGamalProof<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    pub fn prove(
+        w: &HomoElGamalWitness<S>,
+        delta: &HomoElGamalStatement<S>,
+    ) -> HomoELGamalProof<S> {
+        let mut s1: S = ECScalar::new_random();
+        let mut s2: S = ECScalar::new_random();
         let mut A1 = delta.H.clone() * s1.clone();
         let mut A2 = delta.Y.clone() * s2.clone();
         let A3 = delta.G.clone() * s2.clone();
         let T = A1.clone() + A2.clone();
-        let e = HSha256::create_hash_from_ge(&[
+        let e: S = HSha256::create_hash_from_ge(&[
             &T, &A3, &delta.G, &delta.H, &delta.Y, &delta.D, &delta.E,
         ]);
         // dealing with zero field element
-        let z1 = if w.x.clone() != FE::zero() {
+        let z1 = if w.x.clone() != S::zero() {
             s1.clone() + w.x.clone() * e.clone()
         } else {
             s1.clone()
@@ -66,8 +73,8 @@ impl HomoELGamalProof {
         A2.zeroize();
         HomoELGamalProof { T, A3, z1, z2 }
     }
-    pub fn verify(&self, delta: &HomoElGamalStatement) -> Result<(), ProofError> {
-        let e = HSha256::create_hash_from_ge(&[
+    pub fn verify(&self, delta: &HomoElGamalStatement<S>) -> Result<(), ProofError> {
+        let e: S = HSha256::create_hash_from_ge(&[
             &self.T.clone(),
             &self.A3.clone(),
             &delta.G.clone(),
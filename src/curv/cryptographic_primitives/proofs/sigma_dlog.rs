@@ -6,7 +6,7 @@
 */
 
 use super::ProofError;
-use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::secp256_k1::FE;
 use crate::curv::elliptic::curves::traits::*;
 
 use crate::curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
@@ -23,34 +23,39 @@ use zeroize::Zeroize;
 /// How to prove yourself: Practical solutions to identification and signature problems.
 /// In Advances in Cryptology - CRYPTO ’86, Santa Barbara, California, USA, 1986, Proceedings,
 /// pages 186–194, 1986.
+///
+/// The proof is generic over the scalar S of a curve, FE of secp256k1 by default.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct DLogProof {
-    pub pk: GE,
-    pub pk_t_rand_commitment: GE,
-    pub challenge_response: FE,
+pub struct DLogProof<S: CurveScalar = FE> {
+    pub pk: S::Point,
+    pub pk_t_rand_commitment: S::Point,
+    pub challenge_response: S,
 }
 
-pub trait ProveDLog {
-    fn prove(sk: &FE) -> DLogProof;
+pub trait ProveDLog<S: CurveScalar = FE> {
+    fn prove(sk: &S) -> DLogProof<S>;
 
-    fn verify(proof: &DLogProof) -> Result<(), ProofError>;
+    fn verify(proof: &DLogProof<S>) -> Result<(), ProofError>;
 }
 
-impl ProveDLog for DLogProof {
-    fn prove(sk: &FE) -> DLogProof {
-        let base_point: GE = ECPoint::generator();
+impl<S> ProveDLog<S> for DLogProof<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    fn prove(sk: &S) -> DLogProof<S> {
+        let base_point = S::Point::generator();
         let generator_x = base_point.bytes_compressed_to_big_int();
-        let mut sk_t_rand_commitment: FE = ECScalar::new_random();
+        let mut sk_t_rand_commitment: S = ECScalar::new_random();
         let pk_t_rand_commitment = base_point.scalar_mul(&sk_t_rand_commitment.get_element());
-        let ec_point: GE = ECPoint::generator();
+        let ec_point = S::Point::generator();
         let pk = ec_point.scalar_mul(&sk.get_element());
         let challenge = HSha256::create_hash(&[
             &pk_t_rand_commitment.bytes_compressed_to_big_int(),
             &generator_x,
             &pk.bytes_compressed_to_big_int(),
         ]);
-        let challenge_fe: FE = ECScalar::from(&challenge);
-        let challenge_mul_sk = challenge_fe.mul(&sk.get_element());
+        let challenge_fe: S = ECScalar::from(&challenge);
+        let challenge_mul_sk = challenge_fe * sk;
         let challenge_response = sk_t_rand_commitment.sub(&challenge_mul_sk.get_element());
         sk_t_rand_commitment.zeroize();
         DLogProof {
@@ -60,19 +65,19 @@ impl ProveDLog for DLogProof {
         }
     }
 
-    fn verify(proof: &DLogProof) -> Result<(), ProofError> {
-        let ec_point: GE = ECPoint::generator();
+    fn verify(proof: &DLogProof<S>) -> Result<(), ProofError> {
+        let ec_point = S::Point::generator();
         let challenge = HSha256::create_hash(&[
             &proof.pk_t_rand_commitment.bytes_compressed_to_big_int(),
             &ec_point.bytes_compressed_to_big_int(),
             &proof.pk.clone().bytes_compressed_to_big_int(),
         ]);
 
-        let sk_challenge: FE = ECScalar::from(&challenge);
+        let sk_challenge: S = ECScalar::from(&challenge);
         let pk = proof.pk.clone();
         let pk_challenge = pk.scalar_mul(&sk_challenge.get_element());
 
-        let base_point: GE = ECPoint::generator();
+        let base_point = S::Point::generator();
 
         let mut pk_verifier = base_point.scalar_mul(&proof.challenge_response.get_element());
 
@@ -105,4 +110,17 @@ mod tests {
             Err(_e) => assert!(false),
         }
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_dlog_proof_ed25519() {
+        use crate::curv::elliptic::curves::ed25519::FE;
+
+        let witness: FE = ECScalar::new_random();
+        let mut dlog_proof = DLogProof::prove(&witness);
+        assert!(DLogProof::verify(&dlog_proof).is_ok());
+
+        dlog_proof.challenge_response = dlog_proof.challenge_response + FE::new_random();
+        assert!(DLogProof::verify(&dlog_proof).is_err());
+    }
 }
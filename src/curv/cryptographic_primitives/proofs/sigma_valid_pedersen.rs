@@ -0,0 +1,124 @@
+/*
+    This file is part of Curv library
+    Copyright 2018 by Kzen Networks
+    (https://github.com/KZen-networks/curv)
+    License MIT: https://github.com/KZen-networks/curv/blob/master/LICENSE
+*/
+
+use super::ProofError;
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+
+use crate::curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
+use crate::curv::cryptographic_primitives::hashing::traits::Hash;
+use zeroize::Zeroize;
+
+/// protocol for proving that Pedersen commitment c was constructed correctly which is the same as
+/// proof of knowledge of (m,r) such that c = mG + rH.
+/// witness: (m,r), statement: c, The Relation R outputs 1 if c = mG + rH. The protocol:
+/// 1: Prover chooses A1 = s1*G , A2 = s2*H for random s1,s2
+/// prover calculates challenge e = H(G,H,c,A1,A2)
+/// prover calculates z1  = s1 + em, z2 = s2 + er
+/// prover sends pi = {e, A1,A2,c, z1,z2}
+///
+/// verifier checks that z1*G + z2*H  = A1 + A2 + e*c
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PedersenProof {
+    e: FE,
+    a1: GE,
+    a2: GE,
+    pub com: GE,
+    z1: FE,
+    z2: FE,
+}
+
+pub trait ProvePederesen {
+    fn prove(m: &FE, r: &FE) -> PedersenProof;
+
+    fn verify(proof: &PedersenProof) -> Result<(), ProofError>;
+}
+
+impl ProvePederesen for PedersenProof {
+    fn prove(m: &FE, r: &FE) -> PedersenProof {
+        let g: GE = ECPoint::generator();
+        let h = GE::base_point2();
+        let mut s1: FE = ECScalar::new_random();
+        let mut s2: FE = ECScalar::new_random();
+        let a1 = &g * &s1;
+        let a2 = &h * &s2;
+        let com = &g * m + &h * r;
+        let challenge = HSha256::create_hash(&[
+            &g.bytes_compressed_to_big_int(),
+            &h.bytes_compressed_to_big_int(),
+            &com.bytes_compressed_to_big_int(),
+            &a1.bytes_compressed_to_big_int(),
+            &a2.bytes_compressed_to_big_int(),
+        ]);
+        let e: FE = ECScalar::from(&challenge);
+
+        let em = e.mul(&m.get_element());
+        let z1 = s1.add(&em.get_element());
+        let er = e.mul(&r.get_element());
+        let z2 = s2.add(&er.get_element());
+        s1.zeroize();
+        s2.zeroize();
+
+        PedersenProof {
+            e,
+            a1,
+            a2,
+            com,
+            z1,
+            z2,
+        }
+    }
+
+    fn verify(proof: &PedersenProof) -> Result<(), ProofError> {
+        let g: GE = ECPoint::generator();
+        let h = GE::base_point2();
+        let challenge = HSha256::create_hash(&[
+            &g.bytes_compressed_to_big_int(),
+            &h.bytes_compressed_to_big_int(),
+            &proof.com.bytes_compressed_to_big_int(),
+            &proof.a1.bytes_compressed_to_big_int(),
+            &proof.a2.bytes_compressed_to_big_int(),
+        ]);
+        let e: FE = ECScalar::from(&challenge);
+
+        let lhs = &g * &proof.z1 + &h * &proof.z2;
+        let rhs = proof.a1.clone() + &proof.a2 + &proof.com * &e;
+        if e == proof.e && lhs == rhs {
+            Ok(())
+        } else {
+            Err(ProofError)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curv::cryptographic_primitives::proofs::sigma_valid_pedersen::*;
+    use crate::curv::elliptic::curves::secp256_k1::FE;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_pedersen_proof() {
+        let m: FE = ECScalar::new_random();
+        let r: FE = ECScalar::new_random();
+        let pedersen_proof = PedersenProof::prove(&m, &r);
+        assert!(PedersenProof::verify(&pedersen_proof).is_ok());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_bad_pedersen_proof() {
+        let m: FE = ECScalar::new_random();
+        let r: FE = ECScalar::new_random();
+        let mut pedersen_proof = PedersenProof::prove(&m, &r);
+        pedersen_proof.com = pedersen_proof.com.clone() + GE::generator();
+        assert!(PedersenProof::verify(&pedersen_proof).is_err());
+    }
+}
@@ -7,7 +7,7 @@
 */
 
 use crate::curv::arithmetic::num_bigint::BigInt;
-use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::secp256_k1::FE;
 use crate::curv::elliptic::curves::traits::*;
 use crate::errors::TssError::{self, VerifyShareError};
 use num_traits::One;
@@ -19,30 +19,34 @@ pub struct ShamirSecretSharing {
 /// Feldman VSS, based on  Paul Feldman. 1987. A practical scheme for non-interactive verifiable secret sharing.
 /// In Foundations of Computer Science, 1987., 28th Annual Symposium on.IEEE, 427–43
 ///
-/// implementation details: The code is using FE and GE. Each party is given an index from 1,..,n and a secret share of type FE.
-/// The index of the party is also the point on the polynomial where we treat this number as u32 but converting it to FE internally.
+/// implementation details: The code is generic over the scalar S of a curve, FE of secp256k1 by default,
+/// and its points. Each party is given an index from 1,..,n and a secret share of type S.
+/// The index of the party is also the point on the polynomial where we treat this number as u32 but converting it to S internally.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct VerifiableSS {
+pub struct VerifiableSS<S: CurveScalar = FE> {
     pub parameters: ShamirSecretSharing,
-    pub commitments: Vec<GE>,
+    pub commitments: Vec<S::Point>,
 }
 
-impl VerifiableSS {
+impl<S> VerifiableSS<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
     pub fn reconstruct_limit(&self) -> usize {
         self.parameters.threshold + 1
     }
 
     // generate VerifiableSS from a secret
-    pub fn share(t: usize, n: usize, secret: &FE) -> (VerifiableSS, Vec<FE>) {
+    pub fn share(t: usize, n: usize, secret: &S) -> (VerifiableSS<S>, Vec<S>) {
         assert!(t < n);
         let poly = VerifiableSS::sample_polynomial(t, secret);
         let index_vec: Vec<usize> = (1..=n).collect();
         let secret_shares = VerifiableSS::evaluate_polynomial(&poly, &index_vec);
 
-        let G: GE = ECPoint::generator();
+        let G = S::Point::generator();
         let commitments = (0..poly.len())
             .map(|i| G.clone() * poly[i].clone())
-            .collect::<Vec<GE>>();
+            .collect::<Vec<S::Point>>();
         (
             VerifiableSS {
                 parameters: ShamirSecretSharing {
@@ -59,17 +63,17 @@ impl VerifiableSS {
     pub fn share_at_indices(
         t: usize,
         n: usize,
-        secret: &FE,
+        secret: &S,
         index_vec: &[usize],
-    ) -> (VerifiableSS, Vec<FE>) {
+    ) -> (VerifiableSS<S>, Vec<S>) {
         assert_eq!(n, index_vec.len());
         let poly = VerifiableSS::sample_polynomial(t, secret);
         let secret_shares = VerifiableSS::evaluate_polynomial(&poly, index_vec);
 
-        let G: GE = ECPoint::generator();
+        let G = S::Point::generator();
         let commitments = (0..poly.len())
             .map(|i| G.clone() * &poly[i])
-            .collect::<Vec<GE>>();
+            .collect::<Vec<S::Point>>();
         (
             VerifiableSS {
                 parameters: ShamirSecretSharing {
@@ -83,26 +87,26 @@ impl VerifiableSS {
     }
 
     // returns vector of coefficients
-    pub fn sample_polynomial(t: usize, coef0: &FE) -> Vec<FE> {
+    pub fn sample_polynomial(t: usize, coef0: &S) -> Vec<S> {
         let mut coefficients = vec![coef0.clone()];
         // sample the remaining coefficients randomly using secure randomness
-        let random_coefficients: Vec<FE> = (0..t).map(|_| ECScalar::new_random()).collect();
+        let random_coefficients: Vec<S> = (0..t).map(|_| ECScalar::new_random()).collect();
         coefficients.extend(random_coefficients);
         // return
         coefficients
     }
 
-    pub fn evaluate_polynomial(coefficients: &[FE], index_vec: &[usize]) -> Vec<FE> {
+    pub fn evaluate_polynomial(coefficients: &[S], index_vec: &[usize]) -> Vec<S> {
         (0..index_vec.len())
             .map(|point| {
                 let point_bn = BigInt::from(index_vec[point] as u32);
 
                 VerifiableSS::mod_evaluate_polynomial(coefficients, ECScalar::from(&point_bn))
             })
-            .collect::<Vec<FE>>()
+            .collect::<Vec<S>>()
     }
 
-    pub fn mod_evaluate_polynomial(coefficients: &[FE], point: FE) -> FE {
+    pub fn mod_evaluate_polynomial(coefficients: &[S], point: S) -> S {
         // evaluate using Horner's rule
         //  - to combine with fold we consider the coefficients in reverse order
         let mut reversed_coefficients = coefficients.iter().rev();
@@ -110,12 +114,12 @@ impl VerifiableSS {
         let head = reversed_coefficients.next().unwrap();
         let tail = reversed_coefficients;
         tail.fold(head.clone(), |partial, coef| {
-            let partial_times_point = partial.mul(&point.get_element());
-            partial_times_point.add(&coef.get_element())
+            let partial_times_point = partial * &point;
+            partial_times_point + coef
         })
     }
 
-    pub fn reconstruct(&self, indices: &[usize], shares: &[FE]) -> FE {
+    pub fn reconstruct(&self, indices: &[usize], shares: &[S]) -> S {
         assert_eq!(shares.len(), indices.len());
         assert!(shares.len() >= self.reconstruct_limit());
         // add one to indices to get points
@@ -125,7 +129,7 @@ impl VerifiableSS {
                 let index_bn = BigInt::from(*i as u32 + 1 as u32);
                 ECScalar::from(&index_bn)
             })
-            .collect::<Vec<FE>>();
+            .collect::<Vec<S>>();
         VerifiableSS::lagrange_interpolation_at_zero(&points, &shares)
     }
 
@@ -139,7 +143,7 @@ impl VerifiableSS {
     // This is obviously less general than `newton_interpolation_general` as we
     // only get a single value, but it is much faster.
 
-    pub fn lagrange_interpolation_at_zero(points: &[FE], values: &[FE]) -> FE {
+    pub fn lagrange_interpolation_at_zero(points: &[S], values: &[S]) -> S {
         let vec_len = values.len();
 
         assert_eq!(points.len(), vec_len);
@@ -150,8 +154,8 @@ impl VerifiableSS {
                 .map(|i| {
                     let xi = &points[i];
                     let yi = &values[i];
-                    let num: FE = ECScalar::from(&BigInt::one());
-                    let denum: FE = ECScalar::from(&BigInt::one());
+                    let num: S = ECScalar::from(&BigInt::one());
+                    let denum: S = ECScalar::from(&BigInt::one());
                     let num = points.iter().zip(0..vec_len).fold(num, |acc, x| {
                         if i != x.1 {
                             acc * x.0
@@ -170,20 +174,20 @@ impl VerifiableSS {
                     let denum = denum.invert();
                     num * denum * yi
                 })
-                .collect::<Vec<FE>>();
+                .collect::<Vec<S>>();
         let mut lag_coef_iter = lag_coef.iter();
         let head = lag_coef_iter.next().unwrap();
         let tail = lag_coef_iter;
-        tail.fold(head.clone(), |acc, x| acc.add(&x.get_element()))
+        tail.fold(head.clone(), |acc, x| acc + x)
     }
 
-    pub fn validate_share(&self, secret_share: &FE, index: usize) -> Result<(), TssError> {
-        let G: GE = ECPoint::generator();
+    pub fn validate_share(&self, secret_share: &S, index: usize) -> Result<(), TssError> {
+        let G = S::Point::generator();
         let ss_point = G * secret_share;
         self.validate_share_public(&ss_point, index)
     }
 
-    pub fn validate_share_public(&self, ss_point: &GE, index: usize) -> Result<(), TssError> {
+    pub fn validate_share_public(&self, ss_point: &S::Point, index: usize) -> Result<(), TssError> {
         let comm_to_point = self.get_point_commitment(index);
         if *ss_point == comm_to_point {
             Ok(())
@@ -192,12 +196,12 @@ impl VerifiableSS {
         }
     }
 
-    pub fn get_point_commitment(&self, index: usize) -> GE {
-        let index_fe: FE = ECScalar::from(&BigInt::from(index as u32));
+    pub fn get_point_commitment(&self, index: usize) -> S::Point {
+        let index_fe: S = ECScalar::from(&BigInt::from(index as u32));
         let mut comm_iterator = self.commitments.iter().rev();
         let head = comm_iterator.next().unwrap();
         let tail = comm_iterator;
-        let comm_to_point = tail.fold(head.clone(), |acc, x: &GE| {
+        let comm_to_point = tail.fold(head.clone(), |acc, x: &S::Point| {
             x.clone() + acc * index_fe.clone()
         });
         comm_to_point
@@ -205,20 +209,20 @@ impl VerifiableSS {
 
     //compute \lambda_{index,S}, a lagrangian coefficient that change the (t,n) scheme to (|S|,|S|)
     // used in http://stevengoldfeder.com/papers/GG18.pdf
-    pub fn map_share_to_new_params(&self, index: usize, s: &[usize]) -> FE {
+    pub fn map_share_to_new_params(&self, index: usize, s: &[usize]) -> S {
         let s_len = s.len();
         //     assert!(s_len > self.reconstruct_limit());
         // add one to indices to get points
-        let points: Vec<FE> = (0..self.parameters.share_count)
+        let points: Vec<S> = (0..self.parameters.share_count)
             .map(|i| {
                 let index_bn = BigInt::from(i as u32 + 1 as u32);
                 ECScalar::from(&index_bn)
             })
-            .collect::<Vec<FE>>();
+            .collect::<Vec<S>>();
 
         let xi = &points[index];
-        let num: FE = ECScalar::from(&BigInt::one());
-        let denum: FE = ECScalar::from(&BigInt::one());
+        let num: S = ECScalar::from(&BigInt::one());
+        let denum: S = ECScalar::from(&BigInt::one());
         let num = (0..s_len).fold(num, |acc, i| {
             if s[i] != index {
                 acc * points[s[i]].clone()
@@ -237,6 +241,33 @@ impl VerifiableSS {
         let denum = denum.invert();
         num * denum
     }
+
+    //compute \lambda_{index,S}(target), a lagrangian coefficient that evaluates the polynomial
+    // through the shares of S at the point of party `target` instead of at zero
+    pub fn map_share_to_index(&self, index: usize, s: &[usize], target: usize) -> S {
+        // add one to indices to get points
+        let points: Vec<S> = (0..self.parameters.share_count)
+            .map(|i| {
+                let index_bn = BigInt::from(i as u32 + 1 as u32);
+                ECScalar::from(&index_bn)
+            })
+            .collect::<Vec<S>>();
+
+        let xi = &points[index];
+        let x = &points[target];
+        let num: S = ECScalar::from(&BigInt::one());
+        let denum: S = ECScalar::from(&BigInt::one());
+        let num = s.iter().filter(|&&j| j != index).fold(num, |acc, &j| {
+            let x_sub_xj = x.sub(&points[j].get_element());
+            acc * x_sub_xj
+        });
+        let denum = s.iter().filter(|&&j| j != index).fold(denum, |acc, &j| {
+            let xi_sub_xj = xi.sub(&points[j].get_element());
+            acc * xi_sub_xj
+        });
+        let denum = denum.invert();
+        num * denum
+    }
 }
 
 #[cfg(test)]
@@ -346,6 +377,22 @@ mod tests {
         assert_eq!(w, secret_reconstructed);
     }
 
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_map_shares_to_missing_index() {
+        let secret: FE = ECScalar::new_random();
+
+        let (vss_scheme, secret_shares) = VerifiableSS::share(2, 5, &secret);
+
+        // the shares of parties 0, 2 and 4 give the share of party 3
+        let s = &vec![0, 2, 4];
+        let x3 = s.iter().fold(FE::zero(), |acc, &i| {
+            acc + vss_scheme.map_share_to_index(i, s, 3) * secret_shares[i].clone()
+        });
+        assert_eq!(x3, secret_shares[3]);
+        assert!(vss_scheme.validate_share(&x3, 4).is_ok());
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     #[test]
     fn test_secret_sharing_1_out_of_2() {
@@ -418,4 +465,28 @@ mod tests {
         let w = l0 * secret_shares[0].clone() + l2 * secret_shares[2].clone();
         assert_eq!(w, secret_reconstructed);
     }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_secret_sharing_3_out_of_5_ed25519() {
+        use crate::curv::elliptic::curves::ed25519::{FE, GE};
+
+        let secret: FE = ECScalar::new_random();
+
+        let (vss_scheme, secret_shares) = VerifiableSS::share(3, 5, &secret);
+
+        let shares_vec = vec![
+            secret_shares[0].clone(),
+            secret_shares[1].clone(),
+            secret_shares[2].clone(),
+            secret_shares[4].clone(),
+        ];
+        let secret_reconstructed = vss_scheme.reconstruct(&[0, 1, 2, 4], &shares_vec);
+        assert_eq!(secret, secret_reconstructed);
+        assert_eq!(vss_scheme.commitments[0], GE::generator() * &secret);
+
+        // test secret shares are verifiable
+        assert!(vss_scheme.validate_share(&secret_shares[2], 3).is_ok());
+        assert!(vss_scheme.validate_share(&secret_shares[2], 4).is_err());
+    }
 }
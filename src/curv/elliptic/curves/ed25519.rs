@@ -0,0 +1,630 @@
+#![allow(non_snake_case)]
+
+// Ed25519 elliptic curve utility functions (see: https://tools.ietf.org/html/rfc8032).
+//
+// The curve is the twisted Edwards curve -x^2 + y^2 = 1 + d x^2 y^2 over GF(2^255 - 19),
+// and the scalars are the integers mod the order l of the base point. Points are kept in
+// affine coordinates and added in extended coordinates with the complete formulas of
+// https://eprint.iacr.org/2008/522.pdf, so the neutral element (0, 1) is a point like any
+// other. They are serialized with the 32-byte encoding of RFC 8032.
+//
+
+use super::traits::{CurveScalar, ECPoint, ECScalar};
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::{Converter, Modulo, Samplable};
+use crate::errors::TssError::{self, InvalidPublicKey};
+use num_traits::{Num, One, Zero};
+use serde::de;
+use serde::de::Visitor;
+use serde::ser::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use std::ops::{Add, Mul};
+use std::sync::atomic;
+use zeroize::Zeroize;
+
+lazy_static::lazy_static! {
+    // 2^255 - 19
+    static ref FIELD_ORDER: BigInt = (BigInt::one() << 255) - &BigInt::from(19u32);
+    // 2^252 + 27742317777372353535851937790883648493
+    static ref CURVE_ORDER: BigInt = (BigInt::one() << 252)
+        + &BigInt::from_str_radix("27742317777372353535851937790883648493", 10).unwrap();
+    // -121665 / 121666
+    static ref EDWARDS_D: BigInt = BigInt::from_str_radix(
+        "37095705934669439343138083508754565189542113879843219016388785533085940283555",
+        10
+    )
+    .unwrap();
+    static ref EDWARDS_2D: BigInt = (&*EDWARDS_D << 1) % &*FIELD_ORDER;
+    // 2^((p - 1) / 4), a square root of -1
+    static ref SQRT_M1: BigInt = BigInt::from_str_radix(
+        "19681161376707505956807079304988542015446066515923890162744021073123829784752",
+        10
+    )
+    .unwrap();
+    static ref GENERATOR_X: BigInt = BigInt::from_str_radix(
+        "15112221349535400772501151409588531511454012693041857206046113283949847762202",
+        10
+    )
+    .unwrap();
+    // 4/5
+    static ref GENERATOR_Y: BigInt = BigInt::from_str_radix(
+        "46316835694926478169428394003475163141307993866256225615783033603165251855960",
+        10
+    )
+    .unwrap();
+}
+
+pub type SK = BigInt;
+pub type PK = Ed25519Point;
+
+#[derive(Clone, Debug)]
+pub struct Ed25519Scalar {
+    fe: SK,
+}
+#[derive(Clone, Debug)]
+pub struct Ed25519Point {
+    x: BigInt,
+    y: BigInt,
+}
+
+pub type GE = Ed25519Point;
+pub type FE = Ed25519Scalar;
+
+impl Zeroize for FE {
+    fn zeroize(&mut self) {
+        // BigInt does not give access to its limbs: drop them for zero
+        self.fe = BigInt::zero();
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl Zeroize for GE {
+    fn zeroize(&mut self) {
+        *self = GE::generator();
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl ECScalar<SK> for Ed25519Scalar {
+    type ScalarLength = typenum::U32;
+
+    fn new_random() -> Ed25519Scalar {
+        Ed25519Scalar {
+            fe: BigInt::sample_below(&CURVE_ORDER),
+        }
+    }
+
+    fn zero() -> Ed25519Scalar {
+        Ed25519Scalar { fe: BigInt::zero() }
+    }
+
+    fn get_element(&self) -> SK {
+        self.fe.clone()
+    }
+
+    fn set_element(&mut self, element: SK) {
+        self.fe = element
+    }
+
+    fn from(n: &BigInt) -> Ed25519Scalar {
+        Ed25519Scalar {
+            fe: n % &*CURVE_ORDER,
+        }
+    }
+
+    fn to_big_int(&self) -> BigInt {
+        self.fe.clone()
+    }
+
+    fn q() -> BigInt {
+        CURVE_ORDER.clone()
+    }
+
+    fn group_order() -> &'static BigInt {
+        &CURVE_ORDER
+    }
+
+    fn add(&self, other: &SK) -> Ed25519Scalar {
+        Ed25519Scalar {
+            fe: BigInt::mod_add(&self.fe, other, &CURVE_ORDER),
+        }
+    }
+
+    fn mul(&self, other: &SK) -> Ed25519Scalar {
+        Ed25519Scalar {
+            fe: BigInt::mod_mul(&self.fe, other, &CURVE_ORDER),
+        }
+    }
+
+    fn sub(&self, other: &SK) -> Ed25519Scalar {
+        Ed25519Scalar {
+            fe: BigInt::mod_sub(&self.fe, other, &CURVE_ORDER),
+        }
+    }
+
+    fn invert(&self) -> Ed25519Scalar {
+        Ed25519Scalar {
+            fe: BigInt::mod_inv(&self.fe, &CURVE_ORDER),
+        }
+    }
+}
+
+impl CurveScalar for Ed25519Scalar {
+    type SK = SK;
+    type PK = PK;
+    type Point = Ed25519Point;
+}
+
+impl Mul<Ed25519Scalar> for Ed25519Scalar {
+    type Output = Ed25519Scalar;
+    fn mul(self, other: Ed25519Scalar) -> Ed25519Scalar {
+        (&self).mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o Ed25519Scalar> for Ed25519Scalar {
+    type Output = Ed25519Scalar;
+    fn mul(self, other: &'o Ed25519Scalar) -> Ed25519Scalar {
+        (&self).mul(&other.get_element())
+    }
+}
+
+impl Add<Ed25519Scalar> for Ed25519Scalar {
+    type Output = Ed25519Scalar;
+    fn add(self, other: Ed25519Scalar) -> Ed25519Scalar {
+        (&self).add(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o Ed25519Scalar> for Ed25519Scalar {
+    type Output = Ed25519Scalar;
+    fn add(self, other: &'o Ed25519Scalar) -> Ed25519Scalar {
+        (&self).add(&other.get_element())
+    }
+}
+
+impl Serialize for Ed25519Scalar {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_big_int().to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for Ed25519Scalar {
+    fn deserialize<D>(deserializer: D) -> Result<Ed25519Scalar, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Ed25519ScalarVisitor)
+    }
+}
+
+struct Ed25519ScalarVisitor;
+
+impl<'de> Visitor<'de> for Ed25519ScalarVisitor {
+    type Value = Ed25519Scalar;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Ed25519Scalar")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Ed25519Scalar, E> {
+        let v = BigInt::from_str_radix(s, 16).map_err(|_| E::custom("invalid scalar"))?;
+        Ok(ECScalar::from(&v))
+    }
+}
+
+impl PartialEq for Ed25519Scalar {
+    fn eq(&self, other: &Ed25519Scalar) -> bool {
+        self.fe == other.fe
+    }
+}
+
+impl PartialEq for Ed25519Point {
+    fn eq(&self, other: &Ed25519Point) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl Ed25519Point {
+    /// The neutral element (0, 1).
+    pub fn zero() -> Ed25519Point {
+        Ed25519Point {
+            x: BigInt::zero(),
+            y: BigInt::one(),
+        }
+    }
+
+    fn is_on_curve(x: &BigInt, y: &BigInt) -> bool {
+        let p = &*FIELD_ORDER;
+        let xx = BigInt::mod_mul(x, x, p);
+        let yy = BigInt::mod_mul(y, y, p);
+        // -x^2 + y^2 = 1 + d x^2 y^2
+        let lhs = BigInt::mod_sub(&yy, &xx, p);
+        let rhs = BigInt::mod_add(
+            &BigInt::one(),
+            &BigInt::mod_mul(&EDWARDS_D, &BigInt::mod_mul(&xx, &yy, p), p),
+            p,
+        );
+        x < p && y < p && lhs == rhs
+    }
+
+    /// Decodes the 32-byte encoding of RFC 8032, section 5.1.3.
+    fn decode(bytes: &[u8]) -> Result<Ed25519Point, TssError> {
+        let p = &*FIELD_ORDER;
+        let mut y_bytes = bytes.to_vec();
+        let x_0 = y_bytes[31] >> 7;
+        y_bytes[31] &= 0x7f;
+        y_bytes.reverse();
+        let y = BigInt::from_bytes_be(&y_bytes);
+        if &y >= p {
+            return Err(InvalidPublicKey);
+        }
+
+        // x^2 = (y^2 - 1) / (d y^2 + 1)
+        let yy = BigInt::mod_mul(&y, &y, p);
+        let u = BigInt::mod_sub(&yy, &BigInt::one(), p);
+        let v = BigInt::mod_add(&BigInt::mod_mul(&EDWARDS_D, &yy, p), &BigInt::one(), p);
+        let xx = BigInt::mod_mul(&u, &field_invert(&v), p);
+        let exponent: BigInt = (p + &BigInt::from(3u32)) >> 3;
+        let mut x = BigInt::mod_pow(&xx, &exponent, p);
+        if BigInt::mod_mul(&x, &x, p) != xx {
+            x = BigInt::mod_mul(&x, &SQRT_M1, p);
+        }
+        if BigInt::mod_mul(&x, &x, p) != xx {
+            return Err(InvalidPublicKey);
+        }
+        if x.is_zero() && x_0 == 1 {
+            return Err(InvalidPublicKey);
+        }
+        if (&x % 2u32).is_one() != (x_0 == 1) {
+            x = p - &x;
+        }
+        Ok(Ed25519Point { x, y })
+    }
+
+    /// The 32-byte encoding of RFC 8032, section 5.1.2: y in little-endian with the parity
+    /// of x in the top bit.
+    fn encode(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        let y_bytes = self.y.to_bytes_le();
+        bytes[..y_bytes.len()].copy_from_slice(&y_bytes);
+        if (&self.x % 2u32).is_one() {
+            bytes[31] |= 0x80;
+        }
+        bytes
+    }
+
+    fn from_extended(point: &ExtendedPoint) -> Ed25519Point {
+        let p = &*FIELD_ORDER;
+        let z_inv = field_invert(&point.Z);
+        Ed25519Point {
+            x: BigInt::mod_mul(&point.X, &z_inv, p),
+            y: BigInt::mod_mul(&point.Y, &z_inv, p),
+        }
+    }
+
+    fn to_extended(&self) -> ExtendedPoint {
+        ExtendedPoint {
+            X: self.x.clone(),
+            Y: self.y.clone(),
+            Z: BigInt::one(),
+            T: BigInt::mod_mul(&self.x, &self.y, &FIELD_ORDER),
+        }
+    }
+
+    fn neg(&self) -> Ed25519Point {
+        Ed25519Point {
+            x: BigInt::mod_sub(&BigInt::zero(), &self.x, &FIELD_ORDER),
+            y: self.y.clone(),
+        }
+    }
+}
+
+impl ECPoint<PK, SK> for Ed25519Point {
+    type CompressedPointLength = typenum::U32;
+    type UncompressedPointLength = typenum::U64;
+
+    fn generator() -> Ed25519Point {
+        Ed25519Point {
+            x: GENERATOR_X.clone(),
+            y: GENERATOR_Y.clone(),
+        }
+    }
+
+    fn get_element(&self) -> PK {
+        self.clone()
+    }
+
+    fn x_coor(&self) -> Option<BigInt> {
+        Some(self.x.clone())
+    }
+
+    fn y_coor(&self) -> Option<BigInt> {
+        Some(self.y.clone())
+    }
+
+    /// The 32-byte encoding read as a big-endian integer.
+    fn bytes_compressed_to_big_int(&self) -> BigInt {
+        BigInt::from_bytes_be(&self.encode())
+    }
+
+    /// Takes the 32-byte encoding of RFC 8032 or the 64 bytes of x || y in big-endian.
+    fn from_bytes(bytes: &[u8]) -> Result<Ed25519Point, TssError> {
+        match bytes.len() {
+            32 => Ed25519Point::decode(bytes),
+            64 => {
+                let x = BigInt::from_bytes_be(&bytes[..32]);
+                let y = BigInt::from_bytes_be(&bytes[32..]);
+                if !Ed25519Point::is_on_curve(&x, &y) {
+                    return Err(InvalidPublicKey);
+                }
+                Ok(Ed25519Point { x, y })
+            }
+            _ => Err(InvalidPublicKey),
+        }
+    }
+
+    fn pk_to_key_slice(&self) -> Vec<u8> {
+        self.encode().to_vec()
+    }
+
+    /// Double-and-add over the bits of `fe`, from the top.
+    fn scalar_mul(&self, fe: &SK) -> Ed25519Point {
+        let base = self.to_extended();
+        let mut acc = ExtendedPoint::zero();
+        for byte in BigInt::to_vec(fe) {
+            for i in (0..8).rev() {
+                acc = acc.add(&acc);
+                if (byte >> i) & 1 == 1 {
+                    acc = acc.add(&base);
+                }
+            }
+        }
+        Ed25519Point::from_extended(&acc)
+    }
+
+    fn add_point(&self, other: &PK) -> Ed25519Point {
+        let sum = self.to_extended().add(&other.to_extended());
+        Ed25519Point::from_extended(&sum)
+    }
+
+    fn sub_point(&self, other: &PK) -> Ed25519Point {
+        let diff = self.to_extended().add(&other.neg().to_extended());
+        Ed25519Point::from_extended(&diff)
+    }
+
+    fn from_coor(x: &BigInt, y: &BigInt) -> Ed25519Point {
+        assert!(Ed25519Point::is_on_curve(x, y));
+        Ed25519Point {
+            x: x.clone(),
+            y: y.clone(),
+        }
+    }
+
+    /// The 32-byte encoding of RFC 8032 if compressed, x || y in big-endian otherwise.
+    fn to_bytes(&self, compressed: bool) -> Vec<u8> {
+        if compressed {
+            return self.encode().to_vec();
+        }
+        let mut bytes = vec![0u8; 64];
+        let x_bytes = BigInt::to_vec(&self.x);
+        let y_bytes = BigInt::to_vec(&self.y);
+        bytes[32 - x_bytes.len()..32].copy_from_slice(&x_bytes);
+        bytes[64 - y_bytes.len()..].copy_from_slice(&y_bytes);
+        bytes
+    }
+}
+
+/// (X : Y : Z : T) with x = X/Z, y = Y/Z and x y = T/Z.
+struct ExtendedPoint {
+    X: BigInt,
+    Y: BigInt,
+    Z: BigInt,
+    T: BigInt,
+}
+
+impl ExtendedPoint {
+    fn zero() -> ExtendedPoint {
+        ExtendedPoint {
+            X: BigInt::zero(),
+            Y: BigInt::one(),
+            Z: BigInt::one(),
+            T: BigInt::zero(),
+        }
+    }
+
+    /// add-2008-hwcd-3, for a = -1. Complete: it doubles as well.
+    fn add(&self, other: &ExtendedPoint) -> ExtendedPoint {
+        let p = &*FIELD_ORDER;
+        let A = BigInt::mod_mul(
+            &BigInt::mod_sub(&self.Y, &self.X, p),
+            &BigInt::mod_sub(&other.Y, &other.X, p),
+            p,
+        );
+        let B = BigInt::mod_mul(
+            &BigInt::mod_add(&self.Y, &self.X, p),
+            &BigInt::mod_add(&other.Y, &other.X, p),
+            p,
+        );
+        let C = BigInt::mod_mul(&BigInt::mod_mul(&self.T, &other.T, p), &EDWARDS_2D, p);
+        let D = BigInt::mod_mul(&(&self.Z << 1), &other.Z, p);
+        let E = BigInt::mod_sub(&B, &A, p);
+        let F = BigInt::mod_sub(&D, &C, p);
+        let G = BigInt::mod_add(&D, &C, p);
+        let H = BigInt::mod_add(&B, &A, p);
+        ExtendedPoint {
+            X: BigInt::mod_mul(&E, &F, p),
+            Y: BigInt::mod_mul(&G, &H, p),
+            Z: BigInt::mod_mul(&F, &G, p),
+            T: BigInt::mod_mul(&E, &H, p),
+        }
+    }
+}
+
+fn field_invert(a: &BigInt) -> BigInt {
+    let p = &*FIELD_ORDER;
+    BigInt::mod_pow(a, &(p - &BigInt::from(2u32)), p)
+}
+
+impl Mul<Ed25519Scalar> for Ed25519Point {
+    type Output = Ed25519Point;
+    fn mul(self, other: Ed25519Scalar) -> Self::Output {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o Ed25519Scalar> for Ed25519Point {
+    type Output = Ed25519Point;
+    fn mul(self, other: &'o Ed25519Scalar) -> Self::Output {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o Ed25519Scalar> for &'o Ed25519Point {
+    type Output = Ed25519Point;
+    fn mul(self, other: &'o Ed25519Scalar) -> Self::Output {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl Add<Ed25519Point> for Ed25519Point {
+    type Output = Ed25519Point;
+    fn add(self, other: Ed25519Point) -> Self::Output {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o Ed25519Point> for Ed25519Point {
+    type Output = Ed25519Point;
+    fn add(self, other: &'o Ed25519Point) -> Self::Output {
+        self.add_point(other)
+    }
+}
+
+impl<'o> Add<&'o Ed25519Point> for &'o Ed25519Point {
+    type Output = Ed25519Point;
+    fn add(self, other: &'o Ed25519Point) -> Self::Output {
+        self.add_point(other)
+    }
+}
+
+impl Serialize for Ed25519Point {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&hex::encode(self.encode()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Ed25519Point {
+    fn deserialize<D>(deserializer: D) -> Result<Ed25519Point, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(Ed25519PointVisitor)
+    }
+}
+
+struct Ed25519PointVisitor;
+
+impl<'de> Visitor<'de> for Ed25519PointVisitor {
+    type Value = Ed25519Point;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Ed25519Point")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Ed25519Point, E> {
+        let bytes = hex::decode(s).map_err(|_| E::custom("invalid point"))?;
+        match bytes.len() {
+            32 => Ed25519Point::decode(&bytes).map_err(|_| E::custom("invalid point")),
+            _ => Err(E::custom("invalid point")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curv::arithmetic::num_bigint::BigInt;
+    use crate::curv::elliptic::curves::ed25519::{Ed25519Point, FE, GE};
+    use crate::curv::elliptic::curves::traits::ECPoint;
+    use crate::curv::elliptic::curves::traits::ECScalar;
+    use crate::errors::TssError;
+    use serde_json;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_generator_encoding() {
+        let g = GE::generator();
+        assert_eq!(
+            hex::encode(g.to_bytes(true)),
+            "5866666666666666666666666666666666666666666666666666666666666666"
+        );
+        assert_eq!(GE::from_bytes(&g.to_bytes(true)).unwrap(), g);
+        assert_eq!(GE::from_bytes(&g.to_bytes(false)).unwrap(), g);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_public_key_rfc8032() {
+        // TEST 1 of RFC 8032, section 7.1: the clamped SHA-512 of the secret key
+        // 9d61b19d...7f60, reduced mod l
+        let a: FE = ECScalar::from(&BigInt::from_bytes_be(
+            &hex::decode("0fe94d9006f020a5a3c080d96827fffce8852346655006e96ae99be612ac2c7c")
+                .unwrap(),
+        ));
+        let A = GE::generator() * a;
+        assert_eq!(
+            hex::encode(A.to_bytes(true)),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_group_order() {
+        let g = GE::generator();
+        let l_minus_one: FE = ECScalar::from(&(FE::q() - BigInt::from(1u32)));
+        assert_eq!(g.clone() * l_minus_one + &g, Ed25519Point::zero());
+        let a: FE = ECScalar::new_random();
+        let b: FE = ECScalar::new_random();
+        let ab = g.clone() * a.clone() + g.clone() * b.clone();
+        assert_eq!(ab.sub_point(&(g.clone() * b)), g * a);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_serdes_point() {
+        let a: FE = ECScalar::new_random();
+        let pk = GE::generator() * &a;
+        let s = serde_json::to_string(&pk).expect("Failed in serialization");
+        let des_pk: GE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_pk, pk);
+
+        let s = serde_json::to_string(&a).expect("Failed in serialization");
+        let des_a: FE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_a, a);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_from_bytes_not_on_curve() {
+        // y = 2 has no x on the curve
+        let mut bytes = [0u8; 32];
+        bytes[0] = 2;
+        match GE::from_bytes(&bytes).unwrap_err() {
+            TssError::InvalidPublicKey => {}
+            _ => panic!("result should be InvalidPublicKey"),
+        }
+    }
+}
@@ -9,6 +9,8 @@ extern crate rand;
 
 extern crate secp256k1;
 
+pub mod ed25519;
+pub mod p256;
 pub mod secp256_k1;
 
 pub mod traits;
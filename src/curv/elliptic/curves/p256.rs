@@ -0,0 +1,682 @@
+#![allow(non_snake_case)]
+
+// NIST P-256 (secp256r1) elliptic curve utility functions
+// (see: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.186-4.pdf, section D.1.2.3).
+//
+// The curve is y^2 = x^3 - 3x + b over GF(p), p = 2^256 - 2^224 + 2^192 + 2^96 - 1. Points are
+// kept in affine coordinates and added in Jacobian coordinates with the formulas of
+// https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian-3.html. As with secp256k1, the point
+// at infinity is not a value of `P256Point`: a sum that ends there panics. Points are
+// serialized like secp256k1 points, with the encodings of SEC 1.
+//
+
+use super::traits::{CurveScalar, ECPoint, ECScalar};
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::{Converter, Modulo, Samplable};
+use crate::errors::TssError::{self, InvalidPublicKey};
+use num_traits::{Num, One, Zero};
+use serde::de;
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::ser::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use std::ops::{Add, Mul};
+use std::sync::atomic;
+use zeroize::Zeroize;
+
+lazy_static::lazy_static! {
+    static ref FIELD_ORDER: BigInt = BigInt::from_str_radix(
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+        16
+    )
+    .unwrap();
+    static ref CURVE_ORDER: BigInt = BigInt::from_str_radix(
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+        16
+    )
+    .unwrap();
+    static ref CURVE_B: BigInt = BigInt::from_str_radix(
+        "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+        16
+    )
+    .unwrap();
+    static ref GENERATOR_X: BigInt = BigInt::from_str_radix(
+        "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+        16
+    )
+    .unwrap();
+    static ref GENERATOR_Y: BigInt = BigInt::from_str_radix(
+        "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+        16
+    )
+    .unwrap();
+}
+
+pub type SK = BigInt;
+pub type PK = P256Point;
+
+#[derive(Clone, Debug)]
+pub struct P256Scalar {
+    fe: SK,
+}
+#[derive(Clone, Debug)]
+pub struct P256Point {
+    x: BigInt,
+    y: BigInt,
+}
+
+pub type GE = P256Point;
+pub type FE = P256Scalar;
+
+impl Zeroize for FE {
+    fn zeroize(&mut self) {
+        // BigInt does not give access to its limbs: drop them for zero
+        self.fe = BigInt::zero();
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl Zeroize for GE {
+    fn zeroize(&mut self) {
+        *self = GE::generator();
+        atomic::fence(atomic::Ordering::SeqCst);
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl ECScalar<SK> for P256Scalar {
+    type ScalarLength = typenum::U32;
+
+    fn new_random() -> P256Scalar {
+        P256Scalar {
+            fe: BigInt::sample_below(&CURVE_ORDER),
+        }
+    }
+
+    fn zero() -> P256Scalar {
+        P256Scalar { fe: BigInt::zero() }
+    }
+
+    fn get_element(&self) -> SK {
+        self.fe.clone()
+    }
+
+    fn set_element(&mut self, element: SK) {
+        self.fe = element
+    }
+
+    fn from(n: &BigInt) -> P256Scalar {
+        P256Scalar {
+            fe: n % &*CURVE_ORDER,
+        }
+    }
+
+    fn to_big_int(&self) -> BigInt {
+        self.fe.clone()
+    }
+
+    fn q() -> BigInt {
+        CURVE_ORDER.clone()
+    }
+
+    fn group_order() -> &'static BigInt {
+        &CURVE_ORDER
+    }
+
+    fn add(&self, other: &SK) -> P256Scalar {
+        P256Scalar {
+            fe: BigInt::mod_add(&self.fe, other, &CURVE_ORDER),
+        }
+    }
+
+    fn mul(&self, other: &SK) -> P256Scalar {
+        P256Scalar {
+            fe: BigInt::mod_mul(&self.fe, other, &CURVE_ORDER),
+        }
+    }
+
+    fn sub(&self, other: &SK) -> P256Scalar {
+        P256Scalar {
+            fe: BigInt::mod_sub(&self.fe, other, &CURVE_ORDER),
+        }
+    }
+
+    fn invert(&self) -> P256Scalar {
+        P256Scalar {
+            fe: BigInt::mod_inv(&self.fe, &CURVE_ORDER),
+        }
+    }
+}
+
+impl CurveScalar for P256Scalar {
+    type SK = SK;
+    type PK = PK;
+    type Point = P256Point;
+}
+
+impl Mul<P256Scalar> for P256Scalar {
+    type Output = P256Scalar;
+    fn mul(self, other: P256Scalar) -> P256Scalar {
+        (&self).mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o P256Scalar> for P256Scalar {
+    type Output = P256Scalar;
+    fn mul(self, other: &'o P256Scalar) -> P256Scalar {
+        (&self).mul(&other.get_element())
+    }
+}
+
+impl Add<P256Scalar> for P256Scalar {
+    type Output = P256Scalar;
+    fn add(self, other: P256Scalar) -> P256Scalar {
+        (&self).add(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o P256Scalar> for P256Scalar {
+    type Output = P256Scalar;
+    fn add(self, other: &'o P256Scalar) -> P256Scalar {
+        (&self).add(&other.get_element())
+    }
+}
+
+impl Serialize for P256Scalar {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_big_int().to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for P256Scalar {
+    fn deserialize<D>(deserializer: D) -> Result<P256Scalar, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(P256ScalarVisitor)
+    }
+}
+
+struct P256ScalarVisitor;
+
+impl<'de> Visitor<'de> for P256ScalarVisitor {
+    type Value = P256Scalar;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("P256Scalar")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<P256Scalar, E> {
+        let v = BigInt::from_str_radix(s, 16).map_err(|_| E::custom("invalid scalar"))?;
+        Ok(ECScalar::from(&v))
+    }
+}
+
+impl PartialEq for P256Scalar {
+    fn eq(&self, other: &P256Scalar) -> bool {
+        self.fe == other.fe
+    }
+}
+
+impl PartialEq for P256Point {
+    fn eq(&self, other: &P256Point) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl P256Point {
+    fn is_on_curve(x: &BigInt, y: &BigInt) -> bool {
+        let p = &*FIELD_ORDER;
+        x < p && y < p && BigInt::mod_mul(y, y, p) == curve_rhs(x)
+    }
+
+    /// Decodes the 33-byte compressed encoding of SEC 1, section 2.3.4.
+    fn decompress(bytes: &[u8]) -> Result<P256Point, TssError> {
+        let p = &*FIELD_ORDER;
+        if bytes[0] != 2 && bytes[0] != 3 {
+            return Err(InvalidPublicKey);
+        }
+        let x = BigInt::from_bytes_be(&bytes[1..]);
+        if &x >= p {
+            return Err(InvalidPublicKey);
+        }
+        // p = 3 mod 4: a square root of a is a^((p + 1) / 4)
+        let yy = curve_rhs(&x);
+        let exponent: BigInt = (p + &BigInt::one()) >> 2;
+        let mut y = BigInt::mod_pow(&yy, &exponent, p);
+        if BigInt::mod_mul(&y, &y, p) != yy {
+            return Err(InvalidPublicKey);
+        }
+        if (&y % 2u32).is_one() != (bytes[0] == 3) {
+            y = BigInt::mod_sub(&BigInt::zero(), &y, p);
+        }
+        Ok(P256Point { x, y })
+    }
+
+    fn from_jacobian(point: &JacobianPoint) -> P256Point {
+        assert!(!point.is_zero(), "the point at infinity");
+        let p = &*FIELD_ORDER;
+        let z_inv = field_invert(&point.Z);
+        let z_inv_2 = BigInt::mod_mul(&z_inv, &z_inv, p);
+        let z_inv_3 = BigInt::mod_mul(&z_inv_2, &z_inv, p);
+        P256Point {
+            x: BigInt::mod_mul(&point.X, &z_inv_2, p),
+            y: BigInt::mod_mul(&point.Y, &z_inv_3, p),
+        }
+    }
+
+    fn to_jacobian(&self) -> JacobianPoint {
+        JacobianPoint {
+            X: self.x.clone(),
+            Y: self.y.clone(),
+            Z: BigInt::one(),
+        }
+    }
+
+    fn neg(&self) -> P256Point {
+        P256Point {
+            x: self.x.clone(),
+            y: BigInt::mod_sub(&BigInt::zero(), &self.y, &FIELD_ORDER),
+        }
+    }
+}
+
+impl ECPoint<PK, SK> for P256Point {
+    type CompressedPointLength = typenum::U33;
+    type UncompressedPointLength = typenum::U65;
+
+    fn generator() -> P256Point {
+        P256Point {
+            x: GENERATOR_X.clone(),
+            y: GENERATOR_Y.clone(),
+        }
+    }
+
+    fn get_element(&self) -> PK {
+        self.clone()
+    }
+
+    fn x_coor(&self) -> Option<BigInt> {
+        Some(self.x.clone())
+    }
+
+    fn y_coor(&self) -> Option<BigInt> {
+        Some(self.y.clone())
+    }
+
+    /// The 33-byte compressed encoding read as a big-endian integer.
+    fn bytes_compressed_to_big_int(&self) -> BigInt {
+        BigInt::from_bytes_be(&self.to_bytes(true))
+    }
+
+    /// Takes the compressed or uncompressed encoding of SEC 1, or the 64 bytes of x || y.
+    fn from_bytes(bytes: &[u8]) -> Result<P256Point, TssError> {
+        let (x, y) = match bytes.len() {
+            33 => return P256Point::decompress(bytes),
+            65 if bytes[0] == 4 => (&bytes[1..33], &bytes[33..]),
+            64 => (&bytes[..32], &bytes[32..]),
+            _ => return Err(InvalidPublicKey),
+        };
+        let x = BigInt::from_bytes_be(x);
+        let y = BigInt::from_bytes_be(y);
+        if !P256Point::is_on_curve(&x, &y) {
+            return Err(InvalidPublicKey);
+        }
+        Ok(P256Point { x, y })
+    }
+
+    fn pk_to_key_slice(&self) -> Vec<u8> {
+        self.to_bytes(false)
+    }
+
+    /// Double-and-add over the bits of `fe`, from the top.
+    fn scalar_mul(&self, fe: &SK) -> P256Point {
+        let base = self.to_jacobian();
+        let mut acc = JacobianPoint::zero();
+        for byte in BigInt::to_vec(fe) {
+            for i in (0..8).rev() {
+                acc = acc.double();
+                if (byte >> i) & 1 == 1 {
+                    acc = acc.add(&base);
+                }
+            }
+        }
+        P256Point::from_jacobian(&acc)
+    }
+
+    fn add_point(&self, other: &PK) -> P256Point {
+        let sum = self.to_jacobian().add(&other.to_jacobian());
+        P256Point::from_jacobian(&sum)
+    }
+
+    fn sub_point(&self, other: &PK) -> P256Point {
+        let diff = self.to_jacobian().add(&other.neg().to_jacobian());
+        P256Point::from_jacobian(&diff)
+    }
+
+    fn from_coor(x: &BigInt, y: &BigInt) -> P256Point {
+        assert!(P256Point::is_on_curve(x, y));
+        P256Point {
+            x: x.clone(),
+            y: y.clone(),
+        }
+    }
+
+    /// Serializes a point in (un)compressed form
+    fn to_bytes(&self, compressed: bool) -> Vec<u8> {
+        let mut bytes = if compressed {
+            vec![0u8; 33]
+        } else {
+            vec![0u8; 65]
+        };
+        let x_bytes = BigInt::to_vec(&self.x);
+        bytes[33 - x_bytes.len()..33].copy_from_slice(&x_bytes);
+        if compressed {
+            bytes[0] = 2 + (&self.y % 2u32).is_one() as u8;
+        } else {
+            bytes[0] = 4;
+            let y_bytes = BigInt::to_vec(&self.y);
+            bytes[65 - y_bytes.len()..].copy_from_slice(&y_bytes);
+        }
+        bytes
+    }
+}
+
+/// (X : Y : Z) with x = X/Z^2 and y = Y/Z^3, the point at infinity having Z = 0.
+#[derive(Clone)]
+struct JacobianPoint {
+    X: BigInt,
+    Y: BigInt,
+    Z: BigInt,
+}
+
+impl JacobianPoint {
+    fn zero() -> JacobianPoint {
+        JacobianPoint {
+            X: BigInt::zero(),
+            Y: BigInt::one(),
+            Z: BigInt::zero(),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.Z.is_zero()
+    }
+
+    /// dbl-2001-b, for a = -3.
+    fn double(&self) -> JacobianPoint {
+        if self.is_zero() || self.Y.is_zero() {
+            return JacobianPoint::zero();
+        }
+        let p = &*FIELD_ORDER;
+        let delta = BigInt::mod_mul(&self.Z, &self.Z, p);
+        let gamma = BigInt::mod_mul(&self.Y, &self.Y, p);
+        let beta = BigInt::mod_mul(&self.X, &gamma, p);
+        let alpha = BigInt::mod_mul(
+            &BigInt::from(3u32),
+            &BigInt::mod_mul(
+                &BigInt::mod_sub(&self.X, &delta, p),
+                &BigInt::mod_add(&self.X, &delta, p),
+                p,
+            ),
+            p,
+        );
+        let beta_4 = BigInt::mod_mul(&BigInt::from(4u32), &beta, p);
+        let X = BigInt::mod_sub(
+            &BigInt::mod_mul(&alpha, &alpha, p),
+            &BigInt::mod_add(&beta_4, &beta_4, p),
+            p,
+        );
+        let Y_plus_Z = BigInt::mod_add(&self.Y, &self.Z, p);
+        let Z = BigInt::mod_sub(
+            &BigInt::mod_sub(&BigInt::mod_mul(&Y_plus_Z, &Y_plus_Z, p), &gamma, p),
+            &delta,
+            p,
+        );
+        let gamma_2_8 =
+            BigInt::mod_mul(&BigInt::from(8u32), &BigInt::mod_mul(&gamma, &gamma, p), p);
+        let Y = BigInt::mod_sub(
+            &BigInt::mod_mul(&alpha, &BigInt::mod_sub(&beta_4, &X, p), p),
+            &gamma_2_8,
+            p,
+        );
+        JacobianPoint { X, Y, Z }
+    }
+
+    /// add-2007-bl, falling back to doubling for equal points.
+    fn add(&self, other: &JacobianPoint) -> JacobianPoint {
+        if self.is_zero() {
+            return other.clone();
+        }
+        if other.is_zero() {
+            return self.clone();
+        }
+        let p = &*FIELD_ORDER;
+        let Z1Z1 = BigInt::mod_mul(&self.Z, &self.Z, p);
+        let Z2Z2 = BigInt::mod_mul(&other.Z, &other.Z, p);
+        let U1 = BigInt::mod_mul(&self.X, &Z2Z2, p);
+        let U2 = BigInt::mod_mul(&other.X, &Z1Z1, p);
+        let S1 = BigInt::mod_mul(&BigInt::mod_mul(&self.Y, &other.Z, p), &Z2Z2, p);
+        let S2 = BigInt::mod_mul(&BigInt::mod_mul(&other.Y, &self.Z, p), &Z1Z1, p);
+        let H = BigInt::mod_sub(&U2, &U1, p);
+        let S2_minus_S1 = BigInt::mod_sub(&S2, &S1, p);
+        if H.is_zero() {
+            return match S2_minus_S1.is_zero() {
+                true => self.double(),
+                false => JacobianPoint::zero(),
+            };
+        }
+        let r = BigInt::mod_add(&S2_minus_S1, &S2_minus_S1, p);
+        let H_2 = BigInt::mod_add(&H, &H, p);
+        let I = BigInt::mod_mul(&H_2, &H_2, p);
+        let J = BigInt::mod_mul(&H, &I, p);
+        let V = BigInt::mod_mul(&U1, &I, p);
+        let X = BigInt::mod_sub(
+            &BigInt::mod_sub(&BigInt::mod_mul(&r, &r, p), &J, p),
+            &BigInt::mod_add(&V, &V, p),
+            p,
+        );
+        let S1_J = BigInt::mod_mul(&S1, &J, p);
+        let Y = BigInt::mod_sub(
+            &BigInt::mod_mul(&r, &BigInt::mod_sub(&V, &X, p), p),
+            &BigInt::mod_add(&S1_J, &S1_J, p),
+            p,
+        );
+        let Z1_plus_Z2 = BigInt::mod_add(&self.Z, &other.Z, p);
+        let Z = BigInt::mod_mul(
+            &BigInt::mod_sub(
+                &BigInt::mod_sub(&BigInt::mod_mul(&Z1_plus_Z2, &Z1_plus_Z2, p), &Z1Z1, p),
+                &Z2Z2,
+                p,
+            ),
+            &H,
+            p,
+        );
+        JacobianPoint { X, Y, Z }
+    }
+}
+
+/// x^3 - 3x + b
+fn curve_rhs(x: &BigInt) -> BigInt {
+    let p = &*FIELD_ORDER;
+    let xxx = BigInt::mod_mul(&BigInt::mod_mul(x, x, p), x, p);
+    let x_3 = BigInt::mod_mul(&BigInt::from(3u32), x, p);
+    BigInt::mod_add(&BigInt::mod_sub(&xxx, &x_3, p), &CURVE_B, p)
+}
+
+fn field_invert(a: &BigInt) -> BigInt {
+    let p = &*FIELD_ORDER;
+    BigInt::mod_pow(a, &(p - &BigInt::from(2u32)), p)
+}
+
+impl Mul<P256Scalar> for P256Point {
+    type Output = P256Point;
+    fn mul(self, other: P256Scalar) -> Self::Output {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o P256Scalar> for P256Point {
+    type Output = P256Point;
+    fn mul(self, other: &'o P256Scalar) -> Self::Output {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl<'o> Mul<&'o P256Scalar> for &'o P256Point {
+    type Output = P256Point;
+    fn mul(self, other: &'o P256Scalar) -> Self::Output {
+        self.scalar_mul(&other.get_element())
+    }
+}
+
+impl Add<P256Point> for P256Point {
+    type Output = P256Point;
+    fn add(self, other: P256Point) -> Self::Output {
+        self.add_point(&other.get_element())
+    }
+}
+
+impl<'o> Add<&'o P256Point> for P256Point {
+    type Output = P256Point;
+    fn add(self, other: &'o P256Point) -> Self::Output {
+        self.add_point(other)
+    }
+}
+
+impl<'o> Add<&'o P256Point> for &'o P256Point {
+    type Output = P256Point;
+    fn add(self, other: &'o P256Point) -> Self::Output {
+        self.add_point(other)
+    }
+}
+
+impl Serialize for P256Point {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("P256Point", 2)?;
+        state.serialize_field("x", &self.x.to_hex())?;
+        state.serialize_field("y", &self.y.to_hex())?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for P256Point {
+    fn deserialize<D>(deserializer: D) -> Result<P256Point, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let fields = &["x", "y"];
+        deserializer.deserialize_struct("P256Point", fields, P256PointVisitor)
+    }
+}
+
+struct P256PointVisitor;
+
+impl<'de> Visitor<'de> for P256PointVisitor {
+    type Value = P256Point;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("P256Point")
+    }
+
+    fn visit_map<E: MapAccess<'de>>(self, mut map: E) -> Result<P256Point, E::Error> {
+        let invalid = || <E::Error as de::Error>::custom("invalid point");
+        let mut x = None;
+        let mut y = None;
+
+        while let Some(ref key) = map.next_key::<String>()? {
+            let v = map.next_value::<String>()?;
+            let v = BigInt::from_str_radix(&v, 16).map_err(|_| invalid())?;
+            if key == "x" {
+                x = Some(v)
+            } else if key == "y" {
+                y = Some(v)
+            } else {
+                return Err(invalid());
+            }
+        }
+
+        match (x, y) {
+            (Some(x), Some(y)) if P256Point::is_on_curve(&x, &y) => Ok(P256Point { x, y }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curv::arithmetic::num_bigint::BigInt;
+    use crate::curv::elliptic::curves::p256::{FE, GE};
+    use crate::curv::elliptic::curves::traits::ECPoint;
+    use crate::curv::elliptic::curves::traits::ECScalar;
+    use crate::errors::TssError;
+    use serde_json;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_double_generator() {
+        let two: FE = ECScalar::from(&BigInt::from(2u32));
+        let g = GE::generator();
+        assert_eq!(g.clone() + &g, g.clone() * two);
+        assert_eq!(
+            hex::encode((g.clone() + &g).to_bytes(false)),
+            "047cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978\
+             07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1"
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_group_order() {
+        let g = GE::generator();
+        let n_minus_one: FE = ECScalar::from(&(FE::q() - BigInt::from(1u32)));
+        // -g has the x of g
+        assert_eq!((g.clone() * n_minus_one).x_coor(), g.x_coor());
+        let a: FE = ECScalar::new_random();
+        let b: FE = ECScalar::new_random();
+        let ab = g.clone() * a.clone() + g.clone() * b.clone();
+        assert_eq!(ab.sub_point(&(g.clone() * b)), g * a);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_from_bytes() {
+        let a: FE = ECScalar::new_random();
+        let pk = GE::generator() * a;
+        assert_eq!(GE::from_bytes(&pk.to_bytes(true)).unwrap(), pk);
+        assert_eq!(GE::from_bytes(&pk.to_bytes(false)).unwrap(), pk);
+        assert_eq!(GE::from_bytes(&pk.to_bytes(false)[1..]).unwrap(), pk);
+
+        let mut bytes = pk.to_bytes(false);
+        bytes[64] ^= 1;
+        match GE::from_bytes(&bytes).unwrap_err() {
+            TssError::InvalidPublicKey => {}
+            _ => panic!("result should be InvalidPublicKey"),
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_serdes_point() {
+        let a: FE = ECScalar::new_random();
+        let pk = GE::generator() * &a;
+        let s = serde_json::to_string(&pk).expect("Failed in serialization");
+        let des_pk: GE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_pk, pk);
+
+        let s = serde_json::to_string(&a).expect("Failed in serialization");
+        let des_a: FE = serde_json::from_str(&s).expect("Failed in deserialization");
+        assert_eq!(des_a, a);
+    }
+}
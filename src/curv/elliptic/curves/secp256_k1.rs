@@ -18,7 +18,7 @@
 
 use super::rand::{thread_rng, Rng};
 use super::secp256k1::{PublicKey, SecretKey};
-use super::traits::{ECPoint, ECScalar};
+use super::traits::{CurveScalar, ECPoint, ECScalar};
 use crate::curv::arithmetic::num_bigint::from;
 use crate::curv::arithmetic::num_bigint::BigInt;
 use crate::curv::arithmetic::traits::{Converter, Modulo};
@@ -229,6 +229,12 @@ impl ECScalar<SK> for Secp256k1Scalar {
     }
 }
 
+impl CurveScalar for Secp256k1Scalar {
+    type SK = SK;
+    type PK = PK;
+    type Point = Secp256k1Point;
+}
+
 impl Mul<Secp256k1Scalar> for Secp256k1Scalar {
     type Output = Secp256k1Scalar;
     fn mul(self, other: Secp256k1Scalar) -> Secp256k1Scalar {
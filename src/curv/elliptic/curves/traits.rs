@@ -8,7 +8,12 @@
 use crate::curv::arithmetic::num_bigint::BigInt;
 use crate::errors::TssError;
 use generic_array::ArrayLength;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+use std::ops::{Add, Mul};
 use typenum::Unsigned;
+use zeroize::Zeroize;
 
 pub trait ECScalar<SK> {
     type ScalarLength: ArrayLength<u8> + Unsigned;
@@ -48,3 +53,33 @@ where
     fn from_coor(x: &BigInt, y: &BigInt) -> Self;
     fn to_bytes(&self, compressed: bool) -> Vec<u8>;
 }
+
+/// A scalar together with the point type of its curve, for code that is generic over the
+/// curve such as `VerifiableSS` and `DLogProof`. Such code also asks for
+/// `S: ECScalar<<S as CurveScalar>::SK>`, which can not be a supertrait as it names `SK`.
+pub trait CurveScalar:
+    Clone
+    + PartialEq
+    + Debug
+    + Zeroize
+    + Serialize
+    + DeserializeOwned
+    + Add<Self, Output = Self>
+    + for<'a> Add<&'a Self, Output = Self>
+    + Mul<Self, Output = Self>
+    + for<'a> Mul<&'a Self, Output = Self>
+{
+    type SK;
+    type PK;
+    type Point: ECPoint<Self::PK, Self::SK>
+        + Clone
+        + PartialEq
+        + Debug
+        + Zeroize
+        + Serialize
+        + DeserializeOwned
+        + Add<Self::Point, Output = Self::Point>
+        + for<'a> Add<&'a Self::Point, Output = Self::Point>
+        + Mul<Self, Output = Self::Point>
+        + for<'a> Mul<&'a Self, Output = Self::Point>;
+}
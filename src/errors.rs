@@ -34,6 +34,185 @@ pub enum TssError {
     InvalidPublicKey,
     #[error("VerifyShareError")]
     VerifyShareError,
+    #[error("InvalidMessage: {0:?}")]
+    InvalidMessage(BlameItem),
+    #[error("Blame: {0:?}")]
+    Blame(Vec<Blame>),
+    #[error("io error")]
+    IoError(#[from] std::io::Error),
+    #[error("InvalidPresignatureId: {0}")]
+    InvalidPresignatureId(String),
+    #[error("PresignatureNotFound")]
+    PresignatureNotFound,
+    #[error("PresignatureReuse")]
+    PresignatureReuse,
+    #[error("InvalidDerivationPath: {0}")]
+    InvalidDerivationPath(String),
+    #[error("UnexpectedMessage: {0}")]
+    UnexpectedMessage(String),
+    #[error("OutdatedKeyStore: key store from an older version, {0}")]
+    OutdatedKeyStore(String),
+    #[error("RetireKeyShare: {0:?} failed, sign no more with this key share")]
+    RetireKeyShare(BlameItem),
+}
+
+/// A message that failed verification, named after the step of the protocol that produced it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlameItem {
+    /// Proof that h1 and h2 generate the same subgroup of Z*_N_tilde
+    RingPedersenProof,
+    /// Proof that the Paillier key is well formed
+    CorrectKeyProof,
+    /// Proof that the Paillier modulus is a Paillier-Blum modulus
+    PaillierBlumModulusProof,
+    /// Paillier modulus N or ring-Pedersen modulus N_tilde that another party also uses
+    ReusedModulus,
+    /// Decommitment to y_i does not open the keygen commitment of round 1
+    KeyGenDecommitment,
+    /// Proof that the Paillier modulus has no small factors
+    NoSmallFactorProof,
+    /// VSS commitments whose free term is not the decommitted y_i
+    VssCommitment,
+    /// Share that does not match the dealer's VSS commitments, or a complaint left unanswered
+    VssShare,
+    /// Proof of knowledge of the share x_i
+    XiDLogProof,
+    /// Bob's range proof in an MtA or MtAwc message B
+    MtaRangeProof,
+    /// Proof of knowledge of b or beta' in a message B, or alpha inconsistent with them
+    MtaDLogProof,
+    /// MtAwc message B not made with the signer's public share g^w_j
+    MtawcPublicShare,
+    /// Decommitment to g^gamma_j does not open the commitment of round 1
+    GammaDecommitment,
+    /// g^gamma_j differs from the g^b used in the MtA of round 2
+    GammaMtaMismatch,
+    /// Decommitment to V_j, A_j, B_j does not open the phase 5A commitment
+    Phase5ADecommitment,
+    /// Proof that V_j = R^s_j g^l_j and B_j = A_j^l_j
+    HomoElGamalProof,
+    /// Proof of knowledge of rho_j for A_j = g^rho_j
+    RhoDLogProof,
+    /// Decommitment to U_j, T_j does not open the phase 5C commitment
+    Phase5DDecommitment,
+    /// Online share s_j of a signature that does not match R_j and S_j from presigning
+    OnlineSigShare,
+    /// GG20: proof of knowledge of sigma_j and l_j in T_j = g^sigma_j h^l_j
+    TPedersenProof,
+    /// GG20: proof that R_bar_j = R^k_j for the k_j encrypted in the MtA message A
+    PdlWithSlackProof,
+    /// GG20: k_j or alpha_j revealed when the R_bar_j do not add up to g that does not open
+    /// the MtA message A, or does not match the messages B received
+    MtaReveal,
+    /// GG20: delta_j that differs from k_j gamma_j plus the MtA shares revealed for it
+    DeltaShare,
+    /// GG20: proof that S_j = R^sigma_j for the sigma_j committed to in T_j
+    STProof,
+    /// CGGMP21: proof that K_j encrypts a k_j in range
+    EncProof,
+    /// CGGMP21: proof that an MtA ciphertext D is made with the gamma_j of Gamma_j, or with
+    /// the w_j of g^w_j
+    AffGProof,
+    /// CGGMP21: proof that Gamma_j = g^gamma_j for the gamma_j encrypted in G_j
+    LogStarProof,
+    /// CGGMP21: proof that Delta_j = Gamma^k_j for the k_j encrypted in K_j
+    DeltaLogStarProof,
+    /// FROST: share z_j of a signature that does not match D_j, E_j and g^w_j
+    FrostSigShare,
+    /// Lindell17: proof that h1 and h2 generate the same subgroup of Z*_N_tilde
+    TwoPartyRingPedersenProof,
+    /// Lindell17: proof of knowledge of x2 or k2
+    TwoPartyDLogProof,
+    /// Lindell17: decommitment to Q1 or R1 that does not open the commitment of message 1,
+    /// or a proof of knowledge of x1 or k1 that fails
+    TwoPartyDecommitment,
+    /// Lindell17: proof that the Paillier key of party one is well formed
+    TwoPartyCorrectKeyProof,
+    /// Lindell17: proof that c_key encrypts the x1 of Q1, in range
+    EncryptedShareProof,
+    /// Lindell17: encrypted partial signature of party two that does not complete a valid
+    /// signature
+    PartialSignature,
+}
+
+impl BlameItem {
+    /// The round of the GG18, GG20, CGGMP21 or FROST client in which the message is sent. For
+    /// the two-party Lindell17 protocol, the number of the message.
+    pub fn round(&self) -> u16 {
+        match self {
+            BlameItem::RingPedersenProof
+            | BlameItem::CorrectKeyProof
+            | BlameItem::PaillierBlumModulusProof
+            | BlameItem::ReusedModulus => 1,
+            BlameItem::KeyGenDecommitment | BlameItem::NoSmallFactorProof => 2,
+            BlameItem::VssCommitment | BlameItem::VssShare => 4,
+            BlameItem::XiDLogProof => 5,
+            BlameItem::MtaRangeProof | BlameItem::MtaDLogProof | BlameItem::MtawcPublicShare => 2,
+            BlameItem::GammaDecommitment | BlameItem::GammaMtaMismatch => 4,
+            BlameItem::Phase5ADecommitment
+            | BlameItem::HomoElGamalProof
+            | BlameItem::RhoDLogProof => 6,
+            BlameItem::Phase5DDecommitment => 8,
+            // the online step of a presigned signature has a single round
+            BlameItem::OnlineSigShare => 1,
+            BlameItem::TPedersenProof | BlameItem::DeltaShare => 3,
+            BlameItem::PdlWithSlackProof | BlameItem::MtaReveal => 5,
+            BlameItem::STProof => 6,
+            BlameItem::EncProof => 1,
+            BlameItem::AffGProof | BlameItem::LogStarProof => 2,
+            BlameItem::DeltaLogStarProof => 3,
+            BlameItem::FrostSigShare => 2,
+            BlameItem::TwoPartyRingPedersenProof | BlameItem::TwoPartyDLogProof => 2,
+            BlameItem::TwoPartyDecommitment
+            | BlameItem::TwoPartyCorrectKeyProof
+            | BlameItem::EncryptedShareProof => 3,
+            BlameItem::PartialSignature => 4,
+        }
+    }
+}
+
+/// A party accused of sending a message that failed verification.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Blame {
+    pub party: usize,
+    pub round: u16,
+    pub item: BlameItem,
+}
+
+impl Blame {
+    pub fn new(party: usize, item: BlameItem) -> Blame {
+        Blame {
+            party,
+            round: item.round(),
+            item,
+        }
+    }
+}
+
+impl TssError {
+    /// Attributes a failed check of a single message to the `party` that sent it.
+    pub fn blame(self, party: usize) -> TssError {
+        match self {
+            TssError::InvalidMessage(item) => TssError::Blame(vec![Blame::new(party, item)]),
+            e => e,
+        }
+    }
+
+    /// Renumbers the parties of a blame report, e.g. from vector positions to party numbers.
+    pub fn map_blamed_parties<F: Fn(usize) -> usize>(self, f: F) -> TssError {
+        match self {
+            TssError::Blame(blames) => TssError::Blame(
+                blames
+                    .into_iter()
+                    .map(|blame| Blame {
+                        party: f(blame.party),
+                        ..blame
+                    })
+                    .collect(),
+            ),
+            e => e,
+        }
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
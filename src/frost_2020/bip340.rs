@@ -0,0 +1,225 @@
+#![allow(non_snake_case)]
+
+/*
+    BIP-340 Schnorr signatures, https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki,
+    and the key tweak of BIP-341 for Taproot outputs.
+
+    Keys and R are x-only: of the two points with a given x, the one with an even Y.
+*/
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::Result;
+use crate::errors::TssError::{InvalidKey, InvalidPublicKey, InvalidSig};
+
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use sha2::{Digest, Sha256};
+
+/// A signature (R, s), verified as `g^s = R Q^e` for the x-only key Q.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SchnorrSignature {
+    pub R: GE,
+    pub s: FE,
+}
+
+impl SchnorrSignature {
+    /// x(R) || s, as in a Taproot witness.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&x_only(&self.R));
+        bytes[32..].copy_from_slice(&to_bytes32(&self.s.to_big_int()));
+        bytes
+    }
+}
+
+/// The key that signatures verify against, and how the shares of the group key y make
+/// its secret: q = sign * x + tweak.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OutputKey {
+    /// with an even Y
+    pub Q: GE,
+    /// 1 or -1
+    sign: FE,
+    tweak: Option<FE>,
+}
+
+impl OutputKey {
+    /// The x-only key of y for `merkle_root` None, and the Taproot output key of the
+    /// internal key y otherwise: Some of an empty root for a key-path-only output, as in
+    /// BIP-86, or of the 32-byte root of the script tree.
+    pub fn new(y: &GE, merkle_root: Option<&[u8]>) -> Result<OutputKey> {
+        let sign_P = even_y_sign(y);
+        let P = y * &sign_P;
+        let merkle_root = match merkle_root {
+            Some(merkle_root) => merkle_root,
+            None => {
+                return Ok(OutputKey {
+                    Q: P,
+                    sign: sign_P,
+                    tweak: None,
+                })
+            }
+        };
+
+        let t = BigInt::from_bytes_be(&tagged_hash("TapTweak", &[&x_only(&P)[..], merkle_root]));
+        if &t >= FE::group_order() {
+            return Err(InvalidKey);
+        }
+        let t: FE = ECScalar::from(&t);
+        let g: GE = ECPoint::generator();
+        let Q = P + &g * &t;
+        let sign_Q = even_y_sign(&Q);
+        Ok(OutputKey {
+            Q: &Q * &sign_Q,
+            sign: sign_P * &sign_Q,
+            tweak: Some(t * &sign_Q),
+        })
+    }
+
+    pub fn x_only(&self) -> [u8; 32] {
+        x_only(&self.Q)
+    }
+
+    /// What the shares of the group key are multiplied by.
+    pub fn sign(&self) -> &FE {
+        &self.sign
+    }
+
+    /// What is added to the signature for the tweak: e times the tweak of the secret.
+    pub fn tweak_term(&self, e: &FE) -> Option<FE> {
+        self.tweak.as_ref().map(|tweak| tweak.clone() * e)
+    }
+}
+
+/// SHA256(SHA256(tag) || SHA256(tag) || data).
+pub fn tagged_hash(tag: &str, data: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let hash = data
+        .iter()
+        .fold(Sha256::new().chain(&tag_hash).chain(&tag_hash), |acc, d| {
+            acc.chain(d)
+        })
+        .finalize();
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&hash);
+    bytes
+}
+
+/// e = H(x(R) || x(Q) || m) with the tag of BIP-340.
+pub fn challenge(R: &GE, Q: &GE, message: &[u8]) -> FE {
+    let e = tagged_hash(
+        "BIP0340/challenge",
+        &[&x_only(R)[..], &x_only(Q)[..], message],
+    );
+    ECScalar::from(&BigInt::from_bytes_be(&e))
+}
+
+pub fn has_even_y(point: &GE) -> bool {
+    point.y_coor().unwrap().is_even()
+}
+
+pub fn x_only(point: &GE) -> [u8; 32] {
+    to_bytes32(&point.x_coor().unwrap())
+}
+
+/// The point with x and an even Y.
+pub fn lift_x(x: &[u8]) -> Result<GE> {
+    if x.len() != 32 {
+        return Err(InvalidPublicKey);
+    }
+    let mut compressed = [2u8; 33];
+    compressed[1..].copy_from_slice(x);
+    let point = secp256k1::PublicKey::parse_compressed(&compressed)?;
+    ECPoint::from_bytes(&point.serialize()[1..])
+}
+
+/// Checks a 64-byte signature of `message` for a 32-byte x-only key.
+pub fn verify(signature: &[u8], public_key: &[u8], message: &[u8]) -> Result<()> {
+    let Q = lift_x(public_key).map_err(|_| InvalidPublicKey)?;
+    if signature.len() != 64 {
+        return Err(InvalidSig);
+    }
+    let R = lift_x(&signature[..32]).map_err(|_| InvalidSig)?;
+    let s = BigInt::from_bytes_be(&signature[32..]);
+    // s = 0 only signs for R = Q^-e, which no one can aim at
+    if &s >= FE::group_order() || s.is_zero() {
+        return Err(InvalidSig);
+    }
+
+    let g: GE = ECPoint::generator();
+    let s: FE = ECScalar::from(&s);
+    let e = challenge(&R, &Q, message);
+    match &g * &s == R + Q * &e {
+        true => Ok(()),
+        false => Err(InvalidSig),
+    }
+}
+
+/// 1 if the point has an even Y, -1 otherwise.
+pub(crate) fn even_y_sign(point: &GE) -> FE {
+    match has_even_y(point) {
+        true => ECScalar::from(&BigInt::one()),
+        false => ECScalar::from(&(FE::q() - BigInt::one())),
+    }
+}
+
+fn to_bytes32(n: &BigInt) -> [u8; 32] {
+    let bytes = BigInt::to_vec(n);
+    let mut padded = [0u8; 32];
+    padded[32 - bytes.len()..].copy_from_slice(&bytes);
+    padded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_verify_bip340_vectors() {
+        // vectors 0 and 1 of BIP-340
+        let vectors = [
+            (
+                "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+            ),
+            (
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+            ),
+        ];
+        for (public_key, message, signature) in vectors.iter() {
+            let public_key = hex::decode(public_key).unwrap();
+            let message = hex::decode(message).unwrap();
+            let mut signature = hex::decode(signature).unwrap();
+            assert!(verify(&signature, &public_key, &message).is_ok());
+
+            signature[63] ^= 1;
+            assert!(verify(&signature, &public_key, &message).is_err());
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_taproot_output_key() {
+        // first receiving address of the BIP-86 test vectors
+        let internal_key =
+            hex::decode("cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115")
+                .unwrap();
+        let P = lift_x(&internal_key).unwrap();
+        let key = OutputKey::new(&P, Some(&[])).unwrap();
+        assert_eq!(
+            hex::encode(key.x_only()),
+            "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c"
+        );
+        assert!(has_even_y(&key.Q));
+    }
+}
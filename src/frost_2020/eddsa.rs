@@ -0,0 +1,333 @@
+#![allow(non_snake_case)]
+
+/*
+    Threshold EdDSA: FROST, https://eprint.iacr.org/2020/852.pdf, on Ed25519 with the
+    signatures of RFC 8032
+
+    Keygen is the one of GG18 without the Paillier keys, with the VSS and the proofs of
+    knowledge on Ed25519:
+
+    - round 1: each party commits to y_i = g^u_i.
+    - round 2: each party decommits y_i and deals u_i with Feldman VSS, share j to party j.
+    - round 3: each party checks its shares against the VSS commitments, adds them up to
+      x_i and proves knowledge of x_i.
+    - every party checks the proofs against the commitments to the x_j.
+
+    Signing is the one of `party_i` with the challenge of RFC 8032, H(R, y, m) with
+    SHA-512, and without the BIP-340 signs: any RFC 8032 verifier takes the signature for
+    the group key y.
+
+    The nonces of a `SignKeys` sign a single message: a new signature needs new keys.
+*/
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::cryptographic_primitives::commitments::hash_commitment::HashCommitment;
+use crate::curv::cryptographic_primitives::commitments::traits::Commitment;
+use crate::curv::cryptographic_primitives::proofs::sigma_dlog::{DLogProof, ProveDLog};
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::curv::elliptic::curves::ed25519::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::BlameItem::{
+    FrostSigShare, KeyGenDecommitment, VssCommitment, VssShare, XiDLogProof,
+};
+use crate::errors::TssError::{self, InvalidSig};
+use crate::errors::{Blame, Result};
+use crate::frost_2020::rfc8032::{self, Ed25519Signature};
+use crate::gg_2018::party_i::Parameters;
+
+use sha2::{Digest, Sha512};
+
+const SECURITY: usize = 256;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Keys {
+    pub u_i: FE,
+    pub y_i: GE,
+    pub party_index: usize,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeyGenBroadcastMessage1 {
+    pub com: BigInt,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct KeyGenDecommitMessage1 {
+    pub blind_factor: BigInt,
+    pub y_i: GE,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SharedKeys {
+    pub y: GE,
+    pub x_i: FE,
+}
+
+impl Keys {
+    pub fn create(index: usize) -> Keys {
+        let u: FE = ECScalar::new_random();
+        let g: GE = ECPoint::generator();
+        let y = &g * &u;
+        Keys {
+            u_i: u,
+            y_i: y,
+            party_index: index,
+        }
+    }
+
+    pub fn phase1_broadcast(&self) -> (KeyGenBroadcastMessage1, KeyGenDecommitMessage1) {
+        let blind_factor = BigInt::sample(SECURITY);
+        let com = HashCommitment::create_commitment_with_user_defined_randomness(
+            &self.y_i.bytes_compressed_to_big_int(),
+            &blind_factor,
+        );
+        let decom1 = KeyGenDecommitMessage1 {
+            blind_factor,
+            y_i: self.y_i.clone(),
+        };
+        (KeyGenBroadcastMessage1 { com }, decom1)
+    }
+
+    pub fn phase1_verify_com_phase2_distribute(
+        &self,
+        params: &Parameters,
+        decom_vec: &[KeyGenDecommitMessage1],
+        bc1_vec: &[KeyGenBroadcastMessage1],
+    ) -> Result<(VerifiableSS<FE>, Vec<FE>, usize)> {
+        assert_eq!(decom_vec.len(), params.share_count);
+        assert_eq!(bc1_vec.len(), params.share_count);
+        let blames = (0..bc1_vec.len())
+            .filter(|&i| {
+                HashCommitment::create_commitment_with_user_defined_randomness(
+                    &decom_vec[i].y_i.bytes_compressed_to_big_int(),
+                    &decom_vec[i].blind_factor,
+                ) != bc1_vec[i].com
+            })
+            .map(|i| Blame::new(i, KeyGenDecommitment))
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let (vss_scheme, secret_shares) =
+            VerifiableSS::share(params.threshold, params.share_count, &self.u_i);
+        Ok((vss_scheme, secret_shares, self.party_index))
+    }
+
+    pub fn phase2_verify_vss_construct_keypair_phase3_pok_dlog(
+        &self,
+        params: &Parameters,
+        y_vec: &[GE],
+        secret_shares_vec: &[FE],
+        vss_scheme_vec: &[VerifiableSS<FE>],
+        index: usize,
+    ) -> Result<(SharedKeys, DLogProof<FE>)> {
+        assert_eq!(y_vec.len(), params.share_count);
+        assert_eq!(secret_shares_vec.len(), params.share_count);
+        assert_eq!(vss_scheme_vec.len(), params.share_count);
+
+        let blames = (0..y_vec.len())
+            .flat_map(|i| {
+                let commitment_ok = vss_scheme_vec[i].commitments[0] == y_vec[i];
+                let share_ok = vss_scheme_vec[i]
+                    .validate_share(&secret_shares_vec[i], index)
+                    .is_ok();
+                let commitment_blame = Blame::new(i, VssCommitment);
+                let share_blame = Blame::new(i, VssShare);
+                [(commitment_ok, commitment_blame), (share_ok, share_blame)]
+                    .into_iter()
+                    .filter(|(passed, _)| !passed)
+                    .map(|(_, blame)| blame)
+            })
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let y = y_vec[1..]
+            .iter()
+            .fold(y_vec[0].clone(), |acc, y_i| acc + y_i);
+        let x_i = secret_shares_vec
+            .iter()
+            .fold(FE::zero(), |acc, share| acc + share);
+        let dlog_proof = DLogProof::prove(&x_i);
+        Ok((SharedKeys { y, x_i }, dlog_proof))
+    }
+
+    /// g^x_j for every party j, from the VSS schemes of all dealers.
+    pub fn get_commitments_to_xi(vss_scheme_vec: &[VerifiableSS<FE>]) -> Vec<GE> {
+        let share_count = vss_scheme_vec[0].parameters.share_count;
+        (1..=share_count)
+            .map(|i| {
+                let head = vss_scheme_vec[0].get_point_commitment(i);
+                vss_scheme_vec[1..].iter().fold(head, |acc, vss_scheme| {
+                    acc + vss_scheme.get_point_commitment(i)
+                })
+            })
+            .collect()
+    }
+
+    /// Checks that the proof of every party is a valid proof of knowledge of its x_j.
+    pub fn verify_dlog_proofs(
+        params: &Parameters,
+        dlog_proofs_vec: &[DLogProof<FE>],
+        vss_scheme_vec: &[VerifiableSS<FE>],
+    ) -> Result<()> {
+        assert_eq!(dlog_proofs_vec.len(), params.share_count);
+        let X_vec = Keys::get_commitments_to_xi(vss_scheme_vec);
+        let blames = (0..dlog_proofs_vec.len())
+            .filter(|&i| {
+                DLogProof::verify(&dlog_proofs_vec[i]).is_err() || dlog_proofs_vec[i].pk != X_vec[i]
+            })
+            .map(|i| Blame::new(i, XiDLogProof))
+            .collect::<Vec<Blame>>();
+
+        match blames.is_empty() {
+            true => Ok(()),
+            false => Err(TssError::Blame(blames)),
+        }
+    }
+}
+
+/// What a signer keeps during signing. The vectors are in signing order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignKeys {
+    /// our position in the signing order
+    pub position: usize,
+    w_i: FE,
+    d_i: FE,
+    e_i: FE,
+    pub g_w_vec: Vec<GE>,
+}
+
+/// Broadcast in round 1.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignBroadcastPhase1 {
+    pub D_i: GE,
+    pub E_i: GE,
+}
+
+/// Broadcast in round 2.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignBroadcastPhase2 {
+    pub z_i: FE,
+}
+
+impl SignKeys {
+    /// `index` is our 0-based party index and `s` holds those of the signers, in signing
+    /// order.
+    pub fn create(
+        shared_keys: &SharedKeys,
+        vss_scheme_vec: &[VerifiableSS<FE>],
+        index: usize,
+        s: &[usize],
+    ) -> SignKeys {
+        let position = s.iter().position(|&signer| signer == index).unwrap();
+        // only the parameters of the VSS scheme are used
+        let vss_scheme = &vss_scheme_vec[0];
+        let w_i = vss_scheme.map_share_to_new_params(index, s) * &shared_keys.x_i;
+        let X_vec = Keys::get_commitments_to_xi(vss_scheme_vec);
+        let g_w_vec = s
+            .iter()
+            .map(|&signer| X_vec[signer].clone() * vss_scheme.map_share_to_new_params(signer, s))
+            .collect::<Vec<GE>>();
+        SignKeys {
+            position,
+            w_i,
+            d_i: ECScalar::new_random(),
+            e_i: ECScalar::new_random(),
+            g_w_vec,
+        }
+    }
+
+    pub fn phase1_broadcast(&self) -> SignBroadcastPhase1 {
+        let g: GE = ECPoint::generator();
+        SignBroadcastPhase1 {
+            D_i: &g * &self.d_i,
+            E_i: &g * &self.e_i,
+        }
+    }
+
+    /// Our share z_i of the signature of `message` for the group key `y`.
+    pub fn phase2_sign(
+        &self,
+        message: &[u8],
+        y: &GE,
+        bc1_vec: &[SignBroadcastPhase1],
+    ) -> SignBroadcastPhase2 {
+        let rho_vec = binding_factors(message, y, bc1_vec);
+        let R = group_commitment(bc1_vec, &rho_vec);
+        let k = rfc8032::challenge(&R, y, message);
+        let r_i = self.d_i.clone() + rho_vec[self.position].clone() * &self.e_i;
+        SignBroadcastPhase2 {
+            z_i: r_i + k * &self.w_i,
+        }
+    }
+
+    /// Checks the share z_j of every signer against D_j, E_j and g^w_j, and returns the
+    /// signature. Blames refer to the positions in the signing order.
+    pub fn phase2_verify_output_signature(
+        &self,
+        message: &[u8],
+        y: &GE,
+        bc1_vec: &[SignBroadcastPhase1],
+        bc2_vec: &[SignBroadcastPhase2],
+    ) -> Result<Ed25519Signature> {
+        let g: GE = ECPoint::generator();
+        let rho_vec = binding_factors(message, y, bc1_vec);
+        let R = group_commitment(bc1_vec, &rho_vec);
+        let k = rfc8032::challenge(&R, y, message);
+        let blames = (0..bc2_vec.len())
+            .filter(|&j| {
+                let R_j = bc1_vec[j].D_i.clone() + &bc1_vec[j].E_i * &rho_vec[j];
+                &g * &bc2_vec[j].z_i != R_j + &self.g_w_vec[j] * &k
+            })
+            .map(|j| Blame::new(j, FrostSigShare))
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let head = bc2_vec[0].z_i.clone();
+        let s = bc2_vec[1..].iter().fold(head, |acc, bc2| acc + &bc2.z_i);
+        let signature = Ed25519Signature { R, s };
+        rfc8032::verify(&signature.to_bytes(), &y.to_bytes(true), message)
+            .map_err(|_| InvalidSig)?;
+        Ok(signature)
+    }
+}
+
+/// rho_j for every signer, binding its nonces to the message and to the commitments of all.
+fn binding_factors(message: &[u8], y: &GE, bc1_vec: &[SignBroadcastPhase1]) -> Vec<FE> {
+    let hash = bc1_vec
+        .iter()
+        .fold(
+            Sha512::new().chain(message).chain(y.to_bytes(true)),
+            |acc, bc1| {
+                acc.chain(bc1.D_i.to_bytes(true))
+                    .chain(bc1.E_i.to_bytes(true))
+            },
+        )
+        .finalize();
+    (0..bc1_vec.len())
+        .map(|j| {
+            let rho_j = Sha512::new()
+                .chain(hash)
+                .chain((j as u64).to_be_bytes())
+                .finalize();
+            ECScalar::from(&BigInt::from_bytes_le(&rho_j))
+        })
+        .collect()
+}
+
+/// R = D_1 E_1^rho_1 ... D_t E_t^rho_t
+fn group_commitment(bc1_vec: &[SignBroadcastPhase1], rho_vec: &[FE]) -> GE {
+    let head = bc1_vec[0].D_i.clone() + &bc1_vec[0].E_i * &rho_vec[0];
+    bc1_vec[1..]
+        .iter()
+        .zip(&rho_vec[1..])
+        .fold(head, |acc, (bc1, rho)| {
+            acc + bc1.D_i.clone() + &bc1.E_i * rho
+        })
+}
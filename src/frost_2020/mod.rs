@@ -0,0 +1,4 @@
+pub mod bip340;
+pub mod eddsa;
+pub mod party_i;
+pub mod rfc8032;
@@ -0,0 +1,177 @@
+#![allow(non_snake_case)]
+
+/*
+    FROST threshold Schnorr, https://eprint.iacr.org/2020/852.pdf, with BIP-340 signatures
+
+    Signing runs on the shares x_i and the group key y of a GG18 keygen (`SharedKeys`):
+
+    - round 1: each signer publishes the nonce commitments D_i = g^d_i and E_i = g^e_i.
+    - round 2: with the binding factors rho_j = H(j, Q, m, D_1, E_1, ...), each signer
+      computes R = D_1 E_1^rho_1 ... and publishes z_i = k_i + e w_i, for
+      k_i = d_i + rho_i e_i, the BIP-340 challenge e and w_i = lambda_i x_i.
+
+    BIP-340 takes R and the key Q with an even Y: the signers negate the k_i when R has an
+    odd Y, and multiply the w_i by the sign of the `OutputKey`. A Taproot tweak is added
+    once, to the sum of the z_i.
+
+    The nonces of a `SignKeys` sign a single message: a new signature needs new keys.
+*/
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::cryptographic_primitives::hashing::ext::DigestExt;
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::BlameItem::FrostSigShare;
+use crate::errors::TssError::{self, InvalidSig};
+use crate::errors::{Blame, Result};
+use crate::frost_2020::bip340::{self, even_y_sign, OutputKey, SchnorrSignature};
+use crate::gg_2018::party_i::{Keys, SharedKeys};
+use crate::sha2::Digest;
+
+use sha2::Sha256;
+
+/// What a signer keeps during signing. The vectors are in signing order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignKeys {
+    /// our position in the signing order
+    pub position: usize,
+    w_i: FE,
+    d_i: FE,
+    e_i: FE,
+    pub g_w_vec: Vec<GE>,
+}
+
+/// Broadcast in round 1.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignBroadcastPhase1 {
+    pub D_i: GE,
+    pub E_i: GE,
+}
+
+/// Broadcast in round 2.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignBroadcastPhase2 {
+    pub z_i: FE,
+}
+
+impl SignKeys {
+    /// `index` is our 0-based party index and `s` holds those of the signers, in signing
+    /// order.
+    pub fn create(
+        shared_keys: &SharedKeys,
+        vss_scheme_vec: &[VerifiableSS],
+        index: usize,
+        s: &Vec<usize>,
+    ) -> SignKeys {
+        let position = s.iter().position(|&signer| signer == index).unwrap();
+        // only the parameters of the VSS scheme are used
+        let vss_scheme = &vss_scheme_vec[0];
+        let w_i = vss_scheme.map_share_to_new_params(index, s) * &shared_keys.x_i;
+        let X_vec = Keys::get_commitments_to_xi(vss_scheme_vec);
+        let g_w_vec = s
+            .iter()
+            .map(|&signer| Keys::update_commitments_to_xi(&X_vec[signer], vss_scheme, signer, s))
+            .collect::<Vec<GE>>();
+        SignKeys {
+            position,
+            w_i,
+            d_i: ECScalar::new_random(),
+            e_i: ECScalar::new_random(),
+            g_w_vec,
+        }
+    }
+
+    pub fn phase1_broadcast(&self) -> SignBroadcastPhase1 {
+        let g: GE = ECPoint::generator();
+        SignBroadcastPhase1 {
+            D_i: &g * &self.d_i,
+            E_i: &g * &self.e_i,
+        }
+    }
+
+    /// Our share z_i of the signature of `message` for `key`.
+    pub fn phase2_sign(
+        &self,
+        message: &[u8],
+        key: &OutputKey,
+        bc1_vec: &[SignBroadcastPhase1],
+    ) -> SignBroadcastPhase2 {
+        let rho_vec = binding_factors(message, key, bc1_vec);
+        let R = group_commitment(bc1_vec, &rho_vec);
+        let e = bip340::challenge(&R, &key.Q, message);
+        let k_i = self.d_i.clone() + rho_vec[self.position].clone() * &self.e_i;
+        let k_i = k_i * &even_y_sign(&R);
+        SignBroadcastPhase2 {
+            z_i: k_i + e * key.sign() * &self.w_i,
+        }
+    }
+
+    /// Checks the share z_j of every signer against D_j, E_j and g^w_j, and returns the
+    /// signature. Blames refer to the positions in the signing order.
+    pub fn phase2_verify_output_signature(
+        &self,
+        message: &[u8],
+        key: &OutputKey,
+        bc1_vec: &[SignBroadcastPhase1],
+        bc2_vec: &[SignBroadcastPhase2],
+    ) -> Result<SchnorrSignature> {
+        let g: GE = ECPoint::generator();
+        let rho_vec = binding_factors(message, key, bc1_vec);
+        let R = group_commitment(bc1_vec, &rho_vec);
+        let sign_R = even_y_sign(&R);
+        let e = bip340::challenge(&R, &key.Q, message);
+        let e_sign = e.clone() * key.sign();
+        let blames = (0..bc2_vec.len())
+            .filter(|&j| {
+                let R_j = (bc1_vec[j].D_i.clone() + &bc1_vec[j].E_i * &rho_vec[j]) * &sign_R;
+                &g * &bc2_vec[j].z_i != R_j + &self.g_w_vec[j] * &e_sign
+            })
+            .map(|j| Blame::new(j, FrostSigShare))
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let head = bc2_vec[0].z_i.clone();
+        let s = bc2_vec[1..].iter().fold(head, |acc, bc2| acc + &bc2.z_i);
+        let s = match key.tweak_term(&e) {
+            Some(tweak_term) => s + tweak_term,
+            None => s,
+        };
+        let signature = SchnorrSignature { R: &R * &sign_R, s };
+        bip340::verify(&signature.to_bytes(), &key.x_only(), message).map_err(|_| InvalidSig)?;
+        Ok(signature)
+    }
+}
+
+/// rho_j for every signer, binding its nonces to the message and to the commitments of all.
+fn binding_factors(message: &[u8], key: &OutputKey, bc1_vec: &[SignBroadcastPhase1]) -> Vec<FE> {
+    let hash = bc1_vec
+        .iter()
+        .fold(
+            Sha256::new().chain(message).chain_point(&key.Q),
+            |acc, bc1| acc.chain_point(&bc1.D_i).chain_point(&bc1.E_i),
+        )
+        .result_bigint();
+    (0..bc1_vec.len())
+        .map(|j| {
+            let rho_j = Sha256::new()
+                .chain_bigint(&hash)
+                .chain_bigint(&BigInt::from(j as u64))
+                .result_bigint();
+            ECScalar::from(&rho_j)
+        })
+        .collect()
+}
+
+/// R = D_1 E_1^rho_1 ... D_t E_t^rho_t
+fn group_commitment(bc1_vec: &[SignBroadcastPhase1], rho_vec: &[FE]) -> GE {
+    let head = bc1_vec[0].D_i.clone() + &bc1_vec[0].E_i * &rho_vec[0];
+    bc1_vec[1..]
+        .iter()
+        .zip(&rho_vec[1..])
+        .fold(head, |acc, (bc1, rho)| {
+            acc + bc1.D_i.clone() + &bc1.E_i * rho
+        })
+}
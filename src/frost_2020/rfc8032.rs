@@ -0,0 +1,119 @@
+#![allow(non_snake_case)]
+
+/*
+    Ed25519 signatures of RFC 8032, https://tools.ietf.org/html/rfc8032#section-5.1
+
+    A threshold key has no private key to hash into the secret scalar as in section 5.1.5:
+    signatures are made with the scalar directly and verify the same way.
+*/
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::elliptic::curves::ed25519::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::Result;
+use crate::errors::TssError::{InvalidPublicKey, InvalidSig};
+
+use sha2::{Digest, Sha512};
+
+/// A signature (R, s), verified as `g^s = R A^k` for the key A.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Ed25519Signature {
+    pub R: GE,
+    pub s: FE,
+}
+
+impl Ed25519Signature {
+    /// The encoding of R || s in little-endian.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.R.to_bytes(true));
+        bytes[32..].copy_from_slice(&to_bytes32_le(&self.s.to_big_int()));
+        bytes
+    }
+}
+
+/// k = SHA-512(R || A || M) mod l
+pub fn challenge(R: &GE, A: &GE, message: &[u8]) -> FE {
+    let hash = Sha512::new()
+        .chain(R.to_bytes(true))
+        .chain(A.to_bytes(true))
+        .chain(message)
+        .finalize();
+    ECScalar::from(&BigInt::from_bytes_le(&hash))
+}
+
+/// Checks a 64-byte signature of `message` for the 32-byte key `public_key`, as in section
+/// 5.1.7 without the cofactor.
+pub fn verify(signature: &[u8], public_key: &[u8], message: &[u8]) -> Result<()> {
+    if signature.len() != 64 {
+        return Err(InvalidSig);
+    }
+    let A = GE::from_bytes(public_key).map_err(|_| InvalidPublicKey)?;
+    let R = GE::from_bytes(&signature[..32]).map_err(|_| InvalidSig)?;
+    let s = BigInt::from_bytes_le(&signature[32..]);
+    if &s >= FE::group_order() {
+        return Err(InvalidSig);
+    }
+    let s: FE = ECScalar::from(&s);
+
+    let k = challenge(&R, &A, message);
+    let g: GE = ECPoint::generator();
+    match &g * &s == R + &A * &k {
+        true => Ok(()),
+        false => Err(InvalidSig),
+    }
+}
+
+fn to_bytes32_le(n: &BigInt) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    let n_bytes = n.to_bytes_le();
+    bytes[..n_bytes.len()].copy_from_slice(&n_bytes);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_verify_rfc8032_vectors() {
+        // TEST 1 and TEST 2 of RFC 8032, section 7.1
+        let vectors = [
+            (
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "72",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+        ];
+        for (public_key, message, signature) in vectors.iter() {
+            let public_key = hex::decode(public_key).unwrap();
+            let message = hex::decode(message).unwrap();
+            let signature = hex::decode(signature).unwrap();
+            assert!(verify(&signature, &public_key, &message).is_ok());
+            assert!(verify(&signature, &public_key, b"another message").is_err());
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_sign_verify() {
+        let a: FE = ECScalar::new_random();
+        let r: FE = ECScalar::new_random();
+        let g: GE = ECPoint::generator();
+        let A = &g * &a;
+        let R = &g * &r;
+        let message = b"message";
+        let s = r + challenge(&R, &A, message) * &a;
+        let signature = Ed25519Signature { R, s };
+        assert!(verify(&signature.to_bytes(), &A.to_bytes(true), message).is_ok());
+    }
+}
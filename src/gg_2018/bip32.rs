@@ -0,0 +1,163 @@
+//! Non-hardened BIP-32 derivation on a GG18 key,
+//! https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+//!
+//! Child public keys only need y and the chain code. A child key is y + g^t for the sum t of
+//! the tweaks along the path, so the parties sign for it with their shares shifted by t.
+//! Hardened derivation hashes the secret key, which no party knows, and is not supported.
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::cryptographic_primitives::hashing::ext::DigestExt;
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::{Result, TssError};
+use crate::gg_2018::party_i::{KeyGenDecommitMessage1, SharedKeys};
+use crate::sha2::Digest;
+
+use hmac::{Hmac, Mac, NewMac};
+use num_traits::Zero;
+use sha2::{Sha256, Sha512};
+
+pub type ChainCode = [u8; 32];
+
+// indexes from 2^31 on are hardened
+const HARDENED_INDEX: u32 = 1 << 31;
+
+/// The chain code of a keygen, from the blind factors of the round 2 decommitments. Each
+/// was committed to in round 1, before any other was known, so no party can choose it.
+pub fn chain_code(decom_vec: &[KeyGenDecommitMessage1]) -> ChainCode {
+    let mut hasher = Sha256::new();
+    for decom in decom_vec {
+        hasher.input_bigint(&decom.blind_factor);
+    }
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&hasher.finalize());
+    chain_code
+}
+
+/// Parses a path such as `m/0/1`. The leading `m` is optional.
+pub fn parse_path(path: &str) -> Result<Vec<u32>> {
+    let invalid = || TssError::InvalidDerivationPath(path.to_string());
+    let mut indexes = path.split('/').peekable();
+    if indexes.peek() == Some(&"m") {
+        indexes.next();
+    }
+    indexes
+        .filter(|index| !index.is_empty())
+        .map(|index| match index.parse::<u32>() {
+            Ok(index) if index < HARDENED_INDEX => Ok(index),
+            _ => Err(invalid()),
+        })
+        .collect()
+}
+
+/// CKDpub: the tweak, the public key and the chain code of child `index` of `(y, chain_code)`.
+pub fn derive_child(y: &GE, chain_code: &ChainCode, index: u32) -> Result<(FE, GE, ChainCode)> {
+    if index >= HARDENED_INDEX {
+        return Err(TssError::InvalidDerivationPath(index.to_string()));
+    }
+    let mut mac = Hmac::<Sha512>::new_from_slice(chain_code).expect("HMAC takes any key length");
+    mac.update(&y.to_bytes(true));
+    mac.update(&index.to_be_bytes());
+    let i = mac.finalize().into_bytes();
+
+    let il = BigInt::from_bytes_be(&i[..32]);
+    // such an index has no child, with a probability below 2^-127. A zero tweak is as
+    // unlikely, and left out as g^0 has no secp256k1 encoding.
+    if il.is_zero() || &il >= FE::group_order() {
+        return Err(TssError::InvalidKey);
+    }
+    let tweak: FE = ECScalar::from(&il);
+    let child = GE::generator() * &tweak + y;
+    let mut child_chain_code = [0u8; 32];
+    child_chain_code.copy_from_slice(&i[32..]);
+    Ok((tweak, child, child_chain_code))
+}
+
+/// The sum of the tweaks along `path`, and the public key and chain code it leads to.
+pub fn derive(y: &GE, chain_code: &ChainCode, path: &[u32]) -> Result<(FE, GE, ChainCode)> {
+    path.iter().try_fold(
+        (FE::zero(), y.clone(), *chain_code),
+        |(tweak, y, chain_code), &index| {
+            let (child_tweak, child, child_chain_code) = derive_child(&y, &chain_code, index)?;
+            Ok((tweak + child_tweak, child, child_chain_code))
+        },
+    )
+}
+
+/// The shared keys and VSS schemes of the child key y + g^tweak. The Lagrange coefficients
+/// of any t + 1 signers add up to one, so shifting every x_i by the tweak shifts x by it.
+/// The free term of the first scheme moves along, for the g^x_i to still match the x_i.
+pub fn tweak_key(
+    shared_keys: &SharedKeys,
+    vss_scheme_vec: &[VerifiableSS],
+    tweak: &FE,
+) -> (SharedKeys, Vec<VerifiableSS>) {
+    // the tweak of the empty path
+    if tweak.to_big_int().is_zero() {
+        return (shared_keys.clone(), vss_scheme_vec.to_vec());
+    }
+    let g_tweak = GE::generator() * tweak;
+    let shared_keys = SharedKeys {
+        y: shared_keys.y.clone() + &g_tweak,
+        x_i: shared_keys.x_i.clone() + tweak,
+    };
+    let mut vss_scheme_vec = vss_scheme_vec.to_vec();
+    vss_scheme_vec[0].commitments[0] = vss_scheme_vec[0].commitments[0].clone() + &g_tweak;
+    (shared_keys, vss_scheme_vec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn extended_key(chain_code: &str, y: &str) -> (ChainCode, GE) {
+        let mut c = [0u8; 32];
+        c.copy_from_slice(&hex::decode(chain_code).unwrap());
+        (c, GE::from_bytes(&hex::decode(y).unwrap()).unwrap())
+    }
+
+    // test vector 1 of BIP-32: m/0H -> m/0H/1 and m/0H/1/2H -> m/0H/1/2H/2
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_derive_bip32_vectors() {
+        let vectors = [
+            (
+                "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                "035a784662a4a20a65bf6aab9ae98a6c068a81c52e4b032c0fb5400c706cfccc56",
+                1,
+                "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                "03501e454bf00751f24b1b489aa925215d66af2234e3891c3b21a52bedb3cd711c",
+            ),
+            (
+                "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+                "0357bfe1e341d01c69fe5654309956cbea516822fba8a601743a012a7896ee8dc2",
+                2,
+                "cfb71883f01676f587d023cc53a35bc7f88f724b1f8c2892ac1275ac822a3edd",
+                "02e8445082a72f29b75ca48748a914df60622a609cacfce8ed0e35804560741d29",
+            ),
+        ];
+        for (chain_code, y, index, child_chain_code, child) in vectors.iter() {
+            let (chain_code, y) = extended_key(chain_code, y);
+            let (tweak, derived, derived_chain_code) = derive(&y, &chain_code, &[*index]).unwrap();
+            assert_eq!(
+                (derived_chain_code, derived.clone()),
+                extended_key(child_chain_code, child)
+            );
+            assert_eq!(derived, GE::generator() * &tweak + &y);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(parse_path("m/0/1").unwrap(), vec![0, 1]);
+        assert_eq!(parse_path("0/2147483647").unwrap(), vec![0, 2147483647]);
+        assert!(parse_path("m").unwrap().is_empty());
+        assert!(parse_path("m/0'/1").is_err());
+        assert!(parse_path("m/2147483648").is_err());
+    }
+}
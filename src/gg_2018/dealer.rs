@@ -0,0 +1,114 @@
+/*
+    Trusted-dealer key import
+
+    A dealer that knows the secret key x, e.g. an existing single-signature key,
+    shares it with Feldman VSS among n parties and sets up the Paillier keys and
+    h1, h2, N_tilde of every party. It publishes the VSS commitments and, for
+    every party, the keygen messages of rounds 1 and 2 with their proofs. Each
+    party then runs the checks of keygen on them and checks its share x_i
+    against the commitments, so that a key store it accepts works with the
+    signing protocol like one out of keygen. The dealer knows x and all the
+    Paillier keys, and must forget them once the key stores are handed out.
+*/
+
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::curv::elliptic::curves::secp256_k1::FE;
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::BlameItem::{VssCommitment, VssShare};
+use crate::errors::TssError::{self, InvalidKey, InvalidMessage};
+use crate::gg_2018::party_i::{
+    KeyGenBroadcastMessage1, KeyGenDecommitMessage1, Keys, Parameters, SharedKeys,
+};
+
+/// Published by the dealer, and checked by every party.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DealerBroadcastMessage<S: CurveScalar = FE> {
+    pub vss_scheme: VerifiableSS<S>,
+    /// Round 1 of keygen for every party: its Paillier key and h1, h2, N_tilde with proofs
+    pub bc1_vec: Vec<KeyGenBroadcastMessage1>,
+    /// Round 2 of keygen for every party, with the no-small-factor proofs
+    pub decom_vec: Vec<KeyGenDecommitMessage1<S>>,
+}
+
+/// Shares `secret` among `params.share_count` parties. Returns the broadcast message, and
+/// the keys and share x_i of each party, to be sent to it over a private channel.
+pub fn deal<S>(params: &Parameters, secret: &S) -> (DealerBroadcastMessage<S>, Vec<(Keys<S>, S)>)
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    let party_keys_vec = (1..=params.share_count)
+        .map(Keys::create)
+        .collect::<Vec<Keys<S>>>();
+    let (bc1_vec, mut decom_vec): (Vec<_>, Vec<_>) = party_keys_vec
+        .iter()
+        .map(|keys| keys.phase1_broadcast_phase3_proof_of_correct_key())
+        .unzip();
+    for (keys, decom) in party_keys_vec.iter().zip(decom_vec.iter_mut()) {
+        decom.no_small_factor_proofs = keys.phase1_no_small_factor_proofs(&bc1_vec);
+    }
+
+    let (vss_scheme, secret_shares) =
+        VerifiableSS::share(params.threshold, params.share_count, secret);
+    let dealer_msg = DealerBroadcastMessage {
+        vss_scheme,
+        bc1_vec,
+        decom_vec,
+    };
+    (
+        dealer_msg,
+        party_keys_vec.into_iter().zip(secret_shares).collect(),
+    )
+}
+
+/// Checks, as party number `party_keys.party_index`, the broadcast message and the share x_i
+/// received from the dealer, and makes the shared keys. Blames refer to the parties whose
+/// keygen messages do not check. Keys the others do not take for ours fail with
+/// `InvalidKey`, and a share that does not match the commitments with `InvalidMessage`.
+pub fn verify_construct_keypair<S>(
+    params: &Parameters,
+    dealer_msg: &DealerBroadcastMessage<S>,
+    party_keys: &Keys<S>,
+    x_i: &S,
+) -> Result<SharedKeys<S>, TssError>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    let vss_scheme = &dealer_msg.vss_scheme;
+    if dealer_msg.bc1_vec.len() != params.share_count
+        || dealer_msg.decom_vec.len() != params.share_count
+        || vss_scheme.parameters.threshold != params.threshold
+        || vss_scheme.parameters.share_count != params.share_count
+        || vss_scheme.commitments.len() != params.threshold + 1
+    {
+        return Err(InvalidMessage(VssCommitment));
+    }
+    party_keys.phase1_verify_com_phase3_verify_correct_key(
+        params,
+        &dealer_msg.decom_vec,
+        &dealer_msg.bc1_vec,
+    )?;
+    // the keys of the party must be the ones the others take for it
+    let bc1_i = party_keys
+        .party_index
+        .checked_sub(1)
+        .and_then(|i| dealer_msg.bc1_vec.get(i))
+        .ok_or(InvalidKey)?;
+    if bc1_i.e != party_keys.ek
+        || bc1_i.dlog_statement.N != party_keys.N_tilde
+        || bc1_i.dlog_statement.g != party_keys.h1
+        || bc1_i.dlog_statement.ni != party_keys.h2
+    {
+        return Err(InvalidKey);
+    }
+    if vss_scheme
+        .validate_share(x_i, party_keys.party_index)
+        .is_err()
+    {
+        return Err(InvalidMessage(VssShare));
+    }
+
+    Ok(SharedKeys {
+        y: vss_scheme.commitments[0].clone(),
+        x_i: x_i.clone(),
+    })
+}
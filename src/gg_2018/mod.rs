@@ -14,6 +14,10 @@
     @license GPL-3.0+ <https://github.com/KZen-networks/multi-party-ecdsa/blob/master/LICENSE>
 */
 
+pub mod bip32;
+pub mod dealer;
 pub mod mta;
 pub mod party_i;
+pub mod presign_store;
 pub mod range_proofs;
+pub mod state_machine;
@@ -16,19 +16,19 @@
 use crate::curv::arithmetic::num_bigint::BigInt;
 use crate::curv::arithmetic::traits::Samplable;
 use crate::curv::cryptographic_primitives::proofs::sigma_dlog::{DLogProof, ProveDLog};
-use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::secp256_k1::FE;
 use crate::curv::elliptic::curves::traits::*;
 use crate::paillier::{Add, Decrypt, Mul};
 use crate::paillier::{DecryptionKey, EncryptionKey, Paillier, RawCiphertext, RawPlaintext};
 
-use crate::errors::TssError::{self, InvalidKey};
+use crate::errors::BlameItem::{MtaDLogProof, MtaRangeProof};
+use crate::errors::TssError::{self, InvalidKey, InvalidMessage};
 use crate::gg_2018::party_i::PartyPrivate;
 
-use crate::gg_2018::range_proofs::AliceProof;
+use crate::gg_2018::range_proofs::{AliceProof, BobCheck, BobProof, BobProofExt};
 use crate::paillier::zkproofs::DLogStatement;
 use crate::paillier::Randomness;
 
-use crate::curv::elliptic::curves::secp256_k1::Secp256k1Scalar;
 use crate::paillier::traits::EncryptWithChosenRandomness;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -38,29 +38,37 @@ pub struct MessageA {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct MessageB {
+pub struct MessageB<S: CurveScalar = FE> {
     pub c: BigInt, // paillier encryption
-    pub b_proof: DLogProof,
-    pub beta_tag_proof: DLogProof,
+    pub b_proof: DLogProof<S>,
+    pub beta_tag_proof: DLogProof<S>,
+    pub range_proof: Option<BobProof>, // proof (using Alice's h1,h2,N_tilde) that b and beta_tag are small
+    pub range_proof_ext: Option<BobProofExt<S>>, // same, for MtAwc: also binds b to b_proof.pk
 }
 
 impl MessageA {
-    pub fn a(
-        a: &Secp256k1Scalar,
+    pub fn a<S>(
+        a: &S,
         alice_ek: &EncryptionKey,
         dlog_statements: &[DLogStatement],
-    ) -> (Self, BigInt) {
+    ) -> (Self, BigInt)
+    where
+        S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+    {
         let randomness = BigInt::sample_below(&alice_ek.n);
         let m_a = MessageA::a_with_predefined_randomness(a, alice_ek, &randomness, dlog_statements);
         (m_a, randomness)
     }
 
-    pub fn a_with_predefined_randomness(
-        a: &Secp256k1Scalar,
+    pub fn a_with_predefined_randomness<S>(
+        a: &S,
         alice_ek: &EncryptionKey,
         randomness: &BigInt,
         dlog_statements: &[DLogStatement],
-    ) -> Self {
+    ) -> Self
+    where
+        S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+    {
         let c_a = Paillier::encrypt_with_chosen_randomness(
             alice_ek,
             RawPlaintext::from(a.to_big_int()),
@@ -72,7 +80,13 @@ impl MessageA {
         let alice_range_proofs = dlog_statements
             .iter()
             .map(|dlog_statement| {
-                AliceProof::generate(&a.to_big_int(), &c_a, alice_ek, dlog_statement, randomness)
+                AliceProof::generate::<S>(
+                    &a.to_big_int(),
+                    &c_a,
+                    alice_ek,
+                    dlog_statement,
+                    randomness,
+                )
             })
             .collect::<Vec<AliceProof>>();
 
@@ -83,13 +97,19 @@ impl MessageA {
     }
 }
 
-impl MessageB {
+impl<S> MessageB<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    /// MtA response. With `alice_dlog_statement` Bob also proves, against Alice's h1,h2,N_tilde,
+    /// that his affine operation stayed in range.
     pub fn b(
-        b: &Secp256k1Scalar,
+        b: &S,
         alice_ek: &EncryptionKey,
         m_a: MessageA,
         dlog_statements: &[DLogStatement],
-    ) -> Result<(Self, Secp256k1Scalar, BigInt, BigInt), TssError> {
+        alice_dlog_statement: Option<&DLogStatement>,
+    ) -> Result<(Self, S, BigInt, BigInt), TssError> {
         let beta_tag = BigInt::sample_below(&alice_ek.n);
         let randomness = BigInt::sample_below(&alice_ek.n);
         let (m_b, beta) = MessageB::b_with_predefined_randomness(
@@ -99,19 +119,50 @@ impl MessageB {
             &randomness,
             &beta_tag,
             dlog_statements,
+            alice_dlog_statement,
+            false,
         )?;
 
         Ok((m_b, beta, randomness, beta_tag))
     }
 
+    /// MtAwc response: like `b`, but the range proof also shows that the plaintext b is the
+    /// discrete log of `b_proof.pk`.
+    pub fn b_with_check(
+        b: &S,
+        alice_ek: &EncryptionKey,
+        m_a: MessageA,
+        dlog_statements: &[DLogStatement],
+        alice_dlog_statement: &DLogStatement,
+    ) -> Result<(Self, S, BigInt, BigInt), TssError> {
+        let beta_tag = BigInt::sample_below(&alice_ek.n);
+        let randomness = BigInt::sample_below(&alice_ek.n);
+        let (m_b, beta) = MessageB::b_with_predefined_randomness(
+            b,
+            alice_ek,
+            m_a,
+            &randomness,
+            &beta_tag,
+            dlog_statements,
+            Some(alice_dlog_statement),
+            true,
+        )?;
+
+        Ok((m_b, beta, randomness, beta_tag))
+    }
+
+    /// `check` selects the MtAwc range proof; it has no effect without `alice_dlog_statement`.
+    #[allow(clippy::too_many_arguments)]
     pub fn b_with_predefined_randomness(
-        b: &Secp256k1Scalar,
+        b: &S,
         alice_ek: &EncryptionKey,
         m_a: MessageA,
         randomness: &BigInt,
         beta_tag: &BigInt,
         dlog_statements: &[DLogStatement],
-    ) -> Result<(Self, Secp256k1Scalar), TssError> {
+        alice_dlog_statement: Option<&DLogStatement>,
+        check: bool,
+    ) -> Result<(Self, S), TssError> {
         if m_a.range_proofs.len() != dlog_statements.len() {
             return Err(InvalidKey);
         }
@@ -120,12 +171,12 @@ impl MessageB {
             .range_proofs
             .iter()
             .zip(dlog_statements)
-            .map(|(proof, dlog_statement)| proof.verify(&m_a.c, alice_ek, dlog_statement))
+            .map(|(proof, dlog_statement)| proof.verify::<S>(&m_a.c, alice_ek, dlog_statement))
             .all(|x| x)
         {
             return Err(InvalidKey);
         };
-        let beta_tag_fe: Secp256k1Scalar = ECScalar::from(beta_tag);
+        let beta_tag_fe: S = ECScalar::from(beta_tag);
         let c_beta_tag = Paillier::encrypt_with_chosen_randomness(
             alice_ek,
             RawPlaintext::from(beta_tag),
@@ -135,34 +186,90 @@ impl MessageB {
         let b_bn = b.to_big_int();
         let b_c_a = Paillier::mul(
             alice_ek,
-            RawCiphertext::from(m_a.c),
+            RawCiphertext::from(m_a.c.clone()),
             RawPlaintext::from(b_bn),
         );
         let c_b = Paillier::add(alice_ek, b_c_a, c_beta_tag);
-        let beta = FE::zero().sub(&beta_tag_fe.get_element());
+        let c_b = c_b.0.clone().into_owned();
+        let beta = S::zero().sub(&beta_tag_fe.get_element());
         let dlog_proof_b = DLogProof::prove(b);
         let dlog_proof_beta_tag = DLogProof::prove(&beta_tag_fe);
 
+        let (range_proof, range_proof_ext) = match (alice_dlog_statement, check) {
+            (Some(dlog_statement), false) => {
+                let (proof, _) = BobProof::generate(
+                    &m_a.c,
+                    &c_b,
+                    b,
+                    beta_tag,
+                    alice_ek,
+                    dlog_statement,
+                    randomness,
+                    false,
+                );
+                (Some(proof), None)
+            }
+            (Some(dlog_statement), true) => {
+                let proof = BobProofExt::generate(
+                    &m_a.c,
+                    &c_b,
+                    b,
+                    beta_tag,
+                    alice_ek,
+                    dlog_statement,
+                    randomness,
+                );
+                (None, Some(proof))
+            }
+            (None, _) => (None, None),
+        };
+
         Ok((
             Self {
-                c: c_b.0.clone().into_owned(),
+                c: c_b,
                 b_proof: dlog_proof_b,
                 beta_tag_proof: dlog_proof_beta_tag,
+                range_proof,
+                range_proof_ext,
             },
             beta,
         ))
     }
 
+    /// Verifies Bob's range proof for Alice's ciphertext `a_enc`, made against her h1,h2,N_tilde.
+    /// With `check` the MtAwc proof is required, which also binds b to `b_proof.pk`.
+    pub fn verify_range_proof(
+        &self,
+        a_enc: &BigInt,
+        alice_ek: &EncryptionKey,
+        dlog_statement: &DLogStatement,
+        check: bool,
+    ) -> bool {
+        match (check, &self.range_proof, &self.range_proof_ext) {
+            (false, Some(proof), _) => proof.verify(
+                a_enc,
+                &self.c,
+                alice_ek,
+                dlog_statement,
+                None::<&BobCheck<S>>,
+            ),
+            (true, _, Some(proof)) => {
+                proof.verify(a_enc, &self.c, alice_ek, dlog_statement, &self.b_proof.pk)
+            }
+            _ => false,
+        }
+    }
+
     pub fn verify_proofs_get_alpha(
         &self,
         dk: &DecryptionKey,
-        a: &Secp256k1Scalar,
-    ) -> Result<(Secp256k1Scalar, BigInt), TssError> {
+        a: &S,
+    ) -> Result<(S, BigInt), TssError> {
         let alice_share = Paillier::decrypt(dk, &RawCiphertext::from(self.c.clone()));
-        let g: GE = ECPoint::generator();
-        let alpha: FE = ECScalar::from(&alice_share.0);
+        let g = S::Point::generator();
+        let alpha: S = ECScalar::from(&alice_share.0);
         let g_alpha = g * &alpha;
-        let ba_btag = &self.b_proof.pk * a + &self.beta_tag_proof.pk;
+        let ba_btag = self.b_proof.pk.clone() * a + &self.beta_tag_proof.pk;
         match DLogProof::verify(&self.b_proof).is_ok()
             && DLogProof::verify(&self.beta_tag_proof).is_ok()
             && ba_btag == g_alpha
@@ -174,27 +281,35 @@ impl MessageB {
 
     //  another version, supportion PartyPrivate therefore binding mta to gg18.
     //  with the regular version mta can be used in general
+    //  this one also requires Bob's range proof; `check` is set for the MtAwc on w_i
     pub fn verify_proofs_get_alpha_gg18(
         &self,
-        private: &PartyPrivate,
-        a: &FE,
-    ) -> Result<FE, TssError> {
+        private: &PartyPrivate<S>,
+        a: &S,
+        a_enc: &BigInt,
+        alice_ek: &EncryptionKey,
+        dlog_statement: &DLogStatement,
+        check: bool,
+    ) -> Result<S, TssError> {
+        if !self.verify_range_proof(a_enc, alice_ek, dlog_statement, check) {
+            return Err(InvalidMessage(MtaRangeProof));
+        }
         let alice_share = private.decrypt(self.c.clone());
-        let g: GE = ECPoint::generator();
-        let alpha: FE = ECScalar::from(&alice_share.0);
+        let g = S::Point::generator();
+        let alpha: S = ECScalar::from(&alice_share.0);
         let g_alpha = g * &alpha;
-        let ba_btag = &self.b_proof.pk * a + &self.beta_tag_proof.pk;
+        let ba_btag = self.b_proof.pk.clone() * a + &self.beta_tag_proof.pk;
 
         match DLogProof::verify(&self.b_proof).is_ok()
             && DLogProof::verify(&self.beta_tag_proof).is_ok()
-            && ba_btag.get_element() == g_alpha.get_element()
+            && ba_btag == g_alpha
         {
             true => Ok(alpha),
-            false => Err(InvalidKey),
+            false => Err(InvalidMessage(MtaDLogProof)),
         }
     }
 
-    pub fn verify_b_against_public(public_gb: &GE, mta_gb: &GE) -> bool {
-        public_gb.get_element() == mta_gb.get_element()
+    pub fn verify_b_against_public(public_gb: &S::Point, mta_gb: &S::Point) -> bool {
+        public_gb == mta_gb
     }
 }
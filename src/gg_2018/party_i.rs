@@ -16,8 +16,18 @@
     @license GPL-3.0+ <https://github.com/KZen-networks/multi-party-ecdsa/blob/master/LICENSE>
 */
 
-use crate::errors::TssError::{self, InvalidCom, InvalidKey, InvalidSS, InvalidSig};
-use crate::paillier::zkproofs::NICorrectKeyProof;
+use crate::errors::Blame;
+use crate::errors::BlameItem::{
+    self, CorrectKeyProof, GammaDecommitment, GammaMtaMismatch, HomoElGamalProof,
+    KeyGenDecommitment, OnlineSigShare, Phase5ADecommitment, Phase5DDecommitment, RhoDLogProof,
+    RingPedersenProof, VssCommitment, VssShare, XiDLogProof,
+};
+use crate::errors::TssError::{self, InvalidKey, InvalidSig};
+use crate::gg_2018::range_proofs::SampleFromMultiplicativeGroup;
+use crate::paillier::zkproofs::{
+    CompositeDLogProof, DLogStatement, NICorrectKeyProof, NoSmallFactorProof,
+    PaillierBlumModulusProof,
+};
 use crate::paillier::KeyGeneration;
 use crate::paillier::Paillier;
 use crate::paillier::{DecryptionKey, EncryptionKey};
@@ -33,40 +43,70 @@ use crate::curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use crate::curv::cryptographic_primitives::hashing::traits::Hash;
 use crate::curv::cryptographic_primitives::proofs::sigma_correct_homomorphic_elgamal_enc::*;
 use crate::curv::cryptographic_primitives::proofs::sigma_dlog::{DLogProof, ProveDLog};
-use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
-use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::{
+    ShamirSecretSharing, VerifiableSS,
+};
+use crate::curv::elliptic::curves::secp256_k1::FE;
 use crate::paillier::{Decrypt, RawCiphertext, RawPlaintext};
 use num_integer::Integer;
+use num_traits::One;
 
 const SECURITY: usize = 256;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct Keys {
-    pub u_i: FE,
-    pub y_i: GE,
+pub struct Keys<S: CurveScalar = FE> {
+    pub u_i: S,
+    pub y_i: S::Point,
     pub dk: DecryptionKey,
     pub ek: EncryptionKey,
     pub party_index: usize,
+    pub N_tilde: BigInt,
+    pub h1: BigInt,
+    pub h2: BigInt,
+    pub xhi: BigInt,
+    pub xhi_inv: BigInt,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct PartyPrivate {
-    u_i: FE,
-    x_i: FE,
+pub struct PartyPrivate<S: CurveScalar = FE> {
+    u_i: S,
+    x_i: S,
     dk: DecryptionKey,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct KeyGenBroadcastMessage1 {
     pub e: EncryptionKey,
+    pub dlog_statement: DLogStatement,
     pub com: BigInt,
     pub correct_key_proof: NICorrectKeyProof,
+    pub paillier_blum_modulus_proof: PaillierBlumModulusProof,
+    pub composite_dlog_proof_base_h1: CompositeDLogProof,
+    pub composite_dlog_proof_base_h2: CompositeDLogProof,
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct KeyGenDecommitMessage1 {
+pub struct KeyGenDecommitMessage1<S: CurveScalar = FE> {
     pub blind_factor: BigInt,
-    pub y_i: GE,
+    pub y_i: S::Point,
+    /// One proof per party, made against that party's ring-Pedersen parameters.
+    pub no_small_factor_proofs: Vec<NoSmallFactorProof>,
+}
+
+/// Complaint of the party holding share `index` against the dealer at position `dealer`,
+/// whose share did not match its VSS commitments.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct KeyGenComplaint {
+    pub dealer: usize,
+    pub index: usize,
+}
+
+/// Share `index` of the dealer at position `dealer`, published in answer to a complaint.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct KeyGenShareReveal<S: CurveScalar = FE> {
+    pub dealer: usize,
+    pub index: usize,
+    pub share: S,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -76,18 +116,18 @@ pub struct Parameters {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct SharedKeys {
-    pub y: GE,
-    pub x_i: FE,
+pub struct SharedKeys<S: CurveScalar = FE> {
+    pub y: S::Point,
+    pub x_i: S,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-pub struct SignKeys {
-    pub w_i: FE,
-    pub g_w_i: GE,
-    pub k_i: FE,
-    pub gamma_i: FE,
-    pub g_gamma_i: GE,
+pub struct SignKeys<S: CurveScalar = FE> {
+    pub w_i: S,
+    pub g_w_i: S::Point,
+    pub k_i: S,
+    pub gamma_i: S,
+    pub g_gamma_i: S::Point,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -96,19 +136,19 @@ pub struct SignBroadcastPhase1 {
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-pub struct SignDecommitPhase1 {
+pub struct SignDecommitPhase1<S: CurveScalar = FE> {
     pub blind_factor: BigInt,
-    pub g_gamma_i: GE,
+    pub g_gamma_i: S::Point,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
-pub struct LocalSignature {
-    pub l_i: FE,
-    pub rho_i: FE,
-    pub R: GE,
-    pub s_i: FE,
+pub struct LocalSignature<S: CurveScalar = FE> {
+    pub l_i: S,
+    pub rho_i: S,
+    pub R: S::Point,
+    pub s_i: S,
     pub m: BigInt,
-    pub y: GE,
+    pub y: S::Point,
 }
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Phase5Com1 {
@@ -121,45 +161,93 @@ pub struct Phase5Com2 {
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct Phase5ADecom1 {
-    pub V_i: GE,
-    pub A_i: GE,
-    pub B_i: GE,
+pub struct Phase5ADecom1<S: CurveScalar = FE> {
+    pub V_i: S::Point,
+    pub A_i: S::Point,
+    pub B_i: S::Point,
     pub blind_factor: BigInt,
 }
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct Phase5DDecom2 {
-    pub u_i: GE,
-    pub t_i: GE,
+pub struct Phase5DDecom2<S: CurveScalar = FE> {
+    pub u_i: S::Point,
+    pub t_i: S::Point,
     pub blind_factor: BigInt,
 }
 
+/// Broadcast by a signer at the end of presigning.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-pub struct Signature {
-    pub r: FE,
-    pub s: FE,
+pub struct PresignBroadcastMessage<S: CurveScalar = FE> {
+    pub R_i: S::Point,
+    pub S_i: S::Point,
+}
+
+/// What a signer keeps from presigning to sign a single message later.
+/// It is signed with through a `PresignatureStore` only.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Presignature<S: CurveScalar = FE> {
+    pub R: S::Point,
+    k_i: S,
+    sigma_i: S,
+    pub y: S::Point,
+    /// R_j and S_j of every signer, in signing order
+    pub presign_vec: Vec<PresignBroadcastMessage<S>>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Signature<S: CurveScalar = FE> {
+    pub r: S,
+    pub s: S,
     pub recid: u8,
 }
 
-impl Keys {
-    pub fn create(index: usize) -> Keys {
-        let u: FE = ECScalar::new_random();
-        let y = &ECPoint::generator() * &u;
-        let (ek, dk) = Paillier::keypair().keys();
+/// Generates the ring-Pedersen parameters `(N_tilde, h1, h2)` used by the MtA range proofs,
+/// together with the trapdoors `xhi`, `xhi_inv` satisfying `h2 = h1^-xhi` and
+/// `h1 = h2^-xhi_inv mod N_tilde`.
+pub fn generate_h1_h2_N_tilde() -> (BigInt, BigInt, BigInt, BigInt, BigInt) {
+    // safe primes, so that the quadratic residues mod N_tilde form a cyclic group without
+    // small-order elements
+    let (_, dk_tilde) = Paillier::keypair_safe_primes().keys();
+    generate_h1_h2_from_safe_primes(&dk_tilde)
+}
 
-        Keys {
-            u_i: u,
-            y_i: y,
-            dk,
-            ek,
-            party_index: index.clone(),
+/// As `generate_h1_h2_N_tilde`, on the modulus of a Paillier key made of safe primes.
+pub fn generate_h1_h2_from_safe_primes(
+    dk_tilde: &DecryptionKey,
+) -> (BigInt, BigInt, BigInt, BigInt, BigInt) {
+    let N_tilde = &dk_tilde.p * &dk_tilde.q;
+    let one = BigInt::one();
+    let phi = (&dk_tilde.p - &one) * (&dk_tilde.q - &one);
+    // h1 is a random quadratic residue, so h1 and h2 generate the same subgroup
+    let r = BigInt::from_modulo(&N_tilde);
+    let h1 = BigInt::mod_mul(&r, &r, &N_tilde);
+    let xhi = loop {
+        let xhi = BigInt::sample_below(&phi);
+        if xhi.gcd(&phi) == one {
+            break xhi;
         }
+    };
+    let xhi_inv = BigInt::mod_inv(&xhi, &phi);
+    let h1_inv = BigInt::mod_inv(&h1, &N_tilde);
+    let h2 = BigInt::mod_pow(&h1_inv, &xhi, &N_tilde);
+    (N_tilde, h1, h2, xhi, xhi_inv)
+}
+
+impl<S> Keys<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    /// Samples the keys of party `index`. Party indices are 1-based, as are the VSS indices
+    /// of the shares, so the party at position i of the message vectors has index i + 1.
+    pub fn create(index: usize) -> Keys<S> {
+        let u: S = ECScalar::new_random();
+        Keys::create_from(u, index)
     }
 
-    pub fn create_from(u: FE, index: usize) -> Keys {
-        let y = &ECPoint::generator() * &u;
-        let (ek, dk) = Paillier::keypair().keys();
+    pub fn create_from(u: S, index: usize) -> Keys<S> {
+        let y = S::Point::generator() * &u;
+        let (ek, dk) = Paillier::keypair_safe_primes().keys();
+        let (N_tilde, h1, h2, xhi, xhi_inv) = generate_h1_h2_N_tilde();
 
         Keys {
             u_i: u,
@@ -167,99 +255,284 @@ impl Keys {
             dk,
             ek,
             party_index: index.clone(),
+            N_tilde,
+            h1,
+            h2,
+            xhi,
+            xhi_inv,
+        }
+    }
+
+    pub fn dlog_statement(&self) -> DLogStatement {
+        DLogStatement {
+            N: self.N_tilde.clone(),
+            g: self.h1.clone(),
+            ni: self.h2.clone(),
         }
     }
 
     pub fn phase1_broadcast_phase3_proof_of_correct_key(
         &self,
-    ) -> (KeyGenBroadcastMessage1, KeyGenDecommitMessage1) {
+    ) -> (KeyGenBroadcastMessage1, KeyGenDecommitMessage1<S>) {
         let blind_factor = BigInt::sample(SECURITY);
         let correct_key_proof = NICorrectKeyProof::proof(&self.dk);
+        let paillier_blum_modulus_proof = PaillierBlumModulusProof::prove(&self.dk);
+        // h1 and h2 generate the same subgroup: prove dlog_h1(h2) and dlog_h2(h1) are known
+        let dlog_statement_base_h1 = self.dlog_statement();
+        let dlog_statement_base_h2 = DLogStatement {
+            N: self.N_tilde.clone(),
+            g: self.h2.clone(),
+            ni: self.h1.clone(),
+        };
+        let composite_dlog_proof_base_h1 =
+            CompositeDLogProof::prove(&dlog_statement_base_h1, &self.xhi);
+        let composite_dlog_proof_base_h2 =
+            CompositeDLogProof::prove(&dlog_statement_base_h2, &self.xhi_inv);
         let com = HashCommitment::create_commitment_with_user_defined_randomness(
             &self.y_i.bytes_compressed_to_big_int(),
             &blind_factor,
         );
         let bcm1 = KeyGenBroadcastMessage1 {
             e: self.ek.clone(),
+            dlog_statement: dlog_statement_base_h1,
             com,
             correct_key_proof,
+            paillier_blum_modulus_proof,
+            composite_dlog_proof_base_h1,
+            composite_dlog_proof_base_h2,
         };
         let decom1 = KeyGenDecommitMessage1 {
             blind_factor,
             y_i: self.y_i.clone(),
+            no_small_factor_proofs: Vec::new(),
         };
         (bcm1, decom1)
     }
 
+    /// The no-small-factor proofs depend on the ring-Pedersen parameters of every verifier,
+    /// so they can only be added to the decommitment once all round 1 messages are known.
+    pub fn phase1_no_small_factor_proofs(
+        &self,
+        bc1_vec: &[KeyGenBroadcastMessage1],
+    ) -> Vec<NoSmallFactorProof> {
+        bc1_vec
+            .iter()
+            .map(|bc1| NoSmallFactorProof::prove(&self.dk, &bc1.dlog_statement))
+            .collect()
+    }
+
     pub fn phase1_verify_com_phase3_verify_correct_key_phase2_distribute(
         &self,
         params: &Parameters,
-        decom_vec: &Vec<KeyGenDecommitMessage1>,
+        decom_vec: &Vec<KeyGenDecommitMessage1<S>>,
         bc1_vec: &Vec<KeyGenBroadcastMessage1>,
-    ) -> Result<(VerifiableSS, Vec<FE>, usize), TssError> {
-        // test length:
-        assert_eq!(decom_vec.len(), params.share_count);
-        assert_eq!(bc1_vec.len(), params.share_count);
-        // test paillier correct key and test decommitments
-        let correct_key_correct_decom_all = (0..bc1_vec.len())
-            .map(|i| {
-                HashCommitment::create_commitment_with_user_defined_randomness(
-                    &decom_vec[i].y_i.bytes_compressed_to_big_int(),
-                    &decom_vec[i].blind_factor,
-                ) == bc1_vec[i].com
-                    && bc1_vec[i].correct_key_proof.verify(&bc1_vec[i].e).is_ok()
-            })
-            .all(|x| x == true);
+    ) -> Result<(VerifiableSS<S>, Vec<S>, usize), TssError> {
+        self.phase1_verify_com_phase3_verify_correct_key(params, decom_vec, bc1_vec)?;
 
         let (vss_scheme, secret_shares) =
             VerifiableSS::share(params.threshold, params.share_count, &self.u_i);
-        match correct_key_correct_decom_all {
-            true => Ok((vss_scheme, secret_shares, self.party_index.clone())),
-            false => Err(InvalidKey),
+        Ok((vss_scheme, secret_shares, self.party_index))
+    }
+
+    /// The checks of round 2 without the dealing, for the refresh and resharing protocols
+    /// that reuse the keygen messages to set up Paillier keys and h1, h2, N_tilde.
+    pub fn phase1_verify_com_phase3_verify_correct_key(
+        &self,
+        params: &Parameters,
+        decom_vec: &[KeyGenDecommitMessage1<S>],
+        bc1_vec: &[KeyGenBroadcastMessage1],
+    ) -> Result<(), TssError> {
+        if decom_vec.len() != params.share_count || bc1_vec.len() != params.share_count {
+            return Err(TssError::UnexpectedMessage(format!(
+                "{} commitments and {} decommitments of round 1 for {} parties",
+                bc1_vec.len(),
+                decom_vec.len(),
+                params.share_count
+            )));
+        }
+        // the no-small-factor proofs addressed to us are made against our own h1, h2, N_tilde
+        let own_position = self.party_index.checked_sub(1).ok_or(InvalidKey)?;
+        let dlog_statement = self.dlog_statement();
+        // test h1, h2, N_tilde, paillier correct key, Paillier-Blum modulus, no small factors
+        // and decommitments
+        let mut blames = (0..bc1_vec.len())
+            .flat_map(|i| {
+                let no_small_factor = match decom_vec[i].no_small_factor_proofs.get(own_position) {
+                    Some(proof) => proof.verify(&bc1_vec[i].e.n, &dlog_statement).is_ok(),
+                    None => false,
+                };
+                let checks = [
+                    (bc1_vec[i].verify_dlog_statement(), RingPedersenProof),
+                    (
+                        bc1_vec[i].correct_key_proof.verify(&bc1_vec[i].e).is_ok(),
+                        CorrectKeyProof,
+                    ),
+                    (
+                        bc1_vec[i]
+                            .paillier_blum_modulus_proof
+                            .verify(&bc1_vec[i].e)
+                            .is_ok(),
+                        BlameItem::PaillierBlumModulusProof,
+                    ),
+                    (
+                        HashCommitment::create_commitment_with_user_defined_randomness(
+                            &decom_vec[i].y_i.bytes_compressed_to_big_int(),
+                            &decom_vec[i].blind_factor,
+                        ) == bc1_vec[i].com,
+                        KeyGenDecommitment,
+                    ),
+                    (no_small_factor, BlameItem::NoSmallFactorProof),
+                ];
+                blame_failed_checks(i, &checks)
+            })
+            .collect::<Vec<Blame>>();
+        // a modulus copied from another party comes with that party's proofs, so they cannot
+        // tell the two apart: every party that shares its N or N_tilde is blamed
+        blames.extend(
+            (0..bc1_vec.len())
+                .filter(|&i| {
+                    (0..bc1_vec.len()).any(|j| {
+                        j != i
+                            && (bc1_vec[j].e.n == bc1_vec[i].e.n
+                                || bc1_vec[j].dlog_statement.N == bc1_vec[i].dlog_statement.N)
+                    })
+                })
+                .map(|i| Blame::new(i, BlameItem::ReusedModulus)),
+        );
+
+        match blames.is_empty() {
+            true => Ok(()),
+            false => Err(TssError::Blame(blames)),
+        }
+    }
+
+    /// Checks the shares received from every dealer and complains about those that do not
+    /// match the dealer's VSS commitments. The complaints are broadcast before keygen goes on.
+    pub fn phase2_complaints(
+        secret_shares_vec: &[S],
+        vss_scheme_vec: &[VerifiableSS<S>],
+        index: usize,
+    ) -> Vec<KeyGenComplaint> {
+        (0..vss_scheme_vec.len())
+            .filter(|&i| {
+                vss_scheme_vec[i]
+                    .validate_share(&secret_shares_vec[i], index)
+                    .is_err()
+            })
+            .map(|dealer| KeyGenComplaint { dealer, index })
+            .collect()
+    }
+
+    /// Publishes the shares of the dealer at position `dealer` that were complained about.
+    /// Complaints about an index out of 1..=secret_shares.len() name no share and are skipped.
+    pub fn phase2_answer_complaints(
+        dealer: usize,
+        secret_shares: &[S],
+        complaints: &[KeyGenComplaint],
+    ) -> Vec<KeyGenShareReveal<S>> {
+        complaints
+            .iter()
+            .filter(|complaint| complaint.dealer == dealer)
+            .filter_map(|complaint| {
+                let share = secret_shares.get(complaint.index.checked_sub(1)?)?;
+                Some(KeyGenShareReveal {
+                    dealer,
+                    index: complaint.index,
+                    share: share.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Settles the complaints of all parties with the shares revealed by the dealers.
+    /// A dealer that leaves a complaint unanswered or reveals a share that does not match
+    /// its VSS commitments is blamed; anyone can check the accusation against the broadcast
+    /// messages. Otherwise the revealed shares replace the disputed ones among the shares of
+    /// party `index`, which are returned.
+    pub fn phase2_resolve_complaints(
+        secret_shares_vec: &[S],
+        vss_scheme_vec: &[VerifiableSS<S>],
+        complaints: &[KeyGenComplaint],
+        reveals: &[KeyGenShareReveal<S>],
+        index: usize,
+    ) -> Result<Vec<S>, TssError> {
+        let mut secret_shares_vec = secret_shares_vec.to_vec();
+        let mut blames: Vec<Blame> = Vec::new();
+        for complaint in complaints {
+            let reveal = reveals.iter().find(|reveal| {
+                reveal.dealer == complaint.dealer && reveal.index == complaint.index
+            });
+            match reveal {
+                Some(reveal)
+                    if vss_scheme_vec[reveal.dealer]
+                        .validate_share(&reveal.share, reveal.index)
+                        .is_ok() =>
+                {
+                    if reveal.index == index {
+                        secret_shares_vec[reveal.dealer] = reveal.share.clone();
+                    }
+                }
+                _ => {
+                    let blame = Blame::new(complaint.dealer, VssShare);
+                    if !blames.contains(&blame) {
+                        blames.push(blame);
+                    }
+                }
+            }
+        }
+        match blames.is_empty() {
+            true => Ok(secret_shares_vec),
+            false => Err(TssError::Blame(blames)),
         }
     }
 
     pub fn phase2_verify_vss_construct_keypair_phase3_pok_dlog(
         &self,
         params: &Parameters,
-        y_vec: &Vec<GE>,
-        secret_shares_vec: &Vec<FE>,
-        vss_scheme_vec: &Vec<VerifiableSS>,
+        y_vec: &Vec<S::Point>,
+        secret_shares_vec: &Vec<S>,
+        vss_scheme_vec: &Vec<VerifiableSS<S>>,
         index: &usize,
-    ) -> Result<(SharedKeys, DLogProof), TssError> {
+    ) -> Result<(SharedKeys<S>, DLogProof<S>), TssError> {
         assert_eq!(y_vec.len(), params.share_count);
         assert_eq!(secret_shares_vec.len(), params.share_count);
         assert_eq!(vss_scheme_vec.len(), params.share_count);
 
-        let correct_ss_verify = (0..y_vec.len())
-            .map(|i| {
-                vss_scheme_vec[i]
-                    .validate_share(&secret_shares_vec[i], *index)
-                    .is_ok()
-                    && vss_scheme_vec[i].commitments[0].get_element() == y_vec[i].get_element()
+        let blames = (0..y_vec.len())
+            .flat_map(|i| {
+                let checks = [
+                    (vss_scheme_vec[i].commitments[0] == y_vec[i], VssCommitment),
+                    (
+                        vss_scheme_vec[i]
+                            .validate_share(&secret_shares_vec[i], *index)
+                            .is_ok(),
+                        VssShare,
+                    ),
+                ];
+                blame_failed_checks(i, &checks)
             })
-            .all(|x| x == true);
-
-        match correct_ss_verify {
-            true => {
-                let mut y_vec_iter = y_vec.iter();
-                let y0 = y_vec_iter.next().unwrap();
-                let y = y_vec_iter.fold(y0.clone(), |acc, x| acc + x);
-                let x_i = secret_shares_vec.iter().fold(FE::zero(), |acc, x| acc + x);
-                let dlog_proof = DLogProof::prove(&x_i);
-                Ok((SharedKeys { y, x_i }, dlog_proof))
-            }
-            false => Err(InvalidSS),
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
         }
+
+        let mut y_vec_iter = y_vec.iter();
+        let y0 = y_vec_iter.next().unwrap();
+        let y = y_vec_iter.fold(y0.clone(), |acc, x| acc + x);
+        let x_i = secret_shares_vec.iter().fold(S::zero(), |acc, x| acc + x);
+        let dlog_proof = DLogProof::prove(&x_i);
+        Ok((SharedKeys { y, x_i }, dlog_proof))
     }
 
-    pub fn get_commitments_to_xi(vss_scheme_vec: &Vec<VerifiableSS>) -> Vec<GE> {
+    /// The dealers need not be the parties holding the shares, as after a resharing.
+    pub fn get_commitments_to_xi(vss_scheme_vec: &[VerifiableSS<S>]) -> Vec<S::Point> {
         let len = vss_scheme_vec.len();
-        let xi_points_vec = (1..len + 1)
+        let share_count = vss_scheme_vec[0].parameters.share_count;
+        let xi_points_vec = (1..share_count + 1)
             .map(|i| {
                 let xij_points_vec = (0..len)
                     .map(|j| vss_scheme_vec[j].get_point_commitment(i))
-                    .collect::<Vec<GE>>();
+                    .collect::<Vec<S::Point>>();
 
                 let mut xij_points_iter = xij_points_vec.iter();
                 let first = xij_points_iter.next().unwrap();
@@ -267,41 +540,477 @@ impl Keys {
                 let tail = xij_points_iter;
                 tail.fold(first.clone(), |acc, x| acc + x)
             })
-            .collect::<Vec<GE>>();
+            .collect::<Vec<S::Point>>();
 
         xi_points_vec
     }
 
     pub fn update_commitments_to_xi(
-        comm: &GE,
-        vss_scheme: &VerifiableSS,
+        comm: &S::Point,
+        vss_scheme: &VerifiableSS<S>,
         index: usize,
         s: &Vec<usize>,
-    ) -> GE {
+    ) -> S::Point {
         let li = vss_scheme.map_share_to_new_params(index, s);
-        comm * &li
+        comm.clone() * &li
     }
 
     pub fn verify_dlog_proofs(
         params: &Parameters,
-        dlog_proofs_vec: &Vec<DLogProof>,
-        y_vec: &Vec<GE>,
+        dlog_proofs_vec: &Vec<DLogProof<S>>,
+        y_vec: &Vec<S::Point>,
     ) -> Result<(), TssError> {
         assert_eq!(y_vec.len(), params.share_count);
         assert_eq!(dlog_proofs_vec.len(), params.share_count);
-        let xi_dlog_verify = (0..y_vec.len())
-            .map(|i| DLogProof::verify(&dlog_proofs_vec[i]).is_ok())
-            .all(|x| x == true);
+        let blames = (0..y_vec.len())
+            .filter(|&i| DLogProof::verify(&dlog_proofs_vec[i]).is_err())
+            .map(|i| Blame::new(i, XiDLogProof))
+            .collect::<Vec<Blame>>();
 
-        match xi_dlog_verify {
+        match blames.is_empty() {
             true => Ok(()),
-            false => Err(InvalidKey),
+            false => Err(TssError::Blame(blames)),
         }
     }
 }
 
-impl PartyPrivate {
-    pub fn set_private(key: Keys, shared_key: SharedKeys) -> PartyPrivate {
+/*
+    Proactive refresh
+
+    Every party deals a random polynomial g_i of degree t - 1 and party j adds
+    j * sum_i g_i(j) to x_j. The shares then move along a polynomial of degree t
+    with zero free term, so y is kept while shares of different epochs cannot be
+    combined. The Paillier keys and h1, h2, N_tilde are rotated along the way,
+    reusing the keygen messages of rounds 1 and 2.
+*/
+impl<S> Keys<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    /// Fresh Paillier key and ring-Pedersen parameters for the same u_i.
+    pub fn refresh(&self) -> Keys<S> {
+        Keys::create_from(self.u_i.clone(), self.party_index)
+    }
+
+    /// Deals g_i. The shares are sent like keygen shares and go through the same
+    /// complaint round. The shares of a 1-of-n key all equal x, so there is no g_i of degree
+    /// t - 1 to move them along.
+    pub fn refresh_phase2_distribute(
+        params: &Parameters,
+    ) -> Result<(VerifiableSS<S>, Vec<S>), TssError> {
+        if params.threshold == 0 {
+            return Err(TssError::UnknownError {
+                msg: "a 1-of-n key cannot be refreshed".to_string(),
+                line: line!(),
+            });
+        }
+        Ok(VerifiableSS::share(
+            params.threshold - 1,
+            params.share_count,
+            &ECScalar::new_random(),
+        ))
+    }
+
+    /// Adds the zero-sharing to x_i and derives the VSS commitments of the refreshed
+    /// shares from `old_vss_scheme_vec`, the ones of the last keygen, refresh or resharing.
+    pub fn refresh_phase2_update_keypair(
+        params: &Parameters,
+        shared_keys: &SharedKeys<S>,
+        secret_shares_vec: &[S],
+        vss_scheme_vec: &[VerifiableSS<S>],
+        old_vss_scheme_vec: &[VerifiableSS<S>],
+        index: usize,
+    ) -> Result<(SharedKeys<S>, Vec<VerifiableSS<S>>), TssError> {
+        assert_eq!(secret_shares_vec.len(), params.share_count);
+        assert_eq!(vss_scheme_vec.len(), params.share_count);
+        assert_eq!(
+            old_vss_scheme_vec[0].commitments.len(),
+            params.threshold + 1
+        );
+
+        let blames = (0..vss_scheme_vec.len())
+            .flat_map(|i| {
+                let checks = [
+                    (
+                        vss_scheme_vec[i].commitments.len() == params.threshold,
+                        VssCommitment,
+                    ),
+                    (
+                        vss_scheme_vec[i]
+                            .validate_share(&secret_shares_vec[i], index)
+                            .is_ok(),
+                        VssShare,
+                    ),
+                ];
+                blame_failed_checks(i, &checks)
+            })
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let index_fe: S = ECScalar::from(&BigInt::from(index as u32));
+        let g_sum = secret_shares_vec.iter().fold(S::zero(), |acc, x| acc + x);
+        let x_i = shared_keys.x_i.clone() + index_fe * g_sum;
+        // x * g_i(x) adds the commitment to coefficient k - 1 of g_i to that of coefficient k.
+        // Only the sum over the dealers matters, so the sharings of zero are all added to the
+        // first one.
+        let mut new_vss_scheme_vec = old_vss_scheme_vec.to_vec();
+        for vss_scheme in vss_scheme_vec {
+            let first = &mut new_vss_scheme_vec[0].commitments;
+            for (k, delta) in vss_scheme.commitments.iter().enumerate() {
+                first[k + 1] = first[k + 1].clone() + delta;
+            }
+        }
+        Ok((
+            SharedKeys {
+                y: shared_keys.y.clone(),
+                x_i,
+            },
+            new_vss_scheme_vec,
+        ))
+    }
+}
+
+/*
+    Resharing
+
+    The key moves from a (t, n) committee to a (t', n') committee. t + 1 old
+    holders, at positions s, deal lambda_i * x_i with Feldman VSS of degree t'
+    among the new parties, which add up their sub-shares. The commitment to the
+    free term of each dealing is checked against lambda_i * g^x_i, computed from
+    the old VSS commitments, so y is kept. The new parties set up their Paillier
+    keys and h1, h2, N_tilde with the keygen messages of rounds 1 and 2.
+*/
+
+/// Broadcast by an old holder during a resharing.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReshareBroadcastMessage<S: CurveScalar = FE> {
+    /// Public key of the AES channels to the new parties
+    pub y_i: S::Point,
+    /// The VSS commitments of the old key, which the new parties may not have
+    pub old_vss_scheme_vec: Vec<VerifiableSS<S>>,
+    pub vss_scheme: VerifiableSS<S>,
+}
+
+impl<S> Keys<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    /// Deals lambda_i * x_i of the old holder at position `index` among the new parties.
+    pub fn reshare_distribute(
+        new_params: &Parameters,
+        shared_keys: &SharedKeys<S>,
+        old_vss_scheme_vec: &[VerifiableSS<S>],
+        index: usize,
+        s: &[usize],
+    ) -> (VerifiableSS<S>, Vec<S>) {
+        let li = old_vss_scheme_vec[0].map_share_to_new_params(index, s);
+        let w_i = li * &shared_keys.x_i;
+        VerifiableSS::share(new_params.threshold, new_params.share_count, &w_i)
+    }
+
+    /// Checks the dealings of the old holders at positions `s` against the old VSS
+    /// commitments and adds up the sub-shares of new party `index`. Blames refer to the
+    /// positions in `s`.
+    pub fn reshare_verify_construct_keypair(
+        new_params: &Parameters,
+        old_vss_scheme_vec: &[VerifiableSS<S>],
+        s: &[usize],
+        secret_shares_vec: &[S],
+        vss_scheme_vec: &[VerifiableSS<S>],
+        index: usize,
+    ) -> Result<SharedKeys<S>, TssError> {
+        assert_eq!(secret_shares_vec.len(), s.len());
+        assert_eq!(vss_scheme_vec.len(), s.len());
+        let old_vss_scheme_vec = old_vss_scheme_vec.to_vec();
+        let xi_commitments = Keys::get_commitments_to_xi(&old_vss_scheme_vec);
+        let shamir_params = ShamirSecretSharing {
+            threshold: new_params.threshold,
+            share_count: new_params.share_count,
+        };
+
+        let blames = (0..s.len())
+            .flat_map(|k| {
+                let li = old_vss_scheme_vec[0].map_share_to_new_params(s[k], s);
+                let checks = [
+                    (
+                        vss_scheme_vec[k].parameters == shamir_params
+                            && vss_scheme_vec[k].commitments.len() == new_params.threshold + 1
+                            && vss_scheme_vec[k].commitments[0]
+                                == xi_commitments[s[k]].clone() * &li,
+                        VssCommitment,
+                    ),
+                    (
+                        vss_scheme_vec[k]
+                            .validate_share(&secret_shares_vec[k], index)
+                            .is_ok(),
+                        VssShare,
+                    ),
+                ];
+                blame_failed_checks(k, &checks)
+            })
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let mut y_iter = old_vss_scheme_vec.iter().map(|vss| &vss.commitments[0]);
+        let y0 = y_iter.next().unwrap().clone();
+        let y = y_iter.fold(y0, |acc, x| acc + x);
+        let x_i = secret_shares_vec.iter().fold(S::zero(), |acc, x| acc + x);
+        Ok(SharedKeys { y, x_i })
+    }
+
+    /// The old VSS commitments of a resharing: those of an old holder, passed as `known`,
+    /// or for a newcomer the ones sent by a strict majority of the dealers. Blames refer to
+    /// the positions in the dealers.
+    pub fn reshare_old_vss_scheme_vec(
+        reshare_msg_vec: &[ReshareBroadcastMessage<S>],
+        known: Option<&[VerifiableSS<S>]>,
+    ) -> Result<Vec<VerifiableSS<S>>, TssError> {
+        let sent_vec = reshare_msg_vec
+            .iter()
+            .map(|msg| &msg.old_vss_scheme_vec[..])
+            .collect::<Vec<&[VerifiableSS<S>]>>();
+        agree_vss_scheme_vec(&sent_vec, known)
+    }
+}
+
+/// The VSS commitments of a key that parties send to one that does not have them: `known`
+/// if it does after all, else those sent by a strict majority. The parties that sent others
+/// are blamed, by position in `sent_vec`. Without a majority no party can be told apart,
+/// and this fails without blame.
+pub fn agree_vss_scheme_vec<S>(
+    sent_vec: &[&[VerifiableSS<S>]],
+    known: Option<&[VerifiableSS<S>]>,
+) -> Result<Vec<VerifiableSS<S>>, TssError>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    let vss_scheme_vec = match known {
+        Some(known) => known,
+        None => sent_vec
+            .iter()
+            .copied()
+            .find(|&candidate| {
+                let votes = sent_vec.iter().filter(|&&sent| sent == candidate).count();
+                2 * votes > sent_vec.len()
+            })
+            .ok_or_else(|| {
+                TssError::UnexpectedMessage(
+                    "VSS commitments without a majority of the parties".to_string(),
+                )
+            })?,
+    };
+
+    let blames = (0..sent_vec.len())
+        .filter(|&k| sent_vec[k] != vss_scheme_vec)
+        .map(|k| Blame::new(k, VssCommitment))
+        .collect::<Vec<Blame>>();
+    match blames.is_empty() {
+        true => Ok(vss_scheme_vec.to_vec()),
+        false => Err(TssError::Blame(blames)),
+    }
+}
+
+/*
+    Share repair
+
+    t + 1 helpers at positions s recompute the share x_j of the party at
+    position j without learning it. Helper i splits lambda_i(j) * x_i, its
+    term of the Lagrange interpolation at j, into one random part per helper
+    and commits to the parts. Each helper adds up the parts it receives and
+    sends the sum to the recovering party, which adds the sums up to x_j. The
+    commitments tie every step to g^x_i from the VSS commitments, so a helper
+    that cheats is blamed and x_j is checked against g^x_j.
+*/
+
+/// Broadcast by a helper during a share repair.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RepairBroadcastMessage<S: CurveScalar = FE> {
+    /// Commitments to the parts of the helper's term, one per helper in the order of s
+    pub part_commitments: Vec<S::Point>,
+    /// The VSS commitments of the key, which the recovering party has lost
+    pub vss_scheme_vec: Vec<VerifiableSS<S>>,
+}
+
+impl<S> Keys<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    /// Splits the term of the helper at position `index` into the parts sent to the helpers.
+    pub fn repair_phase1_distribute(
+        shared_keys: &SharedKeys<S>,
+        vss_scheme_vec: &[VerifiableSS<S>],
+        index: usize,
+        s: &[usize],
+        lost_index: usize,
+    ) -> (RepairBroadcastMessage<S>, Vec<S>) {
+        let li = vss_scheme_vec[0].map_share_to_index(index, s, lost_index);
+        let term = li * &shared_keys.x_i;
+        let mut parts = (1..s.len())
+            .map(|_| ECScalar::new_random())
+            .collect::<Vec<S>>();
+        let last = parts
+            .iter()
+            .fold(term, |acc, part| acc.sub(&part.get_element()));
+        parts.push(last);
+
+        let g: S::Point = ECPoint::generator();
+        let part_commitments = parts
+            .iter()
+            .map(|part| g.clone() * part)
+            .collect::<Vec<S::Point>>();
+        (
+            RepairBroadcastMessage {
+                part_commitments,
+                vss_scheme_vec: vss_scheme_vec.to_vec(),
+            },
+            parts,
+        )
+    }
+
+    /// Checks the parts received by the helper at position `position` of s and adds them up.
+    /// Blames refer to the positions in s.
+    pub fn repair_phase2_sum_parts(
+        vss_scheme_vec: &[VerifiableSS<S>],
+        s: &[usize],
+        lost_index: usize,
+        parts: &[S],
+        msg_vec: &[RepairBroadcastMessage<S>],
+        position: usize,
+    ) -> Result<S, TssError> {
+        assert_eq!(parts.len(), s.len());
+        assert_eq!(msg_vec.len(), s.len());
+        let g: S::Point = ECPoint::generator();
+        let mut blames = repair_commitment_blames(vss_scheme_vec, s, lost_index, msg_vec);
+        for i in 0..s.len() {
+            let blame = Blame::new(i, VssShare);
+            if !blames.contains(&Blame::new(i, VssCommitment))
+                && g.clone() * &parts[i] != msg_vec[i].part_commitments[position]
+            {
+                blames.push(blame);
+            }
+        }
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        Ok(parts.iter().fold(S::zero(), |acc, x| acc + x))
+    }
+
+    /// Checks the sums of the helpers against their commitments and adds them up to the share
+    /// of the recovering party at position `lost_index`. Blames refer to the positions in s.
+    pub fn repair_phase3_construct_keypair(
+        vss_scheme_vec: &[VerifiableSS<S>],
+        s: &[usize],
+        lost_index: usize,
+        sums: &[S],
+        msg_vec: &[RepairBroadcastMessage<S>],
+    ) -> Result<SharedKeys<S>, TssError> {
+        assert_eq!(sums.len(), s.len());
+        assert_eq!(msg_vec.len(), s.len());
+        let g: S::Point = ECPoint::generator();
+        let mut blames = repair_commitment_blames(vss_scheme_vec, s, lost_index, msg_vec);
+        if blames.is_empty() {
+            blames = (0..s.len())
+                .filter(|&k| {
+                    let mut commitments = msg_vec.iter().map(|msg| &msg.part_commitments[k]);
+                    let head = commitments.next().unwrap().clone();
+                    g.clone() * &sums[k] != commitments.fold(head, |acc, x| acc + x)
+                })
+                .map(|k| Blame::new(k, VssShare))
+                .collect();
+        }
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let x_i = sums.iter().fold(S::zero(), |acc, x| acc + x);
+        let xi_commitments = Keys::get_commitments_to_xi(&vss_scheme_vec.to_vec());
+        if g * &x_i != xi_commitments[lost_index] {
+            return Err(InvalidKey);
+        }
+        let mut y_iter = vss_scheme_vec.iter().map(|vss| &vss.commitments[0]);
+        let y0 = y_iter.next().unwrap().clone();
+        let y = y_iter.fold(y0, |acc, x| acc + x);
+        Ok(SharedKeys { y, x_i })
+    }
+}
+
+/// Blames the helpers whose part commitments do not add up to lambda_i(j) * g^x_i.
+fn repair_commitment_blames<S>(
+    vss_scheme_vec: &[VerifiableSS<S>],
+    s: &[usize],
+    lost_index: usize,
+    msg_vec: &[RepairBroadcastMessage<S>],
+) -> Vec<Blame>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    let xi_commitments = Keys::get_commitments_to_xi(&vss_scheme_vec.to_vec());
+    (0..s.len())
+        .filter(|&i| {
+            let li = vss_scheme_vec[0].map_share_to_index(s[i], s, lost_index);
+            let part_commitments = &msg_vec[i].part_commitments;
+            part_commitments.len() != s.len()
+                || part_commitments[1..]
+                    .iter()
+                    .fold(part_commitments[0].clone(), |acc, x| acc + x)
+                    != xi_commitments[s[i]].clone() * &li
+        })
+        .map(|i| Blame::new(i, VssCommitment))
+        .collect()
+}
+
+fn blame_failed_checks(party: usize, checks: &[(bool, BlameItem)]) -> Vec<Blame> {
+    checks
+        .iter()
+        .filter(|(passed, _)| !passed)
+        .map(|&(_, item)| Blame::new(party, item))
+        .collect()
+}
+
+impl KeyGenBroadcastMessage1 {
+    /// Checks that h1 and h2 are non-trivial and generate the same subgroup of Z*_N_tilde.
+    pub fn verify_dlog_statement(&self) -> bool {
+        verify_dlog_statement(
+            &self.dlog_statement,
+            &self.composite_dlog_proof_base_h1,
+            &self.composite_dlog_proof_base_h2,
+        )
+    }
+}
+
+/// Checks that h1 and h2 are non-trivial and generate the same subgroup of Z*_N_tilde,
+/// with proofs of knowledge of dlog_h1(h2) and of dlog_h2(h1).
+pub fn verify_dlog_statement(
+    statement_base_h1: &DLogStatement,
+    composite_dlog_proof_base_h1: &CompositeDLogProof,
+    composite_dlog_proof_base_h2: &CompositeDLogProof,
+) -> bool {
+    let statement_base_h2 = DLogStatement {
+        N: statement_base_h1.N.clone(),
+        g: statement_base_h1.ni.clone(),
+        ni: statement_base_h1.g.clone(),
+    };
+    let one = BigInt::one();
+    statement_base_h1.g != one
+        && statement_base_h1.ni != one
+        && composite_dlog_proof_base_h1
+            .verify(statement_base_h1)
+            .is_ok()
+        && composite_dlog_proof_base_h2
+            .verify(&statement_base_h2)
+            .is_ok()
+}
+
+impl<S> PartyPrivate<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    pub fn set_private(key: Keys<S>, shared_key: SharedKeys<S>) -> PartyPrivate<S> {
         let key_private = PartyPrivate {
             u_i: key.u_i,
             x_i: shared_key.x_i,
@@ -310,8 +1019,8 @@ impl PartyPrivate {
         key_private
     }
 
-    pub fn y_i(&self) -> GE {
-        let g: GE = ECPoint::generator();
+    pub fn y_i(&self) -> S::Point {
+        let g: S::Point = ECPoint::generator();
         g * self.u_i.clone()
     }
 
@@ -319,21 +1028,12 @@ impl PartyPrivate {
         Paillier::decrypt(&self.dk, &RawCiphertext::from(ciphertext))
     }
 
-    pub fn refresh_private_key(&self, factor: &FE, index: usize) -> Keys {
-        let u: FE = self.u_i.clone() + factor;
-        let y = &ECPoint::generator() * &u;
-        let (ek, dk) = Paillier::keypair().keys();
-
-        Keys {
-            u_i: u,
-            y_i: y,
-            dk,
-            ek,
-            party_index: index.clone(),
-        }
+    pub fn refresh_private_key(&self, factor: &S, index: usize) -> Keys<S> {
+        let u: S = self.u_i.clone() + factor;
+        Keys::create_from(u, index)
     }
 
-    pub fn update_private_key(&self, factor_u_i: &FE, factor_x_i: &FE) -> Self {
+    pub fn update_private_key(&self, factor_u_i: &S, factor_x_i: &S) -> Self {
         PartyPrivate {
             u_i: self.u_i.clone() + factor_u_i,
             x_i: self.x_i.clone() + factor_x_i,
@@ -342,19 +1042,22 @@ impl PartyPrivate {
     }
 }
 
-impl SignKeys {
+impl<S> SignKeys<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
     pub fn create(
-        private: &PartyPrivate,
-        vss_scheme: &VerifiableSS,
+        private: &PartyPrivate<S>,
+        vss_scheme: &VerifiableSS<S>,
         index: usize,
         s: &Vec<usize>,
-    ) -> SignKeys {
+    ) -> SignKeys<S> {
         let li = vss_scheme.map_share_to_new_params(index, s);
         let w_i = li * &private.x_i;
-        let g: GE = ECPoint::generator();
-        let g_w_i = &g * &w_i;
-        let gamma_i: FE = ECScalar::new_random();
-        let g_gamma_i = &g * &gamma_i;
+        let g: S::Point = ECPoint::generator();
+        let g_w_i = g.clone() * &w_i;
+        let gamma_i: S = ECScalar::new_random();
+        let g_gamma_i = g * &gamma_i;
         SignKeys {
             w_i,
             g_w_i,
@@ -364,9 +1067,9 @@ impl SignKeys {
         }
     }
 
-    pub fn phase1_broadcast(&self) -> (SignBroadcastPhase1, SignDecommitPhase1) {
+    pub fn phase1_broadcast(&self) -> (SignBroadcastPhase1, SignDecommitPhase1<S>) {
         let blind_factor = BigInt::sample(SECURITY);
-        let g: GE = ECPoint::generator();
+        let g: S::Point = ECPoint::generator();
         let g_gamma_i = g * &self.gamma_i;
         let com = HashCommitment::create_commitment_with_user_defined_randomness(
             &g_gamma_i.bytes_compressed_to_big_int(),
@@ -382,52 +1085,59 @@ impl SignKeys {
         )
     }
 
-    pub fn phase2_delta_i(&self, alpha_vec: &Vec<FE>, beta_vec: &Vec<FE>) -> FE {
+    pub fn phase2_delta_i(&self, alpha_vec: &Vec<S>, beta_vec: &Vec<S>) -> S {
         let vec_len = alpha_vec.len();
         assert_eq!(alpha_vec.len(), beta_vec.len());
         // assert_eq!(alpha_vec.len(), self.s.len() - 1);
-        let ki_gamma_i = self.k_i.mul(&self.gamma_i.get_element());
+        let ki_gamma_i = self.k_i.clone() * &self.gamma_i;
         let sum = (0..vec_len)
-            .map(|i| alpha_vec[i].add(&beta_vec[i].get_element()))
+            .map(|i| alpha_vec[i].clone() + &beta_vec[i])
             .fold(ki_gamma_i, |acc, x| acc + x);
         return sum;
     }
 
-    pub fn phase2_sigma_i(&self, miu_vec: &Vec<FE>, ni_vec: &Vec<FE>) -> FE {
+    pub fn phase2_sigma_i(&self, miu_vec: &Vec<S>, ni_vec: &Vec<S>) -> S {
         let vec_len = miu_vec.len();
         assert_eq!(miu_vec.len(), ni_vec.len());
         //assert_eq!(miu_vec.len(), self.s.len() - 1);
-        let ki_w_i = self.k_i.mul(&self.w_i.get_element());
+        let ki_w_i = self.k_i.clone() * &self.w_i;
         let sum = (0..vec_len)
-            .map(|i| miu_vec[i].add(&ni_vec[i].get_element()))
+            .map(|i| miu_vec[i].clone() + &ni_vec[i])
             .fold(ki_w_i, |acc, x| acc + x);
         return sum;
     }
 
-    pub fn phase3_reconstruct_delta(delta_vec: &Vec<FE>) -> FE {
-        let sum = delta_vec.iter().fold(FE::zero(), |acc, x| acc + x);
+    pub fn phase3_reconstruct_delta(delta_vec: &Vec<S>) -> S {
+        let sum = delta_vec.iter().fold(S::zero(), |acc, x| acc + x);
         sum.invert()
     }
 
     pub fn phase4(
-        delta_inv: &FE,
-        b_proof_vec: &Vec<&DLogProof>,
-        phase1_decommit_vec: Vec<SignDecommitPhase1>,
+        delta_inv: &S,
+        b_proof_vec: &Vec<&DLogProof<S>>,
+        phase1_decommit_vec: Vec<SignDecommitPhase1<S>>,
         // blind_vec: &Vec<BigInt>,
         //  g_gamma_i_vec: &Vec<GE>,
         bc1_vec: &Vec<SignBroadcastPhase1>,
-    ) -> Result<GE, TssError> {
-        let test_b_vec_and_com = (0..b_proof_vec.len())
-            .map(|i| {
-                b_proof_vec[i].pk.get_element() == phase1_decommit_vec[i].g_gamma_i.get_element()
-                    && HashCommitment::create_commitment_with_user_defined_randomness(
-                        &phase1_decommit_vec[i]
-                            .g_gamma_i
-                            .bytes_compressed_to_big_int(),
-                        &phase1_decommit_vec[i].blind_factor,
-                    ) == bc1_vec[i].com
+    ) -> Result<S::Point, TssError> {
+        // blame is reported by position in the given vectors
+        let blames = (0..b_proof_vec.len())
+            .filter_map(|i| {
+                if HashCommitment::create_commitment_with_user_defined_randomness(
+                    &phase1_decommit_vec[i]
+                        .g_gamma_i
+                        .bytes_compressed_to_big_int(),
+                    &phase1_decommit_vec[i].blind_factor,
+                ) != bc1_vec[i].com
+                {
+                    Some(Blame::new(i, GammaDecommitment))
+                } else if b_proof_vec[i].pk != phase1_decommit_vec[i].g_gamma_i {
+                    Some(Blame::new(i, GammaMtaMismatch))
+                } else {
+                    None
+                }
             })
-            .all(|x| x == true);
+            .collect::<Vec<Blame>>();
 
         let mut g_gamma_i_iter = phase1_decommit_vec.iter();
 
@@ -435,30 +1145,33 @@ impl SignKeys {
 
         let tail = g_gamma_i_iter;
 
-        match test_b_vec_and_com {
+        match blames.is_empty() {
             true => Ok({
                 let gamma_sum = tail.fold(head.g_gamma_i.clone(), |acc, x| acc + &x.g_gamma_i);
                 let R = gamma_sum * delta_inv;
                 R
             }),
-            false => Err(InvalidKey),
+            false => Err(TssError::Blame(blames)),
         }
     }
 }
 
-impl LocalSignature {
+impl<S> LocalSignature<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
     pub fn phase5_local_sig(
-        k_i: &FE,
+        k_i: &S,
         message: &BigInt,
-        R: &GE,
-        sigma_i: &FE,
-        pubkey: &GE,
-    ) -> LocalSignature {
-        let m_fe: FE = ECScalar::from(message);
-        let r: FE = ECScalar::from(&R.x_coor().unwrap().mod_floor(&FE::q()));
+        R: &S::Point,
+        sigma_i: &S,
+        pubkey: &S::Point,
+    ) -> LocalSignature<S> {
+        let m_fe: S = ECScalar::from(message);
+        let r: S = ECScalar::from(&R.x_coor().unwrap().mod_floor(&S::q()));
         let s_i = m_fe * k_i + r * sigma_i;
-        let l_i: FE = ECScalar::new_random();
-        let rho_i: FE = ECScalar::new_random();
+        let l_i: S = ECScalar::new_random();
+        let rho_i: S = ECScalar::new_random();
         LocalSignature {
             l_i,
             rho_i,
@@ -471,14 +1184,19 @@ impl LocalSignature {
 
     pub fn phase5a_broadcast_5b_zkproof(
         &self,
-    ) -> (Phase5Com1, Phase5ADecom1, HomoELGamalProof, DLogProof) {
+    ) -> (
+        Phase5Com1,
+        Phase5ADecom1<S>,
+        HomoELGamalProof<S>,
+        DLogProof<S>,
+    ) {
         let blind_factor = BigInt::sample(SECURITY);
-        let g: GE = ECPoint::generator();
-        let A_i = &g * &self.rho_i;
-        let l_i_rho_i = self.l_i.mul(&self.rho_i.get_element());
-        let B_i = &g * &l_i_rho_i;
-        let V_i = &self.R * &self.s_i + &g * &self.l_i;
-        let input_hash = HSha256::create_hash_from_ge(&[&V_i, &A_i, &B_i]).to_big_int();
+        let g: S::Point = ECPoint::generator();
+        let A_i = g.clone() * &self.rho_i;
+        let l_i_rho_i = self.l_i.clone() * &self.rho_i;
+        let B_i = g.clone() * &l_i_rho_i;
+        let V_i = self.R.clone() * &self.s_i + g.clone() * &self.l_i;
+        let input_hash = HSha256::create_hash_from_ge::<S>(&[&V_i, &A_i, &B_i]).to_big_int();
         let com = HashCommitment::create_commitment_with_user_defined_randomness(
             &input_hash,
             &blind_factor,
@@ -512,18 +1230,19 @@ impl LocalSignature {
 
     pub fn phase5c(
         &self,
-        decom_vec: &Vec<Phase5ADecom1>,
+        decom_vec: &Vec<Phase5ADecom1<S>>,
         com_vec: &Vec<Phase5Com1>,
-        elgamal_proofs: &Vec<HomoELGamalProof>,
-        dlog_proofs_rho: &[DLogProof],
-        v_i: &GE,
-        R: &GE,
-    ) -> Result<(Phase5Com2, Phase5DDecom2), TssError> {
+        elgamal_proofs: &Vec<HomoELGamalProof<S>>,
+        dlog_proofs_rho: &[DLogProof<S>],
+        v_i: &S::Point,
+        R: &S::Point,
+    ) -> Result<(Phase5Com2, Phase5DDecom2<S>), TssError> {
         assert_eq!(decom_vec.len(), com_vec.len());
 
-        let g: GE = ECPoint::generator();
-        let test_com_elgamal = (0..com_vec.len())
-            .map(|i| {
+        let g: S::Point = ECPoint::generator();
+        // blame is reported by position in the given vectors
+        let blames = (0..com_vec.len())
+            .flat_map(|i| {
                 let delta = HomoElGamalStatement {
                     G: decom_vec[i].A_i.clone(),
                     H: R.clone(),
@@ -531,28 +1250,39 @@ impl LocalSignature {
                     D: decom_vec[i].V_i.clone(),
                     E: decom_vec[i].B_i.clone(),
                 };
-                let input_hash = HSha256::create_hash_from_ge(&[
+                let input_hash = HSha256::create_hash_from_ge::<S>(&[
                     &decom_vec[i].V_i,
                     &decom_vec[i].A_i,
                     &decom_vec[i].B_i,
                 ])
                 .to_big_int();
 
-                HashCommitment::create_commitment_with_user_defined_randomness(
+                let mut blames = Vec::new();
+                if HashCommitment::create_commitment_with_user_defined_randomness(
                     &input_hash,
                     &decom_vec[i].blind_factor,
-                ) == com_vec[i].com
-                    && elgamal_proofs[i].verify(&delta).is_ok()
-                    && DLogProof::verify(&dlog_proofs_rho[i]).is_ok()
+                ) != com_vec[i].com
+                {
+                    blames.push(Blame::new(i, Phase5ADecommitment));
+                }
+                if elgamal_proofs[i].verify(&delta).is_err() {
+                    blames.push(Blame::new(i, HomoElGamalProof));
+                }
+                if DLogProof::verify(&dlog_proofs_rho[i]).is_err()
+                    || dlog_proofs_rho[i].pk != decom_vec[i].A_i
+                {
+                    blames.push(Blame::new(i, RhoDLogProof));
+                }
+                blames
             })
-            .all(|x| x == true);
+            .collect::<Vec<Blame>>();
 
         let v_vec = (0..com_vec.len())
             .map(|i| &decom_vec[i].V_i)
-            .collect::<Vec<&GE>>();
+            .collect::<Vec<&S::Point>>();
         let a_vec = (0..com_vec.len())
             .map(|i| &decom_vec[i].A_i)
-            .collect::<Vec<&GE>>();
+            .collect::<Vec<&S::Point>>();
 
         let v = v_vec.iter().fold(v_i.clone(), |acc, x| acc + *x);
         // V = -mG -ry - sum (vi)
@@ -561,22 +1291,22 @@ impl LocalSignature {
         let tail = a_i_iter;
         let a = tail.fold((*head).clone(), |acc, x| acc.add_point(&(*x).get_element()));
 
-        let r: FE = ECScalar::from(&self.R.x_coor().unwrap().mod_floor(&FE::q()));
-        let yr = &self.y * &r;
-        let g: GE = ECPoint::generator();
-        let m_fe: FE = ECScalar::from(&self.m);
-        let gm = &g * &m_fe;
+        let r: S = ECScalar::from(&self.R.x_coor().unwrap().mod_floor(&S::q()));
+        let yr = self.y.clone() * &r;
+        let g: S::Point = ECPoint::generator();
+        let m_fe: S = ECScalar::from(&self.m);
+        let gm = g * &m_fe;
         let v = v.sub_point(&gm.get_element()).sub_point(&yr.get_element());
-        let u_i = &v * &self.rho_i;
-        let t_i = &a * &self.l_i;
-        let input_hash = HSha256::create_hash_from_ge(&[&u_i, &t_i]).to_big_int();
+        let u_i = v * &self.rho_i;
+        let t_i = a * &self.l_i;
+        let input_hash = HSha256::create_hash_from_ge::<S>(&[&u_i, &t_i]).to_big_int();
         let blind_factor = BigInt::sample(SECURITY);
         let com = HashCommitment::create_commitment_with_user_defined_randomness(
             &input_hash,
             &blind_factor,
         );
 
-        match test_com_elgamal {
+        match blames.is_empty() {
             true => Ok({
                 (
                     Phase5Com2 { com },
@@ -587,42 +1317,44 @@ impl LocalSignature {
                     },
                 )
             }),
-            false => Err(InvalidCom),
+            false => Err(TssError::Blame(blames)),
         }
     }
 
     pub fn phase5d(
         &self,
-        decom_vec2: &Vec<Phase5DDecom2>,
+        decom_vec2: &Vec<Phase5DDecom2<S>>,
         com_vec2: &Vec<Phase5Com2>,
-        decom_vec1: &Vec<Phase5ADecom1>,
-    ) -> Result<FE, TssError> {
+        decom_vec1: &Vec<Phase5ADecom1<S>>,
+    ) -> Result<S, TssError> {
         assert_eq!(decom_vec2.len(), decom_vec1.len());
         assert_eq!(decom_vec2.len(), com_vec2.len());
 
-        let test_com = (0..com_vec2.len())
-            .map(|i| {
+        // blame is reported by position in the given vectors
+        let blames = (0..com_vec2.len())
+            .filter(|i| {
                 let input_hash =
-                    HSha256::create_hash_from_ge(&[&decom_vec2[i].u_i, &decom_vec2[i].t_i])
+                    HSha256::create_hash_from_ge::<S>(&[&decom_vec2[*i].u_i, &decom_vec2[*i].t_i])
                         .to_big_int();
                 HashCommitment::create_commitment_with_user_defined_randomness(
                     &input_hash,
-                    &decom_vec2[i].blind_factor,
-                ) == com_vec2[i].com
+                    &decom_vec2[*i].blind_factor,
+                ) != com_vec2[*i].com
             })
-            .all(|x| x == true);
+            .map(|i| Blame::new(i, Phase5DDecommitment))
+            .collect::<Vec<Blame>>();
 
         let t_vec = (0..com_vec2.len())
             .map(|i| &decom_vec2[i].t_i)
-            .collect::<Vec<&GE>>();
+            .collect::<Vec<&S::Point>>();
         let u_vec = (0..com_vec2.len())
             .map(|i| &decom_vec2[i].u_i)
-            .collect::<Vec<&GE>>();
+            .collect::<Vec<&S::Point>>();
         let b_vec = (0..decom_vec1.len())
             .map(|i| &decom_vec1[i].B_i)
-            .collect::<Vec<&GE>>();
+            .collect::<Vec<&S::Point>>();
 
-        let g: GE = ECPoint::generator();
+        let g: S::Point = ECPoint::generator();
         let biased_sum_tb = t_vec
             .iter()
             .zip(b_vec)
@@ -630,20 +1362,21 @@ impl LocalSignature {
         let biased_sum_tb_minus_u = u_vec
             .iter()
             .fold(biased_sum_tb, |acc, x| acc.sub_point(&x.get_element()));
-        match test_com {
+        match blames.is_empty() {
             true => {
+                // a failure here cannot be attributed to a single party in GG18
                 if g == biased_sum_tb_minus_u {
                     Ok(self.s_i.clone())
                 } else {
                     Err(InvalidKey)
                 }
             }
-            false => Err(InvalidCom),
+            false => Err(TssError::Blame(blames)),
         }
     }
-    pub fn output_signature(&self, s_vec: &Vec<FE>) -> Result<Signature, TssError> {
+    pub fn output_signature(&self, s_vec: &Vec<S>) -> Result<Signature<S>, TssError> {
         let mut s = s_vec.iter().fold(self.s_i.clone(), |acc, x| acc + x);
-        let r: FE = ECScalar::from(&self.R.x_coor().unwrap().mod_floor(&FE::q()));
+        let r: S = ECScalar::from(&self.R.x_coor().unwrap().mod_floor(&S::q()));
 
         /*
          Calculate recovery id - it is not possible to compute the public key out of the signature
@@ -655,11 +1388,11 @@ impl LocalSignature {
             .R
             .y_coor()
             .ok_or(TssError::InvalidSig)?
-            .mod_floor(&FE::q());
+            .mod_floor(&S::q());
         let is_ry_odd = ry.test_bit(0);
         let mut recid = if is_ry_odd { 1 } else { 0 };
         let s_bn = s.clone().to_big_int();
-        let s_tag_bn = &FE::q() - &s_bn;
+        let s_tag_bn = &S::q() - &s_bn;
         if s_bn > s_tag_bn {
             s = ECScalar::from(&s_tag_bn);
             recid ^= 1;
@@ -674,17 +1407,120 @@ impl LocalSignature {
     }
 }
 
-pub fn verify(sig: &Signature, y: &GE, message: &BigInt) -> Result<(), TssError> {
+/*
+    Presigning
+
+    R, k_i and sigma_i do not depend on the message, so the signing rounds up
+    to phase 4 can run ahead of time. Each signer then publishes R_i = R^k_i
+    and S_i = R^sigma_i. As R = g^(1/k), the R_i add up to g and the S_i to y.
+    These sums alone prove nothing, as the last signer to publish can pick its
+    R_j and S_j to match them: the R_j and S_j must come with the proofs of
+    phases 5 and 6 of GG20, which presigning runs (see gg_2020::party_i).
+    Once the message is known, s_i = m * k_i + r * sigma_i is sent in a single
+    round and checked with R^s_i = R_i^m * S_i^r, which replaces phase 5. A
+    presignature must never sign two messages, as two s_i for the same k_i
+    give away sigma_i. This is why s_i is only released by a
+    PresignatureStore, which consumes the presignature first.
+*/
+impl<S> Presignature<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    pub fn phase1_broadcast(k_i: &S, sigma_i: &S, R: &S::Point) -> PresignBroadcastMessage<S> {
+        PresignBroadcastMessage {
+            R_i: R.clone() * k_i,
+            S_i: R.clone() * sigma_i,
+        }
+    }
+
+    /// Checks the R_j and S_j of every signer, in signing order, against g and y.
+    /// A failure here cannot be attributed to a single party. The proofs of the R_j and
+    /// S_j are checked by the caller, as `SignBroadcastPhase6::verify_create_presignature`
+    /// of GG20 does.
+    pub fn phase1_verify_create(
+        k_i: &S,
+        sigma_i: &S,
+        R: &S::Point,
+        y: &S::Point,
+        presign_vec: &[PresignBroadcastMessage<S>],
+    ) -> Result<Presignature<S>, TssError> {
+        let g: S::Point = ECPoint::generator();
+        let head = presign_vec.first().cloned().ok_or_else(|| {
+            TssError::UnexpectedMessage("no R_j and S_j of presigning".to_string())
+        })?;
+        let (sum_R_i, sum_S_i) = presign_vec[1..]
+            .iter()
+            .fold((head.R_i, head.S_i), |(R_acc, S_acc), msg| {
+                (R_acc + &msg.R_i, S_acc + &msg.S_i)
+            });
+        if sum_R_i != g || sum_S_i != *y {
+            return Err(InvalidKey);
+        }
+
+        Ok(Presignature {
+            R: R.clone(),
+            k_i: k_i.clone(),
+            sigma_i: sigma_i.clone(),
+            y: y.clone(),
+            presign_vec: presign_vec.to_vec(),
+        })
+    }
+
+    /// The local signature, whose s_i is broadcast in the online round.
+    pub(crate) fn phase2_local_sig(&self, message: &BigInt) -> LocalSignature<S> {
+        LocalSignature::phase5_local_sig(&self.k_i, message, &self.R, &self.sigma_i, &self.y)
+    }
+
+    /// Checks the s_j of every signer, in signing order, and adds them up to the signature.
+    /// Blames refer to the positions in the signing order.
+    pub fn phase2_output_signature(
+        &self,
+        message: &BigInt,
+        s_vec: &[S],
+    ) -> Result<Signature<S>, TssError> {
+        if s_vec.len() != self.presign_vec.len() {
+            return Err(TssError::UnexpectedMessage(format!(
+                "{} shares of the online round for {} signers",
+                s_vec.len(),
+                self.presign_vec.len()
+            )));
+        }
+        let m_fe: S = ECScalar::from(message);
+        let r: S = ECScalar::from(&self.R.x_coor().unwrap().mod_floor(&S::q()));
+        let blames = (0..s_vec.len())
+            .filter(|&j| {
+                let msg = &self.presign_vec[j];
+                self.R.clone() * &s_vec[j] != msg.R_i.clone() * &m_fe + msg.S_i.clone() * &r
+            })
+            .map(|j| Blame::new(j, OnlineSigShare))
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let local_sig = LocalSignature {
+            s_i: s_vec[0].clone(),
+            ..self.phase2_local_sig(message)
+        };
+        local_sig.output_signature(&s_vec[1..].to_vec())
+    }
+}
+
+pub fn verify<S>(sig: &Signature<S>, y: &S::Point, message: &BigInt) -> Result<(), TssError>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
     let b = sig.s.invert();
-    let a: FE = ECScalar::from(message);
+    let a: S = ECScalar::from(message);
     let u1 = a * &b;
     let u2 = sig.r.clone() * &b;
 
-    let g: GE = ECPoint::generator();
-    let gu1 = &g * &u1;
-    let yu2 = y * &u2;
+    let g: S::Point = ECPoint::generator();
+    let gu1 = g * &u1;
+    let yu2 = y.clone() * &u2;
     // can be faster using shamir trick
-    if sig.r.clone() == ECScalar::from(&(gu1 + yu2).x_coor().unwrap().mod_floor(&FE::q())) {
+    let rx: S = ECScalar::from(&(gu1 + yu2).x_coor().unwrap().mod_floor(&S::q()));
+    if sig.r == rx {
         Ok(())
     } else {
         Err(InvalidSig)
@@ -0,0 +1,181 @@
+//! Storage of presignatures that hands each one out at most once.
+//!
+//! Two online signatures made with the same presignature give away the signer's
+//! share of the key. A store therefore marks a presignature consumed before its
+//! `s_i` is computed, and keeps the mark so that the IDs can never be filled again.
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::errors::{Result, TssError};
+use crate::gg_2018::party_i::{LocalSignature, Presignature};
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+pub trait PresignatureStore {
+    /// Stores a serialized presignature. Fails if the IDs were used before, consumed or not.
+    fn insert(&self, key_id: &str, presign_id: &str, presignature: String) -> Result<()>;
+
+    /// Marks a presignature consumed and returns it. Only the first call for the IDs succeeds.
+    fn consume(&self, key_id: &str, presign_id: &str) -> Result<String>;
+
+    /// Whether a presignature is stored under the IDs and not yet consumed.
+    fn is_available(&self, key_id: &str, presign_id: &str) -> Result<bool>;
+
+    fn insert_presignature(
+        &self,
+        key_id: &str,
+        presign_id: &str,
+        presignature: Presignature,
+    ) -> Result<()> {
+        self.insert(key_id, presign_id, serde_json::to_string(&presignature)?)
+    }
+
+    /// Consumes a presignature and signs `message` with it. The presignature is returned
+    /// to check the shares of the other signers with `phase2_output_signature`.
+    fn consume_local_sig(
+        &self,
+        key_id: &str,
+        presign_id: &str,
+        message: &BigInt,
+    ) -> Result<(Presignature, LocalSignature)> {
+        let presignature: Presignature = serde_json::from_str(&self.consume(key_id, presign_id)?)?;
+        let local_sig = presignature.phase2_local_sig(message);
+        Ok((presignature, local_sig))
+    }
+}
+
+/// Keeps presignatures for the lifetime of the process.
+#[derive(Default)]
+pub struct MemoryPresignatureStore {
+    // None once consumed
+    entries: Mutex<HashMap<(String, String), Option<String>>>,
+}
+
+impl MemoryPresignatureStore {
+    pub fn new() -> MemoryPresignatureStore {
+        MemoryPresignatureStore::default()
+    }
+}
+
+impl PresignatureStore for MemoryPresignatureStore {
+    fn insert(&self, key_id: &str, presign_id: &str, presignature: String) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        let id = (key_id.to_string(), presign_id.to_string());
+        if entries.contains_key(&id) {
+            return Err(TssError::PresignatureReuse);
+        }
+        entries.insert(id, Some(presignature));
+        Ok(())
+    }
+
+    fn consume(&self, key_id: &str, presign_id: &str) -> Result<String> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get_mut(&(key_id.to_string(), presign_id.to_string())) {
+            Some(entry) => entry.take().ok_or(TssError::PresignatureReuse),
+            None => Err(TssError::PresignatureNotFound),
+        }
+    }
+
+    fn is_available(&self, key_id: &str, presign_id: &str) -> Result<bool> {
+        let entries = self.entries.lock().unwrap();
+        Ok(matches!(
+            entries.get(&(key_id.to_string(), presign_id.to_string())),
+            Some(Some(_))
+        ))
+    }
+}
+
+/// Keeps each presignature in `<dir>/<key_id>/<presign_id>.json`.
+///
+/// Consuming renames the file to `<presign_id>.consumed`, which succeeds for one caller
+/// only, also across processes. The renamed file is emptied and left as a marker.
+pub struct FilePresignatureStore {
+    dir: PathBuf,
+}
+
+impl FilePresignatureStore {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> FilePresignatureStore {
+        FilePresignatureStore { dir: dir.into() }
+    }
+
+    fn path(&self, key_id: &str, presign_id: &str, extension: &str) -> Result<PathBuf> {
+        for id in [key_id, presign_id] {
+            let valid = !id.is_empty()
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                return Err(TssError::InvalidPresignatureId(id.to_string()));
+            }
+        }
+        Ok(self
+            .dir
+            .join(key_id)
+            .join(format!("{}.{}", presign_id, extension)))
+    }
+}
+
+impl PresignatureStore for FilePresignatureStore {
+    fn insert(&self, key_id: &str, presign_id: &str, presignature: String) -> Result<()> {
+        let path = self.path(key_id, presign_id, "json")?;
+        if self.path(key_id, presign_id, "consumed")?.exists() {
+            return Err(TssError::PresignatureReuse);
+        }
+        fs::create_dir_all(path.parent().unwrap())?;
+
+        // written in full before it shows up under its name
+        let tmp_path = self.path(key_id, presign_id, "tmp")?;
+        let mut tmp = match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)
+        {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                return Err(TssError::PresignatureReuse)
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let written = tmp
+            .write_all(presignature.as_bytes())
+            .and_then(|_| tmp.sync_all());
+        let linked = written.and_then(|_| fs::hard_link(&tmp_path, &path));
+        fs::remove_file(&tmp_path)?;
+        match linked {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => Err(TssError::PresignatureReuse),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn consume(&self, key_id: &str, presign_id: &str) -> Result<String> {
+        let path = self.path(key_id, presign_id, "json")?;
+        let consumed_path = self.path(key_id, presign_id, "consumed")?;
+        if let Err(e) = fs::rename(&path, &consumed_path) {
+            if e.kind() != ErrorKind::NotFound {
+                return Err(e.into());
+            }
+            if consumed_path.exists() {
+                return Err(TssError::PresignatureReuse);
+            }
+            return Err(TssError::PresignatureNotFound);
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&consumed_path)?;
+        let mut presignature = String::new();
+        file.read_to_string(&mut presignature)?;
+        file.set_len(0)?;
+        file.sync_all()?;
+        Ok(presignature)
+    }
+
+    fn is_available(&self, key_id: &str, presign_id: &str) -> Result<bool> {
+        Ok(self.path(key_id, presign_id, "json")?.exists())
+    }
+}
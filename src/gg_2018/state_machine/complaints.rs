@@ -0,0 +1,200 @@
+//! The complaint rounds that follow the sharing of a secret in keygen, refresh and CGGMP21
+//! keygen: every party broadcasts the dealers whose share it rejects, and each accused
+//! dealer answers by revealing the disputed shares.
+
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::curv::elliptic::curves::secp256_k1::FE;
+use crate::errors::{Result, TssError};
+use crate::gg_2018::party_i::{KeyGenComplaint, KeyGenShareReveal, Keys};
+use crate::gg_2018::state_machine::{
+    broadcast_msg, check_incoming, other_party_num, take_round, Msg, StateMachine,
+};
+
+const ROUNDS: [&str; 2] = ["round4_complaints", "round4_reveals"];
+
+/// The complaint rounds of party `party_num`, which dealt `secret_shares` and received
+/// `party_shares` under `vss_scheme_vec`. The output is the shares with the disputed ones
+/// replaced by the revealed ones, and the dealers that did not clear the complaints are
+/// blamed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Complaints {
+    party_num: u16,
+    /// the number of rounds sent, the next one to receive is `ROUNDS[sent - 1]`
+    sent: usize,
+    received: Vec<Msg>,
+    party_shares: Vec<FE>,
+    secret_shares: Vec<FE>,
+    vss_scheme_vec: Vec<VerifiableSS>,
+    complaints: Option<Vec<KeyGenComplaint>>,
+    reveals: Option<Vec<KeyGenShareReveal>>,
+    output: Option<Vec<FE>>,
+}
+
+impl Complaints {
+    pub fn new(
+        party_num: u16,
+        party_shares: Vec<FE>,
+        secret_shares: Vec<FE>,
+        vss_scheme_vec: Vec<VerifiableSS>,
+    ) -> Result<Complaints> {
+        let n = vss_scheme_vec.len();
+        if party_shares.len() != n || secret_shares.len() != n {
+            return Err(TssError::UnexpectedMessage(format!(
+                "{} shares received and {} dealt for {} VSS schemes",
+                party_shares.len(),
+                secret_shares.len(),
+                n
+            )));
+        }
+        if party_num == 0 || usize::from(party_num) > n {
+            return Err(TssError::UnknownError {
+                msg: format!("party number {} out of 1..={}", party_num, n),
+                line: line!(),
+            });
+        }
+        Ok(Complaints {
+            party_num,
+            sent: 0,
+            received: Vec::new(),
+            party_shares,
+            secret_shares,
+            vss_scheme_vec,
+            complaints: None,
+            reveals: None,
+            output: None,
+        })
+    }
+
+    /// Broadcasts the dealers whose share we reject.
+    pub(super) fn complain(&mut self) -> Result<Vec<Msg>> {
+        let complaints_i = Keys::phase2_complaints(
+            &self.party_shares,
+            &self.vss_scheme_vec,
+            usize::from(self.party_num),
+        );
+        let msg = broadcast_msg(
+            self.party_num,
+            "round4_complaints",
+            serde_json::to_string(&complaints_i)?,
+        );
+        self.complaints = Some(complaints_i);
+        Ok(vec![msg])
+    }
+
+    /// Collects the complaints of the other parties and answers those about our shares.
+    pub(super) fn reveal(&mut self, bodies: &[String]) -> Result<Vec<Msg>> {
+        let share_count = self.vss_scheme_vec.len();
+        let complaints = self.complaints.as_mut().unwrap();
+        for (j, body) in bodies.iter().enumerate() {
+            let sender = other_party_num(self.party_num, j);
+            let complaints_j: Vec<KeyGenComplaint> = serde_json::from_str(body)?;
+            complaints.extend(
+                complaints_j
+                    .into_iter()
+                    .filter(|complaint| complaint.dealer < share_count)
+                    .map(|complaint| KeyGenComplaint {
+                        index: usize::from(sender),
+                        ..complaint
+                    }),
+            );
+        }
+
+        let reveals_i = Keys::phase2_answer_complaints(
+            usize::from(self.party_num - 1),
+            &self.secret_shares,
+            complaints,
+        );
+        let msg = broadcast_msg(
+            self.party_num,
+            "round4_reveals",
+            serde_json::to_string(&reveals_i)?,
+        );
+        self.reveals = Some(reveals_i);
+        Ok(vec![msg])
+    }
+
+    /// Collects the answers of the other parties and settles the complaints with them.
+    pub(super) fn resolve(&mut self, bodies: &[String]) -> Result<Vec<FE>> {
+        let mut reveals = self.reveals.take().unwrap();
+        for (j, body) in bodies.iter().enumerate() {
+            let sender = other_party_num(self.party_num, j);
+            let reveals_j: Vec<KeyGenShareReveal> = serde_json::from_str(body)?;
+            reveals.extend(reveals_j.into_iter().map(|reveal| KeyGenShareReveal {
+                dealer: usize::from(sender) - 1,
+                ..reveal
+            }));
+        }
+
+        Keys::phase2_resolve_complaints(
+            &self.party_shares,
+            &self.vss_scheme_vec,
+            self.complaints.as_ref().unwrap(),
+            &reveals,
+            usize::from(self.party_num),
+        )
+        .map_err(|e| e.map_blamed_parties(|i| i + 1))
+    }
+}
+
+impl StateMachine for Complaints {
+    type Output = Vec<FE>;
+
+    fn handle_incoming(&mut self, msg: Msg) -> Result<Vec<Msg>> {
+        let next = self.sent.saturating_sub(1);
+        check_incoming(
+            &msg,
+            self.party_num,
+            self.n(),
+            &ROUNDS,
+            next,
+            &self.received,
+        )?;
+        self.received.push(msg);
+        self.proceed()
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Msg>> {
+        let mut outgoing = Vec::new();
+        while !self.is_finished() {
+            let bodies = if self.sent == 0 {
+                Vec::new()
+            } else {
+                let round = ROUNDS[self.sent - 1];
+                match take_round(&mut self.received, self.party_num, self.n(), round) {
+                    Some(bodies) => bodies,
+                    None => break,
+                }
+            };
+            match self.sent {
+                0 => outgoing.extend(self.complain()?),
+                1 => outgoing.extend(self.reveal(&bodies)?),
+                _ => self.output = Some(self.resolve(&bodies)?),
+            }
+            self.sent += 1;
+        }
+        Ok(outgoing)
+    }
+
+    fn current_round(&self) -> Option<&'static str> {
+        match self.sent {
+            0 => None,
+            sent => ROUNDS.get(sent - 1).copied(),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.output.is_some()
+    }
+
+    fn output(&self) -> Option<&Self::Output> {
+        self.output.as_ref()
+    }
+
+    fn n(&self) -> u16 {
+        self.vss_scheme_vec.len() as u16
+    }
+
+    fn party_num(&self) -> u16 {
+        self.party_num
+    }
+}
@@ -0,0 +1,331 @@
+//! GG18 keygen, with the complaint rounds of the wasm API.
+
+use crate::common::{aes_decrypt, aes_encrypt, aes_key, AEAD};
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::Converter;
+use crate::curv::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::{Result, TssError};
+use crate::gg_2018::bip32;
+use crate::gg_2018::party_i::{
+    KeyGenBroadcastMessage1, KeyGenDecommitMessage1, Keys, Parameters, SharedKeys,
+};
+use crate::gg_2018::state_machine::{
+    broadcast_msg, check_incoming, other_party_num, p2p_msg, parse_bodies, take_round, with_own,
+    Complaints, KeyStore, Msg, StateMachine,
+};
+
+const ROUNDS: [&str; 7] = [
+    "round1",
+    "round2",
+    "round3",
+    "round4",
+    "round4_complaints",
+    "round4_reveals",
+    "round5",
+];
+
+/// Keygen of party `party_num` among `params.share_count` parties.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Keygen {
+    params: Parameters,
+    party_num: u16,
+    /// the number of rounds sent, the next one to receive is `ROUNDS[sent - 1]`
+    sent: usize,
+    received: Vec<Msg>,
+    party_keys: Option<Keys>,
+    bc1_vec: Option<Vec<KeyGenBroadcastMessage1>>,
+    decom_i: Option<KeyGenDecommitMessage1>,
+    decom_vec: Option<Vec<KeyGenDecommitMessage1>>,
+    enc_keys: Option<Vec<Vec<u8>>>,
+    secret_shares: Option<Vec<FE>>,
+    party_shares: Option<Vec<FE>>,
+    vss_scheme_vec: Option<Vec<VerifiableSS>>,
+    complaints: Option<Complaints>,
+    shared_keys: Option<SharedKeys>,
+    dlog_proof: Option<DLogProof>,
+    output: Option<KeyStore>,
+}
+
+impl Keygen {
+    pub fn new(params: Parameters, party_num: u16) -> Result<Keygen> {
+        if party_num == 0 || usize::from(party_num) > params.share_count {
+            return Err(TssError::UnknownError {
+                msg: format!(
+                    "party number {} out of 1..={}",
+                    party_num, params.share_count
+                ),
+                line: line!(),
+            });
+        }
+        Ok(Keygen {
+            params,
+            party_num,
+            sent: 0,
+            received: Vec::new(),
+            party_keys: None,
+            bc1_vec: None,
+            decom_i: None,
+            decom_vec: None,
+            enc_keys: None,
+            secret_shares: None,
+            party_shares: None,
+            vss_scheme_vec: None,
+            complaints: None,
+            shared_keys: None,
+            dlog_proof: None,
+            output: None,
+        })
+    }
+}
+
+impl StateMachine for Keygen {
+    type Output = KeyStore;
+
+    fn handle_incoming(&mut self, msg: Msg) -> Result<Vec<Msg>> {
+        let next = self.sent.saturating_sub(1);
+        check_incoming(
+            &msg,
+            self.party_num,
+            self.n(),
+            &ROUNDS,
+            next,
+            &self.received,
+        )?;
+        self.received.push(msg);
+        self.proceed()
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Msg>> {
+        let mut outgoing = Vec::new();
+        while !self.is_finished() {
+            let bodies = if self.sent == 0 {
+                Vec::new()
+            } else {
+                let round = ROUNDS[self.sent - 1];
+                match take_round(&mut self.received, self.party_num, self.n(), round) {
+                    Some(bodies) => bodies,
+                    None => break,
+                }
+            };
+            outgoing.extend(self.proceed_round(bodies)?);
+            self.sent += 1;
+        }
+        Ok(outgoing)
+    }
+
+    fn current_round(&self) -> Option<&'static str> {
+        match self.sent {
+            0 => None,
+            sent => ROUNDS.get(sent - 1).copied(),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.output.is_some()
+    }
+
+    fn output(&self) -> Option<&Self::Output> {
+        self.output.as_ref()
+    }
+
+    fn n(&self) -> u16 {
+        self.params.share_count as u16
+    }
+
+    fn party_num(&self) -> u16 {
+        self.party_num
+    }
+}
+
+impl Keygen {
+    fn proceed_round(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        match self.sent {
+            0 => self.round1(),
+            1 => self.round2(bodies),
+            2 => self.round3(bodies),
+            3 => self.round4(bodies),
+            4 => self.round4_complaints(bodies),
+            5 => self.round4_reveals(bodies),
+            6 => self.round5(bodies),
+            _ => self.finish(bodies),
+        }
+    }
+
+    fn round1(&mut self) -> Result<Vec<Msg>> {
+        let party_keys = Keys::create(usize::from(self.party_num));
+        let (bc_i, decom_i) = party_keys.phase1_broadcast_phase3_proof_of_correct_key();
+        let msg = broadcast_msg(self.party_num, "round1", serde_json::to_string(&bc_i)?);
+        self.bc1_vec = Some(vec![bc_i]);
+        self.decom_i = Some(decom_i);
+        self.party_keys = Some(party_keys);
+        Ok(vec![msg])
+    }
+
+    fn round2(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let bc1_vec = parse_bodies::<KeyGenBroadcastMessage1>(&bodies)?;
+        let bc_i = self.bc1_vec.take().unwrap().remove(0);
+        let bc1_vec = with_own(bc1_vec, self.party_num, bc_i);
+
+        let decom_i = self.decom_i.as_mut().unwrap();
+        decom_i.no_small_factor_proofs = self
+            .party_keys
+            .as_ref()
+            .unwrap()
+            .phase1_no_small_factor_proofs(&bc1_vec);
+        let msg = broadcast_msg(self.party_num, "round2", serde_json::to_string(decom_i)?);
+        self.bc1_vec = Some(bc1_vec);
+        Ok(vec![msg])
+    }
+
+    fn round3(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let party_keys = self.party_keys.as_ref().unwrap();
+        let decom_vec = parse_bodies::<KeyGenDecommitMessage1>(&bodies)?;
+        let enc_keys = decom_vec
+            .iter()
+            .map(|decom_j| aes_key(&decom_j.y_i, &party_keys.u_i))
+            .collect::<Vec<Vec<u8>>>();
+        let decom_vec = with_own(decom_vec, self.party_num, self.decom_i.clone().unwrap());
+
+        let (vss_scheme, secret_shares, _index) = party_keys
+            .phase1_verify_com_phase3_verify_correct_key_phase2_distribute(
+                &self.params,
+                &decom_vec,
+                self.bc1_vec.as_ref().unwrap(),
+            )
+            .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+
+        let mut outgoing = Vec::new();
+        for (j, key_j) in enc_keys.iter().enumerate() {
+            let i = other_party_num(self.party_num, j);
+            let plaintext = BigInt::to_vec(&secret_shares[usize::from(i - 1)].to_big_int());
+            let aead_pack = aes_encrypt(key_j, &plaintext)?;
+            outgoing.push(p2p_msg(
+                self.party_num,
+                i,
+                "round3",
+                serde_json::to_string(&aead_pack)?,
+            ));
+        }
+
+        self.vss_scheme_vec = Some(vec![vss_scheme]);
+        self.secret_shares = Some(secret_shares);
+        self.enc_keys = Some(enc_keys);
+        self.decom_vec = Some(decom_vec);
+        Ok(outgoing)
+    }
+
+    fn round4(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let enc_keys = self.enc_keys.as_ref().unwrap();
+        let party_shares = bodies
+            .iter()
+            .zip(enc_keys)
+            .map(|(body, key_j)| -> Result<FE> {
+                let aead_pack: AEAD = serde_json::from_str(body)?;
+                // a share that does not decrypt is disputed in round 4 like any invalid share
+                Ok(match aes_decrypt(key_j, aead_pack) {
+                    Ok(out) => ECScalar::from(&BigInt::from_bytes_be(&out[..])),
+                    Err(_) => ECScalar::zero(),
+                })
+            })
+            .collect::<Result<Vec<FE>>>()?;
+        let own_share =
+            self.secret_shares.as_ref().unwrap()[usize::from(self.party_num - 1)].clone();
+        self.party_shares = Some(with_own(party_shares, self.party_num, own_share));
+
+        let vss_scheme = &self.vss_scheme_vec.as_ref().unwrap()[0];
+        let msg = broadcast_msg(self.party_num, "round4", serde_json::to_string(vss_scheme)?);
+        Ok(vec![msg])
+    }
+
+    fn round4_complaints(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let vss_scheme_vec = parse_bodies::<VerifiableSS>(&bodies)?;
+        let vss_scheme_i = self.vss_scheme_vec.take().unwrap().remove(0);
+        let vss_scheme_vec = with_own(vss_scheme_vec, self.party_num, vss_scheme_i);
+
+        let mut complaints = Complaints::new(
+            self.party_num,
+            self.party_shares.clone().unwrap(),
+            self.secret_shares.clone().unwrap(),
+            vss_scheme_vec.clone(),
+        )?;
+        let outgoing = complaints.complain()?;
+        self.vss_scheme_vec = Some(vss_scheme_vec);
+        self.complaints = Some(complaints);
+        Ok(outgoing)
+    }
+
+    fn round4_reveals(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        self.complaints.as_mut().unwrap().reveal(&bodies)
+    }
+
+    fn round5(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let party_shares = self.complaints.take().unwrap().resolve(&bodies)?;
+
+        let index = usize::from(self.party_num);
+        let vss_scheme_vec = self.vss_scheme_vec.as_ref().unwrap();
+        let (shared_keys, dlog_proof) = self
+            .party_keys
+            .as_ref()
+            .unwrap()
+            .phase2_verify_vss_construct_keypair_phase3_pok_dlog(
+                &self.params,
+                &self.y_vec(),
+                &party_shares,
+                vss_scheme_vec,
+                &index,
+            )
+            .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+        let msg = broadcast_msg(
+            self.party_num,
+            "round5",
+            serde_json::to_string(&dlog_proof)?,
+        );
+
+        self.party_shares = Some(party_shares);
+        self.shared_keys = Some(shared_keys);
+        self.dlog_proof = Some(dlog_proof);
+        Ok(vec![msg])
+    }
+
+    fn finish(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let dlog_proof_vec = parse_bodies::<DLogProof>(&bodies)?;
+        let dlog_proof_vec = with_own(
+            dlog_proof_vec,
+            self.party_num,
+            self.dlog_proof.clone().unwrap(),
+        );
+        Keys::verify_dlog_proofs(&self.params, &dlog_proof_vec, &self.y_vec())
+            .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+
+        let bc1_vec = self.bc1_vec.as_ref().unwrap();
+        let shared_keys = self.shared_keys.clone().unwrap();
+        let y_sum = shared_keys.y.clone();
+        self.output = Some((
+            self.party_keys.clone().unwrap(),
+            shared_keys,
+            self.party_num,
+            self.vss_scheme_vec.clone().unwrap(),
+            bc1_vec.iter().map(|bc1| bc1.e.clone()).collect(),
+            y_sum,
+            bc1_vec
+                .iter()
+                .map(|bc1| bc1.dlog_statement.clone())
+                .collect(),
+            Some(bip32::chain_code(self.decom_vec.as_ref().unwrap())),
+        ));
+        Ok(Vec::new())
+    }
+
+    /// The y_i of every party, from the decommitments of round 2.
+    fn y_vec(&self) -> Vec<GE> {
+        self.decom_vec
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|decom| decom.y_i.clone())
+            .collect()
+    }
+}
@@ -0,0 +1,169 @@
+/*
+    Sans-IO state machines of GG18 keygen, refresh and signing, and of the complaint
+    rounds
+
+    A machine does no I/O. `proceed` makes the messages of the next round once
+    all the messages of the current one have arrived, and `handle_incoming`
+    stores a message of another party and proceeds. The driver delivers the
+    messages the machine returns, e.g. through a `Transport`, and feeds it the
+    messages of the other parties in any order. Rounds and bodies are those of
+    the wasm API, so that a machine can take part in a session with its
+    clients. A machine that returned an error is done with.
+*/
+
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::curv::elliptic::curves::secp256_k1::GE;
+use crate::errors::{Result, TssError};
+use crate::gg_2018::bip32::ChainCode;
+use crate::gg_2018::party_i::{Keys, SharedKeys};
+use crate::paillier::zkproofs::DLogStatement;
+use crate::paillier::EncryptionKey;
+use serde::de::DeserializeOwned;
+
+pub mod complaints;
+pub mod keygen;
+pub mod refresh;
+pub mod sign;
+
+pub use complaints::Complaints;
+pub use keygen::Keygen;
+pub use refresh::Refresh;
+pub use sign::{OfflineStage, Sign};
+
+/// The output of keygen: the keys of the party, its shared keys, its party number, the VSS
+/// schemes, Paillier keys and h1,h2,N_tilde of all parties, y_sum and the BIP-32 chain code.
+pub type KeyStore = (
+    Keys,
+    SharedKeys,
+    u16,
+    Vec<VerifiableSS>,
+    Vec<EncryptionKey>,
+    GE,
+    Vec<DLogStatement>,
+    Option<ChainCode>,
+);
+
+/// A message of party `sender` for `round`, to party `receiver` or to all the other parties.
+/// The body is the JSON the wasm API sends in that round.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Msg {
+    pub sender: u16,
+    pub receiver: Option<u16>,
+    pub round: String,
+    pub body: String,
+}
+
+/// A party of a protocol, numbered from 1 among parties 1..=n.
+pub trait StateMachine {
+    type Output;
+
+    /// Stores a message of another party, and proceeds if it completes the current round.
+    fn handle_incoming(&mut self, msg: Msg) -> Result<Vec<Msg>>;
+
+    /// Makes the messages of as many rounds as the messages received so far allow, starting
+    /// with the first round on a new machine.
+    fn proceed(&mut self) -> Result<Vec<Msg>>;
+
+    /// The round the machine waits for the messages of, if any.
+    fn current_round(&self) -> Option<&'static str>;
+
+    fn is_finished(&self) -> bool;
+
+    fn output(&self) -> Option<&Self::Output>;
+
+    fn n(&self) -> u16;
+
+    fn party_num(&self) -> u16;
+}
+
+/// Checks that `msg` is for party `party_num` of parties 1..=n, and for a round from
+/// `rounds[next..]` it did not already get a message of.
+fn check_incoming(
+    msg: &Msg,
+    party_num: u16,
+    n: u16,
+    rounds: &[&str],
+    next: usize,
+    received: &[Msg],
+) -> Result<()> {
+    let unexpected = |reason: &str| {
+        Err(TssError::UnexpectedMessage(format!(
+            "{} of party {} for {}",
+            reason, msg.sender, msg.round
+        )))
+    };
+    if msg.sender == 0 || msg.sender > n || msg.sender == party_num {
+        return unexpected("unknown sender");
+    }
+    if msg.receiver.map_or(false, |receiver| receiver != party_num) {
+        return unexpected("message to another party");
+    }
+    match rounds.iter().position(|&round| round == msg.round) {
+        Some(position) if position >= next => {}
+        _ => return unexpected("message out of the remaining rounds"),
+    }
+    if received
+        .iter()
+        .any(|m| m.sender == msg.sender && m.round == msg.round)
+    {
+        return unexpected("second message");
+    }
+    Ok(())
+}
+
+/// The bodies of `round` of the parties 1..=n but `party_num`, in the order of the parties,
+/// once they have all arrived.
+fn take_round(received: &mut Vec<Msg>, party_num: u16, n: u16, round: &str) -> Option<Vec<String>> {
+    let count = received.iter().filter(|m| m.round == round).count();
+    if count < usize::from(n) - 1 {
+        return None;
+    }
+    let (mut msgs, rest): (Vec<Msg>, Vec<Msg>) = received.drain(..).partition(|m| m.round == round);
+    *received = rest;
+    msgs.sort_by_key(|m| m.sender);
+    debug_assert!(msgs.iter().all(|m| m.sender != party_num));
+    Some(msgs.into_iter().map(|m| m.body).collect())
+}
+
+/// Parses the bodies of a round.
+fn parse_bodies<T: DeserializeOwned>(bodies: &[String]) -> Result<Vec<T>> {
+    Ok(bodies
+        .iter()
+        .map(|body| serde_json::from_str(body))
+        .collect::<serde_json::Result<Vec<T>>>()?)
+}
+
+/// Party number of entry `j` of a vector that holds the messages of every party but us.
+pub fn other_party_num(party_num: u16, j: usize) -> u16 {
+    if j + 1 < usize::from(party_num) {
+        j as u16 + 1
+    } else {
+        j as u16 + 2
+    }
+}
+
+/// The message of `round` from `party_num` to all the other parties.
+fn broadcast_msg(party_num: u16, round: &str, body: String) -> Msg {
+    Msg {
+        sender: party_num,
+        receiver: None,
+        round: round.to_string(),
+        body,
+    }
+}
+
+/// The message of `round` from `party_num` to `receiver` only.
+fn p2p_msg(party_num: u16, receiver: u16, round: &str, body: String) -> Msg {
+    Msg {
+        sender: party_num,
+        receiver: Some(receiver),
+        round: round.to_string(),
+        body,
+    }
+}
+
+/// Inserts our own entry into a vector of the messages of the other parties.
+fn with_own<T>(mut others: Vec<T>, party_num: u16, own: T) -> Vec<T> {
+    others.insert(usize::from(party_num) - 1, own);
+    others
+}
@@ -0,0 +1,296 @@
+//! GG18 refresh: new Paillier keys and h1, h2, N_tilde, and shares moved by a sharing of
+//! zero, with the rounds of the wasm API.
+
+use crate::common::{aes_decrypt, aes_encrypt, aes_key, AEAD};
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::Converter;
+use crate::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use crate::curv::elliptic::curves::secp256_k1::FE;
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::{Result, TssError};
+use crate::gg_2018::party_i::{KeyGenBroadcastMessage1, KeyGenDecommitMessage1, Keys, Parameters};
+use crate::gg_2018::state_machine::{
+    broadcast_msg, check_incoming, other_party_num, p2p_msg, parse_bodies, take_round, with_own,
+    Complaints, KeyStore, Msg, StateMachine,
+};
+
+const ROUNDS: [&str; 6] = [
+    "round1",
+    "round2",
+    "round3",
+    "round4",
+    "round4_complaints",
+    "round4_reveals",
+];
+
+/// Refresh of the key store of a party, run by all `params.share_count` parties. The output
+/// is a key store of the same key, in the format of keygen.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Refresh {
+    params: Parameters,
+    key_store: KeyStore,
+    /// the number of rounds sent, the next one to receive is `ROUNDS[sent - 1]`
+    sent: usize,
+    received: Vec<Msg>,
+    new_party_keys: Option<Keys>,
+    bc1_vec: Option<Vec<KeyGenBroadcastMessage1>>,
+    decom_i: Option<KeyGenDecommitMessage1>,
+    enc_keys: Option<Vec<Vec<u8>>>,
+    vss_scheme: Option<VerifiableSS>,
+    secret_shares: Option<Vec<FE>>,
+    party_shares: Option<Vec<FE>>,
+    vss_scheme_vec: Option<Vec<VerifiableSS>>,
+    complaints: Option<Complaints>,
+    output: Option<KeyStore>,
+}
+
+impl Refresh {
+    pub fn new(params: Parameters, key_store: KeyStore) -> Result<Refresh> {
+        let party_num = key_store.2;
+        if party_num == 0 || usize::from(party_num) > params.share_count {
+            return Err(TssError::UnknownError {
+                msg: format!(
+                    "party number {} out of 1..={}",
+                    party_num, params.share_count
+                ),
+                line: line!(),
+            });
+        }
+        Ok(Refresh {
+            params,
+            key_store,
+            sent: 0,
+            received: Vec::new(),
+            new_party_keys: None,
+            bc1_vec: None,
+            decom_i: None,
+            enc_keys: None,
+            vss_scheme: None,
+            secret_shares: None,
+            party_shares: None,
+            vss_scheme_vec: None,
+            complaints: None,
+            output: None,
+        })
+    }
+}
+
+impl StateMachine for Refresh {
+    type Output = KeyStore;
+
+    fn handle_incoming(&mut self, msg: Msg) -> Result<Vec<Msg>> {
+        let next = self.sent.saturating_sub(1);
+        check_incoming(
+            &msg,
+            self.party_num(),
+            self.n(),
+            &ROUNDS,
+            next,
+            &self.received,
+        )?;
+        self.received.push(msg);
+        self.proceed()
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Msg>> {
+        let mut outgoing = Vec::new();
+        while !self.is_finished() {
+            let bodies = if self.sent == 0 {
+                Vec::new()
+            } else {
+                let round = ROUNDS[self.sent - 1];
+                match take_round(&mut self.received, self.party_num(), self.n(), round) {
+                    Some(bodies) => bodies,
+                    None => break,
+                }
+            };
+            outgoing.extend(self.proceed_round(bodies)?);
+            self.sent += 1;
+        }
+        Ok(outgoing)
+    }
+
+    fn current_round(&self) -> Option<&'static str> {
+        match self.sent {
+            0 => None,
+            sent => ROUNDS.get(sent - 1).copied(),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.output.is_some()
+    }
+
+    fn output(&self) -> Option<&Self::Output> {
+        self.output.as_ref()
+    }
+
+    fn n(&self) -> u16 {
+        self.params.share_count as u16
+    }
+
+    fn party_num(&self) -> u16 {
+        self.key_store.2
+    }
+}
+
+impl Refresh {
+    fn proceed_round(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        match self.sent {
+            0 => self.round1(),
+            1 => self.round2(bodies),
+            2 => self.round3(bodies),
+            3 => self.round4(bodies),
+            4 => self.round4_complaints(bodies),
+            5 => self.round4_reveals(bodies),
+            _ => self.finish(bodies),
+        }
+    }
+
+    fn round1(&mut self) -> Result<Vec<Msg>> {
+        let new_party_keys = self.key_store.0.refresh();
+        let (bc_i, decom_i) = new_party_keys.phase1_broadcast_phase3_proof_of_correct_key();
+        let msg = broadcast_msg(self.party_num(), "round1", serde_json::to_string(&bc_i)?);
+        self.bc1_vec = Some(vec![bc_i]);
+        self.decom_i = Some(decom_i);
+        self.new_party_keys = Some(new_party_keys);
+        Ok(vec![msg])
+    }
+
+    fn round2(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let bc1_vec = parse_bodies::<KeyGenBroadcastMessage1>(&bodies)?;
+        let bc_i = self.bc1_vec.take().unwrap().remove(0);
+        let bc1_vec = with_own(bc1_vec, self.party_num(), bc_i);
+
+        let decom_i = self.decom_i.as_mut().unwrap();
+        decom_i.no_small_factor_proofs = self
+            .new_party_keys
+            .as_ref()
+            .unwrap()
+            .phase1_no_small_factor_proofs(&bc1_vec);
+        let msg = broadcast_msg(self.party_num(), "round2", serde_json::to_string(decom_i)?);
+        self.bc1_vec = Some(bc1_vec);
+        Ok(vec![msg])
+    }
+
+    fn round3(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let decom_vec = parse_bodies::<KeyGenDecommitMessage1>(&bodies)?;
+        let enc_keys = decom_vec
+            .iter()
+            .map(|decom_j| aes_key(&decom_j.y_i, &self.key_store.0.u_i))
+            .collect::<Vec<Vec<u8>>>();
+        let decom_vec = with_own(decom_vec, self.party_num(), self.decom_i.clone().unwrap());
+
+        self.new_party_keys
+            .as_ref()
+            .unwrap()
+            .phase1_verify_com_phase3_verify_correct_key(
+                &self.params,
+                &decom_vec,
+                self.bc1_vec.as_ref().unwrap(),
+            )
+            .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+        let (vss_scheme, secret_shares) = Keys::refresh_phase2_distribute(&self.params)?;
+
+        let mut outgoing = Vec::new();
+        for (j, key_j) in enc_keys.iter().enumerate() {
+            let i = other_party_num(self.party_num(), j);
+            let plaintext = BigInt::to_vec(&secret_shares[usize::from(i - 1)].to_big_int());
+            let aead_pack = aes_encrypt(key_j, &plaintext)?;
+            outgoing.push(p2p_msg(
+                self.party_num(),
+                i,
+                "round3",
+                serde_json::to_string(&aead_pack)?,
+            ));
+        }
+
+        self.vss_scheme = Some(vss_scheme);
+        self.secret_shares = Some(secret_shares);
+        self.enc_keys = Some(enc_keys);
+        Ok(outgoing)
+    }
+
+    fn round4(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let enc_keys = self.enc_keys.as_ref().unwrap();
+        let party_shares = bodies
+            .iter()
+            .zip(enc_keys)
+            .map(|(body, key_j)| -> Result<FE> {
+                let aead_pack: AEAD = serde_json::from_str(body)?;
+                // a share that does not decrypt is disputed in round 4 like any invalid share
+                Ok(match aes_decrypt(key_j, aead_pack) {
+                    Ok(out) => ECScalar::from(&BigInt::from_bytes_be(&out[..])),
+                    Err(_) => ECScalar::zero(),
+                })
+            })
+            .collect::<Result<Vec<FE>>>()?;
+        let own_share =
+            self.secret_shares.as_ref().unwrap()[usize::from(self.party_num() - 1)].clone();
+        self.party_shares = Some(with_own(party_shares, self.party_num(), own_share));
+
+        let vss_scheme = self.vss_scheme.as_ref().unwrap();
+        let msg = broadcast_msg(
+            self.party_num(),
+            "round4",
+            serde_json::to_string(vss_scheme)?,
+        );
+        Ok(vec![msg])
+    }
+
+    fn round4_complaints(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let vss_scheme_vec = parse_bodies::<VerifiableSS>(&bodies)?;
+        let vss_scheme_vec = with_own(
+            vss_scheme_vec,
+            self.party_num(),
+            self.vss_scheme.clone().unwrap(),
+        );
+
+        let mut complaints = Complaints::new(
+            self.party_num(),
+            self.party_shares.clone().unwrap(),
+            self.secret_shares.clone().unwrap(),
+            vss_scheme_vec.clone(),
+        )?;
+        let outgoing = complaints.complain()?;
+        self.vss_scheme_vec = Some(vss_scheme_vec);
+        self.complaints = Some(complaints);
+        Ok(outgoing)
+    }
+
+    fn round4_reveals(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        self.complaints.as_mut().unwrap().reveal(&bodies)
+    }
+
+    fn finish(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let party_shares = self.complaints.take().unwrap().resolve(&bodies)?;
+
+        let (_, shared_keys, party_num, old_vss_scheme_vec, _, y_sum, _, chain_code) =
+            &self.key_store;
+        let (shared_keys, new_vss_scheme_vec) = Keys::refresh_phase2_update_keypair(
+            &self.params,
+            shared_keys,
+            &party_shares,
+            self.vss_scheme_vec.as_ref().unwrap(),
+            old_vss_scheme_vec,
+            usize::from(*party_num),
+        )
+        .map_err(|e| e.map_blamed_parties(|i| i + 1))?;
+
+        let bc1_vec = self.bc1_vec.as_ref().unwrap();
+        self.output = Some((
+            self.new_party_keys.clone().unwrap(),
+            shared_keys,
+            *party_num,
+            new_vss_scheme_vec,
+            bc1_vec.iter().map(|bc1| bc1.e.clone()).collect(),
+            y_sum.clone(),
+            bc1_vec
+                .iter()
+                .map(|bc1| bc1.dlog_statement.clone())
+                .collect(),
+            chain_code.clone(),
+        ));
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,649 @@
+#![allow(non_snake_case)]
+
+//! GG18 signing by t+1 parties, in the rounds of the wasm API.
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::Converter;
+use crate::curv::cryptographic_primitives::proofs::sigma_correct_homomorphic_elgamal_enc::HomoELGamalProof;
+use crate::curv::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::errors::{Blame, BlameItem, Result, TssError};
+use crate::gg_2018::mta::{MessageA, MessageB};
+use crate::gg_2018::party_i::{
+    verify, Keys, LocalSignature, PartyPrivate, Phase5ADecom1, Phase5Com1, Phase5Com2,
+    Phase5DDecom2, SignBroadcastPhase1, SignDecommitPhase1, SignKeys, Signature,
+};
+use crate::gg_2018::state_machine::{
+    broadcast_msg, check_incoming, other_party_num, p2p_msg, parse_bodies, take_round, with_own,
+    KeyStore, Msg, StateMachine,
+};
+use crate::paillier::zkproofs::DLogStatement;
+
+const ROUNDS: [&str; 10] = [
+    "round0", "round1", "round2", "round3", "round4", "round5", "round6", "round7", "round8",
+    "round9",
+];
+
+type Phase5ADecom = (Phase5ADecom1, HomoELGamalProof, DLogProof);
+
+/// Where a machine of `Sign::offline` stops, for presigning and GG20 to go on from.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum OfflineStage {
+    /// Once the messages B of round2 are checked, with delta_i and sigma_i known, before
+    /// delta_i is broadcast.
+    Mta,
+    /// Once the decommitments of round4 are checked, with R known.
+    R,
+}
+
+/// Signing of `message`, already hashed, by the signer at sign position `party_num` among
+/// the t+1 signers. The output signature is checked against the public key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Sign {
+    key_store: KeyStore,
+    threshold: u16,
+    party_num: u16,
+    message: Vec<u8>,
+    stop: Option<OfflineStage>,
+    /// the number of rounds sent, the next one to receive is `ROUNDS[sent - 1]`
+    sent: usize,
+    received: Vec<Msg>,
+    signers_vec: Option<Vec<usize>>,
+    sign_keys: Option<SignKeys>,
+    xi_com_vec: Option<Vec<GE>>,
+    decommit: Option<SignDecommitPhase1>,
+    m_a_k: Option<MessageA>,
+    m_a_randomness: Option<BigInt>,
+    /// the ciphertexts of the messages A of all the signers, in signing order
+    m_a_c_vec: Option<Vec<BigInt>>,
+    bc1_vec: Option<Vec<SignBroadcastPhase1>>,
+    beta_vec: Option<Vec<FE>>,
+    ni_vec: Option<Vec<FE>>,
+    m_b_gamma_rec_vec: Option<Vec<MessageB>>,
+    /// the alpha_ij of the MtA on gamma, one for every message of `m_b_gamma_rec_vec`
+    alpha_vec: Option<Vec<FE>>,
+    delta_i: Option<FE>,
+    delta_inv: Option<FE>,
+    sigma: Option<FE>,
+    R: Option<GE>,
+    local_sig: Option<LocalSignature>,
+    phase_5a_decom: Option<Phase5ADecom>,
+    commit5a_vec: Option<Vec<Phase5Com1>>,
+    decommit5a_vec_includes_i: Option<Vec<Phase5ADecom1>>,
+    phase5_com2: Option<Phase5Com2>,
+    phase_5d_decom2: Option<Phase5DDecom2>,
+    commit5c_vec: Option<Vec<Phase5Com2>>,
+    output: Option<Signature>,
+}
+
+impl Sign {
+    pub fn new(
+        key_store: KeyStore,
+        threshold: u16,
+        party_num: u16,
+        message: Vec<u8>,
+    ) -> Result<Sign> {
+        Sign::with_stop(key_store, threshold, party_num, message, None)
+    }
+
+    /// The rounds of signing that presigning and GG20 share with GG18, without a message.
+    /// The machine is finished at `stop`, and the accessors below give what it knows then.
+    pub fn offline(
+        key_store: KeyStore,
+        threshold: u16,
+        party_num: u16,
+        stop: OfflineStage,
+    ) -> Result<Sign> {
+        Sign::with_stop(key_store, threshold, party_num, Vec::new(), Some(stop))
+    }
+
+    fn with_stop(
+        key_store: KeyStore,
+        threshold: u16,
+        party_num: u16,
+        message: Vec<u8>,
+        stop: Option<OfflineStage>,
+    ) -> Result<Sign> {
+        if party_num == 0 || party_num > threshold + 1 {
+            return Err(TssError::UnknownError {
+                msg: format!("sign position {} out of 1..={}", party_num, threshold + 1),
+                line: line!(),
+            });
+        }
+        Ok(Sign {
+            key_store,
+            threshold,
+            party_num,
+            message,
+            stop,
+            sent: 0,
+            received: Vec::new(),
+            signers_vec: None,
+            sign_keys: None,
+            xi_com_vec: None,
+            decommit: None,
+            m_a_k: None,
+            m_a_randomness: None,
+            m_a_c_vec: None,
+            bc1_vec: None,
+            beta_vec: None,
+            ni_vec: None,
+            m_b_gamma_rec_vec: None,
+            alpha_vec: None,
+            delta_i: None,
+            delta_inv: None,
+            sigma: None,
+            R: None,
+            local_sig: None,
+            phase_5a_decom: None,
+            commit5a_vec: None,
+            decommit5a_vec_includes_i: None,
+            phase5_com2: None,
+            phase_5d_decom2: None,
+            commit5c_vec: None,
+            output: None,
+        })
+    }
+}
+
+impl StateMachine for Sign {
+    type Output = Signature;
+
+    fn handle_incoming(&mut self, msg: Msg) -> Result<Vec<Msg>> {
+        let next = self.sent.saturating_sub(1);
+        check_incoming(
+            &msg,
+            self.party_num,
+            self.n(),
+            &ROUNDS,
+            next,
+            &self.received,
+        )?;
+        self.received.push(msg);
+        self.proceed()
+    }
+
+    fn proceed(&mut self) -> Result<Vec<Msg>> {
+        let mut outgoing = Vec::new();
+        while !self.is_finished() {
+            let bodies = if self.sent == 0 {
+                Vec::new()
+            } else {
+                let round = ROUNDS[self.sent - 1];
+                match take_round(&mut self.received, self.party_num, self.n(), round) {
+                    Some(bodies) => bodies,
+                    None => break,
+                }
+            };
+            outgoing.extend(self.proceed_round(bodies)?);
+            self.sent += 1;
+        }
+        Ok(outgoing)
+    }
+
+    fn current_round(&self) -> Option<&'static str> {
+        if self.is_finished() {
+            return None;
+        }
+        match self.sent {
+            0 => None,
+            sent => ROUNDS.get(sent - 1).copied(),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        match self.stop {
+            None => self.output.is_some(),
+            Some(OfflineStage::Mta) => self.sigma.is_some(),
+            Some(OfflineStage::R) => self.R.is_some(),
+        }
+    }
+
+    fn output(&self) -> Option<&Self::Output> {
+        self.output.as_ref()
+    }
+
+    fn n(&self) -> u16 {
+        self.threshold + 1
+    }
+
+    fn party_num(&self) -> u16 {
+        self.party_num
+    }
+}
+
+impl Sign {
+    fn proceed_round(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        match self.sent {
+            0 => self.round0(),
+            1 => self.round1(bodies),
+            2 => self.round2(bodies),
+            3 => self.round3(bodies),
+            4 => self.round4(bodies),
+            5 => self.round5(bodies),
+            6 => self.round6(bodies),
+            7 => self.round7(bodies),
+            8 => self.round8(bodies),
+            9 => self.round9(bodies),
+            _ => self.finish(bodies),
+        }
+    }
+
+    fn round0(&mut self) -> Result<Vec<Msg>> {
+        let party_id = self.key_store.2;
+        Ok(vec![broadcast_msg(
+            self.party_num,
+            "round0",
+            serde_json::to_string(&party_id)?,
+        )])
+    }
+
+    fn round1(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let signers = parse_bodies::<u16>(&bodies)?;
+        let signers = with_own(signers, self.party_num, self.key_store.2);
+        // the party numbers index the keys of the key store
+        let n = self.key_store.4.len().min(self.key_store.6.len());
+        for (k, &signer) in signers.iter().enumerate() {
+            if signer == 0 || usize::from(signer) > n || signers[..k].contains(&signer) {
+                return Err(TssError::UnexpectedMessage(format!(
+                    "party number {} in round0",
+                    signer
+                )));
+            }
+        }
+        let signers_vec = signers
+            .into_iter()
+            .map(|signer| usize::from(signer - 1))
+            .collect::<Vec<usize>>();
+
+        let (party_keys, shared_keys, _, vss_scheme_vec, ..) = &self.key_store;
+        let private = PartyPrivate::set_private(party_keys.clone(), shared_keys.clone());
+        // only the parameters of the VSS scheme are used, and after a resharing there may be
+        // fewer dealers than parties
+        let sign_keys = SignKeys::create(
+            &private,
+            &vss_scheme_vec[0],
+            signers_vec[usize::from(self.party_num - 1)],
+            &signers_vec,
+        );
+        let xi_com_vec = Keys::get_commitments_to_xi(vss_scheme_vec);
+        self.signers_vec = Some(signers_vec);
+
+        let (com, decommit) = sign_keys.phase1_broadcast();
+        let (m_a_k, m_a_randomness) = MessageA::a(
+            &sign_keys.k_i,
+            &party_keys.ek,
+            &self.dlog_statements_except(self.party_num),
+        );
+        let msg = broadcast_msg(
+            self.party_num,
+            "round1",
+            serde_json::to_string(&(com, m_a_k.clone()))?,
+        );
+
+        self.sign_keys = Some(sign_keys);
+        self.xi_com_vec = Some(xi_com_vec);
+        self.decommit = Some(decommit);
+        self.m_a_k = Some(m_a_k);
+        self.m_a_randomness = Some(m_a_randomness);
+        Ok(vec![msg])
+    }
+
+    fn round2(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let round1_vec = parse_bodies::<(SignBroadcastPhase1, MessageA)>(&bodies)?;
+        let sign_keys = self.sign_keys.as_ref().unwrap();
+        let (_, _, _, _, paillier_key_vec, _, h1_h2_N_tilde_vec, _) = &self.key_store;
+
+        let mut outgoing = Vec::new();
+        let mut beta_vec = Vec::new();
+        let mut ni_vec = Vec::new();
+        let mut bc1_vec = Vec::new();
+        let mut m_a_c_vec = Vec::new();
+        for (j, (bc1_j, m_a_j)) in round1_vec.into_iter().enumerate() {
+            let i = other_party_num(self.party_num, j);
+            let signer_i = self.signers_vec.as_ref().unwrap()[usize::from(i - 1)];
+            let dlog_statements = self.dlog_statements_except(i);
+            m_a_c_vec.push(m_a_j.c.clone());
+            let (m_b_gamma, beta_gamma, _, _) = MessageB::b(
+                &sign_keys.gamma_i,
+                &paillier_key_vec[signer_i],
+                m_a_j.clone(),
+                &dlog_statements,
+                Some(&h1_h2_N_tilde_vec[signer_i]),
+            )?;
+            let (m_b_w, beta_wi, _, _) = MessageB::b_with_check(
+                &sign_keys.w_i,
+                &paillier_key_vec[signer_i],
+                m_a_j,
+                &dlog_statements,
+                &h1_h2_N_tilde_vec[signer_i],
+            )?;
+            outgoing.push(p2p_msg(
+                self.party_num,
+                i,
+                "round2",
+                serde_json::to_string(&(m_b_gamma, m_b_w))?,
+            ));
+            beta_vec.push(beta_gamma);
+            ni_vec.push(beta_wi);
+            bc1_vec.push(bc1_j);
+        }
+
+        let m_a_c = self.m_a_k.as_ref().unwrap().c.clone();
+        self.m_a_c_vec = Some(with_own(m_a_c_vec, self.party_num, m_a_c));
+        self.bc1_vec = Some(bc1_vec);
+        self.beta_vec = Some(beta_vec);
+        self.ni_vec = Some(ni_vec);
+        Ok(outgoing)
+    }
+
+    /// Completes the MtA and MtAwc, blaming the signers whose messages B fail to verify.
+    fn round3(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let round2_vec = parse_bodies::<(MessageB, MessageB)>(&bodies)?;
+        let (party_keys, shared_keys, _, vss_scheme_vec, ..) = &self.key_store;
+        let private = PartyPrivate::set_private(party_keys.clone(), shared_keys.clone());
+        let dlog_statement = party_keys.dlog_statement();
+        let sign_keys = self.sign_keys.as_ref().unwrap();
+        let m_a_k = self.m_a_k.as_ref().unwrap();
+        let signers_vec = self.signers_vec.as_ref().unwrap();
+
+        let mut alpha_vec = Vec::new();
+        let mut miu_vec = Vec::new();
+        let mut m_b_gamma_rec_vec = Vec::new();
+        let mut blames = Vec::new();
+        for (j, (m_b_gamma, m_b_w)) in round2_vec.into_iter().enumerate() {
+            let i = other_party_num(self.party_num, j);
+            let party = self.other_signer_party_num(j);
+            let alpha_ij_gamma = m_b_gamma.verify_proofs_get_alpha_gg18(
+                &private,
+                &sign_keys.k_i,
+                &m_a_k.c,
+                &party_keys.ek,
+                &dlog_statement,
+                false,
+            );
+            let alpha_ij_wi = m_b_w.verify_proofs_get_alpha_gg18(
+                &private,
+                &sign_keys.k_i,
+                &m_a_k.c,
+                &party_keys.ek,
+                &dlog_statement,
+                true,
+            );
+            let signer_i = signers_vec[usize::from(i - 1)];
+            let g_w_i = Keys::update_commitments_to_xi(
+                &self.xi_com_vec.as_ref().unwrap()[signer_i],
+                &vss_scheme_vec[0],
+                signer_i,
+                signers_vec,
+            );
+            if !MessageB::<FE>::verify_b_against_public(&g_w_i, &m_b_w.b_proof.pk) {
+                blames.push(Blame::new(party, BlameItem::MtawcPublicShare));
+            }
+            match (alpha_ij_gamma, alpha_ij_wi) {
+                (Ok(alpha_ij_gamma), Ok(alpha_ij_wi)) => {
+                    alpha_vec.push(alpha_ij_gamma);
+                    miu_vec.push(alpha_ij_wi);
+                }
+                (alpha_ij_gamma, alpha_ij_wi) => {
+                    for e in alpha_ij_gamma.err().into_iter().chain(alpha_ij_wi.err()) {
+                        match e.blame(party) {
+                            TssError::Blame(blame) => blames.extend(blame),
+                            e => return Err(e),
+                        }
+                    }
+                }
+            }
+            m_b_gamma_rec_vec.push(m_b_gamma);
+        }
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let delta_i = sign_keys.phase2_delta_i(&alpha_vec, self.beta_vec.as_ref().unwrap());
+        let sigma = sign_keys.phase2_sigma_i(&miu_vec, self.ni_vec.as_ref().unwrap());
+        let msg = broadcast_msg(self.party_num, "round3", serde_json::to_string(&delta_i)?);
+
+        self.m_b_gamma_rec_vec = Some(m_b_gamma_rec_vec);
+        self.alpha_vec = Some(alpha_vec);
+        self.delta_i = Some(delta_i);
+        self.sigma = Some(sigma);
+        if self.stop == Some(OfflineStage::Mta) {
+            return Ok(Vec::new());
+        }
+        Ok(vec![msg])
+    }
+
+    fn round4(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let delta_vec = parse_bodies::<FE>(&bodies)?;
+        let delta_vec = with_own(delta_vec, self.party_num, self.delta_i.clone().unwrap());
+        self.delta_inv = Some(SignKeys::phase3_reconstruct_delta(&delta_vec));
+
+        // decommit to gamma_i
+        let decommit = self.decommit.as_ref().unwrap();
+        Ok(vec![broadcast_msg(
+            self.party_num,
+            "round4",
+            serde_json::to_string(decommit)?,
+        )])
+    }
+
+    fn round5(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let decommit_vec = parse_bodies::<SignDecommitPhase1>(&bodies)?;
+        let R = self.phase4_R(decommit_vec, self.delta_inv.as_ref().unwrap())?;
+        self.R = Some(R.clone());
+        if self.stop == Some(OfflineStage::R) {
+            return Ok(Vec::new());
+        }
+
+        // we assume the message is already hashed (by the signer).
+        let message_bn = BigInt::from_bytes_be(&self.message[..]);
+        let local_sig = LocalSignature::phase5_local_sig(
+            &self.sign_keys.as_ref().unwrap().k_i,
+            &message_bn,
+            &R,
+            self.sigma.as_ref().unwrap(),
+            &self.key_store.5,
+        );
+        let (phase5_com, phase_5a_decom, helgamal_proof, dlog_proof_rho) =
+            local_sig.phase5a_broadcast_5b_zkproof();
+        let msg = broadcast_msg(
+            self.party_num,
+            "round5",
+            serde_json::to_string(&phase5_com)?,
+        );
+
+        self.local_sig = Some(local_sig);
+        self.phase_5a_decom = Some((phase_5a_decom, helgamal_proof, dlog_proof_rho));
+        Ok(vec![msg])
+    }
+
+    fn round6(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        self.commit5a_vec = Some(parse_bodies::<Phase5Com1>(&bodies)?);
+        let phase_5a_decom = self.phase_5a_decom.as_ref().unwrap();
+        Ok(vec![broadcast_msg(
+            self.party_num,
+            "round6",
+            serde_json::to_string(phase_5a_decom)?,
+        )])
+    }
+
+    fn round7(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let decommit5a_vec = parse_bodies::<Phase5ADecom>(&bodies)?;
+        let (phase_5a_decom, _, _) = self.phase_5a_decom.as_ref().unwrap();
+        let phase_5a_decomm_vec = decommit5a_vec
+            .iter()
+            .map(|decom| decom.0.clone())
+            .collect::<Vec<Phase5ADecom1>>();
+        let phase_5a_elgamal_vec = decommit5a_vec
+            .iter()
+            .map(|decom| decom.1.clone())
+            .collect::<Vec<HomoELGamalProof>>();
+        let phase_5a_dlog_vec = decommit5a_vec
+            .iter()
+            .map(|decom| decom.2.clone())
+            .collect::<Vec<DLogProof>>();
+        let (phase5_com2, phase_5d_decom2) = self
+            .local_sig
+            .as_ref()
+            .unwrap()
+            .phase5c(
+                &phase_5a_decomm_vec,
+                self.commit5a_vec.as_ref().unwrap(),
+                &phase_5a_elgamal_vec,
+                &phase_5a_dlog_vec,
+                &phase_5a_decom.V_i,
+                &self.local_sig.as_ref().unwrap().R,
+            )
+            .map_err(|e| e.map_blamed_parties(|j| self.other_signer_party_num(j)))?;
+        let msg = broadcast_msg(
+            self.party_num,
+            "round7",
+            serde_json::to_string(&phase5_com2)?,
+        );
+
+        self.decommit5a_vec_includes_i = Some(with_own(
+            phase_5a_decomm_vec,
+            self.party_num,
+            phase_5a_decom.clone(),
+        ));
+        self.phase5_com2 = Some(phase5_com2);
+        self.phase_5d_decom2 = Some(phase_5d_decom2);
+        Ok(vec![msg])
+    }
+
+    fn round8(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let commit5c_vec = parse_bodies::<Phase5Com2>(&bodies)?;
+        self.commit5c_vec = Some(with_own(
+            commit5c_vec,
+            self.party_num,
+            self.phase5_com2.clone().unwrap(),
+        ));
+        let phase_5d_decom2 = self.phase_5d_decom2.as_ref().unwrap();
+        Ok(vec![broadcast_msg(
+            self.party_num,
+            "round8",
+            serde_json::to_string(phase_5d_decom2)?,
+        )])
+    }
+
+    fn round9(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let decommit5d_vec = parse_bodies::<Phase5DDecom2>(&bodies)?;
+        let decommit5d_vec = with_own(
+            decommit5d_vec,
+            self.party_num,
+            self.phase_5d_decom2.clone().unwrap(),
+        );
+        let s_i = self
+            .local_sig
+            .as_ref()
+            .unwrap()
+            .phase5d(
+                &decommit5d_vec,
+                self.commit5c_vec.as_ref().unwrap(),
+                self.decommit5a_vec_includes_i.as_ref().unwrap(),
+            )
+            .map_err(|e| e.map_blamed_parties(|j| self.signer_party_num(j + 1)))?;
+        let msg = broadcast_msg(self.party_num, "round9", serde_json::to_string(&s_i)?);
+        Ok(vec![msg])
+    }
+
+    fn finish(&mut self, bodies: Vec<String>) -> Result<Vec<Msg>> {
+        let s_vec = parse_bodies::<FE>(&bodies)?;
+        let local_sig = self.local_sig.as_ref().unwrap();
+        let sig = local_sig.output_signature(&s_vec)?;
+        verify(&sig, &self.key_store.5, &local_sig.m)?;
+        self.output = Some(sig);
+        Ok(Vec::new())
+    }
+
+    /// R from the decommitments to gamma_j of the other signers, in signing order, checked
+    /// against their commitments of round1 and the proofs of their messages B. GG20 gets
+    /// `delta_inv` from its own round3.
+    pub fn phase4_R(&self, decommit_vec: Vec<SignDecommitPhase1>, delta_inv: &FE) -> Result<GE> {
+        let b_proof_vec = self
+            .m_b_gamma_rec_vec
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|m_b| &m_b.b_proof)
+            .collect::<Vec<&DLogProof>>();
+        let R = SignKeys::phase4(
+            delta_inv,
+            &b_proof_vec,
+            decommit_vec,
+            self.bc1_vec.as_ref().unwrap(),
+        )
+        .map_err(|e| e.map_blamed_parties(|j| self.other_signer_party_num(j)))?;
+        // adding local g_gamma_i
+        Ok(R + self.decommit.as_ref().unwrap().g_gamma_i.clone() * delta_inv)
+    }
+
+    pub fn key_store(&self) -> &KeyStore {
+        &self.key_store
+    }
+
+    /// The keygen index, from 0, of the signer at every sign position.
+    pub fn signers_vec(&self) -> Option<&Vec<usize>> {
+        self.signers_vec.as_ref()
+    }
+
+    pub fn sign_keys(&self) -> Option<&SignKeys> {
+        self.sign_keys.as_ref()
+    }
+
+    pub fn decommit(&self) -> Option<&SignDecommitPhase1> {
+        self.decommit.as_ref()
+    }
+
+    /// Our MtA message A and its Paillier randomness.
+    pub fn m_a(&self) -> Option<(&MessageA, &BigInt)> {
+        self.m_a_k.as_ref().zip(self.m_a_randomness.as_ref())
+    }
+
+    /// The ciphertexts of the messages A of all the signers, in signing order.
+    pub fn m_a_c_vec(&self) -> Option<&Vec<BigInt>> {
+        self.m_a_c_vec.as_ref()
+    }
+
+    /// The messages B of the MtA on gamma_j that the other signers sent us, in signing order,
+    /// and the alpha_ij we decrypted from them.
+    pub fn m_b_gamma_rec(&self) -> Option<(&Vec<MessageB>, &Vec<FE>)> {
+        self.m_b_gamma_rec_vec.as_ref().zip(self.alpha_vec.as_ref())
+    }
+
+    pub fn delta_i(&self) -> Option<&FE> {
+        self.delta_i.as_ref()
+    }
+
+    pub fn sigma(&self) -> Option<&FE> {
+        self.sigma.as_ref()
+    }
+
+    pub fn R(&self) -> Option<&GE> {
+        self.R.as_ref()
+    }
+
+    /// The h1,h2,N_tilde of every signer but the one at sign position `party_num`, in
+    /// signing order.
+    pub fn dlog_statements_except(&self, party_num: u16) -> Vec<DLogStatement> {
+        self.signers_vec
+            .as_ref()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != usize::from(party_num - 1))
+            .map(|(_, signer)| self.key_store.6[*signer].clone())
+            .collect()
+    }
+
+    /// Party number, as used in keygen, of the signer at sign position `i` (1-based).
+    pub fn signer_party_num(&self, i: usize) -> usize {
+        self.signers_vec.as_ref().unwrap()[i - 1] + 1
+    }
+
+    /// Party number of entry `j` of a vector that holds the messages of every signer but us.
+    fn other_signer_party_num(&self, j: usize) -> usize {
+        self.signer_party_num(usize::from(other_party_num(self.party_num, j)))
+    }
+}
@@ -0,0 +1,2 @@
+pub mod party_i;
+pub mod zk_pdl_with_slack;
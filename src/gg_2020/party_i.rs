@@ -0,0 +1,342 @@
+#![allow(non_snake_case)]
+
+/*
+    GG20 signing, https://eprint.iacr.org/2020/540.pdf
+
+    Keygen, the signing keys and the MtA and MtAwc of phases 1 and 2 are those of GG18,
+    so a GG18 key store signs with either protocol. GG20 then proves every step that
+    GG18 only checks at the end:
+
+    - phase 3: with delta_i, each signer publishes T_i = g^sigma_i h^l_i and proves
+      knowledge of sigma_i and l_i.
+    - phase 5: once R is known, each signer publishes R_bar_i = R^k_i and proves to
+      every other signer that k_i is the plaintext of its MtA message A.
+    - phase 6: each signer publishes S_i = R^sigma_i and proves that sigma_i is the one
+      committed to in T_i.
+
+    As the R_bar_i add up to g and the S_i to y, the result is a `Presignature`: the
+    online round is the single broadcast of s_i, and a wrong s_j is blamed on its sender.
+
+    If the R_bar_i, each proven, do not add up to g, some delta_j is wrong. As in the
+    identification of the paper, every signer then reveals k_i and its side of the MtA
+    on gamma, which protect nothing once R is dropped, and the signers whose delta_j does
+    not follow from them are blamed.
+*/
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::cryptographic_primitives::proofs::sigma_correct_homomorphic_elgamal_enc::{
+    HomoELGamalProof, HomoElGamalStatement, HomoElGamalWitness,
+};
+use crate::curv::cryptographic_primitives::proofs::sigma_valid_pedersen::{
+    PedersenProof, ProvePederesen,
+};
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::BlameItem::{DeltaShare, MtaReveal, PdlWithSlackProof, STProof, TPedersenProof};
+use crate::errors::TssError::{self, InvalidKey};
+use crate::errors::{Blame, Result};
+use crate::gg_2018::mta::MessageB;
+use crate::gg_2018::party_i::{PresignBroadcastMessage, Presignature, SignKeys};
+use crate::gg_2020::zk_pdl_with_slack::{PDLwSlackProof, PDLwSlackStatement};
+use crate::paillier::traits::EncryptWithChosenRandomness;
+use crate::paillier::zkproofs::DLogStatement;
+use crate::paillier::{EncryptionKey, Paillier, Randomness, RawPlaintext};
+
+/// Broadcast in phase 3.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignBroadcastPhase3 {
+    pub delta_i: FE,
+    /// proof of knowledge of sigma_i and l_i, committing to T_i
+    pub T_proof: PedersenProof,
+}
+
+/// Broadcast in phase 5.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignBroadcastPhase5 {
+    pub R_bar_i: GE,
+    /// one proof for every other signer, in signing order, against its h1,h2,N_tilde
+    pub pdl_proofs: Vec<PDLwSlackProof>,
+}
+
+/// Broadcast after phase 5 when the R_bar_j do not add up to g.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignPhase5Reveal {
+    pub k_i: FE,
+    /// the Paillier randomness of the MtA message A that encrypts k_i
+    pub m_a_randomness: BigInt,
+    /// the messages B of the MtA on gamma_j received from every other signer, in signing
+    /// order, and the alpha_ij decrypted from them
+    pub m_b_gamma_vec: Vec<MessageB>,
+    pub alpha_vec: Vec<FE>,
+}
+
+/// Broadcast in phase 6.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignBroadcastPhase6 {
+    pub S_i: GE,
+    pub ST_proof: HomoELGamalProof,
+}
+
+impl SignBroadcastPhase3 {
+    /// Returns the message and l_i, which is kept for phase 6.
+    pub fn create(delta_i: &FE, sigma_i: &FE) -> (SignBroadcastPhase3, FE) {
+        let l_i: FE = ECScalar::new_random();
+        let T_proof = PedersenProof::prove(sigma_i, &l_i);
+        (
+            SignBroadcastPhase3 {
+                delta_i: delta_i.clone(),
+                T_proof,
+            },
+            l_i,
+        )
+    }
+
+    pub fn T_i(&self) -> &GE {
+        &self.T_proof.com
+    }
+
+    /// Checks the T_j of every signer, in signing order, and returns delta^-1.
+    /// Blames refer to the positions in the signing order.
+    pub fn verify_reconstruct_delta(phase3_vec: &[SignBroadcastPhase3]) -> Result<FE> {
+        let blames = (0..phase3_vec.len())
+            .filter(|&j| PedersenProof::verify(&phase3_vec[j].T_proof).is_err())
+            .map(|j| Blame::new(j, TPedersenProof))
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let delta_vec = phase3_vec
+            .iter()
+            .map(|msg| msg.delta_i.clone())
+            .collect::<Vec<FE>>();
+        Ok(SignKeys::phase3_reconstruct_delta(&delta_vec))
+    }
+}
+
+impl SignBroadcastPhase5 {
+    /// `m_a_randomness` is the Paillier randomness of our MtA message A, whose ciphertext
+    /// is `m_a_c`. `dlog_statements` are those of the other signers, in signing order.
+    pub fn create(
+        sign_keys: &SignKeys,
+        R: &GE,
+        m_a_c: &BigInt,
+        m_a_randomness: &BigInt,
+        ek: &EncryptionKey,
+        dlog_statements: &[DLogStatement],
+    ) -> SignBroadcastPhase5 {
+        let R_bar_i = R * &sign_keys.k_i;
+        let pdl_proofs = dlog_statements
+            .iter()
+            .map(|dlog_statement| {
+                let statement = PDLwSlackStatement {
+                    ciphertext: m_a_c,
+                    ek,
+                    Q: &R_bar_i,
+                    G: R,
+                    dlog_statement,
+                };
+                PDLwSlackProof::prove(&sign_keys.k_i, m_a_randomness, &statement)
+            })
+            .collect::<Vec<PDLwSlackProof>>();
+        SignBroadcastPhase5 {
+            R_bar_i,
+            pdl_proofs,
+        }
+    }
+
+    /// Checks the proofs made for us, at `position` in the signing order, by every other
+    /// signer. `m_a_c_vec` and `ek_vec` hold the MtA ciphertext and Paillier key of every
+    /// signer, in signing order. Blames refer to the positions in the signing order.
+    pub fn verify(
+        phase5_vec: &[SignBroadcastPhase5],
+        R: &GE,
+        m_a_c_vec: &[BigInt],
+        ek_vec: &[EncryptionKey],
+        dlog_statement: &DLogStatement,
+        position: usize,
+    ) -> Result<()> {
+        let blames = (0..phase5_vec.len())
+            .filter(|&j| j != position)
+            .filter(|&j| {
+                let statement = PDLwSlackStatement {
+                    ciphertext: &m_a_c_vec[j],
+                    ek: &ek_vec[j],
+                    Q: &phase5_vec[j].R_bar_i,
+                    G: R,
+                    dlog_statement,
+                };
+                // the prover skips itself in its list of proofs
+                let proof_index = if position < j { position } else { position - 1 };
+                !phase5_vec[j]
+                    .pdl_proofs
+                    .get(proof_index)
+                    .map_or(false, |proof| proof.verify(&statement))
+            })
+            .map(|j| Blame::new(j, PdlWithSlackProof))
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+        Ok(())
+    }
+
+    /// Whether the R_bar_j of every signer add up to g. If not, the signers go on with
+    /// `SignPhase5Reveal` instead of phase 6.
+    pub fn sum_is_g(phase5_vec: &[SignBroadcastPhase5]) -> bool {
+        let g: GE = ECPoint::generator();
+        let head = phase5_vec[0].R_bar_i.clone();
+        let sum_R_bar = phase5_vec[1..]
+            .iter()
+            .fold(head, |acc, msg| acc + &msg.R_bar_i);
+        sum_R_bar == g
+    }
+}
+
+impl SignPhase5Reveal {
+    pub fn create(
+        k_i: &FE,
+        m_a_randomness: &BigInt,
+        m_b_gamma_vec: &[MessageB],
+        alpha_vec: &[FE],
+    ) -> SignPhase5Reveal {
+        SignPhase5Reveal {
+            k_i: k_i.clone(),
+            m_a_randomness: m_a_randomness.clone(),
+            m_b_gamma_vec: m_b_gamma_vec.to_vec(),
+            alpha_vec: alpha_vec.to_vec(),
+        }
+    }
+
+    /// Finds the signers whose delta_j was wrong, from the reveals of every signer, in
+    /// signing order. `delta_vec`, `g_gamma_vec`, `m_a_c_vec` and `ek_vec` hold the
+    /// delta_j of phase 3, the decommitted g^gamma_j, the MtA ciphertext and the Paillier
+    /// key of every signer. First each reveal is checked against the MtA messages; only if
+    /// they all hold is delta_j compared with k_j gamma_j plus the alpha_jl it got and the
+    /// beta_lj it kept, since a false reveal would put the blame on an honest signer.
+    /// Blames refer to the positions in the signing order.
+    pub fn identify(
+        reveal_vec: &[SignPhase5Reveal],
+        delta_vec: &[FE],
+        g_gamma_vec: &[GE],
+        m_a_c_vec: &[BigInt],
+        ek_vec: &[EncryptionKey],
+    ) -> TssError {
+        let g: GE = ECPoint::generator();
+        let n = reveal_vec.len();
+        // position of signer `i` among the other signers of signer `j`
+        let other = |i: usize, j: usize| if i < j { i } else { i - 1 };
+
+        let blames = (0..n)
+            .filter(|&j| {
+                let reveal = &reveal_vec[j];
+                let c = Paillier::encrypt_with_chosen_randomness(
+                    &ek_vec[j],
+                    RawPlaintext::from(reveal.k_i.to_big_int()),
+                    &Randomness::from(reveal.m_a_randomness.clone()),
+                )
+                .0
+                .into_owned();
+                c != m_a_c_vec[j]
+                    || reveal.m_b_gamma_vec.len() != n - 1
+                    || reveal.alpha_vec.len() != n - 1
+                    || (0..n).filter(|&l| l != j).any(|l| {
+                        let m_b = &reveal.m_b_gamma_vec[other(l, j)];
+                        let g_alpha = g.clone() * &reveal.alpha_vec[other(l, j)];
+                        m_b.b_proof.pk != g_gamma_vec[l]
+                            || m_b.b_proof.pk.clone() * &reveal.k_i + &m_b.beta_tag_proof.pk
+                                != g_alpha
+                    })
+            })
+            .map(|j| Blame::new(j, MtaReveal))
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return TssError::Blame(blames);
+        }
+
+        // g^delta_j g^beta'_jl = g^(k_j gamma_j) g^alpha_jl, for the beta'_jl = -beta_jl
+        // that signer j put in its message B to signer l
+        let blames = (0..n)
+            .filter(|&j| {
+                let reveal = &reveal_vec[j];
+                let (lhs, rhs) = (0..n).filter(|&l| l != j).fold(
+                    (
+                        g.clone() * &delta_vec[j],
+                        g_gamma_vec[j].clone() * &reveal.k_i,
+                    ),
+                    |(lhs, rhs), l| {
+                        let m_b = &reveal_vec[l].m_b_gamma_vec[other(j, l)];
+                        (
+                            lhs + &m_b.beta_tag_proof.pk,
+                            rhs + &(g.clone() * &reveal.alpha_vec[other(l, j)]),
+                        )
+                    },
+                );
+                lhs != rhs
+            })
+            .map(|j| Blame::new(j, DeltaShare))
+            .collect::<Vec<Blame>>();
+        if blames.is_empty() {
+            // the delta_j add up, so they must have differed between the signers
+            return InvalidKey;
+        }
+        TssError::Blame(blames)
+    }
+}
+
+impl SignBroadcastPhase6 {
+    pub fn create(R: &GE, sigma_i: &FE, l_i: &FE, T_i: &GE) -> SignBroadcastPhase6 {
+        let S_i = R * sigma_i;
+        let ST_proof = HomoELGamalProof::prove(
+            &HomoElGamalWitness {
+                r: sigma_i.clone(),
+                x: l_i.clone(),
+            },
+            &st_statement(R, T_i, &S_i),
+        );
+        SignBroadcastPhase6 { S_i, ST_proof }
+    }
+
+    /// Checks the S_j of every signer, in signing order, against its T_j, and that they add
+    /// up to y. Blames refer to the positions in the signing order.
+    pub fn verify_create_presignature(
+        phase6_vec: &[SignBroadcastPhase6],
+        phase3_vec: &[SignBroadcastPhase3],
+        phase5_vec: &[SignBroadcastPhase5],
+        R: &GE,
+        k_i: &FE,
+        sigma_i: &FE,
+        y: &GE,
+    ) -> Result<Presignature> {
+        let blames = (0..phase6_vec.len())
+            .filter(|&j| {
+                let statement = st_statement(R, phase3_vec[j].T_i(), &phase6_vec[j].S_i);
+                phase6_vec[j].ST_proof.verify(&statement).is_err()
+            })
+            .map(|j| Blame::new(j, STProof))
+            .collect::<Vec<Blame>>();
+        if !blames.is_empty() {
+            return Err(TssError::Blame(blames));
+        }
+
+        let presign_vec = (0..phase6_vec.len())
+            .map(|j| PresignBroadcastMessage {
+                R_i: phase5_vec[j].R_bar_i.clone(),
+                S_i: phase6_vec[j].S_i.clone(),
+            })
+            .collect::<Vec<PresignBroadcastMessage>>();
+        Presignature::phase1_verify_create(k_i, sigma_i, R, y, &presign_vec)
+    }
+}
+
+/// S_i = R^sigma_i and T_i = g^sigma_i h^l_i, as a homomorphic ElGamal statement
+/// with witness (x, r) = (l_i, sigma_i).
+fn st_statement(R: &GE, T_i: &GE, S_i: &GE) -> HomoElGamalStatement {
+    HomoElGamalStatement {
+        G: R.clone(),
+        H: GE::base_point2(),
+        Y: ECPoint::generator(),
+        D: T_i.clone(),
+        E: S_i.clone(),
+    }
+}
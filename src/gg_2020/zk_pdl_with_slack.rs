@@ -0,0 +1,166 @@
+#![allow(non_snake_case)]
+
+//! Proof that a point Q = G^x is made with the x encrypted in a Paillier ciphertext c,
+//! up to a slack of q^2 on the size of x. This is the proof of phase 5 of GG20
+//! (Section 4.3 of https://eprint.iacr.org/2020/540.pdf), made non-interactive with
+//! Fiat-Shamir. As for the range proofs, the verifier's h1,h2,N_tilde are used for the
+//! commitment z = h1^x h2^rho.
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::cryptographic_primitives::hashing::ext::DigestExt;
+use crate::curv::elliptic::curves::secp256_k1::Secp256k1Scalar as Scalar;
+use crate::curv::elliptic::curves::secp256_k1::GE;
+use crate::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+use crate::gg_2018::range_proofs::SampleFromMultiplicativeGroup;
+use crate::paillier::zkproofs::DLogStatement;
+use crate::paillier::EncryptionKey;
+use crate::sha2::Digest;
+
+use num_traits::pow::Pow;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// What the proof is about: `Q = G^x` for the x in `ciphertext`, encrypted under `ek`.
+pub struct PDLwSlackStatement<'a> {
+    pub ciphertext: &'a BigInt,
+    pub ek: &'a EncryptionKey,
+    pub Q: &'a GE,
+    pub G: &'a GE,
+    /// h1,h2,N_tilde of the verifier
+    pub dlog_statement: &'a DLogStatement,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PDLwSlackProof {
+    z: BigInt,
+    u1: GE,
+    u2: BigInt,
+    u3: BigInt,
+    s1: BigInt,
+    s2: BigInt,
+    s3: BigInt,
+}
+
+impl PDLwSlackProof {
+    /// `x` is the plaintext of the ciphertext and `r` the Paillier randomness used to encrypt it.
+    pub fn prove(x: &Scalar, r: &BigInt, statement: &PDLwSlackStatement) -> Self {
+        let q = Scalar::group_order();
+        let h1 = &statement.dlog_statement.g;
+        let h2 = &statement.dlog_statement.ni;
+        let N_tilde = &statement.dlog_statement.N;
+        let N = &statement.ek.n;
+        let NN = &statement.ek.nn;
+        let x_bn = x.to_big_int();
+
+        let alpha = BigInt::sample_below(&q.pow(3u32));
+        let beta = BigInt::from_paillier_key(statement.ek);
+        let rho = BigInt::sample_below(&(q * N_tilde));
+        let gamma = BigInt::sample_below(&(q.pow(3u32) * N_tilde));
+
+        let z =
+            (BigInt::mod_pow(h1, &x_bn, N_tilde) * BigInt::mod_pow(h2, &rho, N_tilde)) % N_tilde;
+        let alpha_fe: Scalar = ECScalar::from(&alpha);
+        let u1 = statement.G * &alpha_fe;
+        let u2 = ((&alpha * N + 1u32) * BigInt::mod_pow(&beta, N, NN)) % NN;
+        let u3 =
+            (BigInt::mod_pow(h1, &alpha, N_tilde) * BigInt::mod_pow(h2, &gamma, N_tilde)) % N_tilde;
+
+        let e = challenge(statement, &z, &u1, &u2, &u3);
+
+        Self {
+            s1: &e * &x_bn + &alpha,
+            s2: (BigInt::mod_pow(r, &e, N) * &beta) % N,
+            s3: &e * &rho + &gamma,
+            z,
+            u1,
+            u2,
+            u3,
+        }
+    }
+
+    pub fn verify(&self, statement: &PDLwSlackStatement) -> bool {
+        let h1 = &statement.dlog_statement.g;
+        let h2 = &statement.dlog_statement.ni;
+        let N_tilde = &statement.dlog_statement.N;
+        let N = &statement.ek.n;
+        let NN = &statement.ek.nn;
+
+        let e = challenge(statement, &self.z, &self.u1, &self.u2, &self.u3);
+        let e_fe: Scalar = ECScalar::from(&e);
+        let s1_fe: Scalar = ECScalar::from(&self.s1);
+
+        // G^s1 = u1 * Q^e
+        let u1_check = statement.G * &s1_fe == self.u1.clone() + statement.Q * &e_fe;
+        // (1 + N)^s1 * s2^N = u2 * c^e mod N^2
+        let u2_check = ((&self.s1 * N + 1u32) * BigInt::mod_pow(&self.s2, N, NN)) % NN
+            == (&self.u2 * BigInt::mod_pow(statement.ciphertext, &e, NN)) % NN;
+        // h1^s1 * h2^s3 = u3 * z^e mod N_tilde
+        let u3_check = (BigInt::mod_pow(h1, &self.s1, N_tilde)
+            * BigInt::mod_pow(h2, &self.s3, N_tilde))
+            % N_tilde
+            == (&self.u3 * BigInt::mod_pow(&self.z, &e, N_tilde)) % N_tilde;
+
+        u1_check && u2_check && u3_check
+    }
+}
+
+fn challenge(
+    statement: &PDLwSlackStatement,
+    z: &BigInt,
+    u1: &GE,
+    u2: &BigInt,
+    u3: &BigInt,
+) -> BigInt {
+    Sha256::new()
+        .chain_point(statement.G)
+        .chain_point(statement.Q)
+        .chain_bigint(statement.ciphertext)
+        .chain_bigint(z)
+        .chain_point(u1)
+        .chain_bigint(u2)
+        .chain_bigint(u3)
+        .result_bigint()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gg_2018::range_proofs::tests::generate_init;
+    use crate::paillier::traits::EncryptWithChosenRandomness;
+    use crate::paillier::{Paillier, Randomness, RawPlaintext};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_pdl_with_slack() {
+        let (dlog_statement, ek, _) = generate_init();
+        let x = Scalar::new_random();
+        let r = BigInt::from_paillier_key(&ek);
+        let ciphertext = Paillier::encrypt_with_chosen_randomness(
+            &ek,
+            RawPlaintext::from(x.to_big_int()),
+            &Randomness::from(&r),
+        )
+        .0
+        .into_owned();
+        let G = GE::random_point();
+        let Q = &G * &x;
+        let statement = PDLwSlackStatement {
+            ciphertext: &ciphertext,
+            ek: &ek,
+            Q: &Q,
+            G: &G,
+            dlog_statement: &dlog_statement,
+        };
+        let proof = PDLwSlackProof::prove(&x, &r, &statement);
+        assert!(proof.verify(&statement));
+
+        // a point made with another x
+        let Q = &G * &Scalar::new_random();
+        let statement = PDLwSlackStatement { Q: &Q, ..statement };
+        assert!(!proof.verify(&statement));
+    }
+}
@@ -16,14 +16,23 @@ extern crate sha2;
 
 pub mod curv;
 
+pub mod cggmp_2021;
+pub mod frost_2020;
 pub mod gg_2018;
+pub mod gg_2020;
+pub mod lindell_2017;
 pub mod paillier;
 
 #[macro_use]
 pub mod common;
+pub mod manager;
+pub mod transport;
 
 pub mod api;
+pub mod client;
 pub mod errors;
+#[cfg(feature = "simulation")]
+pub mod simulation;
 
 #[cfg(target_arch = "wasm32")]
 extern crate wasm_bindgen;
@@ -0,0 +1,37 @@
+/*
+    Two-party ECDSA of Lindell, https://eprint.iacr.org/2017/552.pdf
+
+    Party one holds x1 and a Paillier key, and is the one that outputs the signature,
+    e.g. a server cosigner. Party two holds x2 and c_key = Enc(x1), e.g. a browser
+    client. The public key is Q = x1 x2 G. Instead of the interactive proofs of the
+    paper, party one proves with the range and PDL-with-slack proofs of GG18 and GG20,
+    made against the ring-Pedersen parameters of party two, that c_key encrypts the
+    x1 of Q1 = x1 G.
+
+    Keygen:  P1 -> P2  commitments to Q1 and to its proof of knowledge
+             P2 -> P1  Q2 with its proof of knowledge, h1, h2, N_tilde
+             P1 -> P2  decommitment, Paillier key, c_key and their proofs
+    Signing: P1 -> P2  commitments to R1 = k1 G and to its proof of knowledge
+             P2 -> P1  R2 = k2 G with its proof of knowledge
+             P1 -> P2  decommitment
+             P2 -> P1  c3 = Enc(rho q + k2^-1 m) + c_key * k2^-1 r x2
+
+    There is no proof that c3 is well formed. Party two can craft a c3 that gives a valid
+    signature only for some values of x1, and learn a bit of x1 each time party one
+    reports a failure, the abort attack discussed in the paper. Party one therefore
+    retires its key share on the first partial signature that fails, which
+    `KeyShare::output_signature` signals with `TssError::RetireKeyShare`.
+*/
+
+use crate::paillier::EncryptionKey;
+
+pub mod party_one;
+pub mod party_two;
+
+// Blum primes of 1024 bits give a modulus of at least 2047 bits, large enough for the
+// plaintext of c3 not to wrap around
+const PAILLIER_MIN_BIT_LENGTH: usize = 2047;
+
+pub(crate) fn is_paillier_key_large_enough(ek: &EncryptionKey) -> bool {
+    ek.n.bits() >= PAILLIER_MIN_BIT_LENGTH
+}
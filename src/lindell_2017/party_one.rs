@@ -0,0 +1,255 @@
+#![allow(non_snake_case)]
+
+//! Party one of the two-party protocol: it holds x1 and the Paillier key, and outputs the
+//! signature.
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::cryptographic_primitives::commitments::hash_commitment::HashCommitment;
+use crate::curv::cryptographic_primitives::commitments::traits::Commitment;
+use crate::curv::cryptographic_primitives::proofs::sigma_dlog::{DLogProof, ProveDLog};
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::BlameItem::{
+    PartialSignature, TwoPartyDLogProof, TwoPartyDecommitment, TwoPartyRingPedersenProof,
+};
+use crate::errors::{
+    Result,
+    TssError::{InvalidMessage, RetireKeyShare},
+};
+use crate::gg_2018::party_i::{verify, verify_dlog_statement, Signature};
+use crate::gg_2018::range_proofs::AliceProof;
+use crate::gg_2020::zk_pdl_with_slack::{PDLwSlackProof, PDLwSlackStatement};
+use crate::lindell_2017::party_two;
+use crate::paillier::traits::EncryptWithChosenRandomness;
+use crate::paillier::zkproofs::NICorrectKeyProof;
+use crate::paillier::{
+    Decrypt, DecryptionKey, EncryptionKey, KeyGeneration, Paillier, Randomness, RawCiphertext,
+    RawPlaintext,
+};
+use num_integer::Integer;
+
+const SECURITY: usize = 256;
+
+/// Commitments to a public share of party one, Q1 or R1, and to its proof of knowledge.
+/// Message 1 of keygen and of signing.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommitMsg {
+    pub pk_commitment: BigInt,
+    pub zk_pok_commitment: BigInt,
+}
+
+/// Opening of a `CommitMsg`. Message 3 of signing, and part of message 3 of keygen.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommWitness {
+    pub pk_commitment_blind_factor: BigInt,
+    pub zk_pok_blind_factor: BigInt,
+    pub public_share: GE,
+    pub d_log_proof: DLogProof,
+}
+
+/// x1 and Q1 at keygen, k1 and R1 at signing.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EcKeyPair {
+    pub public_share: GE,
+    secret_share: FE,
+}
+
+/// Message 3 of keygen.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeyGenSecondMsg {
+    pub comm_witness: CommWitness,
+    pub ek: EncryptionKey,
+    /// Paillier encryption of x1
+    pub c_key: BigInt,
+    pub correct_key_proof: NICorrectKeyProof,
+    /// proof, against the h1,h2,N_tilde of party two, that c_key encrypts a small value
+    pub range_proof: AliceProof,
+    /// proof, against the h1,h2,N_tilde of party two, that c_key encrypts the x1 of Q1
+    pub pdl_proof: PDLwSlackProof,
+}
+
+/// What party one keeps after keygen.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeyShare {
+    /// the public key x1 x2 G
+    pub Q: GE,
+    pub ek: EncryptionKey,
+    dk: DecryptionKey,
+}
+
+impl CommitMsg {
+    /// Samples a secret share, x1 at keygen or k1 at signing, and commits to its public share.
+    pub fn create_commitments() -> (CommitMsg, CommWitness, EcKeyPair) {
+        let secret_share: FE = ECScalar::new_random();
+        let public_share = GE::generator() * &secret_share;
+        let d_log_proof = DLogProof::prove(&secret_share);
+
+        let pk_commitment_blind_factor = BigInt::sample(SECURITY);
+        let pk_commitment = HashCommitment::create_commitment_with_user_defined_randomness(
+            &public_share.bytes_compressed_to_big_int(),
+            &pk_commitment_blind_factor,
+        );
+        let zk_pok_blind_factor = BigInt::sample(SECURITY);
+        let zk_pok_commitment = HashCommitment::create_commitment_with_user_defined_randomness(
+            &d_log_proof
+                .pk_t_rand_commitment
+                .bytes_compressed_to_big_int(),
+            &zk_pok_blind_factor,
+        );
+
+        let commitments = CommitMsg {
+            pk_commitment,
+            zk_pok_commitment,
+        };
+        let comm_witness = CommWitness {
+            pk_commitment_blind_factor,
+            zk_pok_blind_factor,
+            public_share: public_share.clone(),
+            d_log_proof,
+        };
+        let ec_key_pair = EcKeyPair {
+            public_share,
+            secret_share,
+        };
+        (commitments, comm_witness, ec_key_pair)
+    }
+}
+
+impl CommWitness {
+    /// Opens the commitments of message 1 once the proof of knowledge of party two checks.
+    pub fn verify_and_decommit(self, party_two_d_log_proof: &DLogProof) -> Result<CommWitness> {
+        DLogProof::verify(party_two_d_log_proof).map_err(|_| InvalidMessage(TwoPartyDLogProof))?;
+        Ok(self)
+    }
+
+    /// Checks that the witness opens `commitments` and carries a valid proof of knowledge
+    /// for its public share.
+    pub fn verify(&self, commitments: &CommitMsg) -> Result<()> {
+        let pk_commitment = HashCommitment::create_commitment_with_user_defined_randomness(
+            &self.public_share.bytes_compressed_to_big_int(),
+            &self.pk_commitment_blind_factor,
+        );
+        let zk_pok_commitment = HashCommitment::create_commitment_with_user_defined_randomness(
+            &self
+                .d_log_proof
+                .pk_t_rand_commitment
+                .bytes_compressed_to_big_int(),
+            &self.zk_pok_blind_factor,
+        );
+        if pk_commitment == commitments.pk_commitment
+            && zk_pok_commitment == commitments.zk_pok_commitment
+            && self.d_log_proof.pk == self.public_share
+            && DLogProof::verify(&self.d_log_proof).is_ok()
+        {
+            Ok(())
+        } else {
+            Err(InvalidMessage(TwoPartyDecommitment))
+        }
+    }
+}
+
+impl KeyGenSecondMsg {
+    /// Checks message 2 of party two, then decommits Q1 and sends x1 encrypted under a fresh
+    /// Paillier key.
+    pub fn create(
+        comm_witness: CommWitness,
+        ec_key_pair: &EcKeyPair,
+        party_two_first_message: &party_two::KeyGenFirstMsg,
+    ) -> Result<(KeyGenSecondMsg, KeyShare)> {
+        let comm_witness =
+            comm_witness.verify_and_decommit(&party_two_first_message.d_log_proof)?;
+        if !verify_dlog_statement(
+            &party_two_first_message.dlog_statement,
+            &party_two_first_message.composite_dlog_proof_base_h1,
+            &party_two_first_message.composite_dlog_proof_base_h2,
+        ) {
+            return Err(InvalidMessage(TwoPartyRingPedersenProof));
+        }
+        let dlog_statement = &party_two_first_message.dlog_statement;
+
+        let (ek, dk) = Paillier::keypair().keys();
+        let x1 = &ec_key_pair.secret_share;
+        let randomness = BigInt::sample_below(&ek.n);
+        let c_key = Paillier::encrypt_with_chosen_randomness(
+            &ek,
+            RawPlaintext::from(x1.to_big_int()),
+            &Randomness::from(randomness.clone()),
+        )
+        .0
+        .into_owned();
+
+        let correct_key_proof = NICorrectKeyProof::proof(&dk);
+        let range_proof =
+            AliceProof::generate::<FE>(&x1.to_big_int(), &c_key, &ek, dlog_statement, &randomness);
+        let pdl_proof = PDLwSlackProof::prove(
+            x1,
+            &randomness,
+            &PDLwSlackStatement {
+                ciphertext: &c_key,
+                ek: &ek,
+                Q: &ec_key_pair.public_share,
+                G: &GE::generator(),
+                dlog_statement,
+            },
+        );
+
+        let key_share = KeyShare {
+            Q: party_two_first_message.d_log_proof.pk.clone() * x1,
+            ek: ek.clone(),
+            dk,
+        };
+        let second_message = KeyGenSecondMsg {
+            comm_witness,
+            ek,
+            c_key,
+            correct_key_proof,
+            range_proof,
+            pdl_proof,
+        };
+        Ok((second_message, key_share))
+    }
+}
+
+impl KeyShare {
+    /// Decrypts the partial signature of party two and completes it with k1 into a low-s
+    /// signature of `message` under Q, with its recovery id.
+    ///
+    /// Whether c3 decrypts to a valid signature tells party two a bit that depends on x1,
+    /// so a party two that crafts c3 learns x1 bit by bit from the failures. A signature
+    /// that does not verify is therefore `RetireKeyShare`: the caller must sign no more
+    /// with this key share.
+    pub fn output_signature(
+        &self,
+        eph_key_pair: &EcKeyPair,
+        party_two_first_message: &party_two::SignFirstMsg,
+        partial_sig: &party_two::PartialSig,
+        message: &BigInt,
+    ) -> Result<Signature> {
+        let R = party_two_first_message.d_log_proof.pk.clone() * &eph_key_pair.secret_share;
+        let r: FE = ECScalar::from(&R.x_coor().unwrap().mod_floor(&FE::q()));
+
+        let s_tag = Paillier::decrypt(&self.dk, &RawCiphertext::from(partial_sig.c3.clone()));
+        let s_tag: FE = ECScalar::from(&s_tag.0.mod_floor(&FE::q()));
+        let mut s = eph_key_pair.secret_share.invert() * &s_tag;
+
+        // recovery id as in `LocalSignature::output_signature`
+        let ry = R
+            .y_coor()
+            .ok_or(InvalidMessage(PartialSignature))?
+            .mod_floor(&FE::q());
+        let mut recid = if ry.test_bit(0) { 1 } else { 0 };
+        let s_bn = s.to_big_int();
+        let s_tag_bn = FE::q() - &s_bn;
+        if s_bn > s_tag_bn {
+            s = ECScalar::from(&s_tag_bn);
+            recid ^= 1;
+        }
+
+        let sig = Signature { r, s, recid };
+        // party one is honest, so a signature that does not verify is party two's, and may
+        // have leaked a bit of x1 to it
+        verify(&sig, &self.Q, message).map_err(|_| RetireKeyShare(PartialSignature))?;
+        Ok(sig)
+    }
+}
@@ -0,0 +1,187 @@
+#![allow(non_snake_case)]
+
+//! Party two of the two-party protocol: it holds x2 and the encryption c_key of x1, and
+//! sends the last message of signing.
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::cryptographic_primitives::proofs::sigma_dlog::{DLogProof, ProveDLog};
+use crate::curv::elliptic::curves::secp256_k1::{FE, GE};
+use crate::curv::elliptic::curves::traits::*;
+use crate::errors::BlameItem::{EncryptedShareProof, TwoPartyCorrectKeyProof};
+use crate::errors::{Result, TssError::InvalidMessage};
+use crate::gg_2018::party_i::generate_h1_h2_N_tilde;
+use crate::gg_2020::zk_pdl_with_slack::PDLwSlackStatement;
+use crate::lindell_2017::is_paillier_key_large_enough;
+use crate::lindell_2017::party_one;
+use crate::paillier::zkproofs::{CompositeDLogProof, DLogStatement};
+use crate::paillier::{Add, Encrypt, EncryptionKey, Mul, Paillier, RawCiphertext, RawPlaintext};
+use num_integer::Integer;
+use num_traits::Pow;
+
+/// Message 2 of keygen: Q2 with its proof of knowledge, and the ring-Pedersen parameters
+/// the proofs of party one are made against.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeyGenFirstMsg {
+    pub d_log_proof: DLogProof,
+    pub dlog_statement: DLogStatement,
+    pub composite_dlog_proof_base_h1: CompositeDLogProof,
+    pub composite_dlog_proof_base_h2: CompositeDLogProof,
+}
+
+/// x2 and Q2 at keygen, k2 and R2 at signing.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EcKeyPair {
+    pub public_share: GE,
+    secret_share: FE,
+}
+
+/// What party two keeps after keygen.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KeyShare {
+    x2: FE,
+    /// the public key x1 x2 G
+    pub Q: GE,
+    pub ek: EncryptionKey,
+    /// Paillier encryption of x1
+    pub c_key: BigInt,
+}
+
+/// Message 2 of signing: R2 with its proof of knowledge.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignFirstMsg {
+    pub d_log_proof: DLogProof,
+}
+
+/// Message 4 of signing: the partial signature, encrypted under the key of party one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PartialSig {
+    pub c3: BigInt,
+}
+
+impl EcKeyPair {
+    fn create() -> (DLogProof, EcKeyPair) {
+        let secret_share: FE = ECScalar::new_random();
+        let public_share = GE::generator() * &secret_share;
+        let d_log_proof = DLogProof::prove(&secret_share);
+        let ec_key_pair = EcKeyPair {
+            public_share,
+            secret_share,
+        };
+        (d_log_proof, ec_key_pair)
+    }
+}
+
+impl KeyGenFirstMsg {
+    pub fn create() -> (KeyGenFirstMsg, EcKeyPair) {
+        let (d_log_proof, ec_key_pair) = EcKeyPair::create();
+        let (N_tilde, h1, h2, xhi, xhi_inv) = generate_h1_h2_N_tilde();
+        let dlog_statement_base_h1 = DLogStatement {
+            N: N_tilde.clone(),
+            g: h1.clone(),
+            ni: h2.clone(),
+        };
+        let dlog_statement_base_h2 = DLogStatement {
+            N: N_tilde,
+            g: h2,
+            ni: h1,
+        };
+        let composite_dlog_proof_base_h1 = CompositeDLogProof::prove(&dlog_statement_base_h1, &xhi);
+        let composite_dlog_proof_base_h2 =
+            CompositeDLogProof::prove(&dlog_statement_base_h2, &xhi_inv);
+
+        let first_message = KeyGenFirstMsg {
+            d_log_proof,
+            dlog_statement: dlog_statement_base_h1,
+            composite_dlog_proof_base_h1,
+            composite_dlog_proof_base_h2,
+        };
+        (first_message, ec_key_pair)
+    }
+}
+
+impl KeyShare {
+    /// Checks message 3 of party one against its commitments of message 1 and against our
+    /// message 2, and computes the public key.
+    pub fn create(
+        ec_key_pair: &EcKeyPair,
+        first_message: &KeyGenFirstMsg,
+        party_one_first_message: &party_one::CommitMsg,
+        party_one_second_message: &party_one::KeyGenSecondMsg,
+    ) -> Result<KeyShare> {
+        let comm_witness = &party_one_second_message.comm_witness;
+        comm_witness.verify(party_one_first_message)?;
+
+        let ek = &party_one_second_message.ek;
+        if !is_paillier_key_large_enough(ek)
+            || party_one_second_message
+                .correct_key_proof
+                .verify(ek)
+                .is_err()
+        {
+            return Err(InvalidMessage(TwoPartyCorrectKeyProof));
+        }
+
+        let c_key = &party_one_second_message.c_key;
+        let dlog_statement = &first_message.dlog_statement;
+        let pdl_statement = PDLwSlackStatement {
+            ciphertext: c_key,
+            ek,
+            Q: &comm_witness.public_share,
+            G: &GE::generator(),
+            dlog_statement,
+        };
+        if !party_one_second_message
+            .range_proof
+            .verify::<FE>(c_key, ek, dlog_statement)
+            || !party_one_second_message.pdl_proof.verify(&pdl_statement)
+        {
+            return Err(InvalidMessage(EncryptedShareProof));
+        }
+
+        Ok(KeyShare {
+            x2: ec_key_pair.secret_share.clone(),
+            Q: comm_witness.public_share.clone() * &ec_key_pair.secret_share,
+            ek: ek.clone(),
+            c_key: c_key.clone(),
+        })
+    }
+
+    /// Checks the decommitment to R1 of party one and encrypts, with c_key, the signature of
+    /// `message` up to the factor k1^-1 only party one knows.
+    pub fn partial_sign(
+        &self,
+        eph_key_pair: &EcKeyPair,
+        party_one_first_message: &party_one::CommitMsg,
+        party_one_second_message: &party_one::CommWitness,
+        message: &BigInt,
+    ) -> Result<PartialSig> {
+        party_one_second_message.verify(party_one_first_message)?;
+
+        let q = FE::q();
+        let R = party_one_second_message.public_share.clone() * &eph_key_pair.secret_share;
+        let r: FE = ECScalar::from(&R.x_coor().unwrap().mod_floor(&q));
+        let m: FE = ECScalar::from(message);
+        let k2_inv = eph_key_pair.secret_share.invert();
+
+        // rho q masks the multiple of q the plaintext of c3 ends up with
+        let rho = BigInt::sample_below(&q.pow(2u32));
+        let partial_sig = rho * &q + (k2_inv.clone() * &m).to_big_int();
+        let c1 = Paillier::encrypt(&self.ek, RawPlaintext::from(partial_sig));
+        let v = k2_inv * &r * &self.x2;
+        let c2 = Paillier::mul(
+            &self.ek,
+            RawCiphertext::from(self.c_key.clone()),
+            RawPlaintext::from(v.to_big_int()),
+        );
+        let c3 = Paillier::add(&self.ek, c1, c2).0.into_owned();
+        Ok(PartialSig { c3 })
+    }
+}
+
+impl SignFirstMsg {
+    pub fn create() -> (SignFirstMsg, EcKeyPair) {
+        let (d_log_proof, eph_key_pair) = EcKeyPair::create();
+        (SignFirstMsg { d_log_proof }, eph_key_pair)
+    }
+}
@@ -0,0 +1,77 @@
+//! The store of the SM manager and its `/ws` channel, without the web server around them.
+//!
+//! The gg18_sm_manager example serves them with Rocket, and the tests over plain tokio.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use crate::common::{Entry, Key, WsFrame};
+
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+use tokio::sync::broadcast::{error::RecvError, Sender};
+use tokio_tungstenite::tungstenite::Message;
+
+/// The entries of the manager, by key.
+pub type Db = RwLock<HashMap<Key, String>>;
+
+/// Stores `entry`, then tells the channels its key.
+pub fn store(db: &Db, keys: &Sender<Key>, entry: Entry) {
+    let mut hm = db.write().unwrap();
+    hm.insert(entry.key.clone(), entry.value);
+    drop(hm);
+    // no channel may be open
+    let _ = keys.send(entry.key);
+}
+
+/// Runs a channel on which a party sets entries and subscribes to keys, until the party
+/// closes it. The entries of the keys are pushed once they are stored, whether over a
+/// channel or by `store`.
+pub async fn channel<S, E>(mut stream: S, db: &Db, keys: &Sender<Key>) -> Result<(), E>
+where
+    S: Stream<Item = Result<Message, E>> + Sink<Message, Error = E> + Unpin,
+{
+    let mut stored = keys.subscribe();
+    let mut subscribed: HashSet<Key> = HashSet::new();
+    loop {
+        tokio::select! {
+            message = stream.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => return Ok(()),
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(e),
+                };
+                match serde_json::from_str::<WsFrame>(&text) {
+                    Ok(WsFrame::Set(entry)) => store(db, keys, entry),
+                    Ok(WsFrame::Subscribe(subscribe)) => subscribed.extend(subscribe),
+                    _ => continue,
+                }
+            }
+            key = stored.recv() => match key {
+                Ok(key) if !subscribed.contains(&key) => continue,
+                // a lagging channel looks up all its keys
+                Ok(_) | Err(RecvError::Lagged(_)) => (),
+                Err(RecvError::Closed) => return Ok(()),
+            }
+        }
+
+        let ready = {
+            let hm = db.read().unwrap();
+            subscribed
+                .iter()
+                .filter_map(|key| {
+                    hm.get(key).map(|value| Entry {
+                        key: key.clone(),
+                        value: value.clone(),
+                    })
+                })
+                .collect::<Vec<Entry>>()
+        };
+        for entry in ready {
+            subscribed.remove(&entry.key);
+            let frame = serde_json::to_string(&WsFrame::Push(entry)).unwrap();
+            stream.send(Message::Text(frame)).await?;
+        }
+    }
+}
@@ -4,17 +4,34 @@ use crate::curv::arithmetic::num_bigint::BigInt;
 use crate::curv::arithmetic::traits::*;
 use crate::paillier::traits::*;
 use crate::paillier::{Keypair, Paillier};
-use num_traits::{One, Zero};
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
 impl KeyGeneration<Keypair> for Paillier {
     fn keypair_with_modulus_size(bit_length: usize) -> Keypair {
-        let p = BigInt::sample_prime(bit_length / 2);
-        let q = BigInt::sample_prime(bit_length / 2);
+        // Blum primes, so that N can be proven to be a Paillier-Blum modulus
+        let p = BigInt::sample_blum_prime(bit_length / 2);
+        let q = BigInt::sample_blum_prime(bit_length / 2);
+        Keypair { p, q }
+    }
+
+    fn keypair_safe_primes_with_modulus_size(bit_length: usize) -> Keypair {
+        let p = BigInt::sample_safe_prime(bit_length / 2);
+        let q = loop {
+            let q = BigInt::sample_safe_prime(bit_length / 2);
+            if q != p {
+                break q;
+            }
+        };
         Keypair { p, q }
     }
 }
 
 pub trait PrimeSampable {
     fn sample_prime(bitsize: usize) -> Self;
+    /// Samples a prime `p` of exactly `bitsize` bits with `p = 3 mod 4`.
+    fn sample_blum_prime(bitsize: usize) -> Self;
+    /// Samples a safe prime `p = 2p' + 1` of exactly `bitsize` bits, with `p'` prime.
+    fn sample_safe_prime(bitsize: usize) -> Self;
 }
 
 impl PrimeSampable for BigInt {
@@ -24,14 +41,9 @@ impl PrimeSampable for BigInt {
         let one = BigInt::one();
         let two = &one + &one;
         loop {
-            let mut candidate = Self::sample(bitsize);
-            // We flip the LSB to make sure tue candidate is odd.
-            //  BitManipulation::set_bit(&mut candidate, 0, true);
-            BigInt::set_bit(&mut candidate, 0, true);
-
-            // To ensure the appropiate size
-            // we set the MSB of the candidate.
-            BigInt::set_bit(&mut candidate, bitsize - 1, true);
+            // To ensure the appropiate size we set the MSB of the candidate,
+            // and we set the LSB to make sure the candidate is odd.
+            let mut candidate = Self::sample(bitsize) | (&one << (bitsize - 1)) | &one;
 
             // If no prime number is found in 500 iterations,
             // restart the loop (re-seed).
@@ -44,91 +56,258 @@ impl PrimeSampable for BigInt {
             }
         }
     }
+
+    fn sample_blum_prime(bitsize: usize) -> Self {
+        let three = BigInt::from(3u32);
+        let four = BigInt::from(4u32);
+        loop {
+            // Set the MSB for the size and the two low bits so that candidate = 3 mod 4.
+            let mut candidate = Self::sample(bitsize) | (BigInt::one() << (bitsize - 1)) | &three;
+            for _ in 0..500 {
+                if is_prime(&candidate) {
+                    return candidate;
+                }
+                candidate += &four;
+            }
+        }
+    }
+
+    fn sample_safe_prime(bitsize: usize) -> Self {
+        let one = BigInt::one();
+        let two = BigInt::from(2u32);
+        loop {
+            // odd p' of bitsize - 1 bits, so that p = 2p' + 1 has exactly bitsize bits
+            let base = Self::sample(bitsize - 1) | (&one << (bitsize - 2)) | &one;
+            // Almost every candidate pair has a small factor, so sieve p' + delta and
+            // 2(p' + delta) + 1 with the residues of p' before running any expensive test.
+            let residues = SMALL_PRIMES[1..]
+                .iter()
+                .map(|sp| (&base % BigInt::from(*sp)).to_u64().unwrap())
+                .collect::<Vec<u64>>();
+            for delta in (0..SAFE_PRIME_SIEVE_RANGE).step_by(2) {
+                let sieved = SMALL_PRIMES[1..]
+                    .iter()
+                    .zip(residues.iter())
+                    .all(|(sp, r)| {
+                        let sp = u64::from(*sp);
+                        let r = (r + delta) % sp;
+                        r != 0 && (2 * r + 1) % sp != 0
+                    });
+                if !sieved {
+                    continue;
+                }
+                let p_prime = &base + BigInt::from(delta);
+                let p = &two * &p_prime + &one;
+                // cheap base 2 tests first, the full test only for likely safe primes
+                if miller_rabin(&p_prime, &two)
+                    && miller_rabin(&p, &two)
+                    && is_prime(&p_prime)
+                    && is_prime(&p)
+                {
+                    return p;
+                }
+            }
+        }
+    }
 }
 
-// Runs the following three tests on a given `candidate` to determine
-// primality:
+// Number of consecutive odd p' sieved from a random starting point.
+const SAFE_PRIME_SIEVE_RANGE: u64 = 1 << 20;
+
+// Baillie-PSW probable prime test on a given `candidate`:
+//
+// 1. Divide the candidate by the first 2048 small prime numbers.
+// 2. Run a strong Miller-Rabin test to base 2 on the candidate.
+// 3. Run a strong Lucas probable prime test on the candidate.
 //
-// 1. Divide the candidate by the first 999 small prime numbers.
-// 2. Run Fermat's Little Theorem against the candidate.
-// 3. Run five rounds of the Miller-Rabin test on the candidate.
-fn is_prime(candidate: &BigInt) -> bool {
+// No composite number is known to pass both 2. and 3.
+pub(crate) fn is_prime(candidate: &BigInt) -> bool {
+    if *candidate < BigInt::from(2u32) {
+        return false;
+    }
     // First, simple trial divide
     for p in SMALL_PRIMES.iter() {
         let prime = BigInt::from(*p);
+        if *candidate == prime {
+            return true;
+        }
         let r = candidate % &prime;
-        if !NumberTests::is_zero(&r) {
-            continue;
-        } else {
+        if NumberTests::is_zero(&r) {
             return false;
         }
     }
-    // Second, do a little Fermat test on the candidate
-    if !fermat(candidate) {
+
+    // Second, a strong Miller-Rabin test to base 2
+    if !miller_rabin(candidate, &BigInt::from(2u32)) {
         return false;
     }
 
-    // Finally, do a Miller-Rabin test
-    // NIST recommendation is 5 rounds for 512 and 1024 bits. For 1536 bits, the recommendation is 4 rounds.
-    if !miller_rabin(candidate, 5) {
-        return false;
+    // Finally, a strong Lucas test
+    strong_lucas(candidate)
+}
+
+/// Strong Miller-Rabin probable prime test of an odd `candidate` to base `basis`.
+fn miller_rabin(candidate: &BigInt, basis: &BigInt) -> bool {
+    let one = BigInt::one();
+    let candidate_minus_one = candidate - &one;
+    let (s, d) = rewrite(&candidate_minus_one);
+
+    let mut y = BigInt::mod_pow(basis, &d, candidate);
+    if y == one || y == candidate_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        y = BigInt::mod_mul(&y, &y, candidate);
+        if y == candidate_minus_one {
+            return true;
+        }
+        if y == one {
+            return false;
+        }
     }
-    true
+    false
 }
 
-/// Perform test based on Fermat's little theorem
-/// This might be performed more than once, see Handbook of Applied Cryptography [Algorithm 4.9 p136]
-fn fermat(candidate: &BigInt) -> bool {
-    let random = BigInt::sample_below(candidate);
-    let result = BigInt::mod_pow(&random, &(candidate - &BigInt::one()), candidate);
+/// Strong Lucas probable prime test of an odd `candidate`, with the parameters
+/// P = 1, Q = (1 - D) / 4 chosen by Selfridge's method A.
+/// See "Lucas Pseudoprimes", Baillie and Wagstaff, Math. Comp. 35 (1980).
+fn strong_lucas(candidate: &BigInt) -> bool {
+    let n = candidate;
+    // There is no D with (D/n) = -1 when n is a perfect square
+    let root = n.sqrt();
+    if &root * &root == *n {
+        return false;
+    }
+
+    // D is the first of 5, -7, 9, -11, 13, ... with (D/n) = -1
+    let mut d_abs = 5u32;
+    let mut d_negative = false;
+    loop {
+        let d_big = BigInt::from(d_abs);
+        let j = if d_negative {
+            jacobi_minus_one(n) * jacobi(&d_big, n)
+        } else {
+            jacobi(&d_big, n)
+        };
+        if j == -1 {
+            break;
+        }
+        if j == 0 && d_big < *n {
+            return false;
+        }
+        d_abs += 2;
+        d_negative = !d_negative;
+    }
+
+    // D and Q as residues mod n
+    let d = signed_mod(d_abs, d_negative, n);
+    let (q_abs, q_negative) = if d_negative {
+        // Q = (1 + |D|) / 4
+        ((d_abs + 1) / 4, false)
+    } else {
+        // Q = -(|D| - 1) / 4
+        ((d_abs - 1) / 4, true)
+    };
+    let q = signed_mod(q_abs, q_negative, n);
 
-    result == BigInt::one()
+    // n + 1 = 2^s * k, with k odd
+    let (s, k) = rewrite(&(n + BigInt::one()));
+
+    // U_k, V_k and Q^k by the binary method, starting from U_1 = 1, V_1 = P = 1, Q^1
+    let mut u = BigInt::one();
+    let mut v = BigInt::one();
+    let mut q_k = q.clone();
+    for bit in k.to_str_radix(2).chars().skip(1) {
+        // U_2m = U_m V_m, V_2m = V_m^2 - 2 Q^m
+        u = BigInt::mod_mul(&u, &v, n);
+        v = BigInt::mod_sub(&BigInt::mod_mul(&v, &v, n), &(&q_k << 1), n);
+        q_k = BigInt::mod_mul(&q_k, &q_k, n);
+        if bit == '1' {
+            // U_m+1 = (P U_m + V_m) / 2, V_m+1 = (D U_m + P V_m) / 2
+            let u_next = half_mod(&BigInt::mod_add(&u, &v, n), n);
+            v = half_mod(&BigInt::mod_add(&BigInt::mod_mul(&d, &u, n), &v, n), n);
+            u = u_next;
+            q_k = BigInt::mod_mul(&q_k, &q, n);
+        }
+    }
+
+    // n is a strong Lucas probable prime if U_k = 0 or V_(2^r k) = 0 for some 0 <= r < s
+    if u.is_zero() {
+        return true;
+    }
+    for _ in 0..s {
+        if v.is_zero() {
+            return true;
+        }
+        v = BigInt::mod_sub(&BigInt::mod_mul(&v, &v, n), &(&q_k << 1), n);
+        q_k = BigInt::mod_mul(&q_k, &q_k, n);
+    }
+    false
 }
 
-/// Perform Miller-Rabin primality test
-fn miller_rabin(candidate: &BigInt, limit: usize) -> bool {
-    // Iterations recommended for which  p < (1/2)^{80}
-    //  500 bits => 6 iterations
-    // 1000 bits => 3 iterations
-    // 2000 bits => 2 iterations
+// x / 2 mod an odd n
+fn half_mod(x: &BigInt, n: &BigInt) -> BigInt {
+    if x.is_odd() {
+        (x + n) >> 1
+    } else {
+        x >> 1
+    }
+}
 
-    let (s, d) = rewrite(&(candidate - &BigInt::one()));
-    let one = BigInt::one();
-    let two = &one + &one;
+fn signed_mod(abs: u32, negative: bool, n: &BigInt) -> BigInt {
+    let abs = BigInt::from(abs) % n;
+    if negative && !abs.is_zero() {
+        n - abs
+    } else {
+        abs
+    }
+}
 
-    for _ in 0..limit {
-        let basis = BigInt::sample_range(&two, &(candidate - &two));
-        let mut y = BigInt::mod_pow(&basis, &d, candidate);
+// (-1/n) for an odd positive n
+fn jacobi_minus_one(n: &BigInt) -> i8 {
+    match (n % BigInt::from(4u32)).to_u32() {
+        Some(1) => 1,
+        _ => -1,
+    }
+}
 
-        if y == one || y == (candidate - &one) {
-            continue;
-        } else {
-            let mut counter = BigInt::one();
-            while counter < (&s - &one) {
-                y = BigInt::mod_pow(&y, &two, candidate);
-                if y == one {
-                    return false;
-                } else if y == candidate - &one {
-                    break;
-                }
-                counter = counter + BigInt::one();
+/// Jacobi symbol (a/n) for an odd positive n.
+pub(crate) fn jacobi(a: &BigInt, n: &BigInt) -> i8 {
+    let eight = BigInt::from(8u32);
+    let four = BigInt::from(4u32);
+    let mut a = a % n;
+    let mut n = n.clone();
+    let mut t = 1;
+    while !a.is_zero() {
+        while a.is_even() {
+            a >>= 1;
+            let r = (&n % &eight).to_u32().unwrap();
+            if r == 3 || r == 5 {
+                t = -t;
             }
-            return false;
         }
+        std::mem::swap(&mut a, &mut n);
+        if (&a % &four).to_u32() == Some(3) && (&n % &four).to_u32() == Some(3) {
+            t = -t;
+        }
+        a = &a % &n;
+    }
+    if n.is_one() {
+        t
+    } else {
+        0
     }
-    true
 }
 
 /// Rewrite a number n = 2^s * d
 /// (i.e., 2^s is the largest power of 2 that divides the candidate).
-fn rewrite(n: &BigInt) -> (BigInt, BigInt) {
+fn rewrite(n: &BigInt) -> (usize, BigInt) {
     let mut d = n.clone();
-    let mut s = BigInt::zero();
-    let one = BigInt::one();
+    let mut s = 0;
 
-    while BigInt::is_even(&d) {
+    while d.is_even() {
         d = d >> 1_usize;
-        s = &s + &one;
+        s += 1;
     }
 
     (s, d)
@@ -325,3 +504,75 @@ static SMALL_PRIMES: [u32; 2048] = [
     17609, 17623, 17627, 17657, 17659, 17669, 17681, 17683, 17707, 17713, 17729,
     17737, 17747, 17749, 17761, 17783, 17789, 17791, 17807, 17827, 17837, 17839,
     17851, 17863 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_jacobi() {
+        // (a/15) for a = 0..15
+        let expected = [0, 1, 1, 0, 1, 0, 0, -1, 1, 0, 0, -1, 0, -1, -1];
+        let n = BigInt::from(15u32);
+        for (a, e) in expected.iter().enumerate() {
+            assert_eq!(jacobi(&BigInt::from(a as u32), &n), *e);
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_is_prime() {
+        let m127 = (BigInt::one() << 127) - BigInt::one();
+        let m521 = (BigInt::one() << 521) - BigInt::one();
+        for p in [
+            BigInt::from(2u32),
+            BigInt::from(17863u32),
+            m127.clone(),
+            m521.clone(),
+        ]
+        .iter()
+        {
+            assert!(is_prime(p));
+        }
+        // Carmichael number, strong pseudoprime to bases 2, 3, 5 and 7,
+        // square of a prime and product of two large primes
+        let composites = [
+            BigInt::from(561u32),
+            BigInt::from(3_215_031_751u64),
+            &m127 * &m127,
+            &m127 * &m521,
+        ];
+        for c in composites.iter() {
+            assert!(!is_prime(c));
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_baillie_psw_pseudoprimes() {
+        // 2047 = 23 * 89 is a strong pseudoprime to base 2, but not a strong Lucas pseudoprime
+        let n = BigInt::from(2047u32);
+        assert!(miller_rabin(&n, &BigInt::from(2u32)));
+        assert!(!strong_lucas(&n));
+        // 5459 = 53 * 103 and 5777 = 53 * 109 are strong Lucas pseudoprimes,
+        // but not strong pseudoprimes to base 2
+        for n in [5459u32, 5777].iter() {
+            let n = BigInt::from(*n);
+            assert!(strong_lucas(&n));
+            assert!(!miller_rabin(&n, &BigInt::from(2u32)));
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_sample_safe_prime() {
+        let p = BigInt::sample_safe_prime(256);
+        assert_eq!(p.bits(), 256);
+        assert!(is_prime(&p));
+        assert!(is_prime(&(&p >> 1)));
+    }
+}
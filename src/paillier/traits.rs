@@ -11,6 +11,16 @@ pub trait KeyGeneration<KP> {
     ///
     /// Currently recommended security level is a minimum of 2048 bits.
     fn keypair_with_modulus_size(big_length: usize) -> KP;
+
+    /// Generate fresh key pair from safe primes with currently recommended security level
+    /// (2048 bit modulus).
+    fn keypair_safe_primes() -> KP {
+        Self::keypair_safe_primes_with_modulus_size(2048)
+    }
+
+    /// Generate fresh key pair from safe primes `p = 2p' + 1`, `q = 2q' + 1`, with security
+    /// level specified as the `bit_length` of the modulus.
+    fn keypair_safe_primes_with_modulus_size(bit_length: usize) -> KP;
 }
 
 pub trait PrecomputeRandomness<EK, R, PR> {
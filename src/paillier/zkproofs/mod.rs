@@ -18,4 +18,9 @@ mod correct_key_ni;
 pub use self::correct_key_ni::CorrectKeyProofError;
 pub use self::correct_key_ni::NICorrectKeyProof;
 mod wi_dlog_proof;
-pub use self::wi_dlog_proof::DLogStatement;
+pub use self::wi_dlog_proof::IncorrectProof;
+pub use self::wi_dlog_proof::{CompositeDLogProof, DLogStatement};
+mod paillier_blum_modulus;
+pub use self::paillier_blum_modulus::PaillierBlumModulusProof;
+mod no_small_factor;
+pub use self::no_small_factor::NoSmallFactorProof;
@@ -0,0 +1,208 @@
+#![allow(non_snake_case)]
+/*
+    No small factor proof
+
+    Non-interactive version of the protocol Π-fac, figure 28 of
+    "UC Non-Interactive, Proactive, Threshold ECDSA with Identifiable Aborts"
+    (https://eprint.iacr.org/2021/060.pdf).
+
+    Proves that N0 = pq with p, q > 2^l, using the ring-Pedersen parameters
+    (N_tilde, s = h1, t = h2) of the verifier.
+*/
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::cryptographic_primitives::hashing::ext::DigestExt;
+use crate::curv::elliptic::curves::secp256_k1::FE;
+use crate::curv::elliptic::curves::traits::ECScalar;
+use crate::paillier::DecryptionKey;
+use num_bigint::{BigInt as SignedBigInt, Sign};
+use num_traits::{One, Signed};
+use sha2::{Digest, Sha256};
+
+use super::wi_dlog_proof::IncorrectProof;
+use super::DLogStatement;
+
+// minimal bit length of the factors
+const L: usize = 256;
+// slackness parameter
+const EPSILON: usize = 512;
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct NoSmallFactorProof {
+    pub P: BigInt,
+    pub Q: BigInt,
+    pub A: BigInt,
+    pub B: BigInt,
+    pub T: BigInt,
+    pub sigma: SignedBigInt,
+    pub z1: SignedBigInt,
+    pub z2: SignedBigInt,
+    pub w1: SignedBigInt,
+    pub w2: SignedBigInt,
+    pub v: SignedBigInt,
+}
+
+impl NoSmallFactorProof {
+    /// Proves that the Paillier modulus of `dk` has no small factors, towards the verifier
+    /// owning the ring-Pedersen parameters in `statement`.
+    pub fn prove(dk: &DecryptionKey, statement: &DLogStatement) -> NoSmallFactorProof {
+        let N0 = &dk.p * &dk.q;
+        let (N_tilde, s, t) = (&statement.N, &statement.g, &statement.ni);
+        let p = to_signed(&dk.p);
+        let q = to_signed(&dk.q);
+
+        let sqrt_N0_bound = N0.sqrt() << (L + EPSILON);
+        let alpha = sample_plus_minus(&sqrt_N0_bound);
+        let beta = sample_plus_minus(&sqrt_N0_bound);
+        let mu = sample_plus_minus(&(N_tilde << L));
+        let nu = sample_plus_minus(&(N_tilde << L));
+        let sigma = sample_plus_minus(&((&N0 * N_tilde) << L));
+        let r = sample_plus_minus(&((&N0 * N_tilde) << (L + EPSILON)));
+        let x = sample_plus_minus(&(N_tilde << (L + EPSILON)));
+        let y = sample_plus_minus(&(N_tilde << (L + EPSILON)));
+
+        let P = commit(s, &p, t, &mu, N_tilde);
+        let Q = commit(s, &q, t, &nu, N_tilde);
+        let A = commit(s, &alpha, t, &x, N_tilde);
+        let B = commit(s, &beta, t, &y, N_tilde);
+        let T = commit(&Q, &alpha, t, &r, N_tilde);
+
+        let e = to_signed(&challenge(&N0, statement, &[&P, &Q, &A, &B, &T], &sigma));
+        let sigma_hat = &sigma - &nu * &p;
+
+        NoSmallFactorProof {
+            z1: &alpha + &e * &p,
+            z2: &beta + &e * &q,
+            w1: &x + &e * &mu,
+            w2: &y + &e * &nu,
+            v: &r + &e * &sigma_hat,
+            P,
+            Q,
+            A,
+            B,
+            T,
+            sigma,
+        }
+    }
+
+    pub fn verify(&self, N0: &BigInt, statement: &DLogStatement) -> Result<(), IncorrectProof> {
+        let (N_tilde, s, t) = (&statement.N, &statement.g, &statement.ni);
+        let e = challenge(
+            N0,
+            statement,
+            &[&self.P, &self.Q, &self.A, &self.B, &self.T],
+            &self.sigma,
+        );
+
+        // s^z1 t^w1 = A P^e
+        let lhs1 = commit(s, &self.z1, t, &self.w1, N_tilde);
+        let rhs1 = BigInt::mod_mul(&self.A, &BigInt::mod_pow(&self.P, &e, N_tilde), N_tilde);
+        // s^z2 t^w2 = B Q^e
+        let lhs2 = commit(s, &self.z2, t, &self.w2, N_tilde);
+        let rhs2 = BigInt::mod_mul(&self.B, &BigInt::mod_pow(&self.Q, &e, N_tilde), N_tilde);
+        // Q^z1 t^v = T R^e, with R = s^N0 t^sigma
+        let R = commit(s, &to_signed(N0), t, &self.sigma, N_tilde);
+        let lhs3 = commit(&self.Q, &self.z1, t, &self.v, N_tilde);
+        let rhs3 = BigInt::mod_mul(&self.T, &BigInt::mod_pow(&R, &e, N_tilde), N_tilde);
+
+        // z1, z2 in +-sqrt(N0) 2^(l + epsilon)
+        let bound = to_signed(&(N0.sqrt() << (L + EPSILON)));
+        let in_range = self.z1.abs() <= bound && self.z2.abs() <= bound;
+
+        match lhs1 == rhs1 && lhs2 == rhs2 && lhs3 == rhs3 && in_range {
+            true => Ok(()),
+            false => Err(IncorrectProof),
+        }
+    }
+}
+
+fn challenge(
+    N0: &BigInt,
+    statement: &DLogStatement,
+    values: &[&BigInt],
+    sigma: &SignedBigInt,
+) -> BigInt {
+    let (sign, sigma_bytes) = sigma.to_bytes_be();
+    let hasher = [N0, &statement.N, &statement.g, &statement.ni]
+        .iter()
+        .chain(values.iter())
+        .fold(Sha256::new(), |acc, b| acc.chain_bigint(b))
+        .chain([(sign == Sign::Minus) as u8])
+        .chain(sigma_bytes);
+    let q = FE::q();
+    hasher.result_bigint() % q
+}
+
+// s^a t^b mod N for signed exponents
+fn commit(s: &BigInt, a: &SignedBigInt, t: &BigInt, b: &SignedBigInt, N: &BigInt) -> BigInt {
+    BigInt::mod_mul(&mod_pow_signed(s, a, N), &mod_pow_signed(t, b, N), N)
+}
+
+fn mod_pow_signed(base: &BigInt, exponent: &SignedBigInt, modulus: &BigInt) -> BigInt {
+    let exponent_abs = exponent.abs().to_biguint().unwrap();
+    match exponent.sign() {
+        Sign::Minus => BigInt::mod_pow(&BigInt::mod_inv(base, modulus), &exponent_abs, modulus),
+        _ => BigInt::mod_pow(base, &exponent_abs, modulus),
+    }
+}
+
+// uniform in [-bound, bound]
+fn sample_plus_minus(bound: &BigInt) -> SignedBigInt {
+    let sample = BigInt::sample_below(&((bound << 1) + BigInt::one()));
+    to_signed(&sample) - to_signed(bound)
+}
+
+fn to_signed(n: &BigInt) -> SignedBigInt {
+    SignedBigInt::from_biguint(Sign::Plus, n.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gg_2018::party_i::generate_h1_h2_N_tilde;
+    use crate::paillier::{KeyGeneration, Keypair, Paillier, PrimeSampable};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn verifier_statement() -> DLogStatement {
+        let (N, h1, h2, _, _) = generate_h1_h2_N_tilde();
+        DLogStatement { N, g: h1, ni: h2 }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_no_small_factor_proof() {
+        let (ek, dk) = Paillier::keypair().keys();
+        let statement = verifier_statement();
+        let proof = NoSmallFactorProof::prove(&dk, &statement);
+        assert!(proof.verify(&ek.n, &statement).is_ok());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_no_small_factor_proof_small_factor() {
+        // N0 = p * q with a 128 bit factor p
+        let p = BigInt::sample_prime(128);
+        let q = BigInt::sample_prime(1920);
+        let dk = DecryptionKey::from(&Keypair {
+            p: p.clone(),
+            q: q.clone(),
+        });
+        let statement = verifier_statement();
+        let proof = NoSmallFactorProof::prove(&dk, &statement);
+        assert!(proof.verify(&(&p * &q), &statement).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_no_small_factor_proof_wrong_statement() {
+        let (ek, dk) = Paillier::keypair().keys();
+        let statement = verifier_statement();
+        let proof = NoSmallFactorProof::prove(&dk, &statement);
+        let mut bad_statement = statement.clone();
+        bad_statement.ni = BigInt::mod_mul(&statement.ni, &statement.g, &statement.N);
+        assert!(proof.verify(&ek.n, &bad_statement).is_err());
+    }
+}
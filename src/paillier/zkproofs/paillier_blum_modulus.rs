@@ -0,0 +1,169 @@
+#![allow(non_snake_case)]
+/*
+    Paillier-Blum modulus proof
+
+    Non-interactive version of the protocol Π-mod, figure 16 of
+    "UC Non-Interactive, Proactive, Threshold ECDSA with Identifiable Aborts"
+    (https://eprint.iacr.org/2021/060.pdf).
+
+    Proves that N = pq with p = q = 3 mod 4 and gcd(N, phi(N)) = 1.
+*/
+
+use crate::curv::arithmetic::num_bigint::BigInt;
+use crate::curv::arithmetic::traits::*;
+use crate::curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
+use crate::curv::cryptographic_primitives::hashing::traits::Hash;
+use crate::paillier::keygen::{is_prime, jacobi};
+use crate::paillier::{DecryptionKey, EncryptionKey};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+use super::correct_key_ni::mask_generation;
+use super::wi_dlog_proof::IncorrectProof;
+
+// number of challenges, soundness error 2^-M
+const M: usize = 80;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PaillierBlumModulusProof {
+    pub w: BigInt,
+    pub x_vec: Vec<BigInt>,
+    pub a_vec: Vec<bool>,
+    pub b_vec: Vec<bool>,
+    pub z_vec: Vec<BigInt>,
+}
+
+impl PaillierBlumModulusProof {
+    pub fn prove(dk: &DecryptionKey) -> PaillierBlumModulusProof {
+        let (p, q) = (&dk.p, &dk.q);
+        let N = p * q;
+        let one = BigInt::one();
+        let phi = (p - &one) * (q - &one);
+
+        // w is a quadratic non-residue with Jacobi symbol -1
+        let w = loop {
+            let w = BigInt::sample_below(&N);
+            if jacobi(&w, &N) == -1 {
+                break w;
+            }
+        };
+        let N_inv = BigInt::mod_inv(&N, &phi);
+        let p_inv = BigInt::mod_inv(p, q);
+
+        let mut proof = PaillierBlumModulusProof {
+            w,
+            x_vec: Vec::with_capacity(M),
+            a_vec: Vec::with_capacity(M),
+            b_vec: Vec::with_capacity(M),
+            z_vec: Vec::with_capacity(M),
+        };
+        for y in challenges(&N, &proof.w) {
+            proof.z_vec.push(BigInt::mod_pow(&y, &N_inv, &N));
+            // exactly one of y, -y, wy, -wy is a quadratic residue mod N
+            let (a, b, y_prime) = [(false, false), (true, false), (false, true), (true, true)]
+                .iter()
+                .map(|&(a, b)| (a, b, adjust(&y, &proof.w, a, b, &N)))
+                .find(|(_, _, y_prime)| is_qr(y_prime, p) && is_qr(y_prime, q))
+                .expect("y is coprime to N");
+            let x_p = fourth_root(&y_prime, p);
+            let x_q = fourth_root(&y_prime, q);
+            // CRT: x = x_p + p * ((x_q - x_p) * p^-1 mod q)
+            let h = BigInt::mod_mul(&BigInt::mod_sub(&x_q, &x_p, q), &p_inv, q);
+            proof.x_vec.push(x_p + p * h);
+            proof.a_vec.push(a);
+            proof.b_vec.push(b);
+        }
+        proof
+    }
+
+    pub fn verify(&self, ek: &EncryptionKey) -> Result<(), IncorrectProof> {
+        let N = &ek.n;
+        if N.is_even() || is_prime(N) || jacobi(&self.w, N) != -1 {
+            return Err(IncorrectProof);
+        }
+        if self.x_vec.len() != M
+            || self.a_vec.len() != M
+            || self.b_vec.len() != M
+            || self.z_vec.len() != M
+        {
+            return Err(IncorrectProof);
+        }
+        let four = BigInt::from(4u32);
+        let valid = challenges(N, &self.w).iter().enumerate().all(|(i, y)| {
+            self.x_vec[i] < *N
+                && self.z_vec[i] < *N
+                && BigInt::mod_pow(&self.z_vec[i], N, N) == *y
+                && BigInt::mod_pow(&self.x_vec[i], &four, N)
+                    == adjust(y, &self.w, self.a_vec[i], self.b_vec[i], N)
+        });
+        match valid {
+            true => Ok(()),
+            false => Err(IncorrectProof),
+        }
+    }
+}
+
+// y_i = H(N, w, i) mod N
+fn challenges(N: &BigInt, w: &BigInt) -> Vec<BigInt> {
+    let key_length = N.bits();
+    (0..M)
+        .map(|i| {
+            let seed = HSha256::create_hash(&[N, w, &BigInt::from(i as u32)]);
+            mask_generation(&key_length, &seed) % N
+        })
+        .collect()
+}
+
+// (-1)^a * w^b * y mod N
+fn adjust(y: &BigInt, w: &BigInt, a: bool, b: bool, N: &BigInt) -> BigInt {
+    let y = if b {
+        BigInt::mod_mul(w, y, N)
+    } else {
+        y.clone()
+    };
+    if a && !y.is_zero() {
+        N - y
+    } else {
+        y
+    }
+}
+
+// Euler's criterion for an odd prime p
+fn is_qr(y: &BigInt, p: &BigInt) -> bool {
+    let e = (p - BigInt::one()) >> 1;
+    BigInt::mod_pow(y, &e, p) == BigInt::one()
+}
+
+// For p = 3 mod 4 and y a quadratic residue, y^((p+1)/4) is the square root of y
+// that is itself a quadratic residue, so applying it twice yields a fourth root.
+fn fourth_root(y: &BigInt, p: &BigInt) -> BigInt {
+    let e = (p + BigInt::one()) >> 2;
+    BigInt::mod_pow(y, &(&e * &e), p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paillier::KeyGeneration;
+    use crate::paillier::Paillier;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_paillier_blum_modulus_proof() {
+        let (ek, dk) = Paillier::keypair().keys();
+        let proof = PaillierBlumModulusProof::prove(&dk);
+        assert!(proof.verify(&ek).is_ok());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[test]
+    fn test_paillier_blum_modulus_proof_wrong_modulus() {
+        let (_, dk) = Paillier::keypair().keys();
+        let (ek_other, _) = Paillier::keypair().keys();
+        let proof = PaillierBlumModulusProof::prove(&dk);
+        assert!(proof.verify(&ek_other).is_err());
+    }
+}
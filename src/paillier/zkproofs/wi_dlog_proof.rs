@@ -80,7 +80,6 @@ pub struct DLogStatement {
 }
 
 impl CompositeDLogProof {
-    #[allow(dead_code)]
     pub fn prove(statement: &DLogStatement, secret: &BigInt) -> CompositeDLogProof {
         //   pub fn prove(statement: &DLogStatement, secret: &BigInt, dk: &DecryptionKey) -> DLogProof{
 
@@ -88,7 +87,9 @@ impl CompositeDLogProof {
         //  let phi = (&dk.p - &one) * (&dk.q - &one);
         //   let r = BigInt::sample_below(&phi);
 
-        let R = BigInt::from(2u32).pow((K + K_PRIME + SAMPLE_S) as u32);
+        // r has to hide e * secret, so it grows with the secret (h1/h2 trapdoors are ~|N| bits)
+        let secret_bits = std::cmp::max(SAMPLE_S, secret.bits());
+        let R = BigInt::from(2u32).pow((K + K_PRIME + secret_bits) as u32);
         let r = BigInt::sample_below(&R);
         let x = BigInt::mod_pow(&statement.g, &r, &statement.N);
         let e = compute_digest(
@@ -101,14 +102,19 @@ impl CompositeDLogProof {
 
         CompositeDLogProof { x, y }
     }
-    #[allow(dead_code)]
+
     pub fn verify(&self, statement: &DLogStatement) -> Result<(), IncorrectProof> {
-        //assert N > 2^k
-        assert!(statement.N > BigInt::from(2u32).pow(K as u32));
+        // N > 2^k
+        if statement.N <= BigInt::from(2u32).pow(K as u32) {
+            return Err(IncorrectProof);
+        }
 
-        //test that g, ni in multiplecative group Z_N*
-        assert_eq!(statement.g.gcd(&statement.N), BigInt::one());
-        assert_eq!(statement.ni.gcd(&statement.N), BigInt::one());
+        // g, ni in multiplicative group Z_N*
+        if statement.g.gcd(&statement.N) != BigInt::one()
+            || statement.ni.gcd(&statement.N) != BigInt::one()
+        {
+            return Err(IncorrectProof);
+        }
 
         let e = compute_digest(
             iter::once(&self.x)
@@ -0,0 +1,150 @@
+//! In-process simulation of the parties of a protocol session.
+//!
+//! The parties are state machines, e.g. `gg_2018::state_machine::Keygen`, and their messages
+//! go through an in-memory router. The network is simulated in ticks: a message sent at
+//! tick t arrives at tick t + 1 plus a random delay, unless it is dropped. The randomness is
+//! seeded, so that a failing run can be replayed.
+
+use crate::errors::{Result, TssError};
+use crate::gg_2018::state_machine::{Msg, StateMachine};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// How the simulated network delivers messages.
+#[derive(Clone, Debug)]
+pub struct Network {
+    /// Shuffle the messages that arrive at the same tick
+    pub reorder: bool,
+    /// The delay of a message is drawn from 0..=max_delay ticks
+    pub max_delay: u32,
+    /// The probability that a message to a party is lost
+    pub drop_rate: f64,
+    pub seed: u64,
+}
+
+impl Default for Network {
+    /// In order and without loss.
+    fn default() -> Network {
+        Network {
+            reorder: false,
+            max_delay: 0,
+            drop_rate: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+/// What happened to the messages of a run.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub delivered: usize,
+    pub dropped: usize,
+    /// The tick of the last delivery
+    pub ticks: u64,
+}
+
+/// A message on its way to party `to`, 1-based.
+struct InFlight {
+    arrival: u64,
+    to: u16,
+    msg: Msg,
+}
+
+/// Runs `parties`, party i + 1 at index i, until the network is idle. Returns their outputs
+/// once all of them finish. The first error of a party ends the run. A run in which
+/// messages were dropped usually stalls, and fails with the parties left waiting.
+pub fn run<M>(parties: &mut [M], network: &Network) -> Result<(Vec<M::Output>, Stats)>
+where
+    M: StateMachine,
+    M::Output: Clone,
+{
+    let mut rng = StdRng::seed_from_u64(network.seed);
+    let mut in_flight: Vec<InFlight> = Vec::new();
+    let mut stats = Stats::default();
+    let n = parties.len() as u16;
+
+    let mut tick = 0;
+    for party in parties.iter_mut() {
+        let outgoing = party.proceed()?;
+        send(
+            &mut in_flight,
+            &mut stats,
+            &mut rng,
+            network,
+            n,
+            tick,
+            outgoing,
+        );
+    }
+    while !in_flight.is_empty() {
+        tick += 1;
+        let (mut arrived, waiting): (Vec<InFlight>, Vec<InFlight>) = in_flight
+            .into_iter()
+            .partition(|message| message.arrival <= tick);
+        in_flight = waiting;
+        if network.reorder {
+            arrived.shuffle(&mut rng);
+        }
+        for message in arrived {
+            let outgoing = parties[usize::from(message.to - 1)].handle_incoming(message.msg)?;
+            stats.delivered += 1;
+            stats.ticks = tick;
+            send(
+                &mut in_flight,
+                &mut stats,
+                &mut rng,
+                network,
+                n,
+                tick,
+                outgoing,
+            );
+        }
+    }
+
+    let waiting = (1..=n)
+        .filter(|&i| !parties[usize::from(i - 1)].is_finished())
+        .collect::<Vec<u16>>();
+    if !waiting.is_empty() {
+        return Err(TssError::UnknownError {
+            msg: format!("parties {:?} stalled", waiting),
+            line: line!(),
+        });
+    }
+    let outputs = parties
+        .iter()
+        .map(|party| party.output().unwrap().clone())
+        .collect();
+    Ok((outputs, stats))
+}
+
+/// Routes the messages sent at `tick` to the parties they are for.
+fn send(
+    in_flight: &mut Vec<InFlight>,
+    stats: &mut Stats,
+    rng: &mut StdRng,
+    network: &Network,
+    n: u16,
+    tick: u64,
+    outgoing: Vec<Msg>,
+) {
+    for msg in outgoing {
+        let receivers = match msg.receiver {
+            Some(to) => vec![to],
+            None => (1..=n).filter(|&to| to != msg.sender).collect(),
+        };
+        for to in receivers {
+            if network.drop_rate > 0.0 && rng.gen_bool(network.drop_rate) {
+                stats.dropped += 1;
+                continue;
+            }
+            let delay = rng.gen_range(0, u64::from(network.max_delay) + 1);
+            in_flight.push(InFlight {
+                arrival: tick + 1 + delay,
+                to,
+                msg: msg.clone(),
+            });
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! Delivery of the messages of a protocol session.
+//!
+//! The rounds of `api` only send and receive through a `Transport`. Messages are strings,
+//! named by the party numbers and the round, e.g. "round1", so that any channel that can
+//! carry them keyed by `(from, to, round)` can run the protocols. `HttpTransport` polls the
+//! SM manager for them, and the manager pushes them to `WsTransport`.
+
+use crate::common::{broadcast, poll_for_broadcasts, poll_for_p2p, sendp2p, Entry, Key, WsFrame};
+use crate::errors::{Result, TssError};
+
+use async_trait::async_trait;
+use futures::lock::Mutex;
+use futures::StreamExt;
+use reqwest::Client;
+use std::collections::HashMap;
+
+#[cfg(not(target_arch = "wasm32"))]
+use futures::SinkExt;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio_tungstenite::tungstenite::Message;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+#[cfg(target_arch = "wasm32")]
+use futures::channel::mpsc::{self, UnboundedReceiver};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::{closure::Closure, JsCast};
+#[cfg(target_arch = "wasm32")]
+use web_sys::{CloseEvent, MessageEvent, WebSocket};
+
+/// `Sync` natively, so that the futures of a `&dyn Transport` are `Send`. Nothing is in the
+/// browser.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSync: Sync {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Sync> MaybeSync for T {}
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSync {}
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSync for T {}
+
+/// A channel among the parties 1..=n of a session. Parties are numbered from 1. Natively the
+/// futures are `Send`, so that a service can spawn the protocols on a multi-threaded runtime.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Transport: MaybeSync {
+    /// Sends `data` to all the other parties for `round`.
+    async fn send_broadcast(&self, party_num: u16, round: &str, data: String) -> Result<()>;
+
+    /// Sends `data` from `party_from` to `party_to` only, for `round`.
+    async fn send_p2p(
+        &self,
+        party_from: u16,
+        party_to: u16,
+        round: &str,
+        data: String,
+    ) -> Result<()>;
+
+    /// Waits for the broadcasts of `round` of the parties 1..=n but `party_num`, and returns
+    /// them in the order of the parties.
+    async fn recv_broadcasts(&self, party_num: u16, n: u16, round: &str) -> Result<Vec<String>>;
+
+    /// Waits for the messages of `round` the parties 1..=n but `party_num` sent to it, and
+    /// returns them in the order of the parties.
+    async fn recv_p2p(&self, party_num: u16, n: u16, round: &str) -> Result<Vec<String>>;
+}
+
+/// Polls the `/set` and `/get` endpoints of the SM manager, every `delay` ms.
+#[derive(Clone, Debug)]
+pub struct HttpTransport {
+    client: Client,
+    addr: String,
+    uuid: String,
+    delay: u32,
+}
+
+impl HttpTransport {
+    /// A transport for session `uuid` of the manager at `addr`.
+    pub fn new(addr: &str, uuid: &str, delay: u32) -> HttpTransport {
+        HttpTransport::with_client(Client::new(), addr, uuid, delay)
+    }
+
+    pub fn with_client(client: Client, addr: &str, uuid: &str, delay: u32) -> HttpTransport {
+        HttpTransport {
+            client,
+            addr: addr.to_string(),
+            uuid: uuid.to_string(),
+            delay,
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Transport for HttpTransport {
+    async fn send_broadcast(&self, party_num: u16, round: &str, data: String) -> Result<()> {
+        broadcast(
+            &self.client,
+            &self.addr,
+            party_num,
+            round,
+            data,
+            self.uuid.clone(),
+        )
+        .await
+    }
+
+    async fn send_p2p(
+        &self,
+        party_from: u16,
+        party_to: u16,
+        round: &str,
+        data: String,
+    ) -> Result<()> {
+        sendp2p(
+            &self.client,
+            &self.addr,
+            party_from,
+            party_to,
+            round,
+            data,
+            self.uuid.clone(),
+        )
+        .await
+    }
+
+    async fn recv_broadcasts(&self, party_num: u16, n: u16, round: &str) -> Result<Vec<String>> {
+        poll_for_broadcasts(
+            &self.client,
+            &self.addr,
+            party_num,
+            n,
+            round,
+            self.uuid.clone(),
+            self.delay,
+        )
+        .await
+    }
+
+    async fn recv_p2p(&self, party_num: u16, n: u16, round: &str) -> Result<Vec<String>> {
+        poll_for_p2p(
+            &self.client,
+            &self.addr,
+            party_num,
+            n,
+            self.delay,
+            round,
+            self.uuid.clone(),
+        )
+        .await
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type Socket = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// The frames of the manager, as they come.
+#[cfg(not(target_arch = "wasm32"))]
+type Incoming = futures::stream::SplitStream<Socket>;
+#[cfg(target_arch = "wasm32")]
+type Incoming = UnboundedReceiver<Result<String>>;
+
+/// Talks to the `/ws` channel of the SM manager, which pushes the messages of a round as they
+/// are sent instead of being polled for them.
+pub struct WsTransport {
+    uuid: String,
+    #[cfg(not(target_arch = "wasm32"))]
+    outgoing: Mutex<futures::stream::SplitSink<Socket, Message>>,
+    #[cfg(target_arch = "wasm32")]
+    socket: WebSocket,
+    #[cfg(target_arch = "wasm32")]
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+    #[cfg(target_arch = "wasm32")]
+    _onclose: Closure<dyn FnMut(CloseEvent)>,
+    incoming: Mutex<Incoming>,
+    /// The pushed entries nobody took yet, by key
+    inbox: std::sync::Mutex<HashMap<Key, String>>,
+}
+
+/// The URL of the channel of the manager at `addr`, e.g. ws://127.0.0.1:8000/ws for
+/// http://127.0.0.1:8000.
+fn ws_url(addr: &str) -> String {
+    let addr = addr.trim_end_matches('/');
+    match addr.strip_prefix("http") {
+        Some(rest) => format!("ws{}/ws", rest),
+        None => format!("{}/ws", addr),
+    }
+}
+
+impl WsTransport {
+    /// Connects to the channel of the manager at `addr` for session `uuid`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn connect(addr: &str, uuid: &str) -> Result<WsTransport> {
+        let (socket, _) =
+            connect_async(ws_url(addr))
+                .await
+                .map_err(|e| TssError::UnknownError {
+                    msg: format!("websocket connect: {}", e),
+                    line: line!(),
+                })?;
+        let (outgoing, incoming) = socket.split();
+        Ok(WsTransport {
+            uuid: uuid.to_string(),
+            outgoing: Mutex::new(outgoing),
+            incoming: Mutex::new(incoming),
+            inbox: std::sync::Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Connects to the channel of the manager at `addr` for session `uuid`.
+    #[cfg(target_arch = "wasm32")]
+    pub async fn connect(addr: &str, uuid: &str) -> Result<WsTransport> {
+        let socket = WebSocket::new(&ws_url(addr)).map_err(|e| TssError::UnknownError {
+            msg: format!("websocket connect: {:?}", e),
+            line: line!(),
+        })?;
+        let (sender, incoming) = mpsc::unbounded();
+        let onmessage = {
+            let sender = sender.clone();
+            Closure::wrap(Box::new(move |event: MessageEvent| {
+                let text = event
+                    .data()
+                    .as_string()
+                    .ok_or_else(|| TssError::UnknownError {
+                        msg: "websocket: binary frame".to_string(),
+                        line: line!(),
+                    });
+                let _ = sender.unbounded_send(text);
+            }) as Box<dyn FnMut(MessageEvent)>)
+        };
+        let onclose = Closure::wrap(Box::new(move |_: CloseEvent| {
+            let _ = sender.unbounded_send(Err(TssError::UnknownError {
+                msg: "websocket closed".to_string(),
+                line: line!(),
+            }));
+        }) as Box<dyn FnMut(CloseEvent)>);
+        socket.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(onclose.as_ref().unchecked_ref()));
+
+        let open = js_sys::Promise::new(&mut |resolve, reject| {
+            socket.set_onopen(Some(&resolve));
+            socket.set_onerror(Some(&reject));
+        });
+        let opened = wasm_bindgen_futures::JsFuture::from(open).await;
+        socket.set_onopen(None);
+        socket.set_onerror(None);
+        opened.map_err(|e| TssError::UnknownError {
+            msg: format!("websocket connect: {:?}", e),
+            line: line!(),
+        })?;
+        Ok(WsTransport {
+            uuid: uuid.to_string(),
+            socket,
+            _onmessage: onmessage,
+            _onclose: onclose,
+            incoming: Mutex::new(incoming),
+            inbox: std::sync::Mutex::new(HashMap::new()),
+        })
+    }
+
+    async fn send(&self, frame: &WsFrame) -> Result<()> {
+        let text = serde_json::to_string(frame)?;
+        #[cfg(not(target_arch = "wasm32"))]
+        let sent = self
+            .outgoing
+            .lock()
+            .await
+            .send(Message::Text(text.into()))
+            .await
+            .map_err(|e| e.to_string());
+        #[cfg(target_arch = "wasm32")]
+        let sent = self
+            .socket
+            .send_with_str(&text)
+            .map_err(|e| format!("{:?}", e));
+        sent.map_err(|e| TssError::UnknownError {
+            msg: format!("websocket send: {}", e),
+            line: line!(),
+        })
+    }
+
+    /// Subscribes to `keys`, and waits until the manager pushed all of them.
+    async fn recv(&self, keys: Vec<Key>) -> Result<Vec<String>> {
+        self.send(&WsFrame::Subscribe(keys.clone())).await?;
+        loop {
+            // whoever holds `incoming` fills the inbox for all
+            let mut incoming = self.incoming.lock().await;
+            {
+                let mut inbox = self.inbox.lock().unwrap();
+                if keys.iter().all(|key| inbox.contains_key(key)) {
+                    return Ok(keys.iter().map(|key| inbox.remove(key).unwrap()).collect());
+                }
+            }
+            match serde_json::from_str(&next_text(&mut incoming).await?)? {
+                WsFrame::Push(Entry { key, value }) => {
+                    self.inbox.lock().unwrap().insert(key, value);
+                }
+                frame => {
+                    return Err(TssError::UnknownError {
+                        msg: format!("unexpected websocket frame: {:?}", frame),
+                        line: line!(),
+                    })
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn next_text(incoming: &mut Incoming) -> Result<String> {
+    loop {
+        let message = incoming
+            .next()
+            .await
+            .ok_or_else(|| TssError::UnknownError {
+                msg: "websocket closed".to_string(),
+                line: line!(),
+            })?;
+        match message {
+            Ok(Message::Text(text)) => return Ok(text.to_string()),
+            Ok(Message::Close(_)) => {
+                return Err(TssError::UnknownError {
+                    msg: "websocket closed".to_string(),
+                    line: line!(),
+                })
+            }
+            // pings are answered by tungstenite
+            Ok(_) => continue,
+            Err(e) => {
+                return Err(TssError::UnknownError {
+                    msg: format!("websocket receive: {}", e),
+                    line: line!(),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn next_text(incoming: &mut Incoming) -> Result<String> {
+    incoming.next().await.unwrap_or_else(|| {
+        Err(TssError::UnknownError {
+            msg: "websocket closed".to_string(),
+            line: line!(),
+        })
+    })
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for WsTransport {
+    fn drop(&mut self) {
+        // the handlers go away with the transport
+        self.socket.set_onmessage(None);
+        self.socket.set_onclose(None);
+        let _ = self.socket.close();
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Transport for WsTransport {
+    async fn send_broadcast(&self, party_num: u16, round: &str, data: String) -> Result<()> {
+        let key = format!("{}-{}-{}", party_num, round, self.uuid);
+        self.send(&WsFrame::Set(Entry { key, value: data })).await
+    }
+
+    async fn send_p2p(
+        &self,
+        party_from: u16,
+        party_to: u16,
+        round: &str,
+        data: String,
+    ) -> Result<()> {
+        let key = format!("{}-{}-{}-{}", party_from, party_to, round, self.uuid);
+        self.send(&WsFrame::Set(Entry { key, value: data })).await
+    }
+
+    async fn recv_broadcasts(&self, party_num: u16, n: u16, round: &str) -> Result<Vec<String>> {
+        let keys = (1..=n)
+            .filter(|&i| i != party_num)
+            .map(|i| format!("{}-{}-{}", i, round, self.uuid))
+            .collect();
+        self.recv(keys).await
+    }
+
+    async fn recv_p2p(&self, party_num: u16, n: u16, round: &str) -> Result<Vec<String>> {
+        let keys = (1..=n)
+            .filter(|&i| i != party_num)
+            .map(|i| format!("{}-{}-{}-{}", i, party_num, round, self.uuid))
+            .collect();
+        self.recv(keys).await
+    }
+}
@@ -0,0 +1,40 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_sign_with_derived_key_t1_n3() {
+    use tss_wasm::gg_2018::bip32;
+
+    let (party_keys_vec, shared_keys_vec, _, y, vss_scheme_vec) = common::keygen_t_n_parties(1, 3);
+    let chain_code = [7u8; 32];
+    let path = bip32::parse_path("m/0/1").unwrap();
+    let (tweak, child, _) = bip32::derive(&y, &chain_code, &path).unwrap();
+
+    let (child_shared_keys_vec, child_vss_scheme_vecs): (Vec<_>, Vec<_>) = shared_keys_vec
+        .iter()
+        .map(|shared_keys| bip32::tweak_key(shared_keys, &vss_scheme_vec, &tweak))
+        .unzip();
+    for shared_keys in &child_shared_keys_vec {
+        assert_eq!(shared_keys.y, child);
+    }
+
+    // output_signature checks the signature against the child key
+    common::sign_with_keys(
+        1,
+        2,
+        vec![0, 2],
+        &party_keys_vec,
+        &child_shared_keys_vec,
+        &child,
+        &child_vss_scheme_vecs[0][0],
+    );
+}
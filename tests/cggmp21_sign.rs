@@ -0,0 +1,82 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_cggmp21_sign_n3_t1_ttag2() {
+    common::cggmp21_sign(1, 3, vec![0, 2]);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_cggmp21_sign_n3_t2_ttag3() {
+    common::cggmp21_sign(2, 3, vec![0, 1, 2]);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_cggmp21_presign_blames_bad_proofs() {
+    use tss_wasm::cggmp_2021::party_i::{PresignKeys, PresignRound1Message, PresignRound3Message};
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+
+    let s = vec![0, 1, 2];
+    let key_store_vec = common::cggmp21_keygen_t_n_parties(2, 3);
+    let presign_keys_vec = s
+        .iter()
+        .map(|&i| PresignKeys::create(&key_store_vec[i].0, &key_store_vec[i].1, &s))
+        .collect::<Vec<PresignKeys>>();
+
+    // signer 1 sends the proofs of signer 2 for its K_1
+    let mut round1_vec = presign_keys_vec
+        .iter()
+        .map(|keys| keys.round1_broadcast())
+        .collect::<Vec<PresignRound1Message>>();
+    let enc_proofs = round1_vec[2].enc_proofs.clone();
+    round1_vec[1].enc_proofs = enc_proofs;
+    match presign_keys_vec[0].round1_verify(&round1_vec) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::EncProof)])
+        }
+        _ => panic!("bad proof of K_1 accepted"),
+    }
+
+    // signer 2 publishes a Delta_2 made with another k_2
+    let round1_vec = presign_keys_vec
+        .iter()
+        .map(|keys| keys.round1_broadcast())
+        .collect::<Vec<PresignRound1Message>>();
+    let (round2_vec, betas_vec): (Vec<_>, Vec<_>) = presign_keys_vec
+        .iter()
+        .map(|keys| {
+            let (round2, beta_vec, beta_hat_vec) = keys.round2_broadcast(&round1_vec);
+            (round2, (beta_vec, beta_hat_vec))
+        })
+        .unzip();
+    presign_keys_vec[0]
+        .round2_verify(&round1_vec, &round2_vec)
+        .expect("bad MtA");
+    let mut round3_vec = presign_keys_vec
+        .iter()
+        .zip(&betas_vec)
+        .map(|(keys, (beta_vec, beta_hat_vec))| {
+            keys.round3_broadcast(&round2_vec, beta_vec, beta_hat_vec).0
+        })
+        .collect::<Vec<PresignRound3Message>>();
+    round3_vec[2].Delta_i = &round3_vec[2].Delta_i * &FE::new_random();
+    match presign_keys_vec[0].round3_verify_R(&round1_vec, &round2_vec, &round3_vec) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(2, BlameItem::DeltaLogStarProof)])
+        }
+        _ => panic!("bad Delta_2 accepted"),
+    }
+}
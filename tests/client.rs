@@ -0,0 +1,151 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use tss_wasm::client::{
+    derive_key, derive_key_store, gg18_keygen, gg18_sign, key_store_to_string, parse_key_store, run,
+};
+use tss_wasm::errors::{Result, TssError};
+use tss_wasm::gg_2018::party_i::{verify, Parameters, Signature};
+use tss_wasm::gg_2018::state_machine::{KeyStore, Keygen, Sign};
+use tss_wasm::transport::Transport;
+
+/// The messages of a session, kept like the SM manager keeps them.
+#[derive(Default)]
+struct MemoryTransport {
+    messages: Mutex<HashMap<String, String>>,
+}
+
+impl MemoryTransport {
+    async fn recv(&self, keys: Vec<String>) -> Result<Vec<String>> {
+        loop {
+            {
+                let messages = self.messages.lock().unwrap();
+                if keys.iter().all(|key| messages.contains_key(key)) {
+                    return Ok(keys.iter().map(|key| messages[key].clone()).collect());
+                }
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for MemoryTransport {
+    async fn send_broadcast(&self, party_num: u16, round: &str, data: String) -> Result<()> {
+        let key = format!("{}-{}", party_num, round);
+        self.messages.lock().unwrap().insert(key, data);
+        Ok(())
+    }
+
+    async fn send_p2p(
+        &self,
+        party_from: u16,
+        party_to: u16,
+        round: &str,
+        data: String,
+    ) -> Result<()> {
+        let key = format!("{}-{}-{}", party_from, party_to, round);
+        self.messages.lock().unwrap().insert(key, data);
+        Ok(())
+    }
+
+    async fn recv_broadcasts(&self, party_num: u16, n: u16, round: &str) -> Result<Vec<String>> {
+        let keys = (1..=n)
+            .filter(|&i| i != party_num)
+            .map(|i| format!("{}-{}", i, round))
+            .collect();
+        self.recv(keys).await
+    }
+
+    async fn recv_p2p(&self, party_num: u16, n: u16, round: &str) -> Result<Vec<String>> {
+        let keys = (1..=n)
+            .filter(|&i| i != party_num)
+            .map(|i| format!("{}-{}-{}", i, party_num, round))
+            .collect();
+        self.recv(keys).await
+    }
+}
+
+/// Services spawn the client on multi-threaded runtimes, which only this has to compile for.
+#[allow(dead_code)]
+fn client_futures_are_send(key_store: &KeyStore, keygen: &mut Keygen) {
+    fn assert_send<T: Send>(_: T) {}
+    let transport = MemoryTransport::default();
+    assert_send(run(keygen, &transport));
+    assert_send(gg18_keygen("http://127.0.0.1:8000", 1, 3, 100));
+    assert_send(gg18_sign(
+        "http://127.0.0.1:8000",
+        1,
+        key_store,
+        &[0x42u8; 32],
+        Some("m/0/1"),
+        100,
+    ));
+}
+
+async fn keygen_t1_n3() -> Vec<KeyStore> {
+    let params = Parameters {
+        threshold: 1,
+        share_count: 3,
+    };
+    let transport = MemoryTransport::default();
+    let mut keygen_1 = Keygen::new(params.clone(), 1).unwrap();
+    let mut keygen_2 = Keygen::new(params.clone(), 2).unwrap();
+    let mut keygen_3 = Keygen::new(params, 3).unwrap();
+    let (r1, r2, r3) = tokio::join!(
+        run(&mut keygen_1, &transport),
+        run(&mut keygen_2, &transport),
+        run(&mut keygen_3, &transport),
+    );
+    vec![r1.unwrap(), r2.unwrap(), r3.unwrap()]
+}
+
+async fn sign(key_stores: [KeyStore; 2], message: &[u8]) -> Signature {
+    let transport = MemoryTransport::default();
+    let [key_store_1, key_store_2] = key_stores;
+    let mut sign_1 = Sign::new(key_store_1, 1, 1, message.to_vec()).unwrap();
+    let mut sign_2 = Sign::new(key_store_2, 1, 2, message.to_vec()).unwrap();
+    let (sig_1, sig_2) = tokio::join!(run(&mut sign_1, &transport), run(&mut sign_2, &transport));
+    let (sig_1, sig_2) = (sig_1.unwrap(), sig_2.unwrap());
+    assert_eq!(sig_1, sig_2);
+    sig_1
+}
+
+#[tokio::test]
+async fn test_client_keygen_and_sign_t1_n3() {
+    use tss_wasm::curv::arithmetic::num_bigint::BigInt;
+
+    let key_stores = keygen_t1_n3().await;
+    let y = key_stores[0].5.clone();
+
+    // key stores go out and back in as JSON
+    let key_stores = key_stores
+        .iter()
+        .map(|key_store| parse_key_store(&key_store_to_string(key_store).unwrap()).unwrap())
+        .collect::<Vec<KeyStore>>();
+    // a key store of six fields, as written before the MtA range proofs, is refused
+    let fields = serde_json::to_value(&key_stores[0]).unwrap();
+    let old_key_store = serde_json::Value::Array(fields.as_array().unwrap()[..6].to_vec());
+    match parse_key_store(&old_key_store.to_string()) {
+        Err(TssError::OutdatedKeyStore(_)) => {}
+        _ => panic!("key store of six fields accepted"),
+    }
+
+    let message = [0x42u8; 32];
+    let message_bn = BigInt::from_bytes_be(&message);
+    let sig = sign([key_stores[0].clone(), key_stores[2].clone()], &message).await;
+    verify(&sig, &y, &message_bn).unwrap();
+
+    // parties 2 and 3 sign for a child key
+    let (_, child, _) = derive_key(&y, key_stores[0].7, "m/0/1").unwrap();
+    let derived = [
+        derive_key_store(&key_stores[1], "m/0/1").unwrap(),
+        derive_key_store(&key_stores[2], "m/0/1").unwrap(),
+    ];
+    assert_eq!(derived[0].5, child);
+    let sig = sign(derived, &message).await;
+    verify(&sig, &child, &message_bn).unwrap();
+}
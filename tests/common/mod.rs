@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 
 #[cfg(not(target_arch = "wasm32"))]
+#[allow(dead_code)]
 pub const BENCH_SAMPLE_SIZE: usize = 10;
 
 /*
@@ -19,28 +20,118 @@ pub const BENCH_SAMPLE_SIZE: usize = 10;
     @license GPL-3.0+ <https://github.com/KZen-networks/multi-party-ecdsa/blob/master/LICENSE>
 */
 
-use tss_wasm::curv::arithmetic::num_bigint::from;
+use tss_wasm::cggmp_2021::party_i::{
+    AuxInfo, AuxInfoBroadcastMessage, AuxInfoKeys, KeyShare, KeygenCommitMessage,
+    KeygenDecommitMessage, KeygenKeys, KeygenProofMessage, PresignKeys, PresignRound1Message,
+    PresignRound3Message,
+};
+use tss_wasm::curv::arithmetic::num_bigint::{from, BigInt};
+#[cfg(feature = "simulation")]
+use tss_wasm::curv::arithmetic::traits::Converter;
 use tss_wasm::curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
 use tss_wasm::curv::cryptographic_primitives::hashing::traits::Hash;
 use tss_wasm::curv::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
 use tss_wasm::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use tss_wasm::curv::elliptic::curves::ed25519::{FE as EdFE, GE as EdGE};
 use tss_wasm::curv::elliptic::curves::secp256_k1::{FE, GE};
 use tss_wasm::curv::elliptic::curves::traits::*;
 
+use tss_wasm::frost_2020::bip340::{self, OutputKey};
+use tss_wasm::frost_2020::eddsa::{
+    Keys as EddsaKeys, SharedKeys as EddsaSharedKeys, SignKeys as EddsaSignKeys,
+};
+use tss_wasm::frost_2020::party_i::SignKeys as FrostSignKeys;
+use tss_wasm::frost_2020::rfc8032;
 use tss_wasm::gg_2018::mta::*;
 use tss_wasm::gg_2018::party_i::*;
+use tss_wasm::gg_2018::presign_store::{MemoryPresignatureStore, PresignatureStore};
+#[cfg(feature = "simulation")]
+use tss_wasm::gg_2018::state_machine::{KeyStore, Keygen, Refresh, Sign};
+use tss_wasm::gg_2020::party_i::{SignBroadcastPhase3, SignBroadcastPhase5, SignBroadcastPhase6};
+use tss_wasm::lindell_2017::{party_one, party_two};
+use tss_wasm::paillier::zkproofs::DLogStatement;
+use tss_wasm::paillier::EncryptionKey;
+#[cfg(feature = "simulation")]
+use tss_wasm::simulation::{run, Network};
+
+// Only GG18 keygen, refresh and signing have state machines for the simulator to run, and
+// need the simulation feature. The other protocols below go through their rounds by hand.
+
+/// GG18 keygen run by the state machines of n parties over a simulated network. Returns
+/// the key store of party i + 1 at index i.
+#[cfg(feature = "simulation")]
+#[allow(dead_code)]
+pub fn simulate_keygen(t: usize, n: usize) -> Vec<KeyStore> {
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
+    let mut keygens = (1..=n as u16)
+        .map(|party_num| Keygen::new(params.clone(), party_num).unwrap())
+        .collect::<Vec<Keygen>>();
+    let (key_stores, _) = run(&mut keygens, &Network::default()).unwrap();
+    let y = key_stores[0].5.clone();
+    assert!(key_stores.iter().all(|key_store| key_store.5 == y));
+    key_stores
+}
+
+/// GG18 signing by the state machines of the ttag parties with indices `s`, on the key
+/// stores of `simulate_keygen`.
+#[cfg(feature = "simulation")]
+#[allow(dead_code)]
+pub fn simulate_sign(t: usize, n: usize, ttag: usize, s: Vec<usize>) {
+    assert_eq!(s.len(), ttag);
+    let key_stores = simulate_keygen(t, n);
+    let y = key_stores[0].5.clone();
+
+    let message_bn = HSha256::create_hash(&[&from(&b"Chainlink"[..])]);
+    let message = BigInt::to_vec(&message_bn);
+    let mut signs = s
+        .iter()
+        .enumerate()
+        .map(|(i, &j)| {
+            Sign::new(
+                key_stores[j].clone(),
+                (ttag - 1) as u16,
+                (i + 1) as u16,
+                message.clone(),
+            )
+            .unwrap()
+        })
+        .collect::<Vec<Sign>>();
+    let (sigs, _) = run(&mut signs, &Network::default()).unwrap();
+    assert!(sigs.iter().all(|sig| *sig == sigs[0]));
+    verify(&sigs[0], &y, &message_bn).unwrap();
+}
 
 pub fn keygen_t_n_parties(
     t: usize,
     n: usize,
-) -> (Vec<Keys>, Vec<SharedKeys>, Vec<GE>, GE, VerifiableSS) {
+) -> (Vec<Keys>, Vec<SharedKeys>, Vec<GE>, GE, Vec<VerifiableSS>) {
+    keygen_t_n_parties_on_curve::<FE>(t, n)
+}
+
+/// GG18 keygen on the curve of S.
+pub fn keygen_t_n_parties_on_curve<S>(
+    t: usize,
+    n: usize,
+) -> (
+    Vec<Keys<S>>,
+    Vec<SharedKeys<S>>,
+    Vec<S::Point>,
+    S::Point,
+    Vec<VerifiableSS<S>>,
+)
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
     let parames = Parameters {
         threshold: t,
         share_count: n.clone(),
     };
     let party_keys_vec = (0..n.clone())
-        .map(|i| Keys::create(i))
-        .collect::<Vec<Keys>>();
+        .map(|i| Keys::create(i + 1))
+        .collect::<Vec<Keys<S>>>();
 
     let mut bc1_vec = Vec::new();
     let mut decom_vec = Vec::new();
@@ -49,10 +140,14 @@ pub fn keygen_t_n_parties(
         bc1_vec.push(bc1);
         decom_vec.push(decom1);
     }
+    for i in 0..n {
+        decom_vec[i].no_small_factor_proofs =
+            party_keys_vec[i].phase1_no_small_factor_proofs(&bc1_vec);
+    }
 
     let y_vec = (0..n.clone())
         .map(|i| decom_vec[i].y_i.clone())
-        .collect::<Vec<GE>>();
+        .collect::<Vec<S::Point>>();
     let mut y_vec_iter = y_vec.iter();
     let head = y_vec_iter.next().unwrap();
     let tail = y_vec_iter;
@@ -68,7 +163,8 @@ pub fn keygen_t_n_parties(
             .expect("invalid key");
         vss_scheme_vec.push(vss_scheme);
         secret_shares_vec.push(secret_shares);
-        index_vec.push(index);
+        // party numbers start at 1, the indices of `VerifiableSS::reconstruct` at 0
+        index_vec.push(index - 1);
     }
     let vss_scheme_for_test = vss_scheme_vec.clone();
 
@@ -79,9 +175,16 @@ pub fn keygen_t_n_parties(
                     let vec_j = &secret_shares_vec[j];
                     vec_j[i].clone()
                 })
-                .collect::<Vec<FE>>()
+                .collect::<Vec<S>>()
         })
-        .collect::<Vec<Vec<FE>>>();
+        .collect::<Vec<Vec<S>>>();
+
+    // no dealer cheats, so the complaint round is empty
+    for i in 0..n {
+        let complaints =
+            Keys::phase2_complaints(&party_shares[i], &vss_scheme_vec, index_vec[i] + 1);
+        assert!(complaints.is_empty());
+    }
 
     let mut shared_keys_vec = Vec::new();
     let mut dlog_proof_vec = Vec::new();
@@ -101,7 +204,7 @@ pub fn keygen_t_n_parties(
 
     let pk_vec = (0..n.clone())
         .map(|i| dlog_proof_vec[i].pk.clone())
-        .collect::<Vec<GE>>();
+        .collect::<Vec<S::Point>>();
 
     //both parties run:
     Keys::verify_dlog_proofs(&parames, &dlog_proof_vec, &y_vec).expect("bad dlog proof");
@@ -109,13 +212,11 @@ pub fn keygen_t_n_parties(
     //test
     let xi_vec = (0..t.clone() + 1)
         .map(|i| shared_keys_vec[i].x_i.clone())
-        .collect::<Vec<FE>>();
+        .collect::<Vec<S>>();
     let x = vss_scheme_for_test[0]
         .clone()
         .reconstruct(&index_vec[0..t.clone() + 1], &xi_vec);
-    let sum_u_i = party_keys_vec
-        .iter()
-        .fold(FE::zero(), |acc, x| acc + &x.u_i);
+    let sum_u_i = party_keys_vec.iter().fold(S::zero(), |acc, x| acc + &x.u_i);
     assert_eq!(x, sum_u_i);
 
     (
@@ -123,19 +224,296 @@ pub fn keygen_t_n_parties(
         shared_keys_vec,
         pk_vec,
         y_sum,
-        vss_scheme_for_test[0].clone(),
+        vss_scheme_for_test,
     )
 }
 
 #[allow(dead_code)]
 pub fn sign(t: usize, n: usize, ttag: usize, s: Vec<usize>) {
+    sign_on_curve::<FE>(t, n, ttag, s)
+}
+
+#[allow(dead_code)]
+pub fn sign_on_curve<S>(t: usize, n: usize, ttag: usize, s: Vec<usize>)
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
     // full key gen emulation
-    let (party_keys_vec, shared_keys_vec, _pk_vec, y, vss_scheme) =
-        keygen_t_n_parties(t.clone(), n);
+    let (party_keys_vec, shared_keys_vec, _pk_vec, y, vss_scheme_vec) =
+        keygen_t_n_parties_on_curve::<S>(t.clone(), n);
+    sign_with_keys(
+        t,
+        ttag,
+        s,
+        &party_keys_vec,
+        &shared_keys_vec,
+        &y,
+        &vss_scheme_vec[0],
+    );
+}
+
+#[allow(dead_code)]
+pub fn sign_with_keys<S>(
+    t: usize,
+    ttag: usize,
+    s: Vec<usize>,
+    party_keys_vec: &[Keys<S>],
+    shared_keys_vec: &[SharedKeys<S>],
+    y: &S::Point,
+    vss_scheme: &VerifiableSS<S>,
+) where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    let SignPhase4 {
+        sign_keys_vec,
+        sigma_vec,
+        R_vec,
+        ..
+    } = sign_phase1_to_4(t, ttag, &s, party_keys_vec, shared_keys_vec, vss_scheme);
+
+    let message: [u8; 4] = [79, 77, 69, 82];
+
+    let message_bn = HSha256::create_hash(&vec![&from(&message[..])]);
+    let mut local_sig_vec = Vec::new();
+
+    // each party computes s_i but don't send it yet. we start with phase5
+    for i in 0..ttag.clone() {
+        let local_sig = LocalSignature::phase5_local_sig(
+            &sign_keys_vec[i].k_i,
+            &message_bn,
+            &R_vec[i],
+            &sigma_vec[i],
+            y,
+        );
+        local_sig_vec.push(local_sig);
+    }
+
+    let mut phase5_com_vec: Vec<Phase5Com1> = Vec::new();
+    let mut phase_5a_decom_vec: Vec<Phase5ADecom1<S>> = Vec::new();
+    let mut helgamal_proof_vec = Vec::new();
+    // we notice that the proof for V= R^sg^l, B = A^l is a general form of homomorphic elgamal.
+    let mut dlog_proof_rho_vec = Vec::new();
+    for i in 0..ttag.clone() {
+        let (phase5_com, phase_5a_decom, helgamal_proof, dlog_proof_rho) =
+            local_sig_vec[i].phase5a_broadcast_5b_zkproof();
+        phase5_com_vec.push(phase5_com);
+        phase_5a_decom_vec.push(phase_5a_decom);
+        helgamal_proof_vec.push(helgamal_proof);
+        dlog_proof_rho_vec.push(dlog_proof_rho);
+    }
+
+    let mut phase5_com2_vec = Vec::new();
+    let mut phase_5d_decom2_vec = Vec::new();
+    for i in 0..ttag.clone() {
+        let mut phase_5a_decom_vec_clone = phase_5a_decom_vec.clone();
+        let mut phase_5a_com_vec_clone = phase5_com_vec.clone();
+        let mut phase_5b_elgamal_vec_clone = helgamal_proof_vec.clone();
+        let mut phase_5a_dlog_vec_clone = dlog_proof_rho_vec.clone();
+
+        let _decom_i = phase_5a_decom_vec_clone.remove(i);
+        let _com_i = phase_5a_com_vec_clone.remove(i);
+        let _elgamal_i = phase_5b_elgamal_vec_clone.remove(i);
+        let _dlog_proof_rho = phase_5a_dlog_vec_clone.remove(i);
+        //        for j in 0..s_minus_i.len() {
+        let (phase5_com2, phase_5d_decom2) = local_sig_vec[i]
+            .phase5c(
+                &phase_5a_decom_vec_clone,
+                &phase_5a_com_vec_clone,
+                &phase_5b_elgamal_vec_clone,
+                &phase_5a_dlog_vec_clone,
+                &phase_5a_decom_vec[i].V_i,
+                &R_vec[0],
+            )
+            .expect("error phase5");
+        phase5_com2_vec.push(phase5_com2);
+        phase_5d_decom2_vec.push(phase_5d_decom2);
+        //        }
+    }
+
+    // assuming phase5 checks passes each party sends s_i and compute sum_i{s_i}
+    let mut s_vec: Vec<S> = Vec::new();
+    for i in 0..ttag.clone() {
+        let s_i = local_sig_vec[i]
+            .phase5d(&phase_5d_decom2_vec, &phase5_com2_vec, &phase_5a_decom_vec)
+            .expect("bad com 5d");
+        s_vec.push(s_i);
+    }
+
+    // here we compute the signature only of party i=0 to demonstrate correctness.
+    s_vec.remove(0);
+    let _sig = local_sig_vec[0]
+        .output_signature(&s_vec)
+        .expect("verification failed");
+}
+
+#[allow(dead_code)]
+pub fn gg20_sign(t: usize, n: usize, ttag: usize, s: Vec<usize>) {
+    let (party_keys_vec, shared_keys_vec, _pk_vec, y, vss_scheme_vec) = keygen_t_n_parties(t, n);
+    let presignatures = gg20_presign_with_keys(
+        t,
+        ttag,
+        s,
+        &party_keys_vec,
+        &shared_keys_vec,
+        &y,
+        &vss_scheme_vec[0],
+    );
 
+    // the online round: every signer broadcasts s_i and adds up the shares
+    let store = MemoryPresignatureStore::new();
+    for (i, presignature) in presignatures.into_iter().enumerate() {
+        store
+            .insert_presignature(&i.to_string(), "presign", presignature)
+            .unwrap();
+    }
+    let message: [u8; 4] = [79, 77, 69, 82];
+    let message_bn = HSha256::create_hash(&vec![&from(&message[..])]);
+    let (presignatures, local_sigs): (Vec<Presignature>, Vec<_>) = (0..ttag)
+        .map(|i| {
+            store
+                .consume_local_sig(&i.to_string(), "presign", &message_bn)
+                .unwrap()
+        })
+        .unzip();
+    let s_vec = local_sigs
+        .iter()
+        .map(|local_sig| local_sig.s_i.clone())
+        .collect::<Vec<FE>>();
+    for presignature in &presignatures {
+        let sig = presignature
+            .phase2_output_signature(&message_bn, &s_vec)
+            .expect("verification failed");
+        assert!(verify(&sig, &y, &message_bn).is_ok());
+    }
+}
+
+/// Phases 3, 5 and 6 of GG20 signing, on top of phases 1 to 4 of GG18.
+#[allow(dead_code)]
+pub fn gg20_presign_with_keys(
+    t: usize,
+    ttag: usize,
+    s: Vec<usize>,
+    party_keys_vec: &[Keys],
+    shared_keys_vec: &[SharedKeys],
+    y: &GE,
+    vss_scheme: &VerifiableSS,
+) -> Vec<Presignature> {
+    let phase4 = sign_phase1_to_4(t, ttag, &s, party_keys_vec, shared_keys_vec, vss_scheme);
+    let R = &phase4.R_vec[0];
+
+    // each party broadcasts delta_i with T_i = g^sigma_i h^l_i
+    let (phase3_vec, l_vec): (Vec<SignBroadcastPhase3>, Vec<FE>) = (0..ttag)
+        .map(|i| SignBroadcastPhase3::create(&phase4.delta_vec[i], &phase4.sigma_vec[i]))
+        .unzip();
+    let delta_inv =
+        SignBroadcastPhase3::verify_reconstruct_delta(&phase3_vec).expect("bad proof of T_i");
+    assert_eq!(
+        delta_inv,
+        SignKeys::phase3_reconstruct_delta(&phase4.delta_vec)
+    );
+
+    // each party broadcasts R_bar_i = R^k_i with a proof for every other party
+    let phase5_vec = gg20_phase5(&phase4, party_keys_vec, &s, R);
+    let m_a_c_vec = phase4
+        .m_a_vec
+        .iter()
+        .map(|m_a| m_a.c.clone())
+        .collect::<Vec<BigInt>>();
+    let ek_vec = (0..ttag)
+        .map(|i| party_keys_vec[s[i]].ek.clone())
+        .collect::<Vec<EncryptionKey>>();
+    for i in 0..ttag {
+        SignBroadcastPhase5::verify(
+            &phase5_vec,
+            R,
+            &m_a_c_vec,
+            &ek_vec,
+            &party_keys_vec[s[i]].dlog_statement(),
+            i,
+        )
+        .expect("bad R_bar_i");
+    }
+
+    // each party broadcasts S_i = R^sigma_i, proven consistent with T_i
+    let phase6_vec = (0..ttag)
+        .map(|i| {
+            SignBroadcastPhase6::create(R, &phase4.sigma_vec[i], &l_vec[i], phase3_vec[i].T_i())
+        })
+        .collect::<Vec<SignBroadcastPhase6>>();
+    (0..ttag)
+        .map(|i| {
+            SignBroadcastPhase6::verify_create_presignature(
+                &phase6_vec,
+                &phase3_vec,
+                &phase5_vec,
+                R,
+                &phase4.sign_keys_vec[i].k_i,
+                &phase4.sigma_vec[i],
+                y,
+            )
+            .expect("bad S_i")
+        })
+        .collect()
+}
+
+#[allow(dead_code)]
+pub fn gg20_phase5(
+    phase4: &SignPhase4,
+    party_keys_vec: &[Keys],
+    s: &[usize],
+    R: &GE,
+) -> Vec<SignBroadcastPhase5> {
+    let ttag = s.len();
+    (0..ttag)
+        .map(|i| {
+            let dlog_statements = (0..ttag)
+                .filter(|j| *j != i)
+                .map(|j| party_keys_vec[s[j]].dlog_statement())
+                .collect::<Vec<DLogStatement>>();
+            SignBroadcastPhase5::create(
+                &phase4.sign_keys_vec[i],
+                R,
+                &phase4.m_a_vec[i].c,
+                &phase4.m_a_randomness_vec[i],
+                &party_keys_vec[s[i]].ek,
+                &dlog_statements,
+            )
+        })
+        .collect()
+}
+
+/// What every signer holds after phase 4 of signing, in signing order.
+#[allow(dead_code)]
+pub struct SignPhase4<S: CurveScalar = FE> {
+    pub sign_keys_vec: Vec<SignKeys<S>>,
+    pub m_a_vec: Vec<MessageA>,
+    pub m_a_randomness_vec: Vec<BigInt>,
+    /// the messages B of the MtA on gamma_j that every party got from the others, in signing
+    /// order, and the alpha it decrypted from them
+    pub m_b_gamma_rec_vec: Vec<Vec<MessageB<S>>>,
+    pub alpha_rec_vec: Vec<Vec<S>>,
+    pub delta_vec: Vec<S>,
+    pub sigma_vec: Vec<S>,
+    pub R_vec: Vec<S::Point>,
+}
+
+/// Phases 1 to 4 of signing, which do not depend on the message.
+#[allow(dead_code)]
+pub fn sign_phase1_to_4<S>(
+    t: usize,
+    ttag: usize,
+    s: &[usize],
+    party_keys_vec: &[Keys<S>],
+    shared_keys_vec: &[SharedKeys<S>],
+    vss_scheme: &VerifiableSS<S>,
+) -> SignPhase4<S>
+where
+    S: CurveScalar + ECScalar<<S as CurveScalar>::SK>,
+{
+    let s = s.to_vec();
     let private_vec = (0..shared_keys_vec.len())
         .map(|i| PartyPrivate::set_private(party_keys_vec[i].clone(), shared_keys_vec[i].clone()))
-        .collect::<Vec<PartyPrivate>>();
+        .collect::<Vec<PartyPrivate<S>>>();
     // make sure that we have t<t'<n and the group s contains id's for t' parties
     // TODO: make sure s has unique id's and they are all in range 0..n
     // TODO: make sure this code can run when id's are not in ascending order
@@ -146,8 +524,8 @@ pub fn sign(t: usize, n: usize, ttag: usize, s: Vec<usize>) {
     // create a vector of signing keys, one for each party.
     // throughout i will index parties
     let sign_keys_vec = (0..ttag)
-        .map(|i| SignKeys::create(&private_vec[s[i]], &vss_scheme, s[i], &s))
-        .collect::<Vec<SignKeys>>();
+        .map(|i| SignKeys::create(&private_vec[s[i]], vss_scheme, s[i], &s))
+        .collect::<Vec<SignKeys<S>>>();
 
     // each party computes [Ci,Di] = com(g^gamma_i) and broadcast the commitments
     let mut bc1_vec = Vec::new();
@@ -158,13 +536,27 @@ pub fn sign(t: usize, n: usize, ttag: usize, s: Vec<usize>) {
         decommit_vec1.push(decommit_phase_1);
     }
 
+    // the range proofs attached to ma_i are made against the h1,h2,N_tilde of every other signer
+    let dlog_statements_except = |i: usize| {
+        (0..ttag)
+            .filter(|j| *j != i)
+            .map(|j| party_keys_vec[s[j]].dlog_statement())
+            .collect::<Vec<DLogStatement>>()
+    };
+
     // each party i sends encryption of k_i under her Paillier key
     // m_a_vec = [ma_0;ma_1;,...]
     let mut m_a_vec = Vec::new();
+    let mut m_a_randomness_vec = Vec::new();
     for i in 0..ttag.clone() {
-        let (m_a_k, _) = MessageA::a(&sign_keys_vec[i].k_i, &party_keys_vec[s[i]].ek, &[]);
+        let (m_a_k, m_a_randomness) = MessageA::a(
+            &sign_keys_vec[i].k_i,
+            &party_keys_vec[s[i]].ek,
+            &dlog_statements_except(i),
+        );
 
         m_a_vec.push(m_a_k);
+        m_a_randomness_vec.push(m_a_randomness);
     }
 
     // each party i sends responses to m_a_vec she received (one response with input gamma_i and one with w_i)
@@ -189,16 +581,18 @@ pub fn sign(t: usize, n: usize, ttag: usize, s: Vec<usize>) {
                 &sign_keys_vec[i].gamma_i,
                 &party_keys_vec[s[ind]].ek,
                 m_a_vec[ind].clone(),
-                &[],
+                &dlog_statements_except(ind),
+                Some(&party_keys_vec[s[ind]].dlog_statement()),
             )
-            .unwrap();
-            let (m_b_w, beta_wi, _, _) = MessageB::b(
+            .expect("bad range proof");
+            let (m_b_w, beta_wi, _, _) = MessageB::b_with_check(
                 &sign_keys_vec[i].w_i,
                 &party_keys_vec[s[ind]].ek,
                 m_a_vec[ind].clone(),
-                &[],
+                &dlog_statements_except(ind),
+                &party_keys_vec[s[ind]].dlog_statement(),
             )
-            .unwrap();
+            .expect("bad range proof");
 
             m_b_gamma_vec.push(m_b_gamma);
             beta_vec.push(beta_gamma);
@@ -230,12 +624,27 @@ pub fn sign(t: usize, n: usize, ttag: usize, s: Vec<usize>) {
             let ind = if j < i { j } else { j + 1 };
             let m_b = m_b_gamma_vec_i[j].clone();
 
+            let alice_dlog_statement = party_keys_vec[s[ind]].dlog_statement();
             let alpha_ij_gamma = m_b
-                .verify_proofs_get_alpha(&party_keys_vec[s[ind]].dk, &sign_keys_vec[ind].k_i)
+                .verify_proofs_get_alpha_gg18(
+                    &private_vec[s[ind]],
+                    &sign_keys_vec[ind].k_i,
+                    &m_a_vec[ind].c,
+                    &party_keys_vec[s[ind]].ek,
+                    &alice_dlog_statement,
+                    false,
+                )
                 .expect("wrong dlog or m_b");
             let m_b = m_b_w_vec_i[j].clone();
             let alpha_ij_wi = m_b
-                .verify_proofs_get_alpha(&party_keys_vec[s[ind]].dk, &sign_keys_vec[ind].k_i)
+                .verify_proofs_get_alpha_gg18(
+                    &private_vec[s[ind]],
+                    &sign_keys_vec[ind].k_i,
+                    &m_a_vec[ind].c,
+                    &party_keys_vec[s[ind]].ek,
+                    &alice_dlog_statement,
+                    true,
+                )
                 .expect("wrong dlog or m_b");
 
             // since we actually run two MtAwc each party needs to make sure that the values B are the same as the public values
@@ -244,18 +653,38 @@ pub fn sign(t: usize, n: usize, ttag: usize, s: Vec<usize>) {
             // TODO: use pk_vec (first change from x_i to w_i) for this check.
             assert_eq!(m_b.b_proof.pk.clone(), sign_keys_vec[i].g_w_i.clone());
 
-            alpha_vec.push(alpha_ij_gamma.0);
-            miu_vec.push(alpha_ij_wi.0);
+            alpha_vec.push(alpha_ij_gamma);
+            miu_vec.push(alpha_ij_wi);
         }
         alpha_vec_all.push(alpha_vec.clone());
         miu_vec_all.push(miu_vec.clone());
     }
 
+    // alpha_vec_all[i] holds what the other parties decrypted from the messages B of party i:
+    // regroup the MtA on gamma by the party that decrypted it, so that each delta_i is its own
+    let other = |i: usize, j: usize| if i < j { i } else { i - 1 };
+    let m_b_gamma_rec_vec = (0..ttag)
+        .map(|i| {
+            (0..ttag)
+                .filter(|&j| j != i)
+                .map(|j| m_b_gamma_vec_all[j][other(i, j)].clone())
+                .collect::<Vec<MessageB<S>>>()
+        })
+        .collect::<Vec<_>>();
+    let alpha_rec_vec = (0..ttag)
+        .map(|i| {
+            (0..ttag)
+                .filter(|&j| j != i)
+                .map(|j| alpha_vec_all[j][other(i, j)].clone())
+                .collect::<Vec<S>>()
+        })
+        .collect::<Vec<_>>();
+
     let mut delta_vec = Vec::new();
     let mut sigma_vec = Vec::new();
 
     for i in 0..ttag.clone() {
-        let delta = sign_keys_vec[i].phase2_delta_i(&alpha_vec_all[i], &beta_vec_all[i]);
+        let delta = sign_keys_vec[i].phase2_delta_i(&alpha_rec_vec[i], &beta_vec_all[i]);
         let sigma = sign_keys_vec[i].phase2_sigma_i(&miu_vec_all[i], &ni_vec_all[i]);
         delta_vec.push(delta);
         sigma_vec.push(sigma);
@@ -269,7 +698,7 @@ pub fn sign(t: usize, n: usize, ttag: usize, s: Vec<usize>) {
 
     let _g_gamma_i_vec = (0..ttag)
         .map(|i| sign_keys_vec[i].g_gamma_i.clone())
-        .collect::<Vec<GE>>();
+        .collect::<Vec<S::Point>>();
 
     let R_vec = (0..ttag)
         .map(|_| {
@@ -279,84 +708,474 @@ pub fn sign(t: usize, n: usize, ttag: usize, s: Vec<usize>) {
                     let b_gamma_vec = &m_b_gamma_vec_all[j];
                     &b_gamma_vec[0].b_proof
                 })
-                .collect::<Vec<&DLogProof>>();
+                .collect::<Vec<&DLogProof<S>>>();
             let R = SignKeys::phase4(&delta_inv, &b_proof_vec, decommit_vec1.clone(), &bc1_vec)
                 .expect("bad gamma_i decommit");
             R
         })
+        .collect::<Vec<S::Point>>();
+
+    SignPhase4 {
+        sign_keys_vec,
+        m_a_vec,
+        m_a_randomness_vec,
+        m_b_gamma_rec_vec,
+        alpha_rec_vec,
+        delta_vec,
+        sigma_vec,
+        R_vec,
+    }
+}
+
+/// GG18 refresh run by the state machines of n parties over a simulated network, on the
+/// keys of `keygen_t_n_parties`.
+#[cfg(feature = "simulation")]
+#[allow(dead_code)]
+pub fn refresh_t_n_parties(
+    t: usize,
+    n: usize,
+    party_keys_vec: &[Keys],
+    shared_keys_vec: &[SharedKeys],
+    vss_scheme_vec: &[VerifiableSS],
+) -> (Vec<Keys>, Vec<SharedKeys>, Vec<VerifiableSS>) {
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
+    // refresh reads neither the Paillier keys nor h1, h2, N_tilde of the others
+    let mut refreshes = (0..n)
+        .map(|i| {
+            let key_store = (
+                party_keys_vec[i].clone(),
+                shared_keys_vec[i].clone(),
+                i as u16 + 1,
+                vss_scheme_vec.to_vec(),
+                Vec::new(),
+                shared_keys_vec[i].y.clone(),
+                Vec::new(),
+                None,
+            );
+            Refresh::new(params.clone(), key_store).unwrap()
+        })
+        .collect::<Vec<Refresh>>();
+    let (key_stores, _) = run(&mut refreshes, &Network::default()).unwrap();
+
+    // the commitments to the refreshed shares are public, so every party derives the same
+    assert!(key_stores
+        .iter()
+        .all(|key_store| key_store.3 == key_stores[0].3));
+    let new_vss_scheme_vec = key_stores[0].3.clone();
+    let (new_party_keys_vec, new_shared_keys_vec) = key_stores
+        .into_iter()
+        .map(|key_store| (key_store.0, key_store.1))
+        .unzip();
+    (new_party_keys_vec, new_shared_keys_vec, new_vss_scheme_vec)
+}
+
+#[allow(dead_code)]
+pub fn reshare_t_n_parties(
+    new_t: usize,
+    new_n: usize,
+    s: &[usize],
+    shared_keys_vec: &[SharedKeys],
+    vss_scheme_vec: &[VerifiableSS],
+) -> (Vec<Keys>, Vec<SharedKeys>, Vec<VerifiableSS>) {
+    let new_params = Parameters {
+        threshold: new_t,
+        share_count: new_n,
+    };
+    // the new parties set up their Paillier keys and h1, h2, N_tilde as in keygen
+    let new_party_keys_vec = (1..=new_n).map(Keys::create).collect::<Vec<Keys>>();
+    let (bc1_vec, mut decom_vec): (Vec<_>, Vec<_>) = new_party_keys_vec
+        .iter()
+        .map(|keys| keys.phase1_broadcast_phase3_proof_of_correct_key())
+        .unzip();
+    for i in 0..new_n {
+        decom_vec[i].no_small_factor_proofs =
+            new_party_keys_vec[i].phase1_no_small_factor_proofs(&bc1_vec);
+    }
+    for keys in &new_party_keys_vec {
+        keys.phase1_verify_com_phase3_verify_correct_key(&new_params, &decom_vec, &bc1_vec)
+            .expect("invalid key");
+    }
+
+    // the old holders in s deal their Lagrange-weighted shares
+    let (new_vss_scheme_vec, sub_shares_vec): (Vec<_>, Vec<_>) = s
+        .iter()
+        .map(|&i| Keys::reshare_distribute(&new_params, &shared_keys_vec[i], vss_scheme_vec, i, s))
+        .unzip();
+
+    let new_shared_keys_vec = (0..new_n)
+        .map(|j| {
+            let party_shares = (0..s.len())
+                .map(|k| sub_shares_vec[k][j].clone())
+                .collect::<Vec<FE>>();
+            Keys::reshare_verify_construct_keypair(
+                &new_params,
+                vss_scheme_vec,
+                s,
+                &party_shares,
+                &new_vss_scheme_vec,
+                j + 1,
+            )
+            .expect("invalid vss")
+        })
+        .collect::<Vec<SharedKeys>>();
+
+    (new_party_keys_vec, new_shared_keys_vec, new_vss_scheme_vec)
+}
+
+#[allow(dead_code)]
+pub fn repair_share(
+    s: &[usize],
+    lost_index: usize,
+    shared_keys_vec: &[SharedKeys],
+    vss_scheme_vec: &[VerifiableSS],
+) -> SharedKeys {
+    // every helper splits its term into one part per helper
+    let (msg_vec, parts_vec): (Vec<_>, Vec<_>) = s
+        .iter()
+        .map(|&i| {
+            Keys::repair_phase1_distribute(&shared_keys_vec[i], vss_scheme_vec, i, s, lost_index)
+        })
+        .unzip();
+
+    // adds up the parts it receives
+    let sums = (0..s.len())
+        .map(|k| {
+            let parts = (0..s.len())
+                .map(|i| parts_vec[i][k].clone())
+                .collect::<Vec<FE>>();
+            Keys::repair_phase2_sum_parts(vss_scheme_vec, s, lost_index, &parts, &msg_vec, k)
+                .expect("invalid part")
+        })
+        .collect::<Vec<FE>>();
+
+    // and the recovering party adds up the sums
+    Keys::repair_phase3_construct_keypair(vss_scheme_vec, s, lost_index, &sums, &msg_vec)
+        .expect("invalid sum")
+}
+
+/// CGGMP21 keygen and auxiliary info of n parties, returning the key store of each.
+#[allow(dead_code)]
+pub fn cggmp21_keygen_t_n_parties(t: usize, n: usize) -> Vec<(KeyShare, AuxInfo)> {
+    let params = Parameters {
+        threshold: t,
+        share_count: n,
+    };
+    let keygen_keys_vec = (0..n)
+        .map(|i| KeygenKeys::create(&params, i + 1))
+        .collect::<Vec<KeygenKeys>>();
+    let com_vec = keygen_keys_vec
+        .iter()
+        .map(|keys| keys.round1_commit())
+        .collect::<Vec<KeygenCommitMessage>>();
+    let decom_vec = keygen_keys_vec
+        .iter()
+        .map(|keys| keys.round2_decommit())
+        .collect::<Vec<KeygenDecommitMessage>>();
+    KeygenKeys::round2_verify_decommitments(&params, &com_vec, &decom_vec)
+        .expect("invalid decommitment");
+
+    let (key_share_vec, proof_vec): (Vec<KeyShare>, Vec<KeygenProofMessage>) = (0..n)
+        .map(|i| {
+            let party_shares = keygen_keys_vec
+                .iter()
+                .map(|keys| keys.secret_shares()[i].clone())
+                .collect::<Vec<FE>>();
+            keygen_keys_vec[i]
+                .round4_create_key_share(&decom_vec, &party_shares)
+                .expect("invalid vss")
+        })
+        .unzip();
+    for key_share in &key_share_vec {
+        key_share
+            .round5_verify_proofs(&decom_vec, &proof_vec)
+            .expect("invalid proof of x_i");
+        assert_eq!(key_share.y, key_share_vec[0].y);
+    }
+
+    let aux_info_keys_vec = (0..n)
+        .map(|i| AuxInfoKeys::create(i + 1))
+        .collect::<Vec<AuxInfoKeys>>();
+    let bc1_vec = aux_info_keys_vec
+        .iter()
+        .map(|keys| keys.round1_broadcast())
+        .collect::<Vec<AuxInfoBroadcastMessage>>();
+    let proofs_vec = aux_info_keys_vec
+        .iter()
+        .map(|keys| keys.round2_no_small_factor_proofs(&bc1_vec))
+        .collect::<Vec<_>>();
+    aux_info_keys_vec
+        .iter()
+        .zip(key_share_vec)
+        .map(|(keys, key_share)| {
+            let aux_info = keys
+                .round2_verify_create_aux_info(&bc1_vec, &proofs_vec)
+                .expect("invalid aux info");
+            (key_share, aux_info)
+        })
+        .collect()
+}
+
+/// The four rounds of CGGMP21 presigning, for the 0-based party indices in `s`.
+#[allow(dead_code)]
+pub fn cggmp21_presign_with_keys(
+    s: &Vec<usize>,
+    key_store_vec: &[(KeyShare, AuxInfo)],
+) -> Vec<Presignature> {
+    let presign_keys_vec = s
+        .iter()
+        .map(|&i| PresignKeys::create(&key_store_vec[i].0, &key_store_vec[i].1, s))
+        .collect::<Vec<PresignKeys>>();
+
+    let round1_vec = presign_keys_vec
+        .iter()
+        .map(|keys| keys.round1_broadcast())
+        .collect::<Vec<PresignRound1Message>>();
+    for keys in &presign_keys_vec {
+        keys.round1_verify(&round1_vec).expect("bad proof of K_j");
+    }
+
+    let mut round2_vec = Vec::new();
+    let mut betas_vec = Vec::new();
+    for keys in &presign_keys_vec {
+        let (round2, beta_vec, beta_hat_vec) = keys.round2_broadcast(&round1_vec);
+        round2_vec.push(round2);
+        betas_vec.push((beta_vec, beta_hat_vec));
+    }
+    for keys in &presign_keys_vec {
+        keys.round2_verify(&round1_vec, &round2_vec)
+            .expect("bad MtA");
+    }
+
+    let (round3_vec, chi_vec): (Vec<PresignRound3Message>, Vec<FE>) = presign_keys_vec
+        .iter()
+        .zip(&betas_vec)
+        .map(|(keys, (beta_vec, beta_hat_vec))| {
+            keys.round3_broadcast(&round2_vec, beta_vec, beta_hat_vec)
+        })
+        .unzip();
+    let R_vec = presign_keys_vec
+        .iter()
+        .map(|keys| {
+            keys.round3_verify_R(&round1_vec, &round2_vec, &round3_vec)
+                .expect("bad Delta_j")
+        })
         .collect::<Vec<GE>>();
 
-    let message: [u8; 4] = [79, 77, 69, 82];
+    let presign_vec = (0..s.len())
+        .map(|i| presign_keys_vec[i].round4_broadcast(&R_vec[i], &chi_vec[i]))
+        .collect::<Vec<PresignBroadcastMessage>>();
+    (0..s.len())
+        .map(|i| {
+            presign_keys_vec[i]
+                .round4_create_presignature(&R_vec[i], &chi_vec[i], &presign_vec)
+                .expect("invalid presignature")
+        })
+        .collect()
+}
 
-    let message_bn = HSha256::create_hash(&vec![&from(&message[..])]);
-    let mut local_sig_vec = Vec::new();
+#[allow(dead_code)]
+pub fn cggmp21_sign(t: usize, n: usize, s: Vec<usize>) {
+    let key_store_vec = cggmp21_keygen_t_n_parties(t, n);
+    let y = key_store_vec[0].0.y.clone();
+    let presignatures = cggmp21_presign_with_keys(&s, &key_store_vec);
 
-    // each party computes s_i but don't send it yet. we start with phase5
-    for i in 0..ttag.clone() {
-        let local_sig = LocalSignature::phase5_local_sig(
-            &sign_keys_vec[i].k_i,
-            &message_bn,
-            &R_vec[i],
-            &sigma_vec[i],
-            &y,
-        );
-        local_sig_vec.push(local_sig);
+    // the online round is the one of GG18 presignatures
+    let store = MemoryPresignatureStore::new();
+    for (i, presignature) in presignatures.into_iter().enumerate() {
+        store
+            .insert_presignature(&i.to_string(), "presign", presignature)
+            .unwrap();
     }
+    let message: [u8; 4] = [79, 77, 69, 82];
+    let message_bn = HSha256::create_hash(&vec![&from(&message[..])]);
+    let (presignatures, local_sigs): (Vec<Presignature>, Vec<_>) = (0..s.len())
+        .map(|i| {
+            store
+                .consume_local_sig(&i.to_string(), "presign", &message_bn)
+                .unwrap()
+        })
+        .unzip();
+    let s_vec = local_sigs
+        .iter()
+        .map(|local_sig| local_sig.s_i.clone())
+        .collect::<Vec<FE>>();
+    for presignature in &presignatures {
+        let sig = presignature
+            .phase2_output_signature(&message_bn, &s_vec)
+            .expect("verification failed");
+        assert!(verify(&sig, &y, &message_bn).is_ok());
+    }
+}
 
-    let mut phase5_com_vec: Vec<Phase5Com1> = Vec::new();
-    let mut phase_5a_decom_vec: Vec<Phase5ADecom1> = Vec::new();
-    let mut helgamal_proof_vec = Vec::new();
-    // we notice that the proof for V= R^sg^l, B = A^l is a general form of homomorphic elgamal.
-    let mut dlog_proof_rho_vec = Vec::new();
-    for i in 0..ttag.clone() {
-        let (phase5_com, phase_5a_decom, helgamal_proof, dlog_proof_rho) =
-            local_sig_vec[i].phase5a_broadcast_5b_zkproof();
-        phase5_com_vec.push(phase5_com);
-        phase_5a_decom_vec.push(phase_5a_decom);
-        helgamal_proof_vec.push(helgamal_proof);
-        dlog_proof_rho_vec.push(dlog_proof_rho);
+/// A FROST signature of the signers in `s` with the shares of a GG18 keygen, checked as a
+/// BIP-340 signature.
+#[allow(dead_code)]
+pub fn frost_sign(t: usize, n: usize, s: Vec<usize>, merkle_root: Option<&[u8]>) {
+    let (_, shared_keys_vec, _, y, vss_scheme_vec) = keygen_t_n_parties(t, n);
+    let key = OutputKey::new(&y, merkle_root).expect("invalid tweak");
+    let message = HSha256::create_hash(&vec![&from(&[79, 77, 69, 82][..])]).to_bytes_be();
+
+    let sign_keys_vec = s
+        .iter()
+        .map(|&i| FrostSignKeys::create(&shared_keys_vec[i], &vss_scheme_vec, i, &s))
+        .collect::<Vec<FrostSignKeys>>();
+    let bc1_vec = sign_keys_vec
+        .iter()
+        .map(|sign_keys| sign_keys.phase1_broadcast())
+        .collect::<Vec<_>>();
+    let bc2_vec = sign_keys_vec
+        .iter()
+        .map(|sign_keys| sign_keys.phase2_sign(&message, &key, &bc1_vec))
+        .collect::<Vec<_>>();
+    for sign_keys in &sign_keys_vec {
+        let sig = sign_keys
+            .phase2_verify_output_signature(&message, &key, &bc1_vec, &bc2_vec)
+            .expect("invalid signature share");
+        assert!(bip340::verify(&sig.to_bytes(), &key.x_only(), &message).is_ok());
     }
+}
 
-    let mut phase5_com2_vec = Vec::new();
-    let mut phase_5d_decom2_vec = Vec::new();
-    for i in 0..ttag.clone() {
-        let mut phase_5a_decom_vec_clone = phase_5a_decom_vec.clone();
-        let mut phase_5a_com_vec_clone = phase5_com_vec.clone();
-        let mut phase_5b_elgamal_vec_clone = helgamal_proof_vec.clone();
-        let mut phase_5a_dlog_vec_clone = dlog_proof_rho_vec.clone();
+#[allow(dead_code)]
+pub fn eddsa_keygen_t_n_parties(
+    t: usize,
+    n: usize,
+) -> (
+    Vec<EddsaKeys>,
+    Vec<EddsaSharedKeys>,
+    EdGE,
+    Vec<VerifiableSS<EdFE>>,
+) {
+    let parames = Parameters {
+        threshold: t,
+        share_count: n,
+    };
+    let party_keys_vec = (0..n).map(EddsaKeys::create).collect::<Vec<EddsaKeys>>();
+    let (bc1_vec, decom_vec): (Vec<_>, Vec<_>) = party_keys_vec
+        .iter()
+        .map(|party_keys| party_keys.phase1_broadcast())
+        .unzip();
+    let y_vec = decom_vec
+        .iter()
+        .map(|decom| decom.y_i.clone())
+        .collect::<Vec<EdGE>>();
+    let y_sum = y_vec[1..]
+        .iter()
+        .fold(y_vec[0].clone(), |acc, y_i| acc + y_i);
 
-        let _decom_i = phase_5a_decom_vec_clone.remove(i);
-        let _com_i = phase_5a_com_vec_clone.remove(i);
-        let _elgamal_i = phase_5b_elgamal_vec_clone.remove(i);
-        let _dlog_proof_rho = phase_5a_dlog_vec_clone.remove(i);
-        //        for j in 0..s_minus_i.len() {
-        let (phase5_com2, phase_5d_decom2) = local_sig_vec[i]
-            .phase5c(
-                &phase_5a_decom_vec_clone,
-                &phase_5a_com_vec_clone,
-                &phase_5b_elgamal_vec_clone,
-                &phase_5a_dlog_vec_clone,
-                &phase_5a_decom_vec[i].V_i,
-                &R_vec[0],
+    let mut vss_scheme_vec = Vec::new();
+    let mut secret_shares_vec = Vec::new();
+    for party_keys in &party_keys_vec {
+        let (vss_scheme, secret_shares, _index) = party_keys
+            .phase1_verify_com_phase2_distribute(&parames, &decom_vec, &bc1_vec)
+            .expect("invalid key");
+        vss_scheme_vec.push(vss_scheme);
+        secret_shares_vec.push(secret_shares);
+    }
+
+    let mut shared_keys_vec = Vec::new();
+    let mut dlog_proof_vec = Vec::new();
+    for (i, party_keys) in party_keys_vec.iter().enumerate() {
+        let party_shares = secret_shares_vec
+            .iter()
+            .map(|secret_shares| secret_shares[i].clone())
+            .collect::<Vec<EdFE>>();
+        let (shared_keys, dlog_proof) = party_keys
+            .phase2_verify_vss_construct_keypair_phase3_pok_dlog(
+                &parames,
+                &y_vec,
+                &party_shares,
+                &vss_scheme_vec,
+                i + 1,
             )
-            .expect("error phase5");
-        phase5_com2_vec.push(phase5_com2);
-        phase_5d_decom2_vec.push(phase_5d_decom2);
-        //        }
+            .expect("invalid vss");
+        shared_keys_vec.push(shared_keys);
+        dlog_proof_vec.push(dlog_proof);
     }
+    EddsaKeys::verify_dlog_proofs(&parames, &dlog_proof_vec, &vss_scheme_vec)
+        .expect("bad dlog proof");
 
-    // assuming phase5 checks passes each party sends s_i and compute sum_i{s_i}
-    let mut s_vec: Vec<FE> = Vec::new();
-    for i in 0..ttag.clone() {
-        let s_i = local_sig_vec[i]
-            .phase5d(&phase_5d_decom2_vec, &phase5_com2_vec, &phase_5a_decom_vec)
-            .expect("bad com 5d");
-        s_vec.push(s_i);
+    (party_keys_vec, shared_keys_vec, y_sum, vss_scheme_vec)
+}
+
+#[allow(dead_code)]
+pub fn eddsa_sign(t: usize, n: usize, s: Vec<usize>) {
+    let (_, shared_keys_vec, y, vss_scheme_vec) = eddsa_keygen_t_n_parties(t, n);
+    let message = b"OMER";
+
+    let sign_keys_vec = s
+        .iter()
+        .map(|&i| EddsaSignKeys::create(&shared_keys_vec[i], &vss_scheme_vec, i, &s))
+        .collect::<Vec<EddsaSignKeys>>();
+    let bc1_vec = sign_keys_vec
+        .iter()
+        .map(|sign_keys| sign_keys.phase1_broadcast())
+        .collect::<Vec<_>>();
+    let bc2_vec = sign_keys_vec
+        .iter()
+        .map(|sign_keys| sign_keys.phase2_sign(message, &y, &bc1_vec))
+        .collect::<Vec<_>>();
+    for sign_keys in &sign_keys_vec {
+        let sig = sign_keys
+            .phase2_verify_output_signature(message, &y, &bc1_vec, &bc2_vec)
+            .expect("invalid signature share");
+        assert!(rfc8032::verify(&sig.to_bytes(), &y.to_bytes(true), message).is_ok());
     }
+}
 
-    // here we compute the signature only of party i=0 to demonstrate correctness.
-    s_vec.remove(0);
-    let _sig = local_sig_vec[0]
-        .output_signature(&s_vec)
-        .expect("verification failed");
+/// Lindell17 keygen between party one and party two.
+#[allow(dead_code)]
+pub fn lindell17_keygen() -> (party_one::KeyShare, party_two::KeyShare) {
+    let (party_one_first_message, comm_witness, ec_key_pair_party1) =
+        party_one::CommitMsg::create_commitments();
+    let (party_two_first_message, ec_key_pair_party2) = party_two::KeyGenFirstMsg::create();
+    let (party_one_second_message, party_one_key_share) = party_one::KeyGenSecondMsg::create(
+        comm_witness,
+        &ec_key_pair_party1,
+        &party_two_first_message,
+    )
+    .expect("bad message of party two");
+    let party_two_key_share = party_two::KeyShare::create(
+        &ec_key_pair_party2,
+        &party_two_first_message,
+        &party_one_first_message,
+        &party_one_second_message,
+    )
+    .expect("bad message of party one");
+
+    (party_one_key_share, party_two_key_share)
+}
+
+/// Lindell17 signing of `message_bn`, output by party one.
+#[allow(dead_code)]
+pub fn lindell17_sign(
+    party_one_key_share: &party_one::KeyShare,
+    party_two_key_share: &party_two::KeyShare,
+    message_bn: &BigInt,
+) -> Signature {
+    let (party_one_first_message, comm_witness, eph_key_pair_party1) =
+        party_one::CommitMsg::create_commitments();
+    let (party_two_first_message, eph_key_pair_party2) = party_two::SignFirstMsg::create();
+    let party_one_second_message = comm_witness
+        .verify_and_decommit(&party_two_first_message.d_log_proof)
+        .expect("bad proof of knowledge of k2");
+    let partial_sig = party_two_key_share
+        .partial_sign(
+            &eph_key_pair_party2,
+            &party_one_first_message,
+            &party_one_second_message,
+            message_bn,
+        )
+        .expect("bad decommitment of party one");
+
+    party_one_key_share
+        .output_signature(
+            &eph_key_pair_party1,
+            &party_two_first_message,
+            &partial_sig,
+            message_bn,
+        )
+        .expect("bad partial signature")
 }
@@ -0,0 +1,83 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_dealer_import_t1_n3() {
+    use tss_wasm::curv::elliptic::curves::secp256_k1::{FE, GE};
+    use tss_wasm::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+    use tss_wasm::gg_2018::dealer;
+    use tss_wasm::gg_2018::party_i::{Keys, Parameters, SharedKeys};
+
+    let params = Parameters {
+        threshold: 1,
+        share_count: 3,
+    };
+    let secret: FE = ECScalar::new_random();
+    let (dealer_msg, shares) = dealer::deal(&params, &secret);
+
+    let (party_keys_vec, shared_keys_vec): (Vec<Keys>, Vec<SharedKeys>) = shares
+        .into_iter()
+        .map(|(party_keys, x_i)| {
+            let shared_keys =
+                dealer::verify_construct_keypair(&params, &dealer_msg, &party_keys, &x_i).unwrap();
+            (party_keys, shared_keys)
+        })
+        .unzip();
+    let y = GE::generator() * &secret;
+    for shared_keys in &shared_keys_vec {
+        assert_eq!(shared_keys.y, y);
+    }
+
+    common::sign_with_keys(
+        1,
+        2,
+        vec![0, 2],
+        &party_keys_vec,
+        &shared_keys_vec,
+        &y,
+        &dealer_msg.vss_scheme,
+    );
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_dealer_import_rejects_bad_share() {
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::dealer;
+    use tss_wasm::gg_2018::party_i::Parameters;
+
+    let params = Parameters {
+        threshold: 1,
+        share_count: 2,
+    };
+    let secret: FE = ECScalar::new_random();
+    let (dealer_msg, shares) = dealer::deal(&params, &secret);
+    let (party_keys, x_i) = &shares[0];
+
+    let bad_x_i = x_i.clone() + &ECScalar::new_random();
+    assert!(matches!(
+        dealer::verify_construct_keypair(&params, &dealer_msg, party_keys, &bad_x_i),
+        Err(TssError::InvalidMessage(BlameItem::VssShare))
+    ));
+
+    // a Paillier key proof of another party
+    let mut bad_dealer_msg = dealer_msg.clone();
+    bad_dealer_msg.bc1_vec[1].correct_key_proof = dealer_msg.bc1_vec[0].correct_key_proof.clone();
+    match dealer::verify_construct_keypair(&params, &bad_dealer_msg, party_keys, x_i) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::CorrectKeyProof)])
+        }
+        _ => panic!("bad Paillier key proof accepted"),
+    }
+}
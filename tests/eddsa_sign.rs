@@ -0,0 +1,56 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_eddsa_sign_n3_t1_ttag2() {
+    common::eddsa_sign(1, 3, vec![0, 2]);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_eddsa_sign_n3_t2_ttag3() {
+    common::eddsa_sign(2, 3, vec![0, 1, 2]);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_eddsa_sign_blames_bad_share() {
+    use tss_wasm::curv::elliptic::curves::ed25519::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::frost_2020::eddsa::SignKeys;
+
+    let s = vec![0, 1, 2];
+    let (_, shared_keys_vec, y, vss_scheme_vec) = common::eddsa_keygen_t_n_parties(2, 3);
+    let message = b"OMER";
+    let sign_keys_vec = s
+        .iter()
+        .map(|&i| SignKeys::create(&shared_keys_vec[i], &vss_scheme_vec, i, &s))
+        .collect::<Vec<SignKeys>>();
+    let bc1_vec = sign_keys_vec
+        .iter()
+        .map(|sign_keys| sign_keys.phase1_broadcast())
+        .collect::<Vec<_>>();
+
+    // signer 2 publishes a share made with another nonce
+    let mut bc2_vec = sign_keys_vec
+        .iter()
+        .map(|sign_keys| sign_keys.phase2_sign(message, &y, &bc1_vec))
+        .collect::<Vec<_>>();
+    bc2_vec[2].z_i = bc2_vec[2].z_i.clone() + FE::new_random();
+    match sign_keys_vec[0].phase2_verify_output_signature(message, &y, &bc1_vec, &bc2_vec) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(2, BlameItem::FrostSigShare)])
+        }
+        _ => panic!("bad z_2 accepted"),
+    }
+}
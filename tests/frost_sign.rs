@@ -0,0 +1,66 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_frost_sign_n3_t1_ttag2() {
+    common::frost_sign(1, 3, vec![0, 2], None);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_frost_sign_n3_t2_ttag3() {
+    common::frost_sign(2, 3, vec![0, 1, 2], None);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_frost_sign_taproot_tweak() {
+    // key path only, as in BIP-86, then with a script tree
+    common::frost_sign(1, 3, vec![1, 2], Some(&[]));
+    common::frost_sign(1, 3, vec![0, 1], Some(&[7u8; 32]));
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_frost_sign_blames_bad_share() {
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::frost_2020::bip340::OutputKey;
+    use tss_wasm::frost_2020::party_i::SignKeys;
+
+    let s = vec![0, 1, 2];
+    let (_, shared_keys_vec, _, y, vss_scheme_vec) = common::keygen_t_n_parties(2, 3);
+    let key = OutputKey::new(&y, None).unwrap();
+    let message = b"OMER";
+    let sign_keys_vec = s
+        .iter()
+        .map(|&i| SignKeys::create(&shared_keys_vec[i], &vss_scheme_vec, i, &s))
+        .collect::<Vec<SignKeys>>();
+    let bc1_vec = sign_keys_vec
+        .iter()
+        .map(|sign_keys| sign_keys.phase1_broadcast())
+        .collect::<Vec<_>>();
+
+    // signer 1 publishes a share made with another nonce
+    let mut bc2_vec = sign_keys_vec
+        .iter()
+        .map(|sign_keys| sign_keys.phase2_sign(message, &key, &bc1_vec))
+        .collect::<Vec<_>>();
+    bc2_vec[1].z_i = bc2_vec[1].z_i.clone() + FE::new_random();
+    match sign_keys_vec[0].phase2_verify_output_signature(message, &key, &bc1_vec, &bc2_vec) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::FrostSigShare)])
+        }
+        _ => panic!("bad z_1 accepted"),
+    }
+}
@@ -0,0 +1,165 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_gg20_sign_n3_t1_ttag2() {
+    common::gg20_sign(1, 3, 2, vec![0, 2]);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_gg20_sign_n3_t2_ttag3() {
+    common::gg20_sign(2, 3, 3, vec![0, 1, 2]);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_gg20_sign_blames_bad_proofs() {
+    use tss_wasm::curv::arithmetic::num_bigint::BigInt;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::{FE, GE};
+    use tss_wasm::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2020::party_i::{
+        SignBroadcastPhase3, SignBroadcastPhase5, SignBroadcastPhase6,
+    };
+    use tss_wasm::paillier::EncryptionKey;
+
+    let s = vec![0, 1, 2];
+    let (party_keys_vec, shared_keys_vec, _, y, vss_scheme_vec) = common::keygen_t_n_parties(2, 3);
+    let phase4 = common::sign_phase1_to_4(
+        2,
+        3,
+        &s,
+        &party_keys_vec,
+        &shared_keys_vec,
+        &vss_scheme_vec[0],
+    );
+    let R = &phase4.R_vec[0];
+
+    // party 1 publishes an R_bar_1 made with another k_1
+    let mut phase5_vec = common::gg20_phase5(&phase4, &party_keys_vec, &s, R);
+    phase5_vec[1].R_bar_i = R * &FE::new_random();
+    let m_a_c_vec = phase4
+        .m_a_vec
+        .iter()
+        .map(|m_a| m_a.c.clone())
+        .collect::<Vec<BigInt>>();
+    let ek_vec = s
+        .iter()
+        .map(|&i| party_keys_vec[i].ek.clone())
+        .collect::<Vec<EncryptionKey>>();
+    match SignBroadcastPhase5::verify(
+        &phase5_vec,
+        R,
+        &m_a_c_vec,
+        &ek_vec,
+        &party_keys_vec[0].dlog_statement(),
+        0,
+    ) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::PdlWithSlackProof)])
+        }
+        _ => panic!("bad R_bar_i accepted"),
+    }
+
+    // party 2 publishes an S_2 made with another sigma_2 than the one in T_2
+    let (phase3_vec, l_vec): (Vec<SignBroadcastPhase3>, Vec<FE>) = (0..3)
+        .map(|i| SignBroadcastPhase3::create(&phase4.delta_vec[i], &phase4.sigma_vec[i]))
+        .unzip();
+    let phase5_vec = common::gg20_phase5(&phase4, &party_keys_vec, &s, R);
+    let mut phase6_vec = (0..3)
+        .map(|i| {
+            SignBroadcastPhase6::create(R, &phase4.sigma_vec[i], &l_vec[i], phase3_vec[i].T_i())
+        })
+        .collect::<Vec<SignBroadcastPhase6>>();
+    let g: GE = ECPoint::generator();
+    phase6_vec[2].S_i = phase6_vec[2].S_i.clone() + g;
+    match SignBroadcastPhase6::verify_create_presignature(
+        &phase6_vec,
+        &phase3_vec,
+        &phase5_vec,
+        R,
+        &phase4.sign_keys_vec[0].k_i,
+        &phase4.sigma_vec[0],
+        &y,
+    ) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(2, BlameItem::STProof)])
+        }
+        _ => panic!("bad S_i accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_gg20_sign_identifies_bad_delta() {
+    use tss_wasm::curv::arithmetic::num_bigint::BigInt;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::{FE, GE};
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2020::party_i::SignPhase5Reveal;
+    use tss_wasm::paillier::EncryptionKey;
+
+    let s = vec![0, 1, 2];
+    let (party_keys_vec, shared_keys_vec, _, _, vss_scheme_vec) = common::keygen_t_n_parties(2, 3);
+    let phase4 = common::sign_phase1_to_4(
+        2,
+        3,
+        &s,
+        &party_keys_vec,
+        &shared_keys_vec,
+        &vss_scheme_vec[0],
+    );
+    let mut reveal_vec = (0..3)
+        .map(|i| {
+            SignPhase5Reveal::create(
+                &phase4.sign_keys_vec[i].k_i,
+                &phase4.m_a_randomness_vec[i],
+                &phase4.m_b_gamma_rec_vec[i],
+                &phase4.alpha_rec_vec[i],
+            )
+        })
+        .collect::<Vec<SignPhase5Reveal>>();
+    let g_gamma_vec = phase4
+        .sign_keys_vec
+        .iter()
+        .map(|sign_keys| sign_keys.g_gamma_i.clone())
+        .collect::<Vec<GE>>();
+    let m_a_c_vec = phase4
+        .m_a_vec
+        .iter()
+        .map(|m_a| m_a.c.clone())
+        .collect::<Vec<BigInt>>();
+    let ek_vec = s
+        .iter()
+        .map(|&i| party_keys_vec[i].ek.clone())
+        .collect::<Vec<EncryptionKey>>();
+
+    // party 1 broadcast a delta_1 that does not follow from its MtA
+    let mut delta_vec = phase4.delta_vec.clone();
+    delta_vec[1] = delta_vec[1].clone() + FE::new_random();
+    match SignPhase5Reveal::identify(&reveal_vec, &delta_vec, &g_gamma_vec, &m_a_c_vec, &ek_vec) {
+        TssError::Blame(blames) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::DeltaShare)])
+        }
+        _ => panic!("bad delta_1 not identified"),
+    }
+
+    // party 2 covers for it by revealing another alpha
+    reveal_vec[2].alpha_vec[0] = FE::new_random();
+    match SignPhase5Reveal::identify(&reveal_vec, &delta_vec, &g_gamma_vec, &m_a_c_vec, &ek_vec) {
+        TssError::Blame(blames) => {
+            assert_eq!(blames, vec![Blame::new(2, BlameItem::MtaReveal)])
+        }
+        _ => panic!("bad reveal of party 2 accepted"),
+    }
+}
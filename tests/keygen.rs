@@ -16,20 +16,219 @@ use wasm_bindgen_test::*;
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 #[test]
 fn test_keygen_t1_n2() {
-    common::keygen_t_n_parties(1, 2);
+    common::simulate_keygen(1, 2);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_keygen_rejects_bad_h1_h2() {
+    use tss_wasm::curv::arithmetic::num_bigint::BigInt;
+    use tss_wasm::curv::arithmetic::traits::Samplable;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::{Keys, Parameters};
+
+    let params = Parameters {
+        threshold: 1,
+        share_count: 2,
+    };
+    let party_keys_vec = (1..=2).map(Keys::create).collect::<Vec<Keys>>();
+    let (mut bc1_vec, mut decom_vec): (Vec<_>, Vec<_>) = party_keys_vec
+        .iter()
+        .map(|keys| keys.phase1_broadcast_phase3_proof_of_correct_key())
+        .unzip();
+    for i in 0..2 {
+        decom_vec[i].no_small_factor_proofs =
+            party_keys_vec[i].phase1_no_small_factor_proofs(&bc1_vec);
+    }
+
+    // party 1 publishes an h2 it does not know the discrete log of
+    bc1_vec[1].dlog_statement.ni = BigInt::sample_below(&bc1_vec[1].dlog_statement.N);
+    match party_keys_vec[0].phase1_verify_com_phase3_verify_correct_key_phase2_distribute(
+        &params, &decom_vec, &bc1_vec,
+    ) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::RingPedersenProof)])
+        }
+        _ => panic!("bad h1, h2 accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_keygen_rejects_misaddressed_no_small_factor_proof() {
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::{Keys, Parameters};
+
+    let params = Parameters {
+        threshold: 1,
+        share_count: 2,
+    };
+    let party_keys_vec = (1..=2).map(Keys::create).collect::<Vec<Keys>>();
+    let (bc1_vec, mut decom_vec): (Vec<_>, Vec<_>) = party_keys_vec
+        .iter()
+        .map(|keys| keys.phase1_broadcast_phase3_proof_of_correct_key())
+        .unzip();
+    for i in 0..2 {
+        decom_vec[i].no_small_factor_proofs =
+            party_keys_vec[i].phase1_no_small_factor_proofs(&bc1_vec);
+    }
+
+    // party 1 sends party 0 the proof made against its own ring-Pedersen parameters
+    decom_vec[1].no_small_factor_proofs[0] = decom_vec[1].no_small_factor_proofs[1].clone();
+    match party_keys_vec[0].phase1_verify_com_phase3_verify_correct_key_phase2_distribute(
+        &params, &decom_vec, &bc1_vec,
+    ) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::NoSmallFactorProof)])
+        }
+        _ => panic!("misaddressed no small factor proof accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_keygen_rejects_reused_modulus() {
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::{Keys, Parameters};
+
+    let params = Parameters {
+        threshold: 1,
+        share_count: 2,
+    };
+    let party_keys_vec = (1..=2).map(Keys::create).collect::<Vec<Keys>>();
+    let (mut bc1_vec, mut decom_vec): (Vec<_>, Vec<_>) = party_keys_vec
+        .iter()
+        .map(|keys| keys.phase1_broadcast_phase3_proof_of_correct_key())
+        .unzip();
+    for i in 0..2 {
+        decom_vec[i].no_small_factor_proofs =
+            party_keys_vec[i].phase1_no_small_factor_proofs(&bc1_vec);
+    }
+
+    // party indices are 1-based, so 0 names no party
+    let mut keys = party_keys_vec[0].clone();
+    keys.party_index = 0;
+    assert!(matches!(
+        keys.phase1_verify_com_phase3_verify_correct_key(&params, &decom_vec, &bc1_vec),
+        Err(TssError::InvalidKey)
+    ));
+
+    // party 1 replays the messages of party 0, whose proofs all hold
+    bc1_vec[1] = bc1_vec[0].clone();
+    decom_vec[1] = decom_vec[0].clone();
+    match party_keys_vec[0].phase1_verify_com_phase3_verify_correct_key_phase2_distribute(
+        &params, &decom_vec, &bc1_vec,
+    ) {
+        Err(TssError::Blame(blames)) => assert_eq!(
+            blames,
+            vec![
+                Blame::new(0, BlameItem::ReusedModulus),
+                Blame::new(1, BlameItem::ReusedModulus)
+            ]
+        ),
+        _ => panic!("reused modulus accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_keygen_complaint_restores_disputed_share() {
+    use tss_wasm::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::gg_2018::party_i::{KeyGenComplaint, Keys};
+
+    let (vss_scheme_vec, secret_shares_vec): (Vec<_>, Vec<_>) = (0..3)
+        .map(|_| VerifiableSS::share(1, 3, &FE::new_random()))
+        .unzip();
+    let mut party_shares = (0..3)
+        .map(|j| secret_shares_vec[j][0].clone())
+        .collect::<Vec<FE>>();
+
+    // dealer 1 sends party 0 a share that does not match its commitments
+    party_shares[1] = FE::new_random();
+    let complaints = Keys::phase2_complaints(&party_shares, &vss_scheme_vec, 1);
+    assert_eq!(
+        complaints,
+        vec![KeyGenComplaint {
+            dealer: 1,
+            index: 1
+        }]
+    );
+
+    let reveals = Keys::phase2_answer_complaints(1, &secret_shares_vec[1], &complaints);
+    let resolved_shares =
+        Keys::phase2_resolve_complaints(&party_shares, &vss_scheme_vec, &complaints, &reveals, 1)
+            .expect("honest dealer blamed");
+    assert_eq!(resolved_shares[1], secret_shares_vec[1][0]);
+    assert!(Keys::phase2_complaints(&resolved_shares, &vss_scheme_vec, 1).is_empty());
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_keygen_complaint_blames_dealer() {
+    use tss_wasm::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::Keys;
+
+    let (vss_scheme_vec, mut secret_shares_vec): (Vec<_>, Vec<_>) = (0..3)
+        .map(|_| VerifiableSS::share(1, 3, &FE::new_random()))
+        .unzip();
+
+    // dealer 2 hands out and reveals a share that does not match its commitments
+    secret_shares_vec[2][0] = FE::new_random();
+    let party_shares = (0..3)
+        .map(|j| secret_shares_vec[j][0].clone())
+        .collect::<Vec<FE>>();
+    let complaints = Keys::phase2_complaints(&party_shares, &vss_scheme_vec, 1);
+    let reveals = Keys::phase2_answer_complaints(2, &secret_shares_vec[2], &complaints);
+    let expected = vec![Blame::new(2, BlameItem::VssShare)];
+    match Keys::phase2_resolve_complaints(&party_shares, &vss_scheme_vec, &complaints, &reveals, 1)
+    {
+        Err(TssError::Blame(blames)) => assert_eq!(blames, expected),
+        _ => panic!("invalid revealed share accepted"),
+    }
+
+    // a complaint left unanswered is held against the dealer as well
+    match Keys::phase2_resolve_complaints(&party_shares, &vss_scheme_vec, &complaints, &[], 1) {
+        Err(TssError::Blame(blames)) => assert_eq!(blames, expected),
+        _ => panic!("unanswered complaint accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_keygen_answer_skips_complaints_out_of_range() {
+    use tss_wasm::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::gg_2018::party_i::{KeyGenComplaint, Keys};
+
+    let (_, secret_shares) = VerifiableSS::share(1, 3, &FE::new_random());
+    // only the complaint about share 2 names a share of the dealer
+    let complaints = [0, 2, 4]
+        .iter()
+        .map(|&index| KeyGenComplaint { dealer: 0, index })
+        .collect::<Vec<KeyGenComplaint>>();
+    let reveals = Keys::phase2_answer_complaints(0, &secret_shares, &complaints);
+    assert_eq!(reveals.len(), 1);
+    assert_eq!(reveals[0].index, 2);
+    assert_eq!(reveals[0].share, secret_shares[1]);
 }
 
 /* TODO: comment to speed up CI
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 #[test]
 fn test_keygen_t2_n3() {
-    common::keygen_t_n_parties(2, 3);
+    common::simulate_keygen(2, 3);
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 #[test]
 fn test_keygen_t2_n4() {
-    common::keygen_t_n_parties(2, 4);
+    common::simulate_keygen(2, 4);
 }
 */
 
@@ -40,7 +239,7 @@ pub mod bench {
     pub fn bench_full_keygen_party_one_two(c: &mut Criterion) {
         c.bench_function("keygen t=1 n=2", move |b| {
             b.iter(|| {
-                super::common::keygen_t_n_parties(1, 2);
+                super::common::simulate_keygen(1, 2);
             })
         });
     }
@@ -48,7 +247,7 @@ pub mod bench {
     pub fn bench_full_keygen_party_two_three(c: &mut Criterion) {
         c.bench_function("keygen t=2 n=3", move |b| {
             b.iter(|| {
-                super::common::keygen_t_n_parties(2, 3);
+                super::common::simulate_keygen(2, 3);
             })
         });
     }
@@ -0,0 +1,136 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+use tss_wasm::curv::arithmetic::num_bigint::from;
+use tss_wasm::curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
+use tss_wasm::curv::cryptographic_primitives::hashing::traits::Hash;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_lindell17_sign() {
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::gg_2018::party_i::verify;
+
+    let (party_one_key_share, party_two_key_share) = common::lindell17_keygen();
+    assert_eq!(party_one_key_share.Q, party_two_key_share.Q);
+
+    // the same key signs more than one message
+    for message in [&b"OMER"[..], &b"ZOMER"[..]].iter() {
+        let message_bn = HSha256::create_hash(&[&from(message)]);
+        let sig = common::lindell17_sign(&party_one_key_share, &party_two_key_share, &message_bn);
+        assert!(verify(&sig, &party_one_key_share.Q, &message_bn).is_ok());
+        // low s
+        assert!(sig.s.to_big_int() <= FE::q() - sig.s.to_big_int());
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_lindell17_keygen_rejects_bad_c_key() {
+    use tss_wasm::errors::{BlameItem, TssError};
+    use tss_wasm::lindell_2017::{party_one, party_two};
+    use tss_wasm::paillier::{Encrypt, Paillier, RawPlaintext};
+
+    let (party_one_first_message, comm_witness, ec_key_pair_party1) =
+        party_one::CommitMsg::create_commitments();
+    let (party_two_first_message, ec_key_pair_party2) = party_two::KeyGenFirstMsg::create();
+    let (mut party_one_second_message, _) = party_one::KeyGenSecondMsg::create(
+        comm_witness,
+        &ec_key_pair_party1,
+        &party_two_first_message,
+    )
+    .unwrap();
+
+    // party one encrypts another value than the x1 of Q1
+    party_one_second_message.c_key = Paillier::encrypt(
+        &party_one_second_message.ek,
+        RawPlaintext::from(from(&b"OMER"[..])),
+    )
+    .0
+    .into_owned();
+    match party_two::KeyShare::create(
+        &ec_key_pair_party2,
+        &party_two_first_message,
+        &party_one_first_message,
+        &party_one_second_message,
+    ) {
+        Err(TssError::InvalidMessage(item)) => assert_eq!(item, BlameItem::EncryptedShareProof),
+        _ => panic!("bad c_key accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_lindell17_sign_rejects_bad_decommitment() {
+    use tss_wasm::errors::{BlameItem, TssError};
+    use tss_wasm::lindell_2017::{party_one, party_two};
+
+    let (_, party_two_key_share) = common::lindell17_keygen();
+    let message_bn = HSha256::create_hash(&[&from(&b"OMER"[..])]);
+
+    let (party_one_first_message, comm_witness, _) = party_one::CommitMsg::create_commitments();
+    let (party_two_first_message, eph_key_pair_party2) = party_two::SignFirstMsg::create();
+    // party one decommits to an R1 chosen after seeing R2
+    let (_, other_comm_witness, _) = party_one::CommitMsg::create_commitments();
+    let party_one_second_message = party_one::CommWitness {
+        public_share: other_comm_witness.public_share,
+        d_log_proof: other_comm_witness.d_log_proof,
+        ..comm_witness
+    }
+    .verify_and_decommit(&party_two_first_message.d_log_proof)
+    .unwrap();
+    match party_two_key_share.partial_sign(
+        &eph_key_pair_party2,
+        &party_one_first_message,
+        &party_one_second_message,
+        &message_bn,
+    ) {
+        Err(TssError::InvalidMessage(item)) => assert_eq!(item, BlameItem::TwoPartyDecommitment),
+        _ => panic!("bad decommitment accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_lindell17_sign_retires_key_share_on_bad_partial_signature() {
+    use tss_wasm::errors::{BlameItem, TssError};
+    use tss_wasm::lindell_2017::{party_one, party_two};
+
+    let (party_one_key_share, party_two_key_share) = common::lindell17_keygen();
+    let message_bn = HSha256::create_hash(&[&from(&b"OMER"[..])]);
+    let other_message_bn = HSha256::create_hash(&[&from(&b"ZOMER"[..])]);
+
+    let (party_one_first_message, comm_witness, eph_key_pair_party1) =
+        party_one::CommitMsg::create_commitments();
+    let (party_two_first_message, eph_key_pair_party2) = party_two::SignFirstMsg::create();
+    let party_one_second_message = comm_witness
+        .verify_and_decommit(&party_two_first_message.d_log_proof)
+        .unwrap();
+    // party two encrypts a partial signature of another message
+    let partial_sig = party_two_key_share
+        .partial_sign(
+            &eph_key_pair_party2,
+            &party_one_first_message,
+            &party_one_second_message,
+            &other_message_bn,
+        )
+        .unwrap();
+    match party_one_key_share.output_signature(
+        &eph_key_pair_party1,
+        &party_two_first_message,
+        &partial_sig,
+        &message_bn,
+    ) {
+        Err(TssError::RetireKeyShare(item)) => assert_eq!(item, BlameItem::PartialSignature),
+        _ => panic!("bad partial signature accepted"),
+    }
+}
@@ -25,6 +25,8 @@ use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
 use tss_wasm::curv::elliptic::curves::traits::*;
 
 use tss_wasm::gg_2018::mta::*;
+use tss_wasm::gg_2018::party_i::generate_h1_h2_N_tilde;
+use tss_wasm::paillier::zkproofs::DLogStatement;
 use tss_wasm::paillier::*;
 
 #[cfg(target_arch = "wasm32")]
@@ -44,7 +46,7 @@ fn test_mta() {
     */
     let bob_input: FE = ECScalar::new_random();
     let (m_a, _) = MessageA::a(&alice_input, &ek_alice, &[]);
-    let (m_b, beta, _, _) = MessageB::b(&bob_input, &ek_alice, m_a, &[]).unwrap();
+    let (m_b, beta, _, _) = MessageB::b(&bob_input, &ek_alice, m_a, &[], None).unwrap();
     let alpha = m_b
         .verify_proofs_get_alpha(&dk_alice, &alice_input)
         .expect("wrong dlog or m_b");
@@ -53,3 +55,113 @@ fn test_mta() {
     let right = alice_input * bob_input;
     assert_eq!(left.get_element(), right.get_element());
 }
+
+fn dlog_statement() -> DLogStatement {
+    let (N_tilde, h1, h2, _, _) = generate_h1_h2_N_tilde();
+    DLogStatement {
+        N: N_tilde,
+        g: h1,
+        ni: h2,
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_mta_with_range_proofs() {
+    let alice_input: FE = ECScalar::new_random();
+    let (ek_alice, dk_alice) = Paillier::keypair().keys();
+    let bob_input: FE = ECScalar::new_random();
+    let dlog_statements = vec![dlog_statement()];
+
+    let (m_a, _) = MessageA::a(&alice_input, &ek_alice, &dlog_statements);
+    assert_eq!(m_a.range_proofs.len(), 1);
+    let alice_dlog_statement = dlog_statement();
+    let (m_b, beta, _, _) = MessageB::b(
+        &bob_input,
+        &ek_alice,
+        m_a.clone(),
+        &dlog_statements,
+        Some(&alice_dlog_statement),
+    )
+    .expect("range proof rejected");
+    assert!(m_b.verify_range_proof(&m_a.c, &ek_alice, &alice_dlog_statement, false));
+    let alpha = m_b
+        .verify_proofs_get_alpha(&dk_alice, &alice_input)
+        .expect("wrong dlog or m_b");
+    let left = alpha.0 + beta;
+    let right = alice_input * bob_input.clone();
+    assert_eq!(left.get_element(), right.get_element());
+
+    // MtAwc: the range proof is also bound to g^b
+    let (m_b_w, _, _, _) = MessageB::b_with_check(
+        &bob_input,
+        &ek_alice,
+        m_a.clone(),
+        &dlog_statements,
+        &alice_dlog_statement,
+    )
+    .expect("range proof rejected");
+    assert!(m_b_w.verify_range_proof(&m_a.c, &ek_alice, &alice_dlog_statement, true));
+    assert!(!m_b.verify_range_proof(&m_a.c, &ek_alice, &alice_dlog_statement, true));
+
+    // a proof made for someone else's h1,h2,N_tilde must not be accepted
+    assert!(MessageB::b(
+        &bob_input,
+        &ek_alice,
+        m_a.clone(),
+        &[dlog_statement()],
+        None
+    )
+    .is_err());
+    // and neither is a missing one
+    assert!(MessageB::b(&bob_input, &ek_alice, m_a, &[], None).is_err());
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_mta_blames_bad_range_proof() {
+    use tss_wasm::curv::elliptic::curves::secp256_k1::GE;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::{Keys, PartyPrivate, SharedKeys};
+
+    let alice_input: FE = ECScalar::new_random();
+    let (ek_alice, dk_alice) = Paillier::keypair().keys();
+    let alice_dlog_statement = dlog_statement();
+    let alice_keys = Keys {
+        u_i: alice_input.clone(),
+        y_i: GE::generator() * &alice_input,
+        dk: dk_alice,
+        ek: ek_alice.clone(),
+        party_index: 0,
+        N_tilde: alice_dlog_statement.N.clone(),
+        h1: alice_dlog_statement.g.clone(),
+        h2: alice_dlog_statement.ni.clone(),
+        xhi: Default::default(),
+        xhi_inv: Default::default(),
+    };
+    let shared_keys = SharedKeys {
+        y: alice_keys.y_i.clone(),
+        x_i: alice_input.clone(),
+    };
+    let private = PartyPrivate::set_private(alice_keys, shared_keys);
+
+    let bob_input: FE = ECScalar::new_random();
+    let (m_a, _) = MessageA::a(&alice_input, &ek_alice, &[]);
+    let (m_b, _, _, _) = MessageB::b(&bob_input, &ek_alice, m_a.clone(), &[], None).unwrap();
+    // Bob sends no range proof at all
+    let result = m_b.verify_proofs_get_alpha_gg18(
+        &private,
+        &alice_input,
+        &m_a.c,
+        &ek_alice,
+        &alice_dlog_statement,
+        false,
+    );
+    match result.map_err(|e| e.blame(2)) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(2, BlameItem::MtaRangeProof)]);
+            assert_eq!(blames[0].round, 2);
+        }
+        _ => panic!("missing range proof accepted"),
+    }
+}
@@ -0,0 +1,35 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+use tss_wasm::curv::elliptic::curves::p256;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_p256_sign_n3_t1_ttag2() {
+    common::sign_on_curve::<p256::FE>(1, 3, 2, vec![0, 2]);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_p256_sign_n3_t2_ttag3() {
+    common::sign_on_curve::<p256::FE>(2, 3, 3, vec![0, 1, 2]);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_p256_keygen_public_key() {
+    use tss_wasm::curv::elliptic::curves::traits::ECPoint;
+
+    let (_, shared_keys_vec, _, y, _) = common::keygen_t_n_parties_on_curve::<p256::FE>(1, 3);
+    assert!(shared_keys_vec.iter().all(|shared_keys| shared_keys.y == y));
+    // y is a point of P-256 and survives its SEC1 encoding
+    assert_eq!(p256::GE::from_bytes(&y.to_bytes(true)).unwrap(), y);
+}
@@ -0,0 +1,160 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_presign_t1_n3() {
+    use tss_wasm::curv::arithmetic::num_bigint::from;
+    use tss_wasm::curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
+    use tss_wasm::curv::cryptographic_primitives::hashing::traits::Hash;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::{verify, Presignature};
+    use tss_wasm::gg_2018::presign_store::{MemoryPresignatureStore, PresignatureStore};
+
+    let (party_keys_vec, shared_keys_vec, _, y, vss_scheme_vec) = common::keygen_t_n_parties(1, 3);
+    let presignatures = common::gg20_presign_with_keys(
+        1,
+        2,
+        vec![0, 2],
+        &party_keys_vec,
+        &shared_keys_vec,
+        &y,
+        &vss_scheme_vec[0],
+    );
+
+    let store = MemoryPresignatureStore::new();
+    for (i, presignature) in presignatures.into_iter().enumerate() {
+        store
+            .insert_presignature(&i.to_string(), "presign", presignature)
+            .unwrap();
+    }
+
+    // the message is only known in the online round
+    let message: [u8; 4] = [79, 77, 69, 82];
+    let message_bn = HSha256::create_hash(&vec![&from(&message[..])]);
+    let (presignatures, local_sigs): (Vec<Presignature>, Vec<_>) = (0..2)
+        .map(|i| {
+            store
+                .consume_local_sig(&i.to_string(), "presign", &message_bn)
+                .unwrap()
+        })
+        .unzip();
+    let mut s_vec = local_sigs
+        .iter()
+        .map(|local_sig| local_sig.s_i.clone())
+        .collect::<Vec<FE>>();
+    for presignature in &presignatures {
+        let sig = presignature
+            .phase2_output_signature(&message_bn, &s_vec)
+            .expect("verification failed");
+        assert!(verify(&sig, &y, &message_bn).is_ok());
+    }
+
+    // a share of the signer at position 1 that does not match its R_i and S_i is blamed
+    s_vec[1] = s_vec[1].clone() + FE::new_random();
+    match presignatures[0].phase2_output_signature(&message_bn, &s_vec) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::OnlineSigShare)])
+        }
+        _ => panic!("wrong online share accepted"),
+    }
+
+    // as is a missing share
+    match presignatures[0].phase2_output_signature(&message_bn, &s_vec[..1]) {
+        Err(TssError::UnexpectedMessage(_)) => {}
+        _ => panic!("missing online share accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_presignature_single_use() {
+    use tss_wasm::curv::arithmetic::num_bigint::BigInt;
+    use tss_wasm::errors::TssError;
+    use tss_wasm::gg_2018::presign_store::{MemoryPresignatureStore, PresignatureStore};
+
+    let (party_keys_vec, shared_keys_vec, _, y, vss_scheme_vec) = common::keygen_t_n_parties(1, 2);
+    let presignature = common::gg20_presign_with_keys(
+        1,
+        2,
+        vec![0, 1],
+        &party_keys_vec,
+        &shared_keys_vec,
+        &y,
+        &vss_scheme_vec[0],
+    )
+    .remove(0);
+
+    let store = MemoryPresignatureStore::new();
+    assert!(matches!(
+        store.consume("key", "presign"),
+        Err(TssError::PresignatureNotFound)
+    ));
+    store
+        .insert_presignature("key", "presign", presignature)
+        .unwrap();
+    assert!(store.is_available("key", "presign").unwrap());
+    assert!(store
+        .consume_local_sig("key", "presign", &BigInt::from(1u32))
+        .is_ok());
+
+    // neither a second message nor a new presignature under the same IDs
+    assert!(!store.is_available("key", "presign").unwrap());
+    assert!(matches!(
+        store.consume_local_sig("key", "presign", &BigInt::from(2u32)),
+        Err(TssError::PresignatureReuse)
+    ));
+    assert!(matches!(
+        store.insert("key", "presign", "{}".to_string()),
+        Err(TssError::PresignatureReuse)
+    ));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_file_presignature_store() {
+    use tss_wasm::errors::TssError;
+    use tss_wasm::gg_2018::presign_store::{FilePresignatureStore, PresignatureStore};
+
+    let dir = std::env::temp_dir().join(format!("tss-wasm-presign-{}", std::process::id()));
+    let store = FilePresignatureStore::new(&dir);
+    store
+        .insert("key", "presign", "presignature".to_string())
+        .unwrap();
+    assert!(matches!(
+        store.insert("key", "presign", "other".to_string()),
+        Err(TssError::PresignatureReuse)
+    ));
+    assert!(matches!(
+        store.insert("../key", "presign", "other".to_string()),
+        Err(TssError::InvalidPresignatureId(_))
+    ));
+
+    // a second store on the same directory, as another process would see it
+    let other_store = FilePresignatureStore::new(&dir);
+    assert_eq!(store.consume("key", "presign").unwrap(), "presignature");
+    assert!(matches!(
+        other_store.consume("key", "presign"),
+        Err(TssError::PresignatureReuse)
+    ));
+    assert!(matches!(
+        other_store.insert("key", "presign", "presignature".to_string()),
+        Err(TssError::PresignatureReuse)
+    ));
+    assert!(matches!(
+        other_store.consume("key", "other"),
+        Err(TssError::PresignatureNotFound)
+    ));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
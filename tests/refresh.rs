@@ -0,0 +1,115 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_refresh_t1_n2() {
+    use tss_wasm::curv::elliptic::curves::secp256_k1::{FE, GE};
+    use tss_wasm::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+    use tss_wasm::gg_2018::party_i::Keys;
+
+    let (party_keys_vec, shared_keys_vec, _, y, vss_scheme_vec) = common::keygen_t_n_parties(1, 2);
+    let (new_party_keys_vec, new_shared_keys_vec, new_vss_scheme_vec) =
+        common::refresh_t_n_parties(1, 2, &party_keys_vec, &shared_keys_vec, &vss_scheme_vec);
+
+    let g: GE = ECPoint::generator();
+    let xi_commitments = Keys::get_commitments_to_xi(&new_vss_scheme_vec);
+    for i in 0..2 {
+        assert_eq!(new_shared_keys_vec[i].y, y);
+        assert_ne!(new_shared_keys_vec[i].x_i, shared_keys_vec[i].x_i);
+        assert_eq!(xi_commitments[i], &g * &new_shared_keys_vec[i].x_i);
+        assert_ne!(new_party_keys_vec[i].ek.n, party_keys_vec[i].ek.n);
+    }
+    let xi_vec = (0..2)
+        .map(|i| new_shared_keys_vec[i].x_i.clone())
+        .collect::<Vec<FE>>();
+    let x = new_vss_scheme_vec[0].reconstruct(&[0, 1], &xi_vec);
+    let sum_u_i = party_keys_vec
+        .iter()
+        .fold(FE::zero(), |acc, keys| acc + &keys.u_i);
+    assert_eq!(x, sum_u_i);
+
+    common::sign_with_keys(
+        1,
+        2,
+        vec![0, 1],
+        &new_party_keys_vec,
+        &new_shared_keys_vec,
+        &y,
+        &new_vss_scheme_vec[0],
+    );
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_refresh_blames_zero_sharing_of_wrong_degree() {
+    use tss_wasm::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::{FE, GE};
+    use tss_wasm::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::{Keys, Parameters, SharedKeys};
+
+    let params = Parameters {
+        threshold: 1,
+        share_count: 2,
+    };
+    let (vss_scheme_vec, secret_shares_vec): (Vec<_>, Vec<_>) = (0..2)
+        .map(|_| VerifiableSS::share(1, 2, &FE::new_random()))
+        .unzip();
+    let g: GE = ECPoint::generator();
+    let shared_keys = SharedKeys {
+        y: &vss_scheme_vec[0].commitments[0] + &vss_scheme_vec[1].commitments[0],
+        x_i: secret_shares_vec[0][0].clone() + &secret_shares_vec[1][0],
+    };
+    assert_eq!(
+        Keys::get_commitments_to_xi(&vss_scheme_vec)[0],
+        &g * &shared_keys.x_i
+    );
+
+    // party 1 deals a polynomial of degree t instead of t - 1, which would move y
+    let (zero_vss_scheme_vec, zero_shares_vec): (Vec<_>, Vec<_>) = vec![
+        Keys::refresh_phase2_distribute(&params).unwrap(),
+        VerifiableSS::share(1, 2, &FE::new_random()),
+    ]
+    .into_iter()
+    .unzip();
+    let party_shares = vec![zero_shares_vec[0][0].clone(), zero_shares_vec[1][0].clone()];
+    match Keys::refresh_phase2_update_keypair(
+        &params,
+        &shared_keys,
+        &party_shares,
+        &zero_vss_scheme_vec,
+        &vss_scheme_vec,
+        1,
+    ) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::VssCommitment)])
+        }
+        _ => panic!("zero sharing of wrong degree accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_refresh_rejects_1_of_n_key() {
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::errors::TssError;
+    use tss_wasm::gg_2018::party_i::{Keys, Parameters};
+
+    let params = Parameters {
+        threshold: 0,
+        share_count: 2,
+    };
+    assert!(matches!(
+        Keys::<FE>::refresh_phase2_distribute(&params),
+        Err(TssError::UnknownError { .. })
+    ));
+}
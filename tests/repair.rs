@@ -0,0 +1,79 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_repair_t1_n3() {
+    use tss_wasm::gg_2018::party_i::Keys;
+
+    let (mut party_keys_vec, mut shared_keys_vec, _, y, vss_scheme_vec) =
+        common::keygen_t_n_parties(1, 3);
+    let shared_keys = common::repair_share(&[0, 2], 1, &shared_keys_vec, &vss_scheme_vec);
+    assert_eq!(shared_keys.y, y);
+    assert_eq!(shared_keys.x_i, shared_keys_vec[1].x_i);
+
+    // the recovering party signs with its repaired share and a fresh Paillier key
+    party_keys_vec[1] = Keys::create(2);
+    shared_keys_vec[1] = shared_keys;
+    common::sign_with_keys(
+        1,
+        2,
+        vec![1, 2],
+        &party_keys_vec,
+        &shared_keys_vec,
+        &y,
+        &vss_scheme_vec[0],
+    );
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_repair_blames_wrong_sum() {
+    use tss_wasm::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::{Keys, SharedKeys};
+
+    let (vss_scheme_vec, secret_shares_vec): (Vec<_>, Vec<_>) = (0..3)
+        .map(|_| VerifiableSS::share(1, 3, &FE::new_random()))
+        .unzip();
+    let y = vss_scheme_vec
+        .iter()
+        .skip(1)
+        .fold(vss_scheme_vec[0].commitments[0].clone(), |acc, vss| {
+            acc + &vss.commitments[0]
+        });
+    let shared_keys_vec = (0..3)
+        .map(|i| SharedKeys {
+            y: y.clone(),
+            x_i: (0..3).fold(FE::zero(), |acc, j| acc + &secret_shares_vec[j][i]),
+        })
+        .collect::<Vec<SharedKeys>>();
+
+    let s = [0, 2];
+    let (msg_vec, parts_vec): (Vec<_>, Vec<_>) = s
+        .iter()
+        .map(|&i| Keys::repair_phase1_distribute(&shared_keys_vec[i], &vss_scheme_vec, i, &s, 1))
+        .unzip();
+    let mut sums = (0..2)
+        .map(|k| parts_vec[0][k].clone() + &parts_vec[1][k])
+        .collect::<Vec<FE>>();
+
+    // the helper at position 1 of s sends a wrong sum to the recovering party
+    sums[1] = sums[1].clone() + FE::new_random();
+    match Keys::repair_phase3_construct_keypair(&vss_scheme_vec, &s, 1, &sums, &msg_vec) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::VssShare)])
+        }
+        _ => panic!("wrong sum accepted"),
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod common;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_reshare_t1_n3_to_t2_n5() {
+    use tss_wasm::curv::elliptic::curves::secp256_k1::{FE, GE};
+    use tss_wasm::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+    use tss_wasm::gg_2018::party_i::Keys;
+
+    let (party_keys_vec, shared_keys_vec, _, y, vss_scheme_vec) = common::keygen_t_n_parties(1, 3);
+    let (new_party_keys_vec, new_shared_keys_vec, new_vss_scheme_vec) =
+        common::reshare_t_n_parties(2, 5, &[0, 2], &shared_keys_vec, &vss_scheme_vec);
+
+    let g: GE = ECPoint::generator();
+    let xi_commitments = Keys::get_commitments_to_xi(&new_vss_scheme_vec);
+    assert_eq!(xi_commitments.len(), 5);
+    for i in 0..5 {
+        assert_eq!(new_shared_keys_vec[i].y, y);
+        assert_eq!(xi_commitments[i], &g * &new_shared_keys_vec[i].x_i);
+    }
+    let xi_vec = (1..4)
+        .map(|i| new_shared_keys_vec[i].x_i.clone())
+        .collect::<Vec<FE>>();
+    let x = new_vss_scheme_vec[0].reconstruct(&[1, 2, 3], &xi_vec);
+    let sum_u_i = party_keys_vec
+        .iter()
+        .fold(FE::zero(), |acc, keys| acc + &keys.u_i);
+    assert_eq!(x, sum_u_i);
+
+    common::sign_with_keys(
+        2,
+        3,
+        vec![0, 3, 4],
+        &new_party_keys_vec,
+        &new_shared_keys_vec,
+        &y,
+        &new_vss_scheme_vec[0],
+    );
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_reshare_blames_dealing_of_wrong_share() {
+    use tss_wasm::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::{Keys, Parameters, SharedKeys};
+
+    let (vss_scheme_vec, secret_shares_vec): (Vec<_>, Vec<_>) = (0..3)
+        .map(|_| VerifiableSS::share(1, 3, &FE::new_random()))
+        .unzip();
+    let y = vss_scheme_vec
+        .iter()
+        .skip(1)
+        .fold(vss_scheme_vec[0].commitments[0].clone(), |acc, vss| {
+            acc + &vss.commitments[0]
+        });
+    let shared_keys_vec = (0..3)
+        .map(|i| SharedKeys {
+            y: y.clone(),
+            x_i: (0..3).fold(FE::zero(), |acc, j| acc + &secret_shares_vec[j][i]),
+        })
+        .collect::<Vec<SharedKeys>>();
+
+    // the holder at position 1 of s deals a fresh secret instead of its weighted share
+    let new_params = Parameters {
+        threshold: 1,
+        share_count: 2,
+    };
+    let s = [0, 2];
+    let (dealing_vss_scheme_vec, sub_shares_vec): (Vec<_>, Vec<_>) = vec![
+        Keys::reshare_distribute(&new_params, &shared_keys_vec[0], &vss_scheme_vec, 0, &s),
+        VerifiableSS::share(1, 2, &FE::new_random()),
+    ]
+    .into_iter()
+    .unzip();
+    let party_shares = vec![sub_shares_vec[0][0].clone(), sub_shares_vec[1][0].clone()];
+    match Keys::reshare_verify_construct_keypair(
+        &new_params,
+        &vss_scheme_vec,
+        &s,
+        &party_shares,
+        &dealing_vss_scheme_vec,
+        1,
+    ) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::VssCommitment)])
+        }
+        _ => panic!("dealing of a wrong share accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_reshare_newcomer_blames_only_minority_dealer() {
+    use tss_wasm::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::{FE, GE};
+    use tss_wasm::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::{Keys, ReshareBroadcastMessage};
+
+    let old_vss_scheme_vec = (0..3)
+        .map(|_| VerifiableSS::share(1, 3, &FE::new_random()).0)
+        .collect::<Vec<VerifiableSS<FE>>>();
+    let forged_vss_scheme_vec = (0..3)
+        .map(|_| VerifiableSS::share(1, 3, &FE::new_random()).0)
+        .collect::<Vec<VerifiableSS<FE>>>();
+    let g: GE = ECPoint::generator();
+    let msg = |old_vss_scheme_vec: &Vec<VerifiableSS<FE>>| ReshareBroadcastMessage {
+        y_i: g.clone(),
+        old_vss_scheme_vec: old_vss_scheme_vec.clone(),
+        vss_scheme: VerifiableSS::share(1, 2, &FE::new_random()).0,
+    };
+
+    // the first dealer sends forged commitments, the others agree on the real ones
+    let reshare_msg_vec = vec![
+        msg(&forged_vss_scheme_vec),
+        msg(&old_vss_scheme_vec),
+        msg(&old_vss_scheme_vec),
+    ];
+    match Keys::reshare_old_vss_scheme_vec(&reshare_msg_vec, None) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(0, BlameItem::VssCommitment)])
+        }
+        _ => panic!("forged VSS commitments accepted"),
+    }
+
+    // without a majority nobody is blamed
+    match Keys::reshare_old_vss_scheme_vec(&reshare_msg_vec[..2], None) {
+        Err(TssError::UnexpectedMessage(_)) => {}
+        _ => panic!("VSS commitments without a majority accepted"),
+    }
+
+    let agreed = Keys::reshare_old_vss_scheme_vec(&reshare_msg_vec[1..], None).unwrap();
+    assert!(agreed == old_vss_scheme_vec);
+}
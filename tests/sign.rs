@@ -16,26 +16,124 @@ use wasm_bindgen_test::*;
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 #[test]
 fn test_sign_n3_t1_ttag2() {
-    common::sign(1, 3, 2, vec![0, 2]);
+    common::simulate_sign(1, 3, 2, vec![0, 2]);
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 #[test]
 fn test_sign_n3_t2_ttag3() {
-    common::sign(2, 3, 3, vec![0, 1, 2]);
+    common::simulate_sign(2, 3, 3, vec![0, 1, 2]);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_sign_blames_bad_gamma_decommitment() {
+    use tss_wasm::curv::cryptographic_primitives::proofs::sigma_dlog::{DLogProof, ProveDLog};
+    use tss_wasm::curv::elliptic::curves::secp256_k1::{FE, GE};
+    use tss_wasm::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::SignKeys;
+
+    let g: GE = ECPoint::generator();
+    let sign_keys_vec = (0..3)
+        .map(|_| {
+            let w_i: FE = ECScalar::new_random();
+            let gamma_i: FE = ECScalar::new_random();
+            SignKeys {
+                g_w_i: &g * &w_i,
+                w_i,
+                k_i: ECScalar::new_random(),
+                g_gamma_i: &g * &gamma_i,
+                gamma_i,
+            }
+        })
+        .collect::<Vec<SignKeys>>();
+    let (bc1_vec, mut decommit_vec): (Vec<_>, Vec<_>) = sign_keys_vec
+        .iter()
+        .map(|sign_keys| sign_keys.phase1_broadcast())
+        .unzip();
+    // the g^b of the MtA with each party
+    let mut b_proofs = sign_keys_vec
+        .iter()
+        .map(|sign_keys| DLogProof::prove(&sign_keys.gamma_i))
+        .collect::<Vec<DLogProof>>();
+    let delta_inv: FE = ECScalar::new_random();
+
+    // party 1 decommits to a different g^gamma, party 2 used another gamma in the MtA
+    decommit_vec[1].g_gamma_i = &g * &ECScalar::new_random();
+    b_proofs[2] = DLogProof::prove(&ECScalar::new_random());
+    let b_proof_vec = b_proofs.iter().collect::<Vec<&DLogProof>>();
+    match SignKeys::phase4(&delta_inv, &b_proof_vec, decommit_vec, &bc1_vec) {
+        Err(TssError::Blame(blames)) => assert_eq!(
+            blames,
+            vec![
+                Blame::new(1, BlameItem::GammaDecommitment),
+                Blame::new(2, BlameItem::GammaMtaMismatch),
+            ]
+        ),
+        _ => panic!("bad gamma_i decommitment accepted"),
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_sign_blames_bad_phase5_proofs() {
+    use tss_wasm::curv::arithmetic::num_bigint::BigInt;
+    use tss_wasm::curv::cryptographic_primitives::proofs::sigma_dlog::{DLogProof, ProveDLog};
+    use tss_wasm::curv::elliptic::curves::secp256_k1::{FE, GE};
+    use tss_wasm::curv::elliptic::curves::traits::{ECPoint, ECScalar};
+    use tss_wasm::errors::{Blame, BlameItem, TssError};
+    use tss_wasm::gg_2018::party_i::LocalSignature;
+
+    let g: GE = ECPoint::generator();
+    let r = &g * &ECScalar::new_random();
+    let y = &g * &ECScalar::new_random();
+    let message = BigInt::from(1234u32);
+    let local_sig_vec = (0..3)
+        .map(|_| {
+            let k_i: FE = ECScalar::new_random();
+            let sigma_i: FE = ECScalar::new_random();
+            LocalSignature::phase5_local_sig(&k_i, &message, &r, &sigma_i, &y)
+        })
+        .collect::<Vec<LocalSignature>>();
+    let (mut com_vec, mut decom_vec, mut elgamal_proofs, mut dlog_proofs_rho) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for local_sig in local_sig_vec.iter() {
+        let (com, decom, elgamal_proof, dlog_proof_rho) = local_sig.phase5a_broadcast_5b_zkproof();
+        com_vec.push(com);
+        decom_vec.push(decom);
+        elgamal_proofs.push(elgamal_proof);
+        dlog_proofs_rho.push(dlog_proof_rho);
+    }
+
+    // party 2 proves knowledge of some other rho; party 0 checks parties 1 and 2
+    dlog_proofs_rho[2] = DLogProof::prove(&ECScalar::new_random());
+    match local_sig_vec[0].phase5c(
+        &decom_vec[1..].to_vec(),
+        &com_vec[1..].to_vec(),
+        &elgamal_proofs[1..].to_vec(),
+        &dlog_proofs_rho[1..],
+        &decom_vec[0].V_i,
+        &r,
+    ) {
+        Err(TssError::Blame(blames)) => {
+            assert_eq!(blames, vec![Blame::new(1, BlameItem::RhoDLogProof)])
+        }
+        _ => panic!("bad proof of knowledge of rho accepted"),
+    }
 }
 
 /* TODO: comment to speed up CI
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 #[test]
 fn test_sign_n5_t2_ttag4() {
-    common::sign(2, 5, 4, vec![0, 2, 3, 4])
+    common::simulate_sign(2, 5, 4, vec![0, 2, 3, 4])
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 #[test]
 fn test_sign_n8_t4_ttag6() {
-    common::sign(4, 8, 6, vec![0, 1, 2, 4, 6, 7])
+    common::simulate_sign(4, 8, 6, vec![0, 1, 2, 4, 6, 7])
 }
 */
 
@@ -46,7 +144,7 @@ pub mod bench {
     pub fn bench_sign_n3_t1_ttag2(c: &mut Criterion) {
         c.bench_function("sign n=3 t=1 ttag=2", move |b| {
             b.iter(|| {
-                super::common::sign(1, 3, 2, vec![0, 2]);
+                super::common::simulate_sign(1, 3, 2, vec![0, 2]);
             })
         });
     }
@@ -54,7 +152,7 @@ pub mod bench {
     pub fn bench_sign_n3_t2_ttag3(c: &mut Criterion) {
         c.bench_function("sign n=3 t=2 ttag=3", move |b| {
             b.iter(|| {
-                super::common::sign(2, 3, 3, vec![0, 1, 2]);
+                super::common::simulate_sign(2, 3, 3, vec![0, 1, 2]);
             })
         });
     }
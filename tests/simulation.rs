@@ -0,0 +1,79 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+use tss_wasm::gg_2018::party_i::Parameters;
+use tss_wasm::gg_2018::state_machine::{KeyStore, Keygen};
+use tss_wasm::simulation::{run, Network};
+
+fn keygen_t1_n3(network: &Network) -> Vec<KeyStore> {
+    let params = Parameters {
+        threshold: 1,
+        share_count: 3,
+    };
+    let mut keygens = (1..=3)
+        .map(|party_num| Keygen::new(params.clone(), party_num).unwrap())
+        .collect::<Vec<Keygen>>();
+    let (key_stores, stats) = run(&mut keygens, network).unwrap();
+    assert_eq!(stats.dropped, 0);
+    key_stores
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_simulated_keygen_and_sign_with_delays() {
+    use tss_wasm::curv::arithmetic::num_bigint::{from, BigInt};
+    use tss_wasm::curv::arithmetic::traits::Converter;
+    use tss_wasm::curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
+    use tss_wasm::curv::cryptographic_primitives::hashing::traits::Hash;
+    use tss_wasm::gg_2018::party_i::verify;
+    use tss_wasm::gg_2018::state_machine::Sign;
+
+    let network = Network {
+        reorder: true,
+        max_delay: 3,
+        seed: 1,
+        ..Network::default()
+    };
+    let key_stores = keygen_t1_n3(&network);
+    let y = key_stores[0].5.clone();
+    assert!(key_stores.iter().all(|key_store| key_store.5 == y));
+
+    // parties 2 and 3 sign
+    let message_bn = HSha256::create_hash(&[&from(&b"simulation"[..])]);
+    let message = BigInt::to_vec(&message_bn);
+    let mut signs = vec![
+        Sign::new(key_stores[1].clone(), 1, 1, message.clone()).unwrap(),
+        Sign::new(key_stores[2].clone(), 1, 2, message).unwrap(),
+    ];
+    let (sigs, _) = run(&mut signs, &Network { seed: 2, ..network }).unwrap();
+    assert_eq!(sigs[0], sigs[1]);
+    verify(&sigs[0], &y, &message_bn).unwrap();
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_simulated_keygen_stalls_on_lost_messages() {
+    use tss_wasm::errors::TssError;
+
+    let params = Parameters {
+        threshold: 1,
+        share_count: 3,
+    };
+    let mut keygens = (1..=3)
+        .map(|party_num| Keygen::new(params.clone(), party_num).unwrap())
+        .collect::<Vec<Keygen>>();
+    let network = Network {
+        drop_rate: 0.5,
+        ..Network::default()
+    };
+    assert!(matches!(
+        run(&mut keygens, &network),
+        Err(TssError::UnknownError { .. })
+    ));
+}
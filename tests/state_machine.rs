@@ -0,0 +1,195 @@
+#[cfg(target_arch = "wasm32")]
+extern crate wasm_bindgen;
+
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+use tss_wasm::errors::TssError;
+use tss_wasm::gg_2018::state_machine::{Msg, StateMachine};
+
+/// Delivers the messages of `queue` and those they trigger, last sent first, to every party
+/// they are for.
+fn deliver<M: StateMachine>(machines: &mut [M], mut queue: Vec<Msg>) {
+    while let Some(msg) = queue.pop() {
+        for (k, machine) in machines.iter_mut().enumerate() {
+            let party_num = k as u16 + 1;
+            if party_num == msg.sender || msg.receiver.map_or(false, |to| to != party_num) {
+                continue;
+            }
+            queue.extend(machine.handle_incoming(msg.clone()).unwrap());
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_state_machines_t1_n3() {
+    use tss_wasm::curv::arithmetic::num_bigint::{from, BigInt};
+    use tss_wasm::curv::arithmetic::traits::Converter;
+    use tss_wasm::curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
+    use tss_wasm::curv::cryptographic_primitives::hashing::traits::Hash;
+    use tss_wasm::gg_2018::party_i::{verify, Parameters};
+    use tss_wasm::gg_2018::state_machine::{Keygen, OfflineStage, Sign};
+
+    let params = Parameters {
+        threshold: 1,
+        share_count: 3,
+    };
+    let mut keygens = (1..=3)
+        .map(|party_num| Keygen::new(params.clone(), party_num).unwrap())
+        .collect::<Vec<Keygen>>();
+    let queue = keygens
+        .iter_mut()
+        .flat_map(|keygen| keygen.proceed().unwrap())
+        .collect();
+    deliver(&mut keygens, queue);
+    let key_stores = keygens
+        .iter()
+        .map(|keygen| keygen.output().unwrap().clone())
+        .collect::<Vec<_>>();
+    let y = key_stores[0].5.clone();
+    assert!(key_stores.iter().all(|key_store| key_store.5 == y));
+
+    // party numbers in round0 that are out of range or taken
+    for body in ["4", "1"] {
+        let mut sign = Sign::new(key_stores[0].clone(), 1, 1, vec![0x42u8; 32]).unwrap();
+        sign.proceed().unwrap();
+        let round0 = Msg {
+            sender: 2,
+            receiver: None,
+            round: "round0".to_string(),
+            body: body.to_string(),
+        };
+        assert!(matches!(
+            sign.handle_incoming(round0),
+            Err(TssError::UnexpectedMessage(_))
+        ));
+    }
+    assert!(Sign::new(key_stores[0].clone(), 1, 3, Vec::new()).is_err());
+
+    // parties 1 and 3 sign
+    let message_bn = HSha256::create_hash(&[&from(&b"state machine"[..])]);
+    let message = BigInt::to_vec(&message_bn);
+    let mut signs = vec![
+        Sign::new(key_stores[0].clone(), 1, 1, message.clone()).unwrap(),
+        Sign::new(key_stores[2].clone(), 1, 2, message).unwrap(),
+    ];
+    let queue = signs
+        .iter_mut()
+        .flat_map(|sign| sign.proceed().unwrap())
+        .collect();
+    deliver(&mut signs, queue);
+    let sig = signs[0].output().unwrap();
+    assert_eq!(Some(sig), signs[1].output());
+    verify(sig, &y, &message_bn).unwrap();
+
+    // presigning stops once R is known, the same for both signers
+    let mut presigns = vec![
+        Sign::offline(key_stores[0].clone(), 1, 1, OfflineStage::R).unwrap(),
+        Sign::offline(key_stores[2].clone(), 1, 2, OfflineStage::R).unwrap(),
+    ];
+    let queue = presigns
+        .iter_mut()
+        .flat_map(|presign| presign.proceed().unwrap())
+        .collect();
+    deliver(&mut presigns, queue);
+    assert!(presigns.iter().all(|presign| presign.is_finished()));
+    assert!(presigns[0].R().is_some());
+    assert_eq!(presigns[0].R(), presigns[1].R());
+    assert_eq!(presigns[0].current_round(), None);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_state_machine_rejects_unexpected_messages() {
+    use tss_wasm::gg_2018::party_i::Parameters;
+    use tss_wasm::gg_2018::state_machine::Keygen;
+
+    let params = Parameters {
+        threshold: 1,
+        share_count: 3,
+    };
+    assert!(Keygen::new(params.clone(), 0).is_err());
+    assert!(Keygen::new(params.clone(), 4).is_err());
+    let mut keygen = Keygen::new(params, 1).unwrap();
+    keygen.proceed().unwrap();
+    let msg = |sender, receiver, round: &str| Msg {
+        sender,
+        receiver,
+        round: round.to_string(),
+        body: "{}".to_string(),
+    };
+
+    for msg in vec![
+        msg(4, None, "round1"),
+        msg(1, None, "round1"),
+        msg(2, Some(3), "round3"),
+        msg(2, None, "round0"),
+    ] {
+        assert!(matches!(
+            keygen.handle_incoming(msg),
+            Err(TssError::UnexpectedMessage(_))
+        ));
+    }
+    // a message for a later round is kept until its round
+    assert!(keygen
+        .handle_incoming(msg(2, Some(1), "round3"))
+        .unwrap()
+        .is_empty());
+    assert!(keygen
+        .handle_incoming(msg(2, None, "round1"))
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        keygen.handle_incoming(msg(2, None, "round1")),
+        Err(TssError::UnexpectedMessage(_))
+    ));
+    assert_eq!(keygen.current_round(), Some("round1"));
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[test]
+fn test_complaints_restore_disputed_share() {
+    use tss_wasm::curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+    use tss_wasm::curv::elliptic::curves::secp256_k1::FE;
+    use tss_wasm::curv::elliptic::curves::traits::ECScalar;
+    use tss_wasm::gg_2018::state_machine::Complaints;
+
+    let (vss_scheme_vec, secret_shares_vec): (Vec<_>, Vec<_>) = (0..3)
+        .map(|_| VerifiableSS::share(1, 3, &FE::new_random()))
+        .unzip();
+    let mut party_shares_vec = (0..3)
+        .map(|i| {
+            (0..3)
+                .map(|j| secret_shares_vec[j][i].clone())
+                .collect::<Vec<FE>>()
+        })
+        .collect::<Vec<_>>();
+    // dealer 2 sends party 1 a share that does not match its commitments
+    party_shares_vec[0][1] = FE::new_random();
+
+    let mut machines = (0..3)
+        .map(|i| {
+            Complaints::new(
+                i as u16 + 1,
+                party_shares_vec[i].clone(),
+                secret_shares_vec[i].clone(),
+                vss_scheme_vec.clone(),
+            )
+            .unwrap()
+        })
+        .collect::<Vec<Complaints>>();
+    let queue = machines
+        .iter_mut()
+        .flat_map(|machine| machine.proceed().unwrap())
+        .collect();
+    deliver(&mut machines, queue);
+    assert_eq!(
+        machines[0].output().unwrap()[1],
+        secret_shares_vec[1][0].clone()
+    );
+    assert_eq!(machines[1].output().unwrap(), &party_shares_vec[1]);
+}
@@ -0,0 +1,75 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::sync::Arc;
+
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tokio_tungstenite::accept_async;
+use tss_wasm::client::run;
+use tss_wasm::common::Key;
+use tss_wasm::gg_2018::party_i::{verify, Parameters};
+use tss_wasm::gg_2018::state_machine::{Keygen, Sign};
+use tss_wasm::manager::{self, Db};
+use tss_wasm::transport::WsTransport;
+
+/// Serves the `/ws` channel of the SM manager on a free port, and returns its address.
+async fn serve() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let db = Arc::new(Db::default());
+    let (keys, _) = broadcast::channel::<Key>(1024);
+    tokio::spawn(async move {
+        while let Ok((tcp, _)) = listener.accept().await {
+            let (db, keys) = (db.clone(), keys.clone());
+            tokio::spawn(async move {
+                let stream = accept_async(tcp).await.unwrap();
+                // the parties drop their end without a close frame
+                let _ = manager::channel(stream, &db, &keys).await;
+            });
+        }
+    });
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn test_ws_transport_keygen_and_sign_t1_n3() {
+    use tss_wasm::curv::arithmetic::num_bigint::BigInt;
+
+    let addr = serve().await;
+    let params = Parameters {
+        threshold: 1,
+        share_count: 3,
+    };
+    let transports = [
+        WsTransport::connect(&addr, "keygen").await.unwrap(),
+        WsTransport::connect(&addr, "keygen").await.unwrap(),
+        WsTransport::connect(&addr, "keygen").await.unwrap(),
+    ];
+    let mut keygen_1 = Keygen::new(params.clone(), 1).unwrap();
+    let mut keygen_2 = Keygen::new(params.clone(), 2).unwrap();
+    let mut keygen_3 = Keygen::new(params, 3).unwrap();
+    let (r1, r2, r3) = tokio::join!(
+        run(&mut keygen_1, &transports[0]),
+        run(&mut keygen_2, &transports[1]),
+        run(&mut keygen_3, &transports[2]),
+    );
+    r2.unwrap();
+    let (key_store_1, key_store_3) = (r1.unwrap(), r3.unwrap());
+    let y = key_store_1.5.clone();
+
+    // parties 1 and 3 sign, the messages of the session pushed to both
+    let message = [0x42u8; 32];
+    let transports = [
+        WsTransport::connect(&addr, "sign").await.unwrap(),
+        WsTransport::connect(&addr, "sign").await.unwrap(),
+    ];
+    let mut sign_1 = Sign::new(key_store_1, 1, 1, message.to_vec()).unwrap();
+    let mut sign_2 = Sign::new(key_store_3, 1, 2, message.to_vec()).unwrap();
+    let (sig_1, sig_2) = tokio::join!(
+        run(&mut sign_1, &transports[0]),
+        run(&mut sign_2, &transports[1])
+    );
+    let sig = sig_1.unwrap();
+    assert_eq!(sig, sig_2.unwrap());
+    verify(&sig, &y, &BigInt::from_bytes_be(&message)).unwrap();
+}